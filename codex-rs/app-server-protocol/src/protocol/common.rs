@@ -117,6 +117,18 @@ client_request_definitions! {
         params: v2::ThreadListParams,
         response: v2::ThreadListResponse,
     },
+    ThreadFork => "thread/fork" {
+        params: v2::ThreadForkParams,
+        response: v2::ThreadForkResponse,
+    },
+    ThreadRollback => "thread/rollback" {
+        params: v2::ThreadRollbackParams,
+        response: v2::ThreadRollbackResponse,
+    },
+    ThreadRead => "thread/read" {
+        params: v2::ThreadReadParams,
+        response: v2::ThreadReadResponse,
+    },
     SkillsList => "skills/list" {
         params: v2::SkillsListParams,
         response: v2::SkillsListResponse,
@@ -831,4 +843,29 @@ mod tests {
         );
         Ok(())
     }
+
+    #[test]
+    fn serialize_thread_rollback() -> Result<()> {
+        let request = ClientRequest::ThreadRollback {
+            request_id: RequestId::Integer(7),
+            params: v2::ThreadRollbackParams {
+                thread_id: "67e55044-10b1-426f-9247-bb680e5fe0c8".to_string(),
+                num_turns: 2,
+                restore_snapshots: true,
+            },
+        };
+        assert_eq!(
+            json!({
+                "method": "thread/rollback",
+                "id": 7,
+                "params": {
+                    "threadId": "67e55044-10b1-426f-9247-bb680e5fe0c8",
+                    "numTurns": 2,
+                    "restoreSnapshots": true
+                }
+            }),
+            serde_json::to_value(&request)?,
+        );
+        Ok(())
+    }
 }
@@ -27,6 +27,7 @@ use codex_protocol::protocol::SkillMetadata as CoreSkillMetadata;
 use codex_protocol::protocol::SkillScope as CoreSkillScope;
 use codex_protocol::protocol::TokenUsage as CoreTokenUsage;
 use codex_protocol::protocol::TokenUsageInfo as CoreTokenUsageInfo;
+use codex_protocol::user_input::LineRange as CoreLineRange;
 use codex_protocol::user_input::UserInput as CoreUserInput;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use mcp_types::ContentBlock as McpContentBlock;
@@ -102,6 +103,8 @@ pub enum CodexErrorInfo {
         #[ts(rename = "httpStatusCode")]
         http_status_code: Option<u16>,
     },
+    /// A thread rollback could not be applied.
+    ThreadRollbackFailed,
     Other,
 }
 
@@ -126,6 +129,7 @@ impl From<CoreCodexErrorInfo> for CodexErrorInfo {
             CoreCodexErrorInfo::ResponseTooManyFailedAttempts { http_status_code } => {
                 CodexErrorInfo::ResponseTooManyFailedAttempts { http_status_code }
             }
+            CoreCodexErrorInfo::ThreadRollbackFailed => CodexErrorInfo::ThreadRollbackFailed,
             CoreCodexErrorInfo::Other => CodexErrorInfo::Other,
         }
     }
@@ -1053,6 +1057,63 @@ pub struct ThreadListResponse {
     pub next_cursor: Option<String>,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadForkParams {
+    pub thread_id: String,
+    /// Number of leading turns to copy into the new thread. When omitted, the
+    /// full history of the source thread is copied.
+    pub num_turns: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadForkResponse {
+    /// The newly created thread, with the copied turns populated.
+    pub thread: Thread,
+    pub model: String,
+    pub model_provider: String,
+    pub cwd: PathBuf,
+    pub approval_policy: AskForApproval,
+    pub sandbox: SandboxPolicy,
+    pub reasoning_effort: Option<ReasoningEffort>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadRollbackParams {
+    pub thread_id: String,
+    /// Number of turns to drop from the end of the thread. Must be at least 1.
+    pub num_turns: u32,
+    /// When true, also restore the working tree to the ghost snapshot taken
+    /// before the earliest dropped turn.
+    #[serde(default)]
+    pub restore_snapshots: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadRollbackResponse {}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadReadParams {
+    pub thread_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadReadResponse {
+    /// The persisted thread, with its turns and items populated.
+    pub thread: Thread,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
@@ -1168,7 +1229,7 @@ pub struct Thread {
     pub source: SessionSource,
     /// Optional Git metadata captured when the thread was created.
     pub git_info: Option<GitInfo>,
-    /// Only populated on a `thread/resume` response.
+    /// Only populated on `thread/resume`, `thread/fork` and `thread/read` responses.
     /// For all other responses and notifications returning a Thread,
     /// the turns field will be an empty list.
     pub turns: Vec<Turn>,
@@ -1243,7 +1304,7 @@ impl From<CoreTokenUsage> for TokenUsageBreakdown {
 #[ts(export_to = "v2/")]
 pub struct Turn {
     pub id: String,
-    /// Only populated on a `thread/resume` response.
+    /// Only populated on `thread/resume`, `thread/fork` and `thread/read` responses.
     /// For all other responses and notifications returning a Turn,
     /// the items field will be an empty list.
     pub items: Vec<ThreadItem>,
@@ -1382,9 +1443,59 @@ pub struct TurnInterruptResponse {}
 #[ts(tag = "type")]
 #[ts(export_to = "v2/")]
 pub enum UserInput {
-    Text { text: String },
-    Image { url: String },
-    LocalImage { path: PathBuf },
+    Text {
+        text: String,
+    },
+    Image {
+        url: String,
+    },
+    LocalImage {
+        path: PathBuf,
+    },
+    File {
+        path: PathBuf,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[ts(optional)]
+        range: Option<LineRange>,
+    },
+}
+
+/// Inclusive, 1-based range of lines within an attached file.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct LineRange {
+    pub start: u32,
+    pub end: u32,
+}
+
+impl<'de> Deserialize<'de> for LineRange {
+    /// Rejects a zero `start` or an `end` before `start`, so bad ranges from
+    /// clients fail the request instead of reaching core.
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        CoreLineRange::deserialize(deserializer).map(Self::from)
+    }
+}
+
+impl From<CoreLineRange> for LineRange {
+    fn from(value: CoreLineRange) -> Self {
+        Self {
+            start: value.start,
+            end: value.end,
+        }
+    }
+}
+
+impl From<LineRange> for CoreLineRange {
+    fn from(value: LineRange) -> Self {
+        Self {
+            start: value.start,
+            end: value.end,
+        }
+    }
 }
 
 impl UserInput {
@@ -1393,6 +1504,10 @@ impl UserInput {
             UserInput::Text { text } => CoreUserInput::Text { text },
             UserInput::Image { url } => CoreUserInput::Image { image_url: url },
             UserInput::LocalImage { path } => CoreUserInput::LocalImage { path },
+            UserInput::File { path, range } => CoreUserInput::File {
+                path,
+                range: range.map(Into::into),
+            },
         }
     }
 }
@@ -1403,6 +1518,10 @@ impl From<CoreUserInput> for UserInput {
             CoreUserInput::Text { text } => UserInput::Text { text },
             CoreUserInput::Image { image_url } => UserInput::Image { url: image_url },
             CoreUserInput::LocalImage { path } => UserInput::LocalImage { path },
+            CoreUserInput::File { path, range } => UserInput::File {
+                path,
+                range: range.map(Into::into),
+            },
             _ => unreachable!("unsupported user input variant"),
         }
     }
@@ -1847,9 +1966,15 @@ pub struct FileChangeRequestApprovalParams {
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
 pub struct FileChangeRequestApprovalResponse {
     pub decision: ApprovalDecision,
+    /// With `accept`, apply only the changes to these files (matching `FileUpdateChange.path`).
+    /// Changes to the other files are rejected and reported back to the model.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub accepted_paths: Option<Vec<String>>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
@@ -1951,6 +2076,7 @@ mod tests {
     use codex_protocol::items::UserMessageItem;
     use codex_protocol::items::WebSearchItem;
     use codex_protocol::protocol::NetworkAccess as CoreNetworkAccess;
+    use codex_protocol::user_input::LineRange as CoreLineRange;
     use codex_protocol::user_input::UserInput as CoreUserInput;
     use pretty_assertions::assert_eq;
     use serde_json::json;
@@ -1974,6 +2100,27 @@ mod tests {
         assert_eq!(back_to_v2, v2_policy);
     }
 
+    #[test]
+    fn file_input_rejects_zero_and_inverted_ranges() {
+        let file = |start: u32, end: u32| {
+            serde_json::from_value::<UserInput>(json!({
+                "type": "file",
+                "path": "src/lib.rs",
+                "range": {"start": start, "end": end},
+            }))
+        };
+
+        assert_eq!(
+            file(3, 4).ok(),
+            Some(UserInput::File {
+                path: PathBuf::from("src/lib.rs"),
+                range: Some(LineRange { start: 3, end: 4 }),
+            })
+        );
+        assert!(file(0, 4).is_err());
+        assert!(file(4, 3).is_err());
+    }
+
     #[test]
     fn core_turn_item_into_thread_item_converts_supported_variants() {
         let user_item = TurnItem::UserMessage(UserMessageItem {
@@ -1988,6 +2135,10 @@ mod tests {
                 CoreUserInput::LocalImage {
                     path: PathBuf::from("local/image.png"),
                 },
+                CoreUserInput::File {
+                    path: PathBuf::from("src/lib.rs"),
+                    range: Some(CoreLineRange { start: 10, end: 40 }),
+                },
             ],
         });
 
@@ -2005,6 +2156,10 @@ mod tests {
                     UserInput::LocalImage {
                         path: PathBuf::from("local/image.png"),
                     },
+                    UserInput::File {
+                        path: PathBuf::from("src/lib.rs"),
+                        range: Some(LineRange { start: 10, end: 40 }),
+                    },
                 ],
             }
         );
@@ -2097,4 +2252,24 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn file_change_approval_response_accepts_optional_paths() {
+        let response: FileChangeRequestApprovalResponse =
+            serde_json::from_value(json!({ "decision": "accept" })).expect("deserialize");
+        assert_eq!(response.accepted_paths, None);
+
+        let response: FileChangeRequestApprovalResponse = serde_json::from_value(json!({
+            "decision": "accept",
+            "acceptedPaths": ["/repo/src/lib.rs"],
+        }))
+        .expect("deserialize");
+        assert_eq!(
+            response,
+            FileChangeRequestApprovalResponse {
+                decision: ApprovalDecision::Accept,
+                accepted_paths: Some(vec!["/repo/src/lib.rs".to_string()]),
+            }
+        );
+    }
 }
@@ -1,7 +1,9 @@
 use crate::codex_message_processor::ApiVersion;
 use crate::codex_message_processor::PendingInterrupts;
+use crate::codex_message_processor::PendingRollbacks;
 use crate::codex_message_processor::TurnSummary;
 use crate::codex_message_processor::TurnSummaryStore;
+use crate::error_code::INVALID_REQUEST_ERROR_CODE;
 use crate::outgoing_message::OutgoingMessageSender;
 use codex_app_server_protocol::AccountRateLimitsUpdatedNotification;
 use codex_app_server_protocol::AgentMessageDeltaNotification;
@@ -27,6 +29,7 @@ use codex_app_server_protocol::FileUpdateChange;
 use codex_app_server_protocol::InterruptConversationResponse;
 use codex_app_server_protocol::ItemCompletedNotification;
 use codex_app_server_protocol::ItemStartedNotification;
+use codex_app_server_protocol::JSONRPCErrorError;
 use codex_app_server_protocol::McpToolCallError;
 use codex_app_server_protocol::McpToolCallResult;
 use codex_app_server_protocol::McpToolCallStatus;
@@ -40,6 +43,7 @@ use codex_app_server_protocol::ServerNotification;
 use codex_app_server_protocol::ServerRequestPayload;
 use codex_app_server_protocol::TerminalInteractionNotification;
 use codex_app_server_protocol::ThreadItem;
+use codex_app_server_protocol::ThreadRollbackResponse;
 use codex_app_server_protocol::ThreadTokenUsage;
 use codex_app_server_protocol::ThreadTokenUsageUpdatedNotification;
 use codex_app_server_protocol::Turn;
@@ -53,6 +57,7 @@ use codex_app_server_protocol::TurnStatus;
 use codex_core::CodexConversation;
 use codex_core::parse_command::shlex_join;
 use codex_core::protocol::ApplyPatchApprovalRequestEvent;
+use codex_core::protocol::CodexErrorInfo as CoreCodexErrorInfo;
 use codex_core::protocol::Event;
 use codex_core::protocol::EventMsg;
 use codex_core::protocol::ExecApprovalRequestEvent;
@@ -78,12 +83,14 @@ use tracing::error;
 
 type JsonValue = serde_json::Value;
 
+#[allow(clippy::too_many_arguments)]
 pub(crate) async fn apply_bespoke_event_handling(
     event: Event,
     conversation_id: ConversationId,
     conversation: Arc<CodexConversation>,
     outgoing: Arc<OutgoingMessageSender>,
     pending_interrupts: PendingInterrupts,
+    pending_rollbacks: PendingRollbacks,
     turn_summary_store: TurnSummaryStore,
     api_version: ApiVersion,
 ) {
@@ -336,6 +343,38 @@ pub(crate) async fn apply_bespoke_event_handling(
             handle_token_count_event(conversation_id, event_turn_id, token_count_event, &outgoing)
                 .await;
         }
+        EventMsg::Error(ev)
+            if matches!(
+                ev.codex_error_info,
+                Some(CoreCodexErrorInfo::ThreadRollbackFailed)
+            ) =>
+        {
+            // Rollback failures are reported on the pending `thread/rollback` requests rather
+            // than as turn errors.
+            let pending = {
+                let mut map = pending_rollbacks.lock().await;
+                map.remove(&conversation_id).unwrap_or_default()
+            };
+            for request_id in pending {
+                let error = JSONRPCErrorError {
+                    code: INVALID_REQUEST_ERROR_CODE,
+                    message: ev.message.clone(),
+                    data: None,
+                };
+                outgoing.send_error(request_id, error).await;
+            }
+        }
+        EventMsg::ThreadRolledBack(_) => {
+            let pending = {
+                let mut map = pending_rollbacks.lock().await;
+                map.remove(&conversation_id).unwrap_or_default()
+            };
+            for request_id in pending {
+                outgoing
+                    .send_response(request_id, ThreadRollbackResponse {})
+                    .await;
+            }
+        }
         EventMsg::Error(ev) => {
             let turn_error = TurnError {
                 message: ev.message,
@@ -1100,19 +1139,27 @@ async fn on_file_change_request_approval_response(
                     error!("failed to deserialize FileChangeRequestApprovalResponse: {err}");
                     FileChangeRequestApprovalResponse {
                         decision: ApprovalDecision::Decline,
+                        accepted_paths: None,
                     }
                 });
 
-            let (decision, completion_status) = match response.decision {
-                ApprovalDecision::Accept
-                | ApprovalDecision::AcceptForSession
-                | ApprovalDecision::AcceptWithExecpolicyAmendment { .. } => {
-                    (ReviewDecision::Approved, None)
-                }
-                ApprovalDecision::Decline => {
+            let (decision, completion_status) = match (response.decision, response.accepted_paths) {
+                (ApprovalDecision::Accept, Some(accepted_paths)) => (
+                    ReviewDecision::ApprovedSubset {
+                        approved_paths: accepted_paths.into_iter().map(PathBuf::from).collect(),
+                    },
+                    None,
+                ),
+                (
+                    ApprovalDecision::Accept
+                    | ApprovalDecision::AcceptForSession
+                    | ApprovalDecision::AcceptWithExecpolicyAmendment { .. },
+                    _,
+                ) => (ReviewDecision::Approved, None),
+                (ApprovalDecision::Decline, _) => {
                     (ReviewDecision::Denied, Some(PatchApplyStatus::Declined))
                 }
-                ApprovalDecision::Cancel => {
+                (ApprovalDecision::Cancel, _) => {
                     (ReviewDecision::Abort, Some(PatchApplyStatus::Declined))
                 }
             };
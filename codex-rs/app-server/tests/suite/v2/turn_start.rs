@@ -721,6 +721,7 @@ async fn turn_start_file_change_approval_v2() -> Result<()> {
         request_id,
         serde_json::to_value(FileChangeRequestApprovalResponse {
             decision: ApprovalDecision::Accept,
+            accepted_paths: None,
         })?,
     )
     .await?;
@@ -887,6 +888,7 @@ async fn turn_start_file_change_approval_decline_v2() -> Result<()> {
         request_id,
         serde_json::to_value(FileChangeRequestApprovalResponse {
             decision: ApprovalDecision::Decline,
+            accepted_paths: None,
         })?,
     )
     .await?;
@@ -13,6 +13,7 @@ pub use parser::Hunk;
 pub use parser::ParseError;
 use parser::ParseError::*;
 use parser::UpdateFileChunk;
+pub use parser::format_patch;
 pub use parser::parse_patch;
 use similar::TextDiff;
 use thiserror::Error;
@@ -82,7 +83,7 @@ pub struct ApplyPatchArgs {
     pub workdir: Option<String>,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub enum ApplyPatchFileChange {
     Add {
         content: String,
@@ -138,6 +139,27 @@ impl ApplyPatchAction {
         &self.changes
     }
 
+    /// Returns a copy of this action limited to the files in `paths` (keys of
+    /// [`Self::changes`]), with `patch` rewritten so it only touches those files.
+    pub fn retain_files(&self, paths: &[PathBuf]) -> Result<Self, ParseError> {
+        let hunks: Vec<Hunk> = parse_patch(&self.patch)?
+            .hunks
+            .into_iter()
+            .filter(|hunk| paths.contains(&hunk.resolve_path(&self.cwd)))
+            .collect();
+        let changes = self
+            .changes
+            .iter()
+            .filter(|(path, _)| paths.contains(path))
+            .map(|(path, change)| (path.clone(), change.clone()))
+            .collect();
+        Ok(Self {
+            changes,
+            patch: format_patch(&hunks),
+            cwd: self.cwd.clone(),
+        })
+    }
+
     /// Should be used exclusively for testing. (Not worth the overhead of
     /// creating a feature flag for this.)
     pub fn new_add_for_test(path: &Path, content: String) -> Self {
@@ -1062,4 +1084,36 @@ g
         let result = apply_patch(&patch, &mut stdout, &mut stderr);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_retain_files_drops_rejected_hunks() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("keep.txt"), "one\n").unwrap();
+        fs::write(dir.path().join("drop.txt"), "two\n").unwrap();
+        let patch = wrap_patch(
+            "*** Update File: keep.txt\n@@\n-one\n+uno\n*** Update File: drop.txt\n@@\n-two\n+dos\n*** Add File: new.txt\n+three",
+        );
+        let argv = vec!["apply_patch".to_string(), patch];
+        let MaybeApplyPatchVerified::Body(action) =
+            maybe_parse_apply_patch_verified(&argv, dir.path())
+        else {
+            panic!("expected a verified patch");
+        };
+
+        let keep = dir.path().join("keep.txt");
+        let added = dir.path().join("new.txt");
+        let retained = action.retain_files(&[keep.clone(), added.clone()]).unwrap();
+        let mut paths: Vec<&PathBuf> = retained.changes().keys().collect();
+        paths.sort();
+        assert_eq!(paths, vec![&added, &keep]);
+
+        let hunks = parse_patch(&retained.patch).unwrap().hunks;
+        assert_eq!(
+            hunks
+                .iter()
+                .map(|hunk| hunk.resolve_path(dir.path()))
+                .collect::<Vec<_>>(),
+            vec![keep, added]
+        );
+    }
 }
@@ -430,6 +430,105 @@ fn parse_update_file_chunk(
     Ok((chunk, parsed_lines + start_index))
 }
 
+/// Renders hunks back into patch text that [`parse_patch`] parses into the same hunks.
+///
+/// Context and changed lines are not tracked separately once parsed, so each chunk is
+/// written as its common leading and trailing lines around one block of removals and
+/// additions.
+pub fn format_patch(hunks: &[Hunk]) -> String {
+    let mut out = format!("{BEGIN_PATCH_MARKER}\n");
+    for hunk in hunks {
+        match hunk {
+            AddFile { path, contents } => {
+                out.push_str(&format!("{ADD_FILE_MARKER}{}\n", path.display()));
+                for line in contents.split_terminator('\n') {
+                    out.push_str(&format!("+{line}\n"));
+                }
+            }
+            DeleteFile { path } => {
+                out.push_str(&format!("{DELETE_FILE_MARKER}{}\n", path.display()));
+            }
+            UpdateFile {
+                path,
+                move_path,
+                chunks,
+            } => {
+                out.push_str(&format!("{UPDATE_FILE_MARKER}{}\n", path.display()));
+                if let Some(move_path) = move_path {
+                    out.push_str(&format!("{MOVE_TO_MARKER}{}\n", move_path.display()));
+                }
+                for chunk in chunks {
+                    format_update_file_chunk(chunk, &mut out);
+                }
+            }
+        }
+    }
+    out.push_str(END_PATCH_MARKER);
+    out.push('\n');
+    out
+}
+
+fn format_update_file_chunk(chunk: &UpdateFileChunk, out: &mut String) {
+    match &chunk.change_context {
+        Some(context) => out.push_str(&format!("{CHANGE_CONTEXT_MARKER}{context}\n")),
+        None => out.push_str(&format!("{EMPTY_CHANGE_CONTEXT_MARKER}\n")),
+    }
+    let old = &chunk.old_lines;
+    let new = &chunk.new_lines;
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    for line in &old[..prefix] {
+        out.push_str(&format!(" {line}\n"));
+    }
+    for line in &old[prefix..old.len() - suffix] {
+        out.push_str(&format!("-{line}\n"));
+    }
+    for line in &new[prefix..new.len() - suffix] {
+        out.push_str(&format!("+{line}\n"));
+    }
+    for line in &old[old.len() - suffix..] {
+        out.push_str(&format!(" {line}\n"));
+    }
+    if chunk.is_end_of_file {
+        out.push_str(&format!("{EOF_MARKER}\n"));
+    }
+}
+
+#[test]
+fn test_format_patch_round_trips() {
+    let patch = "*** Begin Patch\n\
+                 *** Add File: path/add.py\n\
+                 +abc\n\
+                 +def\n\
+                 *** Delete File: path/delete.py\n\
+                 *** Update File: path/update.py\n\
+                 *** Move to: path/update2.py\n\
+                 @@ def f():\n\
+                 -    pass\n\
+                 +    return 123\n\
+                 @@\n\
+                 \x20context\n\
+                 -old\n\
+                 \x20mid\n\
+                 +new\n\
+                 \x20tail\n\
+                 *** End of File\n\
+                 *** End Patch";
+    let hunks = parse_patch_text(patch, ParseMode::Strict).unwrap().hunks;
+    let formatted = format_patch(&hunks);
+    assert_eq!(
+        parse_patch_text(&formatted, ParseMode::Strict)
+            .unwrap()
+            .hunks,
+        hunks
+    );
+}
+
 #[test]
 fn test_parse_patch() {
     assert_eq!(
@@ -1,3 +1,4 @@
+use anyhow::Context;
 use clap::Args;
 use clap::CommandFactory;
 use clap::Parser;
@@ -15,12 +16,15 @@ use codex_cli::login::run_login_with_api_key;
 use codex_cli::login::run_login_with_chatgpt;
 use codex_cli::login::run_login_with_device_code;
 use codex_cli::login::run_logout;
+use codex_client::HttpCassetteMode;
+use codex_client::install_http_cassette;
 use codex_cloud_tasks::Cli as CloudTasksCli;
 use codex_common::CliConfigOverrides;
 use codex_exec::Cli as ExecCli;
 use codex_exec::Command as ExecCommand;
 use codex_exec::ReviewArgs;
 use codex_execpolicy::ExecPolicyCheckCommand;
+use codex_execpolicy::ExecPolicySimulateCommand;
 use codex_responses_api_proxy::Args as ResponsesApiProxyArgs;
 use codex_tui::AppExitInfo;
 use codex_tui::Cli as TuiCli;
@@ -31,10 +35,12 @@ use std::path::PathBuf;
 use supports_color::Stream;
 
 mod mcp_cmd;
+mod sessions_cmd;
 #[cfg(not(windows))]
 mod wsl_paths;
 
 use crate::mcp_cmd::McpCli;
+use crate::sessions_cmd::SessionsCli;
 
 use codex_core::config::Config;
 use codex_core::config::ConfigOverrides;
@@ -68,6 +74,9 @@ struct MultitoolCli {
     #[clap(flatten)]
     pub feature_toggles: FeatureToggles,
 
+    #[clap(flatten)]
+    pub http_cassette: HttpCassetteArgs,
+
     #[clap(flatten)]
     interactive: TuiCli,
 
@@ -117,6 +126,9 @@ enum Subcommand {
     /// Resume a previous interactive session (picker by default; use --last to continue the most recent).
     Resume(ResumeCommand),
 
+    /// Inspect and export recorded sessions.
+    Sessions(SessionsCli),
+
     /// [EXPERIMENTAL] Browse tasks from Codex Cloud and apply changes locally.
     #[clap(name = "cloud", alias = "cloud-tasks")]
     Cloud(CloudTasksCli),
@@ -190,6 +202,10 @@ enum ExecpolicySubcommand {
     /// Check execpolicy files against a command.
     #[clap(name = "check")]
     Check(ExecPolicyCheckCommand),
+
+    /// Replay commands from recorded sessions through candidate execpolicy files.
+    #[clap(name = "simulate")]
+    Simulate(ExecPolicySimulateCommand),
 }
 
 #[derive(Debug, Parser)]
@@ -361,6 +377,14 @@ fn run_execpolicycheck(cmd: ExecPolicyCheckCommand) -> anyhow::Result<()> {
     cmd.run()
 }
 
+fn run_execpolicysimulate(cmd: ExecPolicySimulateCommand) -> anyhow::Result<()> {
+    let codex_home = codex_core::config::find_codex_home().ok();
+    cmd.run(
+        codex_home.as_deref(),
+        &codex_core::bash::parse_shell_lc_plain_commands,
+    )
+}
+
 #[derive(Debug, Default, Parser, Clone)]
 struct FeatureToggles {
     /// Enable a feature (repeatable). Equivalent to `-c features.<name>=true`.
@@ -395,6 +419,42 @@ impl FeatureToggles {
     }
 }
 
+#[derive(Debug, Default, Parser, Clone)]
+struct HttpCassetteArgs {
+    /// Record every HTTP exchange with the model provider to DIR so the
+    /// session can be replayed later with `--replay-http`.
+    #[arg(
+        long = "record-http",
+        value_name = "DIR",
+        global = true,
+        conflicts_with = "replay_http"
+    )]
+    record_http: Option<PathBuf>,
+
+    /// Answer model provider requests from a directory recorded with
+    /// `--record-http` instead of the network.
+    #[arg(long = "replay-http", value_name = "DIR", global = true)]
+    replay_http: Option<PathBuf>,
+
+    /// With `--replay-http`, answer a request whose body differs from the
+    /// recording with the next recording for the same URL instead of failing.
+    #[arg(long = "replay-http-loose", global = true, requires = "replay_http")]
+    replay_http_loose: bool,
+}
+
+impl HttpCassetteArgs {
+    fn install(self) -> anyhow::Result<()> {
+        let (mode, dir) = match (self.record_http, self.replay_http) {
+            (Some(dir), _) => (HttpCassetteMode::Record, dir),
+            (None, Some(dir)) if self.replay_http_loose => (HttpCassetteMode::ReplayLoose, dir),
+            (None, Some(dir)) => (HttpCassetteMode::Replay, dir),
+            (None, None) => return Ok(()),
+        };
+        install_http_cassette(mode, dir.clone())
+            .with_context(|| format!("failed to open HTTP cassette {}", dir.display()))
+    }
+}
+
 #[derive(Debug, Parser)]
 struct FeaturesCli {
     #[command(subcommand)]
@@ -437,10 +497,13 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
     let MultitoolCli {
         config_overrides: mut root_config_overrides,
         feature_toggles,
+        http_cassette,
         mut interactive,
         subcommand,
     } = MultitoolCli::parse();
 
+    http_cassette.install()?;
+
     // Fold --enable/--disable into config overrides so they flow to all subcommands.
     let toggle_overrides = feature_toggles.to_overrides()?;
     root_config_overrides.raw_overrides.extend(toggle_overrides);
@@ -509,6 +572,9 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
             let exit_info = run_interactive_tui(interactive, codex_linux_sandbox_exe).await?;
             handle_app_exit(exit_info)?;
         }
+        Some(Subcommand::Sessions(sessions_cli)) => {
+            sessions_cli.run().await?;
+        }
         Some(Subcommand::Login(mut login_cli)) => {
             prepend_config_flags(
                 &mut login_cli.config_overrides,
@@ -594,6 +660,7 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
         },
         Some(Subcommand::Execpolicy(ExecpolicyCommand { sub })) => match sub {
             ExecpolicySubcommand::Check(cmd) => run_execpolicycheck(cmd)?,
+            ExecpolicySubcommand::Simulate(cmd) => run_execpolicysimulate(cmd)?,
         },
         Some(Subcommand::Apply(mut apply_cli)) => {
             prepend_config_flags(
@@ -795,6 +862,7 @@ mod tests {
             config_overrides: root_overrides,
             subcommand,
             feature_toggles: _,
+            http_cassette: _,
         } = cli;
 
         let Subcommand::Resume(ResumeCommand {
@@ -1009,4 +1077,24 @@ mod tests {
             .expect_err("feature should be rejected");
         assert_eq!(err.to_string(), "Unknown feature flag: does_not_exist");
     }
+
+    #[test]
+    fn http_cassette_flags_apply_to_subcommands() {
+        let cli = MultitoolCli::try_parse_from(["codex", "exec", "--replay-http", "/tmp/cassette"])
+            .expect("parse");
+        assert_eq!(
+            cli.http_cassette.replay_http,
+            Some(PathBuf::from("/tmp/cassette"))
+        );
+
+        let err = MultitoolCli::try_parse_from([
+            "codex",
+            "--record-http",
+            "/tmp/a",
+            "--replay-http",
+            "/tmp/b",
+        ])
+        .expect_err("record and replay are mutually exclusive");
+        assert_eq!(err.kind(), clap::error::ErrorKind::ArgumentConflict);
+    }
 }
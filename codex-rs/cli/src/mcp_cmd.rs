@@ -232,6 +232,7 @@ async fn run_add(config_overrides: &CliConfigOverrides, add_args: AddArgs) -> Re
         } => McpServerTransportConfig::StreamableHttp {
             url,
             bearer_token_env_var,
+            auth_command: None,
             http_headers: None,
             env_http_headers: None,
         },
@@ -260,6 +261,7 @@ async fn run_add(config_overrides: &CliConfigOverrides, add_args: AddArgs) -> Re
     if let McpServerTransportConfig::StreamableHttp {
         url,
         bearer_token_env_var: None,
+        auth_command: None,
         http_headers,
         env_http_headers,
     } = transport
@@ -429,6 +431,7 @@ async fn run_list(config_overrides: &CliConfigOverrides, list_args: ListArgs) ->
                     McpServerTransportConfig::StreamableHttp {
                         url,
                         bearer_token_env_var,
+                        auth_command,
                         http_headers,
                         env_http_headers,
                     } => {
@@ -436,6 +439,7 @@ async fn run_list(config_overrides: &CliConfigOverrides, list_args: ListArgs) ->
                             "type": "streamable_http",
                             "url": url,
                             "bearer_token_env_var": bearer_token_env_var,
+                            "auth_command": auth_command,
                             "http_headers": http_headers,
                             "env_http_headers": env_http_headers,
                         })
@@ -675,12 +679,14 @@ async fn run_get(config_overrides: &CliConfigOverrides, get_args: GetArgs) -> Re
             McpServerTransportConfig::StreamableHttp {
                 url,
                 bearer_token_env_var,
+                auth_command,
                 http_headers,
                 env_http_headers,
             } => serde_json::json!({
                 "type": "streamable_http",
                 "url": url,
                 "bearer_token_env_var": bearer_token_env_var,
+                "auth_command": auth_command,
                 "http_headers": http_headers,
                 "env_http_headers": env_http_headers,
             }),
@@ -752,6 +758,7 @@ async fn run_get(config_overrides: &CliConfigOverrides, get_args: GetArgs) -> Re
         McpServerTransportConfig::StreamableHttp {
             url,
             bearer_token_env_var,
+            auth_command,
             http_headers,
             env_http_headers,
         } => {
@@ -759,6 +766,11 @@ async fn run_get(config_overrides: &CliConfigOverrides, get_args: GetArgs) -> Re
             println!("  url: {url}");
             let bearer_token_display = bearer_token_env_var.as_deref().unwrap_or("-");
             println!("  bearer_token_env_var: {bearer_token_display}");
+            let auth_command_display = auth_command
+                .as_ref()
+                .map(|argv| argv.join(" "))
+                .unwrap_or_else(|| "-".to_string());
+            println!("  auth_command: {auth_command_display}");
             let headers_display = match http_headers {
                 Some(map) if !map.is_empty() => {
                     let mut pairs: Vec<_> = map.iter().collect();
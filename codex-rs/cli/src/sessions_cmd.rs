@@ -0,0 +1,84 @@
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+use codex_core::config::find_codex_home;
+use codex_core::export::ExportFormat;
+use codex_core::export::ExportOptions;
+use codex_core::export::export_rollout;
+use codex_core::find_conversation_path_by_id_str;
+
+/// Subcommands:
+/// - `export` — render a recorded session as Markdown, HTML or plain text
+#[derive(Debug, clap::Parser)]
+pub struct SessionsCli {
+    #[command(subcommand)]
+    pub subcommand: SessionsSubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum SessionsSubcommand {
+    /// Export a session transcript.
+    Export(ExportArgs),
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct ExportArgs {
+    /// Session id (UUID) or path to a rollout `.jsonl` file.
+    #[arg(value_name = "SESSION")]
+    pub session: String,
+
+    /// Output format: md, html or txt.
+    #[arg(long, short = 'f', default_value = "md")]
+    pub format: ExportFormat,
+
+    /// Write the transcript to this file instead of stdout.
+    #[arg(long, short = 'o', value_name = "FILE")]
+    pub output: Option<PathBuf>,
+
+    /// Replace API keys, tokens, passwords and private keys with `[REDACTED]`.
+    #[arg(long, default_value_t = false)]
+    pub redact: bool,
+}
+
+impl SessionsCli {
+    pub async fn run(self) -> Result<()> {
+        match self.subcommand {
+            SessionsSubcommand::Export(args) => run_export(args).await,
+        }
+    }
+}
+
+async fn run_export(args: ExportArgs) -> Result<()> {
+    let candidate = PathBuf::from(&args.session);
+    let path = if candidate.is_file() {
+        candidate
+    } else {
+        let codex_home = find_codex_home().context("failed to resolve CODEX_HOME")?;
+        match find_conversation_path_by_id_str(&codex_home, &args.session).await? {
+            Some(path) => path,
+            None => bail!("no recorded session found for `{}`", args.session),
+        }
+    };
+
+    let transcript = export_rollout(
+        &path,
+        ExportOptions {
+            format: args.format,
+            redact_secrets: args.redact,
+        },
+    )
+    .await
+    .with_context(|| format!("failed to read session from {}", path.display()))?;
+
+    match args.output {
+        Some(output) => {
+            std::fs::write(&output, transcript)
+                .with_context(|| format!("failed to write {}", output.display()))?;
+            eprintln!("Exported session to {}", output.display());
+        }
+        None => print!("{transcript}"),
+    }
+    Ok(())
+}
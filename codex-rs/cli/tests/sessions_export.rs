@@ -0,0 +1,104 @@
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Result;
+use predicates::str::contains;
+use serde_json::json;
+use tempfile::TempDir;
+
+const SECRET: &str = "sk-abcdefghijklmnopqrstuvwx";
+
+fn codex_command(codex_home: &Path) -> Result<assert_cmd::Command> {
+    let mut cmd = assert_cmd::Command::cargo_bin("codex")?;
+    cmd.env("CODEX_HOME", codex_home);
+    Ok(cmd)
+}
+
+/// Writes a rollout with one user message and one agent reply that leaks a
+/// secret.
+fn write_rollout(dir: &Path) -> Result<PathBuf> {
+    let timestamp = "2025-01-02T03:04:05.000Z";
+    let lines = [
+        json!({
+            "timestamp": timestamp,
+            "type": "session_meta",
+            "payload": {
+                "id": "67e55044-10b1-426f-9247-bb680e5fe0c8",
+                "timestamp": timestamp,
+                "cwd": "/",
+                "originator": "codex",
+                "cli_version": "0.0.0",
+                "instructions": null,
+                "source": "cli",
+                "model_provider": null
+            }
+        }),
+        json!({
+            "timestamp": timestamp,
+            "type": "event_msg",
+            "payload": {"type": "user_message", "message": "list files"}
+        }),
+        json!({
+            "timestamp": timestamp,
+            "type": "event_msg",
+            "payload": {"type": "agent_message", "message": format!("Use key {SECRET}.")}
+        }),
+    ];
+    let path = dir.join("rollout.jsonl");
+    let contents: String = lines.iter().map(|line| format!("{line}\n")).collect();
+    std::fs::write(&path, contents)?;
+    Ok(path)
+}
+
+#[test]
+fn export_prints_markdown_to_stdout() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let rollout = write_rollout(codex_home.path())?;
+
+    let mut cmd = codex_command(codex_home.path())?;
+    cmd.arg("sessions")
+        .arg("export")
+        .arg(&rollout)
+        .assert()
+        .success()
+        .stdout(contains("## User\n\nlist files"))
+        .stdout(contains(SECRET));
+
+    Ok(())
+}
+
+#[test]
+fn export_writes_redacted_html_to_file() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let rollout = write_rollout(codex_home.path())?;
+    let output = codex_home.path().join("session.html");
+
+    let mut cmd = codex_command(codex_home.path())?;
+    cmd.arg("sessions")
+        .arg("export")
+        .arg(&rollout)
+        .args(["--format", "html", "--redact", "--output"])
+        .arg(&output)
+        .assert()
+        .success();
+
+    let html = std::fs::read_to_string(&output)?;
+    assert!(html.starts_with("<!DOCTYPE html>"), "{html}");
+    assert!(html.contains("[REDACTED]"), "{html}");
+    assert!(!html.contains(SECRET), "{html}");
+
+    Ok(())
+}
+
+#[test]
+fn export_rejects_unknown_session() -> Result<()> {
+    let codex_home = TempDir::new()?;
+
+    let mut cmd = codex_command(codex_home.path())?;
+    cmd.args(["sessions", "export", "67e55044-10b1-426f-9247-bb680e5fe0c8"])
+        .assert()
+        .failure()
+        .stderr(contains("no recorded session found"));
+
+    Ok(())
+}
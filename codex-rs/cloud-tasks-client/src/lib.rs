@@ -20,10 +20,18 @@ mod mock;
 #[cfg(feature = "online")]
 mod http;
 
+#[cfg(feature = "local")]
+mod local;
+
 #[cfg(feature = "mock")]
 pub use mock::MockClient;
 
 #[cfg(feature = "online")]
 pub use http::HttpClient;
 
+#[cfg(feature = "local")]
+pub use local::LOCAL_ENVIRONMENT_LABEL;
+#[cfg(feature = "local")]
+pub use local::LocalClient;
+
 // Reusable apply engine now lives in the shared crate `codex-git`.
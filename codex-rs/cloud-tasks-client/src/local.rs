@@ -0,0 +1,878 @@
+//! Offline best-of-N backend.
+//!
+//! Each attempt of a task runs `codex exec` in its own detached git worktree
+//! created from the requested ref. When an attempt finishes, its diff against
+//! the commit the worktree was created at (including anything the attempt
+//! committed) and its final message are recorded in
+//! `<state_dir>/tasks/<task_id>.json` and the worktree is removed, whether the
+//! attempt succeeded, failed or was cancelled. Applying an attempt uses the
+//! same `git apply` engine as the hosted backend.
+//!
+//! Attempts run as background tasks of the current process; attempts that
+//! were still running when a previous process exited are reported as failed.
+
+use crate::ApplyOutcome;
+use crate::ApplyStatus;
+use crate::AttemptStatus;
+use crate::CloudBackend;
+use crate::CloudTaskError;
+use crate::CreatedTask;
+use crate::DiffSummary;
+use crate::Result;
+use crate::TaskId;
+use crate::TaskStatus;
+use crate::TaskSummary;
+use crate::TaskText;
+use crate::TurnAttempt;
+use chrono::DateTime;
+use chrono::Utc;
+use serde::Deserialize;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::sync::Arc;
+use tokio::process::Command;
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+
+/// Label reported for tasks created by this backend.
+pub const LOCAL_ENVIRONMENT_LABEL: &str = "Local worktrees";
+
+#[derive(Clone)]
+pub struct LocalClient {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    repo_root: PathBuf,
+    state_dir: PathBuf,
+    codex_exe: PathBuf,
+    /// Serializes read-modify-write cycles on task records.
+    records: Mutex<()>,
+    /// Runner for each task started by this process.
+    running: Mutex<HashMap<String, JoinHandle<()>>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct LocalTask {
+    id: String,
+    title: String,
+    prompt: String,
+    git_ref: String,
+    environment_id: String,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    #[serde(default)]
+    applied: bool,
+    attempts: Vec<LocalAttempt>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct LocalAttempt {
+    turn_id: String,
+    placement: i64,
+    status: LocalAttemptStatus,
+    #[serde(default)]
+    started_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    diff: Option<String>,
+    #[serde(default)]
+    messages: Vec<String>,
+    #[serde(default)]
+    error: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+enum LocalAttemptStatus {
+    Pending,
+    InProgress,
+    Completed,
+    Failed,
+}
+
+impl LocalClient {
+    /// Creates a backend that runs attempts against the git repository at
+    /// `repo_root` and keeps task records and worktrees under `state_dir`.
+    pub fn new(repo_root: PathBuf, state_dir: PathBuf) -> Self {
+        let codex_exe = std::env::current_exe().unwrap_or_else(|_| PathBuf::from("codex"));
+        Self {
+            inner: Arc::new(Inner {
+                repo_root,
+                state_dir,
+                codex_exe,
+                records: Mutex::new(()),
+                running: Mutex::new(HashMap::new()),
+            }),
+        }
+    }
+
+    /// Overrides the executable invoked as `<exe> exec …` for each attempt.
+    pub fn with_codex_exe(self, codex_exe: PathBuf) -> Self {
+        let inner = &self.inner;
+        Self {
+            inner: Arc::new(Inner {
+                repo_root: inner.repo_root.clone(),
+                state_dir: inner.state_dir.clone(),
+                codex_exe,
+                records: Mutex::new(()),
+                running: Mutex::new(HashMap::new()),
+            }),
+        }
+    }
+
+    /// Waits until every attempt of a task started by this process finishes.
+    pub async fn wait_for_task(&self, id: &TaskId) {
+        let handle = self.inner.running.lock().await.remove(&id.0);
+        if let Some(handle) = handle {
+            let _ = handle.await;
+        }
+    }
+
+    async fn is_running(&self, id: &str) -> bool {
+        self.inner
+            .running
+            .lock()
+            .await
+            .get(id)
+            .is_some_and(|handle| !handle.is_finished())
+    }
+
+    async fn load(&self, id: &TaskId) -> Result<LocalTask> {
+        self.inner.load(&id.0).await
+    }
+
+    /// Loads a task and marks attempts orphaned by an earlier process as
+    /// failed, so they are not reported as running forever.
+    async fn load_effective(&self, id: &TaskId) -> Result<LocalTask> {
+        let mut task = self.load(id).await?;
+        if !self.is_running(&task.id).await {
+            for attempt in &mut task.attempts {
+                if matches!(
+                    attempt.status,
+                    LocalAttemptStatus::Pending | LocalAttemptStatus::InProgress
+                ) {
+                    attempt.status = LocalAttemptStatus::Failed;
+                    attempt.error = Some("interrupted before completion".to_string());
+                }
+            }
+        }
+        Ok(task)
+    }
+
+    async fn apply(
+        &self,
+        id: TaskId,
+        diff_override: Option<String>,
+        preflight: bool,
+    ) -> Result<ApplyOutcome> {
+        let task = self.load_effective(&id).await?;
+        let diff = match diff_override.or_else(|| primary_attempt(&task)?.diff.clone()) {
+            Some(diff) if !diff.trim().is_empty() => diff,
+            _ => {
+                return Err(CloudTaskError::Msg(format!(
+                    "No diff available for task {}",
+                    id.0
+                )));
+            }
+        };
+
+        let req = codex_git::ApplyGitRequest {
+            cwd: self.inner.repo_root.clone(),
+            diff,
+            revert: false,
+            preflight,
+        };
+        let r = codex_git::apply_git_patch(&req)
+            .map_err(|e| CloudTaskError::Io(format!("git apply failed to run: {e}")))?;
+
+        let status = if r.exit_code == 0 {
+            ApplyStatus::Success
+        } else if !r.applied_paths.is_empty() || !r.conflicted_paths.is_empty() {
+            ApplyStatus::Partial
+        } else {
+            ApplyStatus::Error
+        };
+        let applied = matches!(status, ApplyStatus::Success) && !preflight;
+        let id = id.0;
+        let message = match (preflight, &status) {
+            (true, ApplyStatus::Success) => {
+                format!("Preflight passed for task {id} (applies cleanly)")
+            }
+            (false, ApplyStatus::Success) => format!(
+                "Applied task {id} locally ({} files)",
+                r.applied_paths.len()
+            ),
+            (_, ApplyStatus::Partial | ApplyStatus::Error) => format!(
+                "{} for task {id} (applied={}, skipped={}, conflicts={})",
+                if preflight {
+                    "Preflight: patch does not apply cleanly"
+                } else {
+                    "Apply did not fully succeed"
+                },
+                r.applied_paths.len(),
+                r.skipped_paths.len(),
+                r.conflicted_paths.len()
+            ),
+        };
+
+        if applied {
+            self.inner.update(&id, |task| task.applied = true).await?;
+        }
+
+        Ok(ApplyOutcome {
+            applied,
+            status,
+            message,
+            skipped_paths: r.skipped_paths,
+            conflict_paths: r.conflicted_paths,
+        })
+    }
+}
+
+impl Inner {
+    fn task_path(&self, id: &str) -> Result<PathBuf> {
+        let valid = !id.is_empty()
+            && id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid {
+            return Err(CloudTaskError::Msg(format!("invalid local task id: {id}")));
+        }
+        Ok(self.state_dir.join("tasks").join(format!("{id}.json")))
+    }
+
+    fn worktree_path(&self, id: &str, placement: i64) -> PathBuf {
+        self.state_dir
+            .join("worktrees")
+            .join(id)
+            .join(format!("attempt-{}", placement + 1))
+    }
+
+    async fn load(&self, id: &str) -> Result<LocalTask> {
+        let path = self.task_path(id)?;
+        let contents = match tokio::fs::read_to_string(&path).await {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Err(CloudTaskError::Msg(format!("Task {id} not found")));
+            }
+            Err(e) => return Err(CloudTaskError::Io(e.to_string())),
+        };
+        serde_json::from_str(&contents)
+            .map_err(|e| CloudTaskError::Io(format!("failed to parse {}: {e}", path.display())))
+    }
+
+    async fn save(&self, task: &LocalTask) -> Result<()> {
+        let path = self.task_path(&task.id)?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(|e| CloudTaskError::Io(e.to_string()))?;
+        }
+        let json =
+            serde_json::to_string_pretty(task).map_err(|e| CloudTaskError::Io(e.to_string()))?;
+        // Write then rename so readers never observe a partial record.
+        let tmp = path.with_extension("json.tmp");
+        tokio::fs::write(&tmp, json)
+            .await
+            .map_err(|e| CloudTaskError::Io(e.to_string()))?;
+        tokio::fs::rename(&tmp, &path)
+            .await
+            .map_err(|e| CloudTaskError::Io(e.to_string()))
+    }
+
+    async fn update(&self, id: &str, f: impl FnOnce(&mut LocalTask)) -> Result<()> {
+        let _guard = self.records.lock().await;
+        let mut task = self.load(id).await?;
+        f(&mut task);
+        task.updated_at = Utc::now();
+        self.save(&task).await
+    }
+
+    async fn update_attempt(&self, id: &str, placement: i64, f: impl FnOnce(&mut LocalAttempt)) {
+        let result = self
+            .update(id, |task| {
+                if let Some(attempt) = task
+                    .attempts
+                    .iter_mut()
+                    .find(|attempt| attempt.placement == placement)
+                {
+                    f(attempt);
+                }
+            })
+            .await;
+        if let Err(err) = result {
+            tracing::warn!("failed to record attempt {placement} of local task {id}: {err}");
+        }
+    }
+
+    async fn run_attempt(self: Arc<Self>, task: LocalTask, placement: i64) {
+        self.update_attempt(&task.id, placement, |attempt| {
+            attempt.status = LocalAttemptStatus::InProgress;
+            attempt.started_at = Some(Utc::now());
+        })
+        .await;
+
+        let worktree = self.worktree_path(&task.id, placement);
+        let outcome = {
+            let _cleanup = WorktreeGuard {
+                repo_root: self.repo_root.clone(),
+                worktree: worktree.clone(),
+            };
+            self.run_in_worktree(&task, &worktree).await
+        };
+
+        self.update_attempt(&task.id, placement, |attempt| match outcome {
+            Ok((diff, messages)) => {
+                attempt.status = LocalAttemptStatus::Completed;
+                attempt.diff = (!diff.trim().is_empty()).then_some(diff);
+                attempt.messages = messages;
+            }
+            Err(err) => {
+                attempt.status = LocalAttemptStatus::Failed;
+                attempt.error = Some(err.clone());
+                attempt.messages = vec![err];
+            }
+        })
+        .await;
+    }
+
+    /// Returns the attempt's diff against the commit `git_ref` pointed to when
+    /// the worktree was created, and its final message.
+    async fn run_in_worktree(
+        &self,
+        task: &LocalTask,
+        worktree: &Path,
+    ) -> std::result::Result<(String, Vec<String>), String> {
+        if let Some(parent) = worktree.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(|e| format!("failed to create {}: {e}", parent.display()))?;
+        }
+        let base = git(
+            &self.repo_root,
+            &[
+                "rev-parse",
+                "--verify",
+                &format!("{}^{{commit}}", task.git_ref),
+            ],
+        )
+        .await?
+        .trim()
+        .to_string();
+        let worktree_arg = worktree.to_string_lossy().into_owned();
+        git(
+            &self.repo_root,
+            &["worktree", "add", "--detach", &worktree_arg, &base],
+        )
+        .await?;
+
+        let last_message = worktree.with_extension("last-message.txt");
+        let log = std::fs::File::create(worktree.with_extension("log"))
+            .map_err(|e| format!("failed to create attempt log: {e}"))?;
+        let log_err = log
+            .try_clone()
+            .map_err(|e| format!("failed to create attempt log: {e}"))?;
+        let status = Command::new(&self.codex_exe)
+            .arg("exec")
+            .arg("--full-auto")
+            .arg("--cd")
+            .arg(worktree)
+            .arg("--output-last-message")
+            .arg(&last_message)
+            .arg("--")
+            .arg(&task.prompt)
+            .stdin(Stdio::null())
+            .stdout(log)
+            .stderr(log_err)
+            .kill_on_drop(true)
+            .status()
+            .await
+            .map_err(|e| format!("failed to run {}: {e}", self.codex_exe.display()))?;
+        if !status.success() {
+            return Err(format!("codex exec exited with {status}"));
+        }
+
+        git(worktree, &["add", "-A"]).await?;
+        // Diff against the base rather than HEAD so commits made by the
+        // attempt are part of the result.
+        let diff = git(worktree, &["diff", "--cached", "--binary", &base]).await?;
+        let messages = tokio::fs::read_to_string(&last_message)
+            .await
+            .ok()
+            .map(|message| message.trim().to_string())
+            .filter(|message| !message.is_empty())
+            .into_iter()
+            .collect();
+        Ok((diff, messages))
+    }
+}
+
+/// Removes an attempt's worktree when dropped, so failed, panicking and
+/// cancelled attempts do not leave worktrees behind. Runs git synchronously
+/// because `Drop` cannot await.
+struct WorktreeGuard {
+    repo_root: PathBuf,
+    worktree: PathBuf,
+}
+
+impl Drop for WorktreeGuard {
+    fn drop(&mut self) {
+        let git = |args: &[&std::ffi::OsStr]| {
+            std::process::Command::new("git")
+                .arg("-C")
+                .arg(&self.repo_root)
+                .args(args)
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .status()
+        };
+        if self.worktree.exists() {
+            let removed = git(&[
+                "worktree".as_ref(),
+                "remove".as_ref(),
+                "--force".as_ref(),
+                self.worktree.as_os_str(),
+            ]);
+            if !removed.is_ok_and(|status| status.success())
+                && let Err(err) = std::fs::remove_dir_all(&self.worktree)
+            {
+                tracing::warn!(
+                    "failed to remove worktree {}: {err}",
+                    self.worktree.display()
+                );
+            }
+        }
+        // Drops the registration of a worktree whose directory is gone, e.g.
+        // one that failed halfway through `git worktree add`.
+        if let Err(err) = git(&["worktree".as_ref(), "prune".as_ref()]) {
+            tracing::warn!("failed to prune worktrees: {err}");
+        }
+    }
+}
+
+async fn git(cwd: &Path, args: &[&str]) -> std::result::Result<String, String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(cwd)
+        .args(args)
+        .stdin(Stdio::null())
+        .output()
+        .await
+        .map_err(|e| format!("failed to run git: {e}"))?;
+    if !output.status.success() {
+        return Err(format!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// The attempt surfaced as the task's main result: the first attempt that
+/// produced a diff, falling back to the first attempt.
+fn primary_attempt(task: &LocalTask) -> Option<&LocalAttempt> {
+    task.attempts
+        .iter()
+        .find(|attempt| attempt.diff.is_some())
+        .or_else(|| task.attempts.first())
+}
+
+fn attempt_status(status: LocalAttemptStatus) -> AttemptStatus {
+    match status {
+        LocalAttemptStatus::Pending => AttemptStatus::Pending,
+        LocalAttemptStatus::InProgress => AttemptStatus::InProgress,
+        LocalAttemptStatus::Completed => AttemptStatus::Completed,
+        LocalAttemptStatus::Failed => AttemptStatus::Failed,
+    }
+}
+
+fn task_status(task: &LocalTask) -> TaskStatus {
+    let statuses = task.attempts.iter().map(|attempt| attempt.status);
+    if task.applied {
+        TaskStatus::Applied
+    } else if statuses.clone().any(|status| {
+        matches!(
+            status,
+            LocalAttemptStatus::Pending | LocalAttemptStatus::InProgress
+        )
+    }) {
+        TaskStatus::Pending
+    } else if statuses.any(|status| status == LocalAttemptStatus::Completed) {
+        TaskStatus::Ready
+    } else {
+        TaskStatus::Error
+    }
+}
+
+fn diff_summary(diff: Option<&str>) -> DiffSummary {
+    let mut summary = DiffSummary::default();
+    for line in diff.unwrap_or_default().lines() {
+        if line.starts_with("diff --git ") {
+            summary.files_changed += 1;
+        } else if line.starts_with("+++") || line.starts_with("---") {
+            continue;
+        } else if line.starts_with('+') {
+            summary.lines_added += 1;
+        } else if line.starts_with('-') {
+            summary.lines_removed += 1;
+        }
+    }
+    summary
+}
+
+fn task_summary(task: &LocalTask) -> TaskSummary {
+    TaskSummary {
+        id: TaskId(task.id.clone()),
+        title: task.title.clone(),
+        status: task_status(task),
+        updated_at: task.updated_at,
+        environment_id: Some(task.environment_id.clone()),
+        environment_label: Some(LOCAL_ENVIRONMENT_LABEL.to_string()),
+        summary: diff_summary(primary_attempt(task).and_then(|attempt| attempt.diff.as_deref())),
+        is_review: false,
+        attempt_total: Some(task.attempts.len()),
+    }
+}
+
+#[async_trait::async_trait]
+impl CloudBackend for LocalClient {
+    async fn list_tasks(&self, env: Option<&str>) -> Result<Vec<TaskSummary>> {
+        let dir = self.inner.state_dir.join("tasks");
+        let mut entries = match tokio::fs::read_dir(&dir).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(CloudTaskError::Io(e.to_string())),
+        };
+
+        let mut tasks = Vec::new();
+        while let Some(entry) = entries
+            .next_entry()
+            .await
+            .map_err(|e| CloudTaskError::Io(e.to_string()))?
+        {
+            let path = entry.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                continue;
+            }
+            let Some(id) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                continue;
+            };
+            match self.load_effective(&TaskId(id.to_string())).await {
+                Ok(task) if env.is_none_or(|env| env == task.environment_id) => {
+                    tasks.push(task_summary(&task));
+                }
+                Ok(_) => {}
+                Err(err) => tracing::warn!("skipping local task {id}: {err}"),
+            }
+        }
+        tasks.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+        Ok(tasks)
+    }
+
+    async fn get_task_summary(&self, id: TaskId) -> Result<TaskSummary> {
+        Ok(task_summary(&self.load_effective(&id).await?))
+    }
+
+    async fn get_task_diff(&self, id: TaskId) -> Result<Option<String>> {
+        let task = self.load_effective(&id).await?;
+        Ok(primary_attempt(&task).and_then(|attempt| attempt.diff.clone()))
+    }
+
+    async fn get_task_messages(&self, id: TaskId) -> Result<Vec<String>> {
+        let task = self.load_effective(&id).await?;
+        Ok(primary_attempt(&task)
+            .map(|attempt| attempt.messages.clone())
+            .unwrap_or_default())
+    }
+
+    async fn get_task_text(&self, id: TaskId) -> Result<TaskText> {
+        let task = self.load_effective(&id).await?;
+        let primary = primary_attempt(&task);
+        Ok(TaskText {
+            prompt: Some(task.prompt.clone()),
+            messages: primary
+                .map(|attempt| attempt.messages.clone())
+                .unwrap_or_default(),
+            turn_id: primary.map(|attempt| attempt.turn_id.clone()),
+            sibling_turn_ids: task
+                .attempts
+                .iter()
+                .filter(|attempt| {
+                    Some(attempt.turn_id.as_str()) != primary.map(|p| p.turn_id.as_str())
+                })
+                .map(|attempt| attempt.turn_id.clone())
+                .collect(),
+            attempt_placement: primary.map(|attempt| attempt.placement),
+            attempt_status: primary
+                .map(|attempt| attempt_status(attempt.status))
+                .unwrap_or_default(),
+        })
+    }
+
+    async fn list_sibling_attempts(
+        &self,
+        task: TaskId,
+        turn_id: String,
+    ) -> Result<Vec<TurnAttempt>> {
+        let task = self.load_effective(&task).await?;
+        Ok(task
+            .attempts
+            .into_iter()
+            .filter(|attempt| attempt.turn_id != turn_id)
+            .map(|attempt| TurnAttempt {
+                turn_id: attempt.turn_id,
+                attempt_placement: Some(attempt.placement),
+                created_at: attempt.started_at,
+                status: attempt_status(attempt.status),
+                diff: attempt.diff,
+                messages: attempt.messages,
+            })
+            .collect())
+    }
+
+    async fn apply_task_preflight(
+        &self,
+        id: TaskId,
+        diff_override: Option<String>,
+    ) -> Result<ApplyOutcome> {
+        self.apply(id, diff_override, true).await
+    }
+
+    async fn apply_task(&self, id: TaskId, diff_override: Option<String>) -> Result<ApplyOutcome> {
+        self.apply(id, diff_override, false).await
+    }
+
+    async fn create_task(
+        &self,
+        env_id: &str,
+        prompt: &str,
+        git_ref: &str,
+        qa_mode: bool,
+        best_of_n: usize,
+    ) -> Result<CreatedTask> {
+        // QA mode configures hosted environments; local worktrees have none.
+        let _ = qa_mode;
+        let now = Utc::now();
+        let id = format!("local_{}", now.format("%Y%m%d%H%M%S%3f"));
+        let title = prompt
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .unwrap_or("Untitled task")
+            .chars()
+            .take(120)
+            .collect();
+        let attempts = (0..best_of_n.max(1) as i64)
+            .map(|placement| LocalAttempt {
+                turn_id: format!("{id}-attempt-{}", placement + 1),
+                placement,
+                status: LocalAttemptStatus::Pending,
+                started_at: None,
+                diff: None,
+                messages: Vec::new(),
+                error: None,
+            })
+            .collect();
+        let task = LocalTask {
+            id: id.clone(),
+            title,
+            prompt: prompt.to_string(),
+            git_ref: git_ref.to_string(),
+            environment_id: env_id.to_string(),
+            created_at: now,
+            updated_at: now,
+            applied: false,
+            attempts,
+        };
+        self.inner.save(&task).await?;
+
+        let inner = Arc::clone(&self.inner);
+        let runner = tokio::spawn(async move {
+            let attempts: Vec<JoinHandle<()>> = task
+                .attempts
+                .iter()
+                .map(|attempt| {
+                    tokio::spawn(Arc::clone(&inner).run_attempt(task.clone(), attempt.placement))
+                })
+                .collect();
+            for attempt in attempts {
+                let _ = attempt.await;
+            }
+        });
+        self.inner.running.lock().await.insert(id.clone(), runner);
+
+        Ok(CreatedTask { id: TaskId(id) })
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::os::unix::fs::PermissionsExt;
+
+    fn run_git(dir: &Path, args: &[&str]) {
+        let status = std::process::Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(args)
+            .status()
+            .expect("run git");
+        assert!(status.success(), "git {args:?} failed");
+    }
+
+    /// Stand-in for `codex exec` that edits README.md in the worktree passed
+    /// via `--cd`, writes the final message and then runs `extra`.
+    fn fake_codex(dir: &Path, extra: &str) -> PathBuf {
+        let script = dir.join("fake-codex");
+        std::fs::write(
+            &script,
+            format!(
+                r#"#!/bin/sh
+while [ $# -gt 0 ]; do
+  case "$1" in
+    --cd) cd "$2"; shift 2 ;;
+    --output-last-message) out="$2"; shift 2 ;;
+    *) shift ;;
+  esac
+done
+echo "changed by $(basename "$PWD")" >> README.md
+echo "done" > "$out"
+{extra}
+"#
+            ),
+        )
+        .expect("write script");
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).expect("chmod");
+        script
+    }
+
+    fn init_repo(dir: &Path) -> PathBuf {
+        let repo = dir.join("repo");
+        std::fs::create_dir(&repo).expect("mkdir");
+        run_git(&repo, &["init", "-q"]);
+        run_git(&repo, &["config", "user.email", "dev@example.com"]);
+        run_git(&repo, &["config", "user.name", "Dev"]);
+        std::fs::write(repo.join("README.md"), "hello\n").expect("write");
+        run_git(&repo, &["add", "."]);
+        run_git(&repo, &["commit", "-qm", "init"]);
+        repo
+    }
+
+    /// Number of worktrees git still has registered, including the main one.
+    fn registered_worktrees(repo: &Path) -> usize {
+        let output = std::process::Command::new("git")
+            .arg("-C")
+            .arg(repo)
+            .args(["worktree", "list", "--porcelain"])
+            .output()
+            .expect("run git");
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter(|line| line.starts_with("worktree "))
+            .count()
+    }
+
+    #[tokio::test]
+    async fn runs_attempts_in_worktrees_and_applies_selected_diff() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let repo = init_repo(tmp.path());
+
+        let client = LocalClient::new(repo.clone(), tmp.path().join("state"))
+            .with_codex_exe(fake_codex(tmp.path(), ""));
+        let created = client
+            .create_task("local", "Update the README", "HEAD", false, 2)
+            .await
+            .expect("create task");
+        client.wait_for_task(&created.id).await;
+
+        let summary = client
+            .get_task_summary(created.id.clone())
+            .await
+            .expect("summary");
+        assert_eq!(summary.status, TaskStatus::Ready);
+        assert_eq!(summary.attempt_total, Some(2));
+        assert_eq!(
+            summary.summary,
+            DiffSummary {
+                files_changed: 1,
+                lines_added: 1,
+                lines_removed: 0,
+            }
+        );
+
+        let text = client
+            .get_task_text(created.id.clone())
+            .await
+            .expect("text");
+        assert_eq!(text.messages, vec!["done".to_string()]);
+        let siblings = client
+            .list_sibling_attempts(created.id.clone(), text.turn_id.expect("turn id"))
+            .await
+            .expect("siblings");
+        assert_eq!(siblings.len(), 1);
+        let sibling_diff = siblings[0].diff.clone().expect("sibling diff");
+        assert!(sibling_diff.contains("+changed by attempt-2"));
+
+        let outcome = client
+            .apply_task(created.id.clone(), Some(sibling_diff))
+            .await
+            .expect("apply");
+        assert_eq!(outcome.status, ApplyStatus::Success);
+        assert_eq!(
+            std::fs::read_to_string(repo.join("README.md")).expect("read"),
+            "hello\nchanged by attempt-2\n"
+        );
+        assert_eq!(
+            client
+                .get_task_summary(created.id)
+                .await
+                .expect("summary")
+                .status,
+            TaskStatus::Applied
+        );
+    }
+
+    #[tokio::test]
+    async fn records_committed_changes_and_removes_worktree() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let repo = init_repo(tmp.path());
+        let client = LocalClient::new(repo.clone(), tmp.path().join("state"))
+            .with_codex_exe(fake_codex(tmp.path(), "git commit -qam attempt"));
+        let created = client
+            .create_task("local", "Update the README", "HEAD", false, 1)
+            .await
+            .expect("create task");
+        client.wait_for_task(&created.id).await;
+
+        let diff = client
+            .get_task_diff(created.id)
+            .await
+            .expect("diff")
+            .expect("committed change is part of the diff");
+        assert!(diff.contains("+changed by attempt-1"), "{diff}");
+        assert_eq!(registered_worktrees(&repo), 1);
+    }
+
+    #[tokio::test]
+    async fn removes_worktree_of_failed_attempt() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let repo = init_repo(tmp.path());
+        let client = LocalClient::new(repo.clone(), tmp.path().join("state"))
+            .with_codex_exe(fake_codex(tmp.path(), "exit 3"));
+        let created = client
+            .create_task("local", "Update the README", "HEAD", false, 1)
+            .await
+            .expect("create task");
+        client.wait_for_task(&created.id).await;
+
+        let summary = client.get_task_summary(created.id).await.expect("summary");
+        assert_eq!(summary.status, TaskStatus::Error);
+        assert_eq!(registered_worktrees(&repo), 1);
+    }
+}
@@ -33,16 +33,20 @@ struct ApplyJob {
     diff_override: Option<String>,
 }
 
+/// Environment id used for tasks run by the local worktree backend.
+const LOCAL_ENVIRONMENT_ID: &str = "local";
+
 struct BackendContext {
     backend: Arc<dyn codex_cloud_tasks_client::CloudBackend>,
     base_url: String,
+    /// Set when tasks run locally in git worktrees instead of in the cloud.
+    local: Option<Arc<codex_cloud_tasks_client::LocalClient>>,
 }
 
 async fn init_backend(user_agent_suffix: &str) -> anyhow::Result<BackendContext> {
-    let use_mock = matches!(
-        std::env::var("CODEX_CLOUD_TASKS_MODE").ok().as_deref(),
-        Some("mock") | Some("MOCK")
-    );
+    let mode = std::env::var("CODEX_CLOUD_TASKS_MODE").ok();
+    let use_mock = matches!(mode.as_deref(), Some("mock") | Some("MOCK"));
+    let use_local = matches!(mode.as_deref(), Some("local") | Some("LOCAL"));
     let base_url = std::env::var("CODEX_CLOUD_TASKS_BASE_URL")
         .unwrap_or_else(|_| "https://chatgpt.com/backend-api".to_string());
 
@@ -52,6 +56,27 @@ async fn init_backend(user_agent_suffix: &str) -> anyhow::Result<BackendContext>
         return Ok(BackendContext {
             backend: Arc::new(codex_cloud_tasks_client::MockClient),
             base_url,
+            local: None,
+        });
+    }
+
+    if use_local {
+        let cwd = std::env::current_dir()?;
+        let repo_root = codex_core::git_info::get_git_repo_root(&cwd)
+            .ok_or_else(|| anyhow!("local mode must be run inside a git repository"))?;
+        let state_dir = codex_core::config::find_codex_home()?.join("local-tasks");
+        append_error_log(format!(
+            "startup: local mode repo={} state={}",
+            repo_root.display(),
+            state_dir.display()
+        ));
+        let local = Arc::new(codex_cloud_tasks_client::LocalClient::new(
+            repo_root, state_dir,
+        ));
+        return Ok(BackendContext {
+            backend: local.clone(),
+            base_url,
+            local: Some(local),
         });
     }
 
@@ -101,6 +126,7 @@ async fn init_backend(user_agent_suffix: &str) -> anyhow::Result<BackendContext>
     Ok(BackendContext {
         backend: Arc::new(http),
         base_url,
+        local: None,
     })
 }
 
@@ -161,7 +187,11 @@ async fn run_exec_command(args: crate::cli::ExecCommand) -> anyhow::Result<()> {
     } = args;
     let ctx = init_backend("codex_cloud_tasks_exec").await?;
     let prompt = resolve_query_input(query)?;
-    let env_id = resolve_environment_id(&ctx, &environment).await?;
+    let env_id = if ctx.local.is_some() {
+        LOCAL_ENVIRONMENT_ID.to_string()
+    } else {
+        resolve_environment_id(&ctx, &environment).await?
+    };
     let git_ref = resolve_git_ref(branch.as_ref()).await;
     let created = codex_cloud_tasks_client::CloudBackend::create_task(
         &*ctx.backend,
@@ -172,6 +202,22 @@ async fn run_exec_command(args: crate::cli::ExecCommand) -> anyhow::Result<()> {
         attempts,
     )
     .await?;
+    if let Some(local) = ctx.local.as_ref() {
+        eprintln!(
+            "Running {attempts} attempt(s) locally for task {}…",
+            created.id.0
+        );
+        local.wait_for_task(&created.id).await;
+        let summary =
+            codex_cloud_tasks_client::CloudBackend::get_task_summary(&**local, created.id.clone())
+                .await?;
+        println!("{}", created.id.0);
+        let colorize = supports_color::on(SupportStream::Stdout).is_some();
+        for line in format_task_status_lines(&summary, Utc::now(), colorize) {
+            println!("{line}");
+        }
+        return Ok(());
+    }
     let url = util::task_url(&ctx.base_url, &created.id.0);
     println!("{url}");
     Ok(())
@@ -665,7 +711,7 @@ pub async fn run_main(cli: Cli, _codex_linux_sandbox_exe: Option<PathBuf>) -> an
         .try_init();
 
     info!("Launching Cloud Tasks list UI");
-    let BackendContext { backend, .. } = init_backend("codex_cloud_tasks_tui").await?;
+    let BackendContext { backend, local, .. } = init_backend("codex_cloud_tasks_tui").await?;
     let backend = backend;
 
     // Terminal setup
@@ -701,6 +747,17 @@ pub async fn run_main(cli: Cli, _codex_linux_sandbox_exe: Option<PathBuf>) -> an
 
     // App state
     let mut app = app::App::new();
+    if local.is_some() {
+        // Local mode has a single fixed environment; there is nothing to fetch.
+        app.environments = vec![app::EnvironmentRow {
+            id: LOCAL_ENVIRONMENT_ID.to_string(),
+            label: Some(codex_cloud_tasks_client::LOCAL_ENVIRONMENT_LABEL.to_string()),
+            is_pinned: true,
+            repo_hints: None,
+        }];
+        app.env_filter = Some(LOCAL_ENVIRONMENT_ID.to_string());
+        app.env_last_loaded = Some(std::time::Instant::now());
+    }
     // Initial load
     let force_internal = matches!(
         std::env::var("CODEX_CLOUD_TASKS_FORCE_INTERNAL")
@@ -737,16 +794,14 @@ pub async fn run_main(cli: Cli, _codex_linux_sandbox_exe: Option<PathBuf>) -> an
     {
         let backend = Arc::clone(&backend);
         let tx = tx.clone();
+        let env = app.env_filter.clone();
         tokio::spawn(async move {
-            let res = app::load_tasks(&*backend, None).await;
-            let _ = tx.send(app::AppEvent::TasksLoaded {
-                env: None,
-                result: res,
-            });
+            let res = app::load_tasks(&*backend, env.as_deref()).await;
+            let _ = tx.send(app::AppEvent::TasksLoaded { env, result: res });
         });
     }
     // Fetch environment list in parallel so the header can show friendly names quickly.
-    {
+    if local.is_none() {
         let tx = tx.clone();
         tokio::spawn(async move {
             let base_url = util::normalize_base_url(
@@ -761,7 +816,7 @@ pub async fn run_main(cli: Cli, _codex_linux_sandbox_exe: Option<PathBuf>) -> an
 
     // Try to auto-detect a likely environment id on startup and refresh if found.
     // Do this concurrently so the initial list shows quickly; on success we refetch with filter.
-    {
+    if local.is_none() {
         let tx = tx.clone();
         tokio::spawn(async move {
             let base_url = util::normalize_base_url(
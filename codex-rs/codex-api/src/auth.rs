@@ -1,4 +1,6 @@
+use crate::provider::WireApi;
 use codex_client::Request;
+use http::HeaderName;
 
 /// Provides bearer and account identity information for API requests.
 ///
@@ -12,11 +14,24 @@ pub trait AuthProvider: Send + Sync {
     }
 }
 
-pub(crate) fn add_auth_headers<A: AuthProvider>(auth: &A, mut req: Request) -> Request {
-    if let Some(token) = auth.bearer_token()
-        && let Ok(header) = format!("Bearer {token}").parse()
-    {
-        let _ = req.headers.insert(http::header::AUTHORIZATION, header);
+/// Attaches credentials the way `wire` expects them: a bearer token for OpenAI-style APIs,
+/// `x-api-key` for Anthropic and `x-goog-api-key` for Gemini.
+pub(crate) fn add_auth_headers<A: AuthProvider>(
+    auth: &A,
+    wire: &WireApi,
+    mut req: Request,
+) -> Request {
+    if let Some(token) = auth.bearer_token() {
+        let (name, value) = match wire {
+            WireApi::Messages => (HeaderName::from_static("x-api-key"), token),
+            WireApi::Gemini => (HeaderName::from_static("x-goog-api-key"), token),
+            WireApi::Responses | WireApi::Chat | WireApi::Compact => {
+                (http::header::AUTHORIZATION, format!("Bearer {token}"))
+            }
+        };
+        if let Ok(header) = value.parse() {
+            let _ = req.headers.insert(name, header);
+        }
     }
     if let Some(account_id) = auth.account_id()
         && let Ok(header) = account_id.parse()
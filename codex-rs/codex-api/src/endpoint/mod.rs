@@ -1,5 +1,7 @@
 pub mod chat;
 pub mod compact;
+pub mod gemini;
+pub mod messages;
 pub mod models;
 pub mod responses;
 mod streaming;
@@ -93,10 +93,13 @@ impl<T: HttpTransport, A: AuthProvider> ResponsesClient<T, A> {
         self.stream_request(request).await
     }
 
-    fn path(&self) -> &'static str {
+    fn path(&self) -> Result<&'static str, ApiError> {
         match self.streaming.provider().wire {
-            WireApi::Responses | WireApi::Compact => "responses",
-            WireApi::Chat => "chat/completions",
+            WireApi::Responses | WireApi::Compact => Ok("responses"),
+            WireApi::Chat => Ok("chat/completions"),
+            WireApi::Messages | WireApi::Gemini => Err(ApiError::Stream(
+                "responses endpoint does not support the messages or gemini wire api".to_string(),
+            )),
         }
     }
 
@@ -106,7 +109,7 @@ impl<T: HttpTransport, A: AuthProvider> ResponsesClient<T, A> {
         extra_headers: HeaderMap,
     ) -> Result<ResponseStream, ApiError> {
         self.streaming
-            .stream(self.path(), body, extra_headers, spawn_response_stream)
+            .stream(self.path()?, body, extra_headers, spawn_response_stream)
             .await
     }
 }
@@ -12,6 +12,10 @@ pub enum WireApi {
     Responses,
     Chat,
     Compact,
+    /// Anthropic Messages API (`/v1/messages`).
+    Messages,
+    /// Google Gemini `streamGenerateContent` API.
+    Gemini,
 }
 
 /// High-level retry configuration for a provider.
@@ -74,7 +78,7 @@ impl Provider {
                 .map(|(k, v)| format!("{k}={v}"))
                 .collect::<Vec<_>>()
                 .join("&");
-            url.push('?');
+            url.push(if url.contains('?') { '&' } else { '?' });
             url.push_str(&qs);
         }
 
@@ -0,0 +1,339 @@
+use crate::error::ApiError;
+use crate::provider::Provider;
+use crate::requests::headers::build_conversation_headers;
+use crate::requests::headers::insert_header;
+use crate::requests::headers::subagent_header;
+use crate::requests::messages::parse_data_url;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::FunctionCallOutputContentItem;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::openai_models::ReasoningEffort as ReasoningEffortConfig;
+use codex_protocol::protocol::SessionSource;
+use http::HeaderMap;
+use serde_json::Value;
+use serde_json::json;
+use std::collections::HashMap;
+
+/// Assembled request body, path and headers for Gemini streaming calls.
+pub struct GeminiRequest {
+    pub path: String,
+    pub body: Value,
+    pub headers: HeaderMap,
+}
+
+pub struct GeminiRequestBuilder<'a> {
+    model: &'a str,
+    instructions: &'a str,
+    input: &'a [ResponseItem],
+    tools: &'a [Value],
+    reasoning_effort: Option<ReasoningEffortConfig>,
+    conversation_id: Option<String>,
+    session_source: Option<SessionSource>,
+}
+
+impl<'a> GeminiRequestBuilder<'a> {
+    pub fn new(
+        model: &'a str,
+        instructions: &'a str,
+        input: &'a [ResponseItem],
+        tools: &'a [Value],
+    ) -> Self {
+        Self {
+            model,
+            instructions,
+            input,
+            tools,
+            reasoning_effort: None,
+            conversation_id: None,
+            session_source: None,
+        }
+    }
+
+    /// Requests thought summaries with a thinking budget derived from `effort`.
+    pub fn reasoning_effort(mut self, effort: Option<ReasoningEffortConfig>) -> Self {
+        self.reasoning_effort = effort;
+        self
+    }
+
+    pub fn conversation_id(mut self, id: Option<String>) -> Self {
+        self.conversation_id = id;
+        self
+    }
+
+    pub fn session_source(mut self, source: Option<SessionSource>) -> Self {
+        self.session_source = source;
+        self
+    }
+
+    pub fn build(self, _provider: &Provider) -> Result<GeminiRequest, ApiError> {
+        let mut body = json!({
+            "systemInstruction": {"parts": [{"text": self.instructions}]},
+            "contents": build_contents(self.input),
+        });
+        if let Some(obj) = body.as_object_mut() {
+            if !self.tools.is_empty() {
+                obj.insert(
+                    "tools".to_string(),
+                    json!([{"functionDeclarations": self.tools}]),
+                );
+            }
+            if let Some(effort) = self.reasoning_effort {
+                let budget = thinking_budget_tokens(effort);
+                obj.insert(
+                    "generationConfig".to_string(),
+                    json!({
+                        "thinkingConfig": {
+                            "includeThoughts": budget > 0,
+                            "thinkingBudget": budget,
+                        },
+                    }),
+                );
+            }
+        }
+
+        let mut headers = build_conversation_headers(self.conversation_id);
+        if let Some(subagent) = subagent_header(&self.session_source) {
+            insert_header(&mut headers, "x-openai-subagent", &subagent);
+        }
+
+        Ok(GeminiRequest {
+            path: format!("models/{}:streamGenerateContent?alt=sse", self.model),
+            body,
+            headers,
+        })
+    }
+}
+
+fn thinking_budget_tokens(effort: ReasoningEffortConfig) -> u64 {
+    match effort {
+        ReasoningEffortConfig::None => 0,
+        ReasoningEffortConfig::Minimal => 1_024,
+        ReasoningEffortConfig::Low => 4_096,
+        ReasoningEffortConfig::Medium => 8_192,
+        ReasoningEffortConfig::High => 16_384,
+        ReasoningEffortConfig::XHigh => 24_576,
+    }
+}
+
+/// Converts history into Gemini `contents`, merging consecutive turns from
+/// the same role. Reasoning items only carry the thought signature, which is
+/// attached to the next model part as the API requires.
+fn build_contents(input: &[ResponseItem]) -> Vec<Value> {
+    let mut contents: Vec<(&'static str, Vec<Value>)> = Vec::new();
+    let mut function_names: HashMap<&str, &str> = HashMap::new();
+    let mut pending_signature: Option<&str> = None;
+
+    let mut push = |role: &'static str, mut part: Value, signature: Option<&str>| {
+        if let (Some(signature), Some(obj)) = (signature, part.as_object_mut()) {
+            obj.insert("thoughtSignature".to_string(), json!(signature));
+        }
+        match contents.last_mut() {
+            Some((last_role, parts)) if *last_role == role => parts.push(part),
+            _ => contents.push((role, vec![part])),
+        }
+    };
+
+    for item in input {
+        match item {
+            ResponseItem::Message { role, content, .. } => {
+                let role = if role == "assistant" { "model" } else { "user" };
+                for part in content.iter().filter_map(content_part) {
+                    let signature = if role == "model" {
+                        pending_signature.take()
+                    } else {
+                        None
+                    };
+                    push(role, part, signature);
+                }
+            }
+            ResponseItem::Reasoning {
+                encrypted_content: Some(signature),
+                ..
+            } => pending_signature = Some(signature),
+            ResponseItem::FunctionCall {
+                name,
+                arguments,
+                call_id,
+                ..
+            } => {
+                function_names.insert(call_id, name);
+                let args = serde_json::from_str::<Value>(arguments)
+                    .ok()
+                    .filter(Value::is_object)
+                    .unwrap_or_else(|| json!({}));
+                push(
+                    "model",
+                    json!({"functionCall": {"name": name, "args": args}}),
+                    pending_signature.take(),
+                );
+            }
+            ResponseItem::CustomToolCall {
+                call_id,
+                name,
+                input,
+                ..
+            } => {
+                function_names.insert(call_id, name);
+                push(
+                    "model",
+                    json!({"functionCall": {"name": name, "args": {"input": input}}}),
+                    pending_signature.take(),
+                );
+            }
+            ResponseItem::FunctionCallOutput { call_id, output } => {
+                let name = function_names.get(call_id.as_str()).copied().unwrap_or("");
+                let content = match &output.content_items {
+                    Some(items) => items
+                        .iter()
+                        .filter_map(|item| match item {
+                            FunctionCallOutputContentItem::InputText { text } => {
+                                Some(text.as_str())
+                            }
+                            FunctionCallOutputContentItem::InputImage { .. } => None,
+                        })
+                        .collect::<Vec<_>>()
+                        .join("\n"),
+                    None => output.content.clone(),
+                };
+                push(
+                    "user",
+                    json!({"functionResponse": {"name": name, "response": {"content": content}}}),
+                    None,
+                );
+            }
+            ResponseItem::CustomToolCallOutput { call_id, output } => {
+                let name = function_names.get(call_id.as_str()).copied().unwrap_or("");
+                push(
+                    "user",
+                    json!({"functionResponse": {"name": name, "response": {"content": output}}}),
+                    None,
+                );
+            }
+            ResponseItem::Reasoning { .. }
+            | ResponseItem::LocalShellCall { .. }
+            | ResponseItem::WebSearchCall { .. }
+            | ResponseItem::GhostSnapshot { .. }
+            | ResponseItem::Compaction { .. }
+            | ResponseItem::Other => {}
+        }
+    }
+
+    contents
+        .into_iter()
+        .map(|(role, parts)| json!({"role": role, "parts": parts}))
+        .collect()
+}
+
+fn content_part(item: &ContentItem) -> Option<Value> {
+    match item {
+        ContentItem::InputText { text } | ContentItem::OutputText { text } => {
+            (!text.is_empty()).then(|| json!({"text": text}))
+        }
+        ContentItem::InputImage { image_url } => match parse_data_url(image_url) {
+            Some((mime_type, data)) => {
+                Some(json!({"inlineData": {"mimeType": mime_type, "data": data}}))
+            }
+            None => Some(json!({"fileData": {"fileUri": image_url}})),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::RetryConfig;
+    use crate::provider::WireApi;
+    use codex_protocol::models::FunctionCallOutputPayload;
+    use pretty_assertions::assert_eq;
+    use std::time::Duration;
+
+    fn provider() -> Provider {
+        Provider {
+            name: "gemini".to_string(),
+            base_url: "https://generativelanguage.googleapis.com/v1beta".to_string(),
+            query_params: None,
+            wire: WireApi::Gemini,
+            headers: HeaderMap::new(),
+            retry: RetryConfig {
+                max_attempts: 1,
+                base_delay: Duration::from_millis(10),
+                retry_429: false,
+                retry_5xx: true,
+                retry_transport: true,
+            },
+            stream_idle_timeout: Duration::from_secs(1),
+        }
+    }
+
+    #[test]
+    fn translates_history_and_replays_thought_signatures() {
+        let input = vec![
+            ResponseItem::Message {
+                id: None,
+                role: "user".to_string(),
+                content: vec![
+                    ContentItem::InputText {
+                        text: "what is in this image?".to_string(),
+                    },
+                    ContentItem::InputImage {
+                        image_url: "data:image/png;base64,AAAA".to_string(),
+                    },
+                ],
+            },
+            ResponseItem::Reasoning {
+                id: String::new(),
+                summary: Vec::new(),
+                content: None,
+                encrypted_content: Some("sig".to_string()),
+            },
+            ResponseItem::FunctionCall {
+                id: None,
+                name: "shell".to_string(),
+                arguments: r#"{"command":["ls"]}"#.to_string(),
+                call_id: "call_1".to_string(),
+            },
+            ResponseItem::FunctionCallOutput {
+                call_id: "call_1".to_string(),
+                output: FunctionCallOutputPayload {
+                    content: "a.png".to_string(),
+                    content_items: None,
+                    success: Some(true),
+                },
+            },
+        ];
+        let tools = vec![json!({"name": "shell", "parameters": {"type": "object"}})];
+        let req = GeminiRequestBuilder::new("gemini-test", "inst", &input, &tools)
+            .reasoning_effort(Some(ReasoningEffortConfig::Medium))
+            .build(&provider())
+            .expect("request");
+
+        assert_eq!(req.path, "models/gemini-test:streamGenerateContent?alt=sse");
+        assert_eq!(
+            req.body,
+            json!({
+                "systemInstruction": {"parts": [{"text": "inst"}]},
+                "contents": [
+                    {"role": "user", "parts": [
+                        {"text": "what is in this image?"},
+                        {"inlineData": {"mimeType": "image/png", "data": "AAAA"}},
+                    ]},
+                    {"role": "model", "parts": [
+                        {
+                            "functionCall": {"name": "shell", "args": {"command": ["ls"]}},
+                            "thoughtSignature": "sig",
+                        },
+                    ]},
+                    {"role": "user", "parts": [
+                        {"functionResponse": {"name": "shell", "response": {"content": "a.png"}}},
+                    ]},
+                ],
+                "tools": [{"functionDeclarations": [
+                    {"name": "shell", "parameters": {"type": "object"}},
+                ]}],
+                "generationConfig": {
+                    "thinkingConfig": {"includeThoughts": true, "thinkingBudget": 8_192},
+                },
+            })
+        );
+    }
+}
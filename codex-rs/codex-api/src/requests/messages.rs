@@ -0,0 +1,461 @@
+use crate::error::ApiError;
+use crate::provider::Provider;
+use crate::requests::headers::build_conversation_headers;
+use crate::requests::headers::insert_header;
+use crate::requests::headers::subagent_header;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::FunctionCallOutputContentItem;
+use codex_protocol::models::FunctionCallOutputPayload;
+use codex_protocol::models::ReasoningItemContent;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::openai_models::ReasoningEffort as ReasoningEffortConfig;
+use codex_protocol::protocol::SessionSource;
+use http::HeaderMap;
+use serde_json::Value;
+use serde_json::json;
+
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+/// Output budget for the visible answer; thinking budgets are added on top.
+const DEFAULT_MAX_OUTPUT_TOKENS: u64 = 8_192;
+
+/// Assembled request body plus headers for Anthropic Messages streaming calls.
+pub struct MessagesRequest {
+    pub body: Value,
+    pub headers: HeaderMap,
+}
+
+pub struct MessagesRequestBuilder<'a> {
+    model: &'a str,
+    instructions: &'a str,
+    input: &'a [ResponseItem],
+    tools: &'a [Value],
+    parallel_tool_calls: bool,
+    reasoning_effort: Option<ReasoningEffortConfig>,
+    max_output_tokens: Option<u64>,
+    conversation_id: Option<String>,
+    session_source: Option<SessionSource>,
+}
+
+impl<'a> MessagesRequestBuilder<'a> {
+    pub fn new(
+        model: &'a str,
+        instructions: &'a str,
+        input: &'a [ResponseItem],
+        tools: &'a [Value],
+    ) -> Self {
+        Self {
+            model,
+            instructions,
+            input,
+            tools,
+            parallel_tool_calls: true,
+            reasoning_effort: None,
+            max_output_tokens: None,
+            conversation_id: None,
+            session_source: None,
+        }
+    }
+
+    pub fn parallel_tool_calls(mut self, enabled: bool) -> Self {
+        self.parallel_tool_calls = enabled;
+        self
+    }
+
+    /// Enables extended thinking with a budget derived from `effort`.
+    pub fn reasoning_effort(mut self, effort: Option<ReasoningEffortConfig>) -> Self {
+        self.reasoning_effort = effort;
+        self
+    }
+
+    pub fn max_output_tokens(mut self, max_output_tokens: Option<u64>) -> Self {
+        self.max_output_tokens = max_output_tokens;
+        self
+    }
+
+    pub fn conversation_id(mut self, id: Option<String>) -> Self {
+        self.conversation_id = id;
+        self
+    }
+
+    pub fn session_source(mut self, source: Option<SessionSource>) -> Self {
+        self.session_source = source;
+        self
+    }
+
+    pub fn build(self, _provider: &Provider) -> Result<MessagesRequest, ApiError> {
+        let mut messages = build_messages(self.input);
+        mark_last_block_cacheable(&mut messages);
+
+        let mut tools = self.tools.to_vec();
+        if let Some(Value::Object(last)) = tools.last_mut() {
+            last.insert("cache_control".to_string(), ephemeral_cache_control());
+        }
+
+        let thinking_budget = self.reasoning_effort.and_then(thinking_budget_tokens);
+        let max_tokens = self.max_output_tokens.unwrap_or(DEFAULT_MAX_OUTPUT_TOKENS)
+            + thinking_budget.unwrap_or_default();
+
+        let mut body = json!({
+            "model": self.model,
+            "max_tokens": max_tokens,
+            "system": [{
+                "type": "text",
+                "text": self.instructions,
+                "cache_control": ephemeral_cache_control(),
+            }],
+            "messages": messages,
+            "stream": true,
+        });
+        if let Some(obj) = body.as_object_mut() {
+            if !tools.is_empty() {
+                obj.insert("tools".to_string(), Value::Array(tools));
+                obj.insert(
+                    "tool_choice".to_string(),
+                    json!({
+                        "type": "auto",
+                        "disable_parallel_tool_use": !self.parallel_tool_calls,
+                    }),
+                );
+            }
+            if let Some(budget) = thinking_budget {
+                obj.insert(
+                    "thinking".to_string(),
+                    json!({"type": "enabled", "budget_tokens": budget}),
+                );
+            }
+        }
+
+        let mut headers = build_conversation_headers(self.conversation_id);
+        insert_header(&mut headers, "anthropic-version", ANTHROPIC_VERSION);
+        if let Some(subagent) = subagent_header(&self.session_source) {
+            insert_header(&mut headers, "x-openai-subagent", &subagent);
+        }
+
+        Ok(MessagesRequest { body, headers })
+    }
+}
+
+/// Thinking budget for a reasoning effort; `None` disables extended thinking.
+fn thinking_budget_tokens(effort: ReasoningEffortConfig) -> Option<u64> {
+    match effort {
+        ReasoningEffortConfig::None => None,
+        ReasoningEffortConfig::Minimal => Some(1_024),
+        ReasoningEffortConfig::Low => Some(4_096),
+        ReasoningEffortConfig::Medium => Some(8_192),
+        ReasoningEffortConfig::High => Some(16_384),
+        ReasoningEffortConfig::XHigh => Some(24_576),
+    }
+}
+
+fn ephemeral_cache_control() -> Value {
+    json!({"type": "ephemeral"})
+}
+
+/// Converts history into alternating `user`/`assistant` messages. Consecutive
+/// items with the same role are merged, since the API rejects repeated roles.
+fn build_messages(input: &[ResponseItem]) -> Vec<Value> {
+    let mut messages: Vec<(&'static str, Vec<Value>)> = Vec::new();
+    let mut push = |role: &'static str, block: Value| match messages.last_mut() {
+        Some((last_role, blocks)) if *last_role == role => blocks.push(block),
+        _ => messages.push((role, vec![block])),
+    };
+
+    for item in input {
+        match item {
+            ResponseItem::Message { role, content, .. } => {
+                let role = if role == "assistant" {
+                    "assistant"
+                } else {
+                    "user"
+                };
+                for block in content.iter().filter_map(content_block) {
+                    push(role, block);
+                }
+            }
+            ResponseItem::Reasoning {
+                content,
+                encrypted_content: Some(signature),
+                ..
+            } => match content {
+                // Thinking can only be replayed together with its signature.
+                Some(content) => {
+                    let thinking: String = content
+                        .iter()
+                        .map(|entry| match entry {
+                            ReasoningItemContent::ReasoningText { text }
+                            | ReasoningItemContent::Text { text } => text.as_str(),
+                        })
+                        .collect();
+                    push(
+                        "assistant",
+                        json!({"type": "thinking", "thinking": thinking, "signature": signature}),
+                    );
+                }
+                None => push(
+                    "assistant",
+                    json!({"type": "redacted_thinking", "data": signature}),
+                ),
+            },
+            ResponseItem::FunctionCall {
+                name,
+                arguments,
+                call_id,
+                ..
+            } => {
+                let input = serde_json::from_str::<Value>(arguments)
+                    .ok()
+                    .filter(Value::is_object)
+                    .unwrap_or_else(|| json!({}));
+                push(
+                    "assistant",
+                    json!({"type": "tool_use", "id": call_id, "name": name, "input": input}),
+                );
+            }
+            ResponseItem::CustomToolCall {
+                call_id,
+                name,
+                input,
+                ..
+            } => push(
+                "assistant",
+                json!({"type": "tool_use", "id": call_id, "name": name, "input": {"input": input}}),
+            ),
+            ResponseItem::LocalShellCall {
+                id,
+                call_id,
+                action,
+                ..
+            } => {
+                let Some(call_id) = call_id.as_ref().or(id.as_ref()) else {
+                    continue;
+                };
+                push(
+                    "assistant",
+                    json!({"type": "tool_use", "id": call_id, "name": "local_shell", "input": action}),
+                );
+            }
+            ResponseItem::FunctionCallOutput { call_id, output } => {
+                push("user", tool_result_block(call_id, output));
+            }
+            ResponseItem::CustomToolCallOutput { call_id, output } => push(
+                "user",
+                json!({"type": "tool_result", "tool_use_id": call_id, "content": output}),
+            ),
+            ResponseItem::Reasoning { .. }
+            | ResponseItem::WebSearchCall { .. }
+            | ResponseItem::GhostSnapshot { .. }
+            | ResponseItem::Compaction { .. }
+            | ResponseItem::Other => {}
+        }
+    }
+
+    messages
+        .into_iter()
+        .map(|(role, content)| json!({"role": role, "content": content}))
+        .collect()
+}
+
+fn content_block(item: &ContentItem) -> Option<Value> {
+    match item {
+        ContentItem::InputText { text } | ContentItem::OutputText { text } => {
+            // Empty text blocks are rejected by the API.
+            (!text.is_empty()).then(|| json!({"type": "text", "text": text}))
+        }
+        ContentItem::InputImage { image_url } => Some(image_block(image_url)),
+    }
+}
+
+fn image_block(image_url: &str) -> Value {
+    match parse_data_url(image_url) {
+        Some((media_type, data)) => json!({
+            "type": "image",
+            "source": {"type": "base64", "media_type": media_type, "data": data},
+        }),
+        None => json!({
+            "type": "image",
+            "source": {"type": "url", "url": image_url},
+        }),
+    }
+}
+
+/// Splits a `data:<media type>;base64,<data>` URL into its media type and payload.
+pub(crate) fn parse_data_url(url: &str) -> Option<(&str, &str)> {
+    let rest = url.strip_prefix("data:")?;
+    let (meta, data) = rest.split_once(',')?;
+    let media_type = meta.strip_suffix(";base64")?;
+    Some((media_type, data))
+}
+
+fn tool_result_block(call_id: &str, output: &FunctionCallOutputPayload) -> Value {
+    let content = match &output.content_items {
+        Some(items) => Value::Array(
+            items
+                .iter()
+                .map(|item| match item {
+                    FunctionCallOutputContentItem::InputText { text } => {
+                        json!({"type": "text", "text": text})
+                    }
+                    FunctionCallOutputContentItem::InputImage { image_url } => {
+                        image_block(image_url)
+                    }
+                })
+                .collect(),
+        ),
+        None => json!(output.content),
+    };
+    let mut block = json!({"type": "tool_result", "tool_use_id": call_id, "content": content});
+    if output.success == Some(false)
+        && let Some(obj) = block.as_object_mut()
+    {
+        obj.insert("is_error".to_string(), Value::Bool(true));
+    }
+    block
+}
+
+/// Places a cache breakpoint on the newest content block so the whole
+/// conversation prefix is reused by the next request.
+fn mark_last_block_cacheable(messages: &mut [Value]) {
+    if let Some(Value::Object(block)) = messages
+        .last_mut()
+        .and_then(|message| message.get_mut("content"))
+        .and_then(Value::as_array_mut)
+        .and_then(|blocks| blocks.last_mut())
+    {
+        block.insert("cache_control".to_string(), ephemeral_cache_control());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::RetryConfig;
+    use crate::provider::WireApi;
+    use pretty_assertions::assert_eq;
+    use std::time::Duration;
+
+    fn provider() -> Provider {
+        Provider {
+            name: "anthropic".to_string(),
+            base_url: "https://api.anthropic.com/v1".to_string(),
+            query_params: None,
+            wire: WireApi::Messages,
+            headers: HeaderMap::new(),
+            retry: RetryConfig {
+                max_attempts: 1,
+                base_delay: Duration::from_millis(10),
+                retry_429: false,
+                retry_5xx: true,
+                retry_transport: true,
+            },
+            stream_idle_timeout: Duration::from_secs(1),
+        }
+    }
+
+    fn message(role: &str, text: &str) -> ResponseItem {
+        ResponseItem::Message {
+            id: None,
+            role: role.to_string(),
+            content: vec![ContentItem::InputText {
+                text: text.to_string(),
+            }],
+        }
+    }
+
+    #[test]
+    fn translates_history_into_alternating_messages() {
+        let input = vec![
+            message("developer", "be brief"),
+            message("user", "list files"),
+            ResponseItem::Reasoning {
+                id: String::new(),
+                summary: Vec::new(),
+                content: Some(vec![ReasoningItemContent::ReasoningText {
+                    text: "use ls".to_string(),
+                }]),
+                encrypted_content: Some("sig".to_string()),
+            },
+            ResponseItem::FunctionCall {
+                id: None,
+                name: "shell".to_string(),
+                arguments: r#"{"command":["ls"]}"#.to_string(),
+                call_id: "toolu_1".to_string(),
+            },
+            ResponseItem::FunctionCallOutput {
+                call_id: "toolu_1".to_string(),
+                output: FunctionCallOutputPayload {
+                    content: "a.txt".to_string(),
+                    content_items: None,
+                    success: Some(false),
+                },
+            },
+        ];
+        let tools = vec![json!({"name": "shell", "input_schema": {"type": "object"}})];
+        let req = MessagesRequestBuilder::new("claude-test", "inst", &input, &tools)
+            .parallel_tool_calls(false)
+            .reasoning_effort(Some(ReasoningEffortConfig::Low))
+            .build(&provider())
+            .expect("request");
+
+        assert_eq!(
+            req.body,
+            json!({
+                "model": "claude-test",
+                "max_tokens": 12_288,
+                "system": [{
+                    "type": "text",
+                    "text": "inst",
+                    "cache_control": {"type": "ephemeral"},
+                }],
+                "messages": [
+                    {"role": "user", "content": [
+                        {"type": "text", "text": "be brief"},
+                        {"type": "text", "text": "list files"},
+                    ]},
+                    {"role": "assistant", "content": [
+                        {"type": "thinking", "thinking": "use ls", "signature": "sig"},
+                        {"type": "tool_use", "id": "toolu_1", "name": "shell", "input": {"command": ["ls"]}},
+                    ]},
+                    {"role": "user", "content": [
+                        {
+                            "type": "tool_result",
+                            "tool_use_id": "toolu_1",
+                            "content": "a.txt",
+                            "is_error": true,
+                            "cache_control": {"type": "ephemeral"},
+                        },
+                    ]},
+                ],
+                "stream": true,
+                "tools": [{
+                    "name": "shell",
+                    "input_schema": {"type": "object"},
+                    "cache_control": {"type": "ephemeral"},
+                }],
+                "tool_choice": {"type": "auto", "disable_parallel_tool_use": true},
+                "thinking": {"type": "enabled", "budget_tokens": 4_096},
+            })
+        );
+        assert_eq!(
+            req.headers.get("anthropic-version").map(|v| v.as_bytes()),
+            Some(ANTHROPIC_VERSION.as_bytes())
+        );
+    }
+
+    #[test]
+    fn converts_data_url_images_to_base64_sources() {
+        assert_eq!(
+            image_block("data:image/png;base64,AAAA"),
+            json!({
+                "type": "image",
+                "source": {"type": "base64", "media_type": "image/png", "data": "AAAA"},
+            })
+        );
+        assert_eq!(
+            image_block("https://example.com/a.png"),
+            json!({
+                "type": "image",
+                "source": {"type": "url", "url": "https://example.com/a.png"},
+            })
+        );
+    }
+}
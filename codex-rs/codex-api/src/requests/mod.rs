@@ -1,8 +1,14 @@
 pub mod chat;
+pub mod gemini;
 pub(crate) mod headers;
+pub mod messages;
 pub mod responses;
 
 pub use chat::ChatRequest;
 pub use chat::ChatRequestBuilder;
+pub use gemini::GeminiRequest;
+pub use gemini::GeminiRequestBuilder;
+pub use messages::MessagesRequest;
+pub use messages::MessagesRequestBuilder;
 pub use responses::ResponsesRequest;
 pub use responses::ResponsesRequestBuilder;
@@ -0,0 +1,535 @@
+use crate::common::ResponseEvent;
+use crate::common::ResponseStream;
+use crate::error::ApiError;
+use crate::telemetry::SseTelemetry;
+use codex_client::StreamResponse;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ReasoningItemContent;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::TokenUsage;
+use eventsource_stream::Eventsource;
+use futures::Stream;
+use futures::StreamExt;
+use serde::Deserialize;
+use serde_json::Value;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+use tokio::time::timeout;
+use tracing::debug;
+use tracing::trace;
+
+pub(crate) fn spawn_gemini_stream(
+    stream_response: StreamResponse,
+    idle_timeout: Duration,
+    telemetry: Option<Arc<dyn SseTelemetry>>,
+) -> ResponseStream {
+    let (tx_event, rx_event) = mpsc::channel::<Result<ResponseEvent, ApiError>>(1600);
+    tokio::spawn(async move {
+        process_gemini_sse(stream_response.bytes, tx_event, idle_timeout, telemetry).await;
+    });
+    ResponseStream { rx_event }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GenerateContentChunk {
+    #[serde(default)]
+    candidates: Vec<Candidate>,
+    #[serde(default)]
+    usage_metadata: Option<UsageMetadata>,
+    #[serde(default)]
+    response_id: Option<String>,
+    #[serde(default)]
+    prompt_feedback: Option<PromptFeedback>,
+    #[serde(default)]
+    error: Option<GeminiError>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Candidate {
+    #[serde(default)]
+    content: Option<CandidateContent>,
+    #[serde(default)]
+    finish_reason: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CandidateContent {
+    #[serde(default)]
+    parts: Vec<Part>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Part {
+    #[serde(default)]
+    text: Option<String>,
+    #[serde(default)]
+    thought: bool,
+    #[serde(default)]
+    thought_signature: Option<String>,
+    #[serde(default)]
+    function_call: Option<FunctionCall>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FunctionCall {
+    #[serde(default)]
+    id: Option<String>,
+    name: String,
+    #[serde(default)]
+    args: Option<Value>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UsageMetadata {
+    #[serde(default)]
+    prompt_token_count: i64,
+    #[serde(default)]
+    cached_content_token_count: i64,
+    #[serde(default)]
+    candidates_token_count: i64,
+    #[serde(default)]
+    thoughts_token_count: i64,
+    #[serde(default)]
+    total_token_count: Option<i64>,
+}
+
+impl From<UsageMetadata> for TokenUsage {
+    fn from(val: UsageMetadata) -> Self {
+        let output_tokens = val.candidates_token_count + val.thoughts_token_count;
+        TokenUsage {
+            input_tokens: val.prompt_token_count,
+            cached_input_tokens: val.cached_content_token_count,
+            output_tokens,
+            reasoning_output_tokens: val.thoughts_token_count,
+            total_tokens: val
+                .total_token_count
+                .unwrap_or(val.prompt_token_count + output_tokens),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PromptFeedback {
+    #[serde(default)]
+    block_reason: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GeminiError {
+    #[serde(default)]
+    code: Option<u16>,
+    #[serde(default)]
+    message: String,
+    #[serde(default)]
+    status: Option<String>,
+}
+
+/// Accumulates thoughts and their signature until the next model output that
+/// the signature must precede when replayed.
+#[derive(Default)]
+struct PendingReasoning {
+    item: Option<ResponseItem>,
+    signature: Option<String>,
+}
+
+impl PendingReasoning {
+    fn take(&mut self) -> Option<ResponseItem> {
+        let signature = self.signature.take();
+        match self.item.take() {
+            Some(ResponseItem::Reasoning {
+                id,
+                summary,
+                content,
+                ..
+            }) => Some(ResponseItem::Reasoning {
+                id,
+                summary,
+                content,
+                encrypted_content: signature,
+            }),
+            Some(other) => Some(other),
+            None => signature.map(|signature| ResponseItem::Reasoning {
+                id: String::new(),
+                summary: Vec::new(),
+                content: None,
+                encrypted_content: Some(signature),
+            }),
+        }
+    }
+}
+
+pub async fn process_gemini_sse<S>(
+    stream: S,
+    tx_event: mpsc::Sender<Result<ResponseEvent, ApiError>>,
+    idle_timeout: Duration,
+    telemetry: Option<Arc<dyn SseTelemetry>>,
+) where
+    S: Stream<Item = Result<bytes::Bytes, codex_client::TransportError>> + Unpin,
+{
+    let mut stream = stream.eventsource();
+    let mut reasoning = PendingReasoning::default();
+    let mut assistant_item: Option<ResponseItem> = None;
+    let mut response_id: Option<String> = None;
+    let mut usage: Option<UsageMetadata> = None;
+    let mut finished = false;
+    let mut created_sent = false;
+    let mut call_count = 0usize;
+
+    loop {
+        let start = Instant::now();
+        let response = timeout(idle_timeout, stream.next()).await;
+        if let Some(t) = telemetry.as_ref() {
+            t.on_sse_poll(&response, start.elapsed());
+        }
+        let sse = match response {
+            Ok(Some(Ok(sse))) => sse,
+            Ok(Some(Err(e))) => {
+                let _ = tx_event.send(Err(ApiError::Stream(e.to_string()))).await;
+                return;
+            }
+            Ok(None) => {
+                if !finished {
+                    let _ = tx_event
+                        .send(Err(ApiError::Stream(
+                            "stream closed before finishReason".into(),
+                        )))
+                        .await;
+                    return;
+                }
+                if let Some(item) = reasoning.take() {
+                    let _ = tx_event.send(Ok(ResponseEvent::OutputItemDone(item))).await;
+                }
+                if let Some(item) = assistant_item.take() {
+                    let _ = tx_event.send(Ok(ResponseEvent::OutputItemDone(item))).await;
+                }
+                let _ = tx_event
+                    .send(Ok(ResponseEvent::Completed {
+                        response_id: response_id.unwrap_or_default(),
+                        token_usage: usage.map(Into::into),
+                    }))
+                    .await;
+                return;
+            }
+            Err(_) => {
+                let _ = tx_event
+                    .send(Err(ApiError::Stream("idle timeout waiting for SSE".into())))
+                    .await;
+                return;
+            }
+        };
+
+        trace!("SSE event: {}", sse.data);
+
+        if sse.data.trim().is_empty() {
+            continue;
+        }
+
+        let chunk: GenerateContentChunk = match serde_json::from_str(&sse.data) {
+            Ok(chunk) => chunk,
+            Err(err) => {
+                debug!(
+                    "Failed to parse Gemini SSE event: {err}, data: {}",
+                    &sse.data
+                );
+                continue;
+            }
+        };
+
+        if let Some(error) = chunk.error {
+            let err = match (error.code, error.status.as_deref()) {
+                (Some(429 | 500 | 503), _) | (_, Some("RESOURCE_EXHAUSTED" | "UNAVAILABLE")) => {
+                    ApiError::Retryable {
+                        message: error.message,
+                        delay: None,
+                    }
+                }
+                _ => ApiError::Stream(error.message),
+            };
+            let _ = tx_event.send(Err(err)).await;
+            return;
+        }
+        if let Some(reason) = chunk.prompt_feedback.and_then(|f| f.block_reason) {
+            let _ = tx_event
+                .send(Err(ApiError::Stream(format!("prompt blocked: {reason}"))))
+                .await;
+            return;
+        }
+
+        if !created_sent {
+            created_sent = true;
+            let _ = tx_event.send(Ok(ResponseEvent::Created)).await;
+        }
+        if chunk.response_id.is_some() {
+            response_id = chunk.response_id;
+        }
+        if chunk.usage_metadata.is_some() {
+            usage = chunk.usage_metadata;
+        }
+
+        for candidate in chunk.candidates {
+            for part in candidate.content.map(|c| c.parts).unwrap_or_default() {
+                if let Some(signature) = part.thought_signature {
+                    reasoning.signature = Some(signature);
+                }
+                if let Some(call) = part.function_call {
+                    if let Some(item) = reasoning.take() {
+                        let _ = tx_event.send(Ok(ResponseEvent::OutputItemDone(item))).await;
+                    }
+                    let call_id = call
+                        .id
+                        .unwrap_or_else(|| fallback_call_id(response_id.as_deref(), call_count));
+                    call_count += 1;
+                    let arguments = call
+                        .args
+                        .map(|args| args.to_string())
+                        .unwrap_or_else(|| "{}".to_string());
+                    let item = ResponseItem::FunctionCall {
+                        id: None,
+                        name: call.name,
+                        arguments,
+                        call_id,
+                    };
+                    let _ = tx_event.send(Ok(ResponseEvent::OutputItemDone(item))).await;
+                    continue;
+                }
+                let Some(text) = part.text.filter(|text| !text.is_empty()) else {
+                    continue;
+                };
+                if part.thought {
+                    append_reasoning_text(&tx_event, &mut reasoning.item, text).await;
+                } else {
+                    append_assistant_text(&tx_event, &mut assistant_item, text).await;
+                }
+            }
+            if candidate.finish_reason.is_some() {
+                finished = true;
+            }
+        }
+    }
+}
+
+/// Older Gemini models omit call ids, so synthesize ones unique to this response.
+fn fallback_call_id(response_id: Option<&str>, index: usize) -> String {
+    match response_id {
+        Some(response_id) => format!("call_{response_id}_{index}"),
+        None => {
+            let nanos = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_nanos())
+                .unwrap_or_default();
+            format!("call_{nanos}_{index}")
+        }
+    }
+}
+
+async fn append_assistant_text(
+    tx_event: &mpsc::Sender<Result<ResponseEvent, ApiError>>,
+    assistant_item: &mut Option<ResponseItem>,
+    text: String,
+) {
+    if assistant_item.is_none() {
+        let item = ResponseItem::Message {
+            id: None,
+            role: "assistant".to_string(),
+            content: vec![],
+        };
+        *assistant_item = Some(item.clone());
+        let _ = tx_event
+            .send(Ok(ResponseEvent::OutputItemAdded(item)))
+            .await;
+    }
+
+    if let Some(ResponseItem::Message { content, .. }) = assistant_item {
+        match content.last_mut() {
+            Some(ContentItem::OutputText { text: existing }) => existing.push_str(&text),
+            _ => content.push(ContentItem::OutputText { text: text.clone() }),
+        }
+        let _ = tx_event
+            .send(Ok(ResponseEvent::OutputTextDelta(text)))
+            .await;
+    }
+}
+
+async fn append_reasoning_text(
+    tx_event: &mpsc::Sender<Result<ResponseEvent, ApiError>>,
+    reasoning_item: &mut Option<ResponseItem>,
+    text: String,
+) {
+    if reasoning_item.is_none() {
+        let item = ResponseItem::Reasoning {
+            id: String::new(),
+            summary: Vec::new(),
+            content: Some(vec![]),
+            encrypted_content: None,
+        };
+        *reasoning_item = Some(item.clone());
+        let _ = tx_event
+            .send(Ok(ResponseEvent::OutputItemAdded(item)))
+            .await;
+    }
+
+    if let Some(ResponseItem::Reasoning {
+        content: Some(content),
+        ..
+    }) = reasoning_item
+    {
+        match content.last_mut() {
+            Some(ReasoningItemContent::ReasoningText { text: existing }) => {
+                existing.push_str(&text)
+            }
+            _ => content.push(ReasoningItemContent::ReasoningText { text: text.clone() }),
+        }
+        let _ = tx_event
+            .send(Ok(ResponseEvent::ReasoningContentDelta {
+                delta: text,
+                content_index: 0,
+            }))
+            .await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::assert_matches;
+    use futures::TryStreamExt;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tokio_util::io::ReaderStream;
+
+    fn build_body(events: &[Value]) -> String {
+        let mut body = String::new();
+        for e in events {
+            body.push_str(&format!("data: {e}\n\n"));
+        }
+        body
+    }
+
+    async fn collect_events(body: &str) -> Vec<Result<ResponseEvent, ApiError>> {
+        let reader = ReaderStream::new(std::io::Cursor::new(body.to_string()))
+            .map_err(|err| codex_client::TransportError::Network(err.to_string()));
+        let (tx, mut rx) = mpsc::channel::<Result<ResponseEvent, ApiError>>(16);
+        tokio::spawn(process_gemini_sse(
+            reader,
+            tx,
+            Duration::from_millis(1000),
+            None,
+        ));
+
+        let mut out = Vec::new();
+        while let Some(ev) = rx.recv().await {
+            out.push(ev);
+        }
+        out
+    }
+
+    #[tokio::test]
+    async fn emits_thoughts_and_signed_function_call() {
+        let body = build_body(&[
+            json!({"responseId": "r1", "candidates": [{"content": {"role": "model", "parts": [
+                {"text": "thinking about it", "thought": true},
+            ]}}]}),
+            json!({"responseId": "r1", "candidates": [{"content": {"role": "model", "parts": [
+                {"functionCall": {"name": "shell", "args": {"command": ["ls"]}}, "thoughtSignature": "sig"},
+            ]}, "finishReason": "STOP"}], "usageMetadata": {
+                "promptTokenCount": 100,
+                "cachedContentTokenCount": 40,
+                "candidatesTokenCount": 10,
+                "thoughtsTokenCount": 5,
+                "totalTokenCount": 115,
+            }}),
+        ]);
+        let events: Vec<ResponseEvent> = collect_events(&body)
+            .await
+            .into_iter()
+            .map(|ev| ev.expect("stream error"))
+            .collect();
+
+        assert_matches!(events[0], ResponseEvent::Created);
+        assert_matches!(
+            events[1],
+            ResponseEvent::OutputItemAdded(ResponseItem::Reasoning { .. })
+        );
+        assert_matches!(
+            &events[2],
+            ResponseEvent::ReasoningContentDelta { delta, content_index: 0 } if delta == "thinking about it"
+        );
+        assert_matches!(
+            &events[3],
+            ResponseEvent::OutputItemDone(ResponseItem::Reasoning {
+                encrypted_content: Some(signature),
+                ..
+            }) if signature == "sig"
+        );
+        assert_matches!(
+            &events[4],
+            ResponseEvent::OutputItemDone(ResponseItem::FunctionCall { call_id, name, arguments, .. })
+                if call_id == "call_r1_0" && name == "shell" && arguments == r#"{"command":["ls"]}"#
+        );
+        let ResponseEvent::Completed {
+            response_id,
+            token_usage: Some(usage),
+        } = &events[5]
+        else {
+            panic!("expected completion, got {:?}", events[5]);
+        };
+        assert_eq!(response_id, "r1");
+        assert_eq!(
+            (
+                usage.input_tokens,
+                usage.cached_input_tokens,
+                usage.output_tokens,
+                usage.reasoning_output_tokens,
+                usage.total_tokens,
+            ),
+            (100, 40, 15, 5, 115)
+        );
+        assert_eq!(events.len(), 6);
+    }
+
+    #[tokio::test]
+    async fn concatenates_streamed_text() {
+        let body = build_body(&[
+            json!({"candidates": [{"content": {"parts": [{"text": "Hel"}]}}]}),
+            json!({"candidates": [{"content": {"parts": [{"text": "lo"}]}, "finishReason": "STOP"}]}),
+        ]);
+        let events: Vec<ResponseEvent> = collect_events(&body)
+            .await
+            .into_iter()
+            .map(|ev| ev.expect("stream error"))
+            .collect();
+
+        assert_matches!(
+            &events[4],
+            ResponseEvent::OutputItemDone(ResponseItem::Message { content, .. })
+                if content == &vec![ContentItem::OutputText { text: "Hello".to_string() }]
+        );
+        assert_matches!(events[5], ResponseEvent::Completed { .. });
+    }
+
+    #[tokio::test]
+    async fn blocked_prompt_is_an_error() {
+        let body = build_body(&[json!({"promptFeedback": {"blockReason": "SAFETY"}})]);
+        let events = collect_events(&body).await;
+
+        assert_matches!(&events[0], Err(ApiError::Stream(message)) if message == "prompt blocked: SAFETY");
+    }
+
+    #[tokio::test]
+    async fn stream_closed_without_finish_reason_is_an_error() {
+        let body =
+            build_body(&[json!({"candidates": [{"content": {"parts": [{"text": "Hel"}]}}]})]);
+        let events = collect_events(&body).await;
+
+        assert_matches!(events.last(), Some(Err(ApiError::Stream(_))));
+    }
+}
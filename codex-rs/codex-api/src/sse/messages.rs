@@ -0,0 +1,520 @@
+use crate::common::ResponseEvent;
+use crate::common::ResponseStream;
+use crate::error::ApiError;
+use crate::telemetry::SseTelemetry;
+use codex_client::StreamResponse;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ReasoningItemContent;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::TokenUsage;
+use eventsource_stream::Eventsource;
+use futures::Stream;
+use futures::StreamExt;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+use tokio::time::timeout;
+use tracing::debug;
+use tracing::trace;
+
+pub(crate) fn spawn_messages_stream(
+    stream_response: StreamResponse,
+    idle_timeout: Duration,
+    telemetry: Option<Arc<dyn SseTelemetry>>,
+) -> ResponseStream {
+    let (tx_event, rx_event) = mpsc::channel::<Result<ResponseEvent, ApiError>>(1600);
+    tokio::spawn(async move {
+        process_messages_sse(stream_response.bytes, tx_event, idle_timeout, telemetry).await;
+    });
+    ResponseStream { rx_event }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum MessagesEvent {
+    MessageStart {
+        message: MessageStart,
+    },
+    ContentBlockStart {
+        index: usize,
+        content_block: Value,
+    },
+    ContentBlockDelta {
+        index: usize,
+        delta: Value,
+    },
+    ContentBlockStop {
+        index: usize,
+    },
+    MessageDelta {
+        #[serde(default)]
+        delta: MessageDelta,
+        #[serde(default)]
+        usage: Option<MessagesUsage>,
+    },
+    MessageStop,
+    Ping,
+    Error {
+        error: MessagesError,
+    },
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Deserialize)]
+struct MessageStart {
+    #[serde(default)]
+    id: String,
+    #[serde(default)]
+    usage: Option<MessagesUsage>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct MessageDelta {
+    #[serde(default)]
+    stop_reason: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, Copy, Deserialize)]
+struct MessagesUsage {
+    #[serde(default)]
+    input_tokens: Option<i64>,
+    #[serde(default)]
+    cache_creation_input_tokens: Option<i64>,
+    #[serde(default)]
+    cache_read_input_tokens: Option<i64>,
+    #[serde(default)]
+    output_tokens: Option<i64>,
+}
+
+impl MessagesUsage {
+    /// `message_delta` usage is cumulative, so later values replace earlier ones.
+    fn merge(&mut self, other: MessagesUsage) {
+        self.input_tokens = other.input_tokens.or(self.input_tokens);
+        self.cache_creation_input_tokens = other
+            .cache_creation_input_tokens
+            .or(self.cache_creation_input_tokens);
+        self.cache_read_input_tokens = other
+            .cache_read_input_tokens
+            .or(self.cache_read_input_tokens);
+        self.output_tokens = other.output_tokens.or(self.output_tokens);
+    }
+}
+
+impl From<MessagesUsage> for TokenUsage {
+    fn from(val: MessagesUsage) -> Self {
+        let cached = val.cache_read_input_tokens.unwrap_or(0);
+        let input_tokens =
+            val.input_tokens.unwrap_or(0) + val.cache_creation_input_tokens.unwrap_or(0) + cached;
+        let output_tokens = val.output_tokens.unwrap_or(0);
+        TokenUsage {
+            input_tokens,
+            cached_input_tokens: cached,
+            output_tokens,
+            reasoning_output_tokens: 0,
+            total_tokens: input_tokens + output_tokens,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct MessagesError {
+    #[serde(rename = "type", default)]
+    kind: String,
+    #[serde(default)]
+    message: String,
+}
+
+/// In-flight content block, keyed by its `index` in the stream.
+enum Block {
+    Text(ResponseItem),
+    Thinking(ResponseItem),
+    RedactedThinking(String),
+    ToolUse {
+        id: String,
+        name: String,
+        input_json: String,
+    },
+}
+
+pub async fn process_messages_sse<S>(
+    stream: S,
+    tx_event: mpsc::Sender<Result<ResponseEvent, ApiError>>,
+    idle_timeout: Duration,
+    telemetry: Option<Arc<dyn SseTelemetry>>,
+) where
+    S: Stream<Item = Result<bytes::Bytes, codex_client::TransportError>> + Unpin,
+{
+    let mut stream = stream.eventsource();
+    let mut blocks: HashMap<usize, Block> = HashMap::new();
+    let mut response_id = String::new();
+    let mut usage: Option<MessagesUsage> = None;
+
+    loop {
+        let start = Instant::now();
+        let response = timeout(idle_timeout, stream.next()).await;
+        if let Some(t) = telemetry.as_ref() {
+            t.on_sse_poll(&response, start.elapsed());
+        }
+        let sse = match response {
+            Ok(Some(Ok(sse))) => sse,
+            Ok(Some(Err(e))) => {
+                let _ = tx_event.send(Err(ApiError::Stream(e.to_string()))).await;
+                return;
+            }
+            Ok(None) => {
+                let _ = tx_event
+                    .send(Err(ApiError::Stream(
+                        "stream closed before message_stop".into(),
+                    )))
+                    .await;
+                return;
+            }
+            Err(_) => {
+                let _ = tx_event
+                    .send(Err(ApiError::Stream("idle timeout waiting for SSE".into())))
+                    .await;
+                return;
+            }
+        };
+
+        trace!("SSE event: {}", sse.data);
+
+        if sse.data.trim().is_empty() {
+            continue;
+        }
+
+        let event: MessagesEvent = match serde_json::from_str(&sse.data) {
+            Ok(event) => event,
+            Err(err) => {
+                debug!(
+                    "Failed to parse Messages SSE event: {err}, data: {}",
+                    &sse.data
+                );
+                continue;
+            }
+        };
+
+        match event {
+            MessagesEvent::MessageStart { message } => {
+                response_id = message.id;
+                usage = message.usage;
+                let _ = tx_event.send(Ok(ResponseEvent::Created)).await;
+            }
+            MessagesEvent::ContentBlockStart {
+                index,
+                content_block,
+            } => {
+                let Some(block) = start_block(&content_block) else {
+                    continue;
+                };
+                match &block {
+                    Block::Text(item) | Block::Thinking(item) => {
+                        let _ = tx_event
+                            .send(Ok(ResponseEvent::OutputItemAdded(item.clone())))
+                            .await;
+                    }
+                    Block::RedactedThinking(_) | Block::ToolUse { .. } => {}
+                }
+                blocks.insert(index, block);
+                if let Some(text) = content_block.get("text").and_then(Value::as_str)
+                    && !text.is_empty()
+                {
+                    apply_delta(&tx_event, &mut blocks, index, "text_delta", text).await;
+                }
+            }
+            MessagesEvent::ContentBlockDelta { index, delta } => {
+                let kind = delta.get("type").and_then(Value::as_str).unwrap_or("");
+                let text = ["text", "thinking", "signature", "partial_json"]
+                    .iter()
+                    .find_map(|key| delta.get(*key).and_then(Value::as_str))
+                    .unwrap_or("");
+                apply_delta(&tx_event, &mut blocks, index, kind, text).await;
+            }
+            MessagesEvent::ContentBlockStop { index } => {
+                if let Some(item) = blocks.remove(&index).map(finish_block) {
+                    let _ = tx_event.send(Ok(ResponseEvent::OutputItemDone(item))).await;
+                }
+            }
+            MessagesEvent::MessageDelta {
+                delta,
+                usage: delta_usage,
+            } => {
+                if let Some(delta_usage) = delta_usage {
+                    usage.get_or_insert_default().merge(delta_usage);
+                }
+                if delta.stop_reason.as_deref() == Some("model_context_window_exceeded") {
+                    let _ = tx_event.send(Err(ApiError::ContextWindowExceeded)).await;
+                    return;
+                }
+            }
+            MessagesEvent::MessageStop => {
+                let _ = tx_event
+                    .send(Ok(ResponseEvent::Completed {
+                        response_id,
+                        token_usage: usage.map(Into::into),
+                    }))
+                    .await;
+                return;
+            }
+            MessagesEvent::Error { error } => {
+                let err = match error.kind.as_str() {
+                    "overloaded_error" | "api_error" => ApiError::Retryable {
+                        message: error.message,
+                        delay: None,
+                    },
+                    _ => ApiError::Stream(format!("{}: {}", error.kind, error.message)),
+                };
+                let _ = tx_event.send(Err(err)).await;
+                return;
+            }
+            MessagesEvent::Ping | MessagesEvent::Unknown => {}
+        }
+    }
+}
+
+fn start_block(content_block: &Value) -> Option<Block> {
+    let kind = content_block.get("type").and_then(Value::as_str)?;
+    let str_field = |key: &str| {
+        content_block
+            .get(key)
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string()
+    };
+    match kind {
+        "text" => Some(Block::Text(ResponseItem::Message {
+            id: None,
+            role: "assistant".to_string(),
+            content: Vec::new(),
+        })),
+        "thinking" => Some(Block::Thinking(ResponseItem::Reasoning {
+            id: String::new(),
+            summary: Vec::new(),
+            content: Some(Vec::new()),
+            encrypted_content: None,
+        })),
+        "redacted_thinking" => Some(Block::RedactedThinking(str_field("data"))),
+        "tool_use" => Some(Block::ToolUse {
+            id: str_field("id"),
+            name: str_field("name"),
+            input_json: String::new(),
+        }),
+        _ => None,
+    }
+}
+
+async fn apply_delta(
+    tx_event: &mpsc::Sender<Result<ResponseEvent, ApiError>>,
+    blocks: &mut HashMap<usize, Block>,
+    index: usize,
+    kind: &str,
+    text: &str,
+) {
+    match (blocks.get_mut(&index), kind) {
+        (Some(Block::Text(ResponseItem::Message { content, .. })), "text_delta") => {
+            match content.last_mut() {
+                Some(ContentItem::OutputText { text: existing }) => existing.push_str(text),
+                _ => content.push(ContentItem::OutputText {
+                    text: text.to_string(),
+                }),
+            }
+            let _ = tx_event
+                .send(Ok(ResponseEvent::OutputTextDelta(text.to_string())))
+                .await;
+        }
+        (
+            Some(Block::Thinking(ResponseItem::Reasoning {
+                content: Some(content),
+                ..
+            })),
+            "thinking_delta",
+        ) => {
+            match content.last_mut() {
+                Some(ReasoningItemContent::ReasoningText { text: existing }) => {
+                    existing.push_str(text)
+                }
+                _ => content.push(ReasoningItemContent::ReasoningText {
+                    text: text.to_string(),
+                }),
+            }
+            let _ = tx_event
+                .send(Ok(ResponseEvent::ReasoningContentDelta {
+                    delta: text.to_string(),
+                    content_index: 0,
+                }))
+                .await;
+        }
+        (
+            Some(Block::Thinking(ResponseItem::Reasoning {
+                encrypted_content, ..
+            })),
+            "signature_delta",
+        ) => {
+            encrypted_content
+                .get_or_insert_with(String::new)
+                .push_str(text);
+        }
+        (Some(Block::ToolUse { input_json, .. }), "input_json_delta") => {
+            input_json.push_str(text);
+        }
+        _ => {}
+    }
+}
+
+fn finish_block(block: Block) -> ResponseItem {
+    match block {
+        Block::Text(item) | Block::Thinking(item) => item,
+        // The signature is all we need to replay redacted thinking.
+        Block::RedactedThinking(data) => ResponseItem::Reasoning {
+            id: String::new(),
+            summary: Vec::new(),
+            content: None,
+            encrypted_content: Some(data),
+        },
+        Block::ToolUse {
+            id,
+            name,
+            input_json,
+        } => ResponseItem::FunctionCall {
+            id: None,
+            name,
+            arguments: if input_json.trim().is_empty() {
+                "{}".to_string()
+            } else {
+                input_json
+            },
+            call_id: id,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::assert_matches;
+    use futures::TryStreamExt;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tokio_util::io::ReaderStream;
+
+    fn build_body(events: &[Value]) -> String {
+        let mut body = String::new();
+        for e in events {
+            let kind = e.get("type").and_then(Value::as_str).unwrap_or("message");
+            body.push_str(&format!("event: {kind}\ndata: {e}\n\n"));
+        }
+        body
+    }
+
+    async fn collect_events(body: &str) -> Vec<Result<ResponseEvent, ApiError>> {
+        let reader = ReaderStream::new(std::io::Cursor::new(body.to_string()))
+            .map_err(|err| codex_client::TransportError::Network(err.to_string()));
+        let (tx, mut rx) = mpsc::channel::<Result<ResponseEvent, ApiError>>(16);
+        tokio::spawn(process_messages_sse(
+            reader,
+            tx,
+            Duration::from_millis(1000),
+            None,
+        ));
+
+        let mut out = Vec::new();
+        while let Some(ev) = rx.recv().await {
+            out.push(ev);
+        }
+        out
+    }
+
+    #[tokio::test]
+    async fn emits_thinking_text_and_tool_use() {
+        let body = build_body(&[
+            json!({"type": "message_start", "message": {"id": "msg_1", "usage": {"input_tokens": 10, "cache_creation_input_tokens": 5, "cache_read_input_tokens": 100, "output_tokens": 1}}}),
+            json!({"type": "content_block_start", "index": 0, "content_block": {"type": "thinking", "thinking": ""}}),
+            json!({"type": "content_block_delta", "index": 0, "delta": {"type": "thinking_delta", "thinking": "hmm"}}),
+            json!({"type": "content_block_delta", "index": 0, "delta": {"type": "signature_delta", "signature": "sig"}}),
+            json!({"type": "content_block_stop", "index": 0}),
+            json!({"type": "content_block_start", "index": 1, "content_block": {"type": "text", "text": ""}}),
+            json!({"type": "content_block_delta", "index": 1, "delta": {"type": "text_delta", "text": "Hel"}}),
+            json!({"type": "content_block_delta", "index": 1, "delta": {"type": "text_delta", "text": "lo"}}),
+            json!({"type": "content_block_stop", "index": 1}),
+            json!({"type": "content_block_start", "index": 2, "content_block": {"type": "tool_use", "id": "toolu_1", "name": "shell", "input": {}}}),
+            json!({"type": "content_block_delta", "index": 2, "delta": {"type": "input_json_delta", "partial_json": "{\"command\":"}}),
+            json!({"type": "content_block_delta", "index": 2, "delta": {"type": "input_json_delta", "partial_json": "[\"ls\"]}"}}),
+            json!({"type": "content_block_stop", "index": 2}),
+            json!({"type": "message_delta", "delta": {"stop_reason": "tool_use"}, "usage": {"output_tokens": 42}}),
+            json!({"type": "message_stop"}),
+        ]);
+        let events: Vec<ResponseEvent> = collect_events(&body)
+            .await
+            .into_iter()
+            .map(|ev| ev.expect("stream error"))
+            .collect();
+
+        assert_matches!(events[0], ResponseEvent::Created);
+        assert_matches!(
+            &events[3],
+            ResponseEvent::OutputItemDone(ResponseItem::Reasoning {
+                content: Some(content),
+                encrypted_content: Some(signature),
+                ..
+            }) if signature == "sig"
+                && content == &vec![ReasoningItemContent::ReasoningText { text: "hmm".to_string() }]
+        );
+        assert_matches!(
+            &events[7],
+            ResponseEvent::OutputItemDone(ResponseItem::Message { content, .. })
+                if content == &vec![ContentItem::OutputText { text: "Hello".to_string() }]
+        );
+        assert_matches!(
+            &events[8],
+            ResponseEvent::OutputItemDone(ResponseItem::FunctionCall { call_id, name, arguments, .. })
+                if call_id == "toolu_1" && name == "shell" && arguments == "{\"command\":[\"ls\"]}"
+        );
+        let ResponseEvent::Completed {
+            response_id,
+            token_usage: Some(usage),
+        } = &events[9]
+        else {
+            panic!("expected completion, got {:?}", events[9]);
+        };
+        assert_eq!(response_id, "msg_1");
+        assert_eq!(
+            (
+                usage.input_tokens,
+                usage.cached_input_tokens,
+                usage.output_tokens,
+                usage.total_tokens,
+            ),
+            (115, 100, 42, 157)
+        );
+        assert_eq!(events.len(), 10);
+    }
+
+    #[tokio::test]
+    async fn overloaded_error_is_retryable() {
+        let body = build_body(&[
+            json!({"type": "message_start", "message": {"id": "msg_1"}}),
+            json!({"type": "error", "error": {"type": "overloaded_error", "message": "Overloaded"}}),
+        ]);
+        let events = collect_events(&body).await;
+
+        assert_matches!(events[0], Ok(ResponseEvent::Created));
+        assert_matches!(
+            &events[1],
+            Err(ApiError::Retryable { message, delay: None }) if message == "Overloaded"
+        );
+    }
+
+    #[tokio::test]
+    async fn stream_closed_before_message_stop_is_an_error() {
+        let body = build_body(&[json!({"type": "message_start", "message": {"id": "msg_1"}})]);
+        let events = collect_events(&body).await;
+
+        assert_matches!(events[0], Ok(ResponseEvent::Created));
+        assert_matches!(events[1], Err(ApiError::Stream(_)));
+    }
+}
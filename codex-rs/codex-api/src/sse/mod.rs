@@ -1,4 +1,6 @@
 pub mod chat;
+pub mod gemini;
+pub mod messages;
 pub mod responses;
 
 pub use responses::process_sse;
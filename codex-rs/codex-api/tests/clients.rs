@@ -7,6 +7,8 @@ use async_trait::async_trait;
 use bytes::Bytes;
 use codex_api::AuthProvider;
 use codex_api::ChatClient;
+use codex_api::GeminiClient;
+use codex_api::MessagesClient;
 use codex_api::Provider;
 use codex_api::ResponsesClient;
 use codex_api::ResponsesOptions;
@@ -284,6 +286,66 @@ async fn streaming_client_adds_auth_headers() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn messages_client_sends_api_key_header() -> Result<()> {
+    let state = RecordingState::default();
+    let transport = RecordingTransport::new(state.clone());
+    let auth = StaticAuth::new("sk-ant", "acct-1");
+    let client = MessagesClient::new(transport, provider("anthropic", WireApi::Messages), auth);
+
+    let body = serde_json::json!({ "model": "claude-test" });
+    let _stream = client.stream(body, HeaderMap::new()).await?;
+
+    let requests = state.take_stream_requests();
+    assert_path_ends_with(&requests, "/messages");
+    let req = &requests[0];
+    assert_eq!(
+        req.headers.get("x-api-key").and_then(|v| v.to_str().ok()),
+        Some("sk-ant")
+    );
+    assert!(req.headers.get(http::header::AUTHORIZATION).is_none());
+    Ok(())
+}
+
+#[tokio::test]
+async fn gemini_client_streams_from_model_path() -> Result<()> {
+    let state = RecordingState::default();
+    let transport = RecordingTransport::new(state.clone());
+    let auth = StaticAuth::new("goog-key", "acct-1");
+    let client = GeminiClient::new(transport, provider("gemini", WireApi::Gemini), auth);
+
+    let prompt = codex_api::Prompt {
+        instructions: "Say hi".to_string(),
+        input: vec![ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: "hi".to_string(),
+            }],
+        }],
+        tools: Vec::<Value>::new(),
+        parallel_tool_calls: false,
+        output_schema: None,
+    };
+    let _stream = client
+        .stream_prompt("gemini-test", &prompt, None, None, None)
+        .await?;
+
+    let requests = state.take_stream_requests();
+    assert_path_ends_with(
+        &requests,
+        "/models/gemini-test:streamGenerateContent?alt=sse",
+    );
+    assert_eq!(
+        requests[0]
+            .headers
+            .get("x-goog-api-key")
+            .and_then(|v| v.to_str().ok()),
+        Some("goog-key")
+    );
+    Ok(())
+}
+
 #[tokio::test]
 async fn streaming_client_retries_on_transport_error() -> Result<()> {
     let transport = FlakyTransport::new();
@@ -0,0 +1,229 @@
+//! Drives the Messages and Gemini clients against a local mock server that
+//! replays recorded SSE fixtures from `tests/fixtures`.
+
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::Result;
+use codex_api::AuthProvider;
+use codex_api::GeminiClient;
+use codex_api::MessagesClient;
+use codex_api::Prompt;
+use codex_api::ResponseEvent;
+use codex_api::ResponseStream;
+use codex_api::provider::Provider;
+use codex_api::provider::RetryConfig;
+use codex_api::provider::WireApi;
+use codex_client::ReqwestTransport;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use futures::StreamExt;
+use http::HeaderMap;
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+use serde_json::json;
+use wiremock::Mock;
+use wiremock::MockServer;
+use wiremock::ResponseTemplate;
+use wiremock::matchers::method;
+use wiremock::matchers::path;
+use wiremock::matchers::query_param;
+
+#[derive(Clone)]
+struct StaticKey(&'static str);
+
+impl AuthProvider for StaticKey {
+    fn bearer_token(&self) -> Option<String> {
+        Some(self.0.to_string())
+    }
+}
+
+fn provider(base_url: String, wire: WireApi) -> Provider {
+    Provider {
+        name: "fixture".to_string(),
+        base_url,
+        query_params: None,
+        wire,
+        headers: HeaderMap::new(),
+        retry: RetryConfig {
+            max_attempts: 1,
+            base_delay: Duration::from_millis(1),
+            retry_429: false,
+            retry_5xx: false,
+            retry_transport: false,
+        },
+        stream_idle_timeout: Duration::from_secs(5),
+    }
+}
+
+fn prompt() -> Prompt {
+    Prompt {
+        instructions: "Be brief.".to_string(),
+        input: vec![ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: "list the files".to_string(),
+            }],
+        }],
+        tools: Vec::<Value>::new(),
+        parallel_tool_calls: false,
+        output_schema: None,
+    }
+}
+
+fn fixture(name: &str) -> Result<String> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(name);
+    Ok(std::fs::read_to_string(path)?)
+}
+
+fn sse_response(body: String) -> ResponseTemplate {
+    ResponseTemplate::new(200)
+        .insert_header("content-type", "text/event-stream")
+        .set_body_raw(body, "text/event-stream")
+}
+
+async fn collect(mut stream: ResponseStream) -> Result<Vec<ResponseEvent>> {
+    let mut events = Vec::new();
+    while let Some(event) = stream.next().await {
+        events.push(event?);
+    }
+    Ok(events)
+}
+
+/// The completed output items plus the id of the completed response.
+fn outputs(events: &[ResponseEvent]) -> (Vec<ResponseItem>, Option<String>) {
+    let items = events
+        .iter()
+        .filter_map(|event| match event {
+            ResponseEvent::OutputItemDone(item) => Some(item.clone()),
+            _ => None,
+        })
+        .collect();
+    let completed = events.iter().find_map(|event| match event {
+        ResponseEvent::Completed { response_id, .. } => Some(response_id.clone()),
+        _ => None,
+    });
+    (items, completed)
+}
+
+/// Checks for the assistant text and `shell` call recorded in both fixtures.
+/// Gemini emits buffered text after function calls, so order is not checked.
+fn assert_text_and_shell_call(items: &[ResponseItem]) -> Result<()> {
+    assert_eq!(items.len(), 2, "unexpected items: {items:?}");
+    let text = items.iter().find_map(|item| match item {
+        ResponseItem::Message { role, content, .. } if role == "assistant" => Some(content),
+        _ => None,
+    });
+    assert_eq!(
+        text,
+        Some(&vec![ContentItem::OutputText {
+            text: "Listing files.".to_string()
+        }])
+    );
+    let call = items.iter().find_map(|item| match item {
+        ResponseItem::FunctionCall {
+            name, arguments, ..
+        } => Some((name, arguments)),
+        _ => None,
+    });
+    let Some((name, arguments)) = call else {
+        panic!("expected a function call, got {items:?}");
+    };
+    assert_eq!(name, "shell");
+    let arguments: Value = serde_json::from_str(arguments)?;
+    assert_eq!(arguments, json!({"command": ["ls"]}));
+    Ok(())
+}
+
+#[tokio::test]
+async fn messages_client_streams_recorded_fixture() -> Result<()> {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/messages"))
+        .respond_with(sse_response(fixture("messages_text_and_tool.sse")?))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = MessagesClient::new(
+        ReqwestTransport::new(reqwest::Client::new()),
+        provider(format!("{}/v1", server.uri()), WireApi::Messages),
+        StaticKey("sk-ant-fixture"),
+    );
+    let stream = client
+        .stream_prompt("claude-test", &prompt(), None, None, None)
+        .await?;
+    let events = collect(stream).await?;
+
+    let (items, completed) = outputs(&events);
+    assert_text_and_shell_call(&items)?;
+    assert_eq!(completed.as_deref(), Some("msg_fixture"));
+
+    let requests = server.received_requests().await.unwrap_or_default();
+    let [request] = requests.as_slice() else {
+        panic!("expected one request, got {}", requests.len());
+    };
+    assert_eq!(
+        request
+            .headers
+            .get("x-api-key")
+            .and_then(|v| v.to_str().ok()),
+        Some("sk-ant-fixture")
+    );
+    assert!(request.headers.get("anthropic-version").is_some());
+    let body: Value = serde_json::from_slice(&request.body)?;
+    assert_eq!(body["model"], json!("claude-test"));
+    assert_eq!(body["stream"], json!(true));
+    assert_eq!(body["system"][0]["text"], json!("Be brief."));
+    assert_eq!(body["messages"][0]["role"], json!("user"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn gemini_client_streams_recorded_fixture() -> Result<()> {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1beta/models/gemini-test:streamGenerateContent"))
+        .and(query_param("alt", "sse"))
+        .respond_with(sse_response(fixture("gemini_text_and_function_call.sse")?))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = GeminiClient::new(
+        ReqwestTransport::new(reqwest::Client::new()),
+        provider(format!("{}/v1beta", server.uri()), WireApi::Gemini),
+        StaticKey("goog-fixture"),
+    );
+    let stream = client
+        .stream_prompt("gemini-test", &prompt(), None, None, None)
+        .await?;
+    let events = collect(stream).await?;
+
+    let (items, completed) = outputs(&events);
+    assert_text_and_shell_call(&items)?;
+    assert_eq!(completed.as_deref(), Some("gemini_fixture"));
+
+    let requests = server.received_requests().await.unwrap_or_default();
+    let [request] = requests.as_slice() else {
+        panic!("expected one request, got {}", requests.len());
+    };
+    assert_eq!(
+        request
+            .headers
+            .get("x-goog-api-key")
+            .and_then(|v| v.to_str().ok()),
+        Some("goog-fixture")
+    );
+    let body: Value = serde_json::from_slice(&request.body)?;
+    assert_eq!(
+        body["systemInstruction"],
+        json!({"parts": [{"text": "Be brief."}]})
+    );
+    assert_eq!(body["contents"][0]["role"], json!("user"));
+    Ok(())
+}
@@ -0,0 +1,766 @@
+//! Record/replay of HTTP traffic for reproducible sessions.
+//!
+//! In record mode every request sent through [`crate::ReqwestTransport`] is
+//! written to a directory as one JSON "cassette" file per interaction,
+//! including each streamed chunk and when it arrived. In replay mode the
+//! transport never touches the network: requests are answered from the
+//! recorded interactions, matched by method, URL and request body. A request
+//! whose body matches no recording is an error unless loose matching was
+//! asked for with [`HttpCassetteMode::ReplayLoose`].
+//!
+//! Request headers are never recorded, so credentials do not end up in the
+//! cassette. Request and response bodies are recorded verbatim.
+
+use crate::error::TransportError;
+use crate::request::Request;
+use crate::request::Response;
+use crate::transport::StreamResponse;
+use bytes::Bytes;
+use futures::StreamExt;
+use http::HeaderMap;
+use http::HeaderName;
+use http::HeaderValue;
+use http::StatusCode;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::OnceLock;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use std::time::Instant;
+use tracing::warn;
+
+/// Top-level request fields that differ between otherwise identical runs and
+/// are ignored when matching a request against the cassette.
+const VOLATILE_BODY_FIELDS: &[&str] = &["prompt_cache_key"];
+
+/// Response headers that are not worth keeping in a cassette.
+const SKIPPED_RESPONSE_HEADERS: &[&str] = &["set-cookie"];
+
+static HTTP_CASSETTE: OnceLock<Arc<HttpCassette>> = OnceLock::new();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HttpCassetteMode {
+    /// Forward requests and write every interaction to the cassette.
+    Record,
+    /// Answer requests from the cassette without any network access.
+    Replay,
+    /// Like `Replay`, but a request whose body matches no recording is
+    /// answered by the next unused recording for the same method and URL.
+    ReplayLoose,
+}
+
+/// Installs a process-wide cassette used by every [`crate::ReqwestTransport`]
+/// created afterwards. Must be called before the first request is sent.
+pub fn install_http_cassette(mode: HttpCassetteMode, dir: PathBuf) -> io::Result<()> {
+    let cassette = match mode {
+        HttpCassetteMode::Record => HttpCassette::record(dir)?,
+        HttpCassetteMode::Replay => HttpCassette::replay(dir)?,
+        HttpCassetteMode::ReplayLoose => HttpCassette::replay_loose(dir)?,
+    };
+    HTTP_CASSETTE
+        .set(Arc::new(cassette))
+        .map_err(|_| io::Error::other("an HTTP cassette is already installed"))
+}
+
+pub(crate) fn installed_http_cassette() -> Option<Arc<HttpCassette>> {
+    HTTP_CASSETTE.get().cloned()
+}
+
+#[derive(Debug)]
+pub struct HttpCassette {
+    mode: HttpCassetteMode,
+    dir: PathBuf,
+    next_seq: AtomicUsize,
+    /// Recorded interactions available for replay, paired with whether each
+    /// one has already been served.
+    interactions: Mutex<Vec<(Interaction, bool)>>,
+}
+
+impl HttpCassette {
+    /// Creates `dir` if needed. New interactions are numbered after any that
+    /// are already in the directory.
+    pub fn record(dir: PathBuf) -> io::Result<Self> {
+        std::fs::create_dir_all(&dir)?;
+        let existing = cassette_files(&dir)?.len();
+        Ok(Self {
+            mode: HttpCassetteMode::Record,
+            dir,
+            next_seq: AtomicUsize::new(existing),
+            interactions: Mutex::new(Vec::new()),
+        })
+    }
+
+    pub fn replay(dir: PathBuf) -> io::Result<Self> {
+        Self::open_replay(dir, HttpCassetteMode::Replay)
+    }
+
+    pub fn replay_loose(dir: PathBuf) -> io::Result<Self> {
+        Self::open_replay(dir, HttpCassetteMode::ReplayLoose)
+    }
+
+    fn open_replay(dir: PathBuf, mode: HttpCassetteMode) -> io::Result<Self> {
+        let interactions = read_interactions(&dir)?
+            .into_iter()
+            .map(|interaction| (interaction, false))
+            .collect();
+        Ok(Self {
+            mode,
+            dir,
+            next_seq: AtomicUsize::new(0),
+            interactions: Mutex::new(interactions),
+        })
+    }
+
+    pub fn mode(&self) -> HttpCassetteMode {
+        self.mode
+    }
+
+    /// Whether requests are answered from the cassette.
+    pub fn is_replay(&self) -> bool {
+        self.mode != HttpCassetteMode::Record
+    }
+
+    pub(crate) fn record_response(&self, req: &Request, result: &Result<Response, TransportError>) {
+        let response = match result {
+            Ok(response) => RecordedResponse::Response {
+                status: response.status.as_u16(),
+                headers: record_headers(&response.headers),
+                body: String::from_utf8_lossy(&response.body).into_owned(),
+            },
+            Err(err) => RecordedResponse::Error {
+                error: RecordedError::from(err),
+            },
+        };
+        let interaction = Interaction {
+            request: RecordedRequest::from(req),
+            response,
+        };
+        if let Err(err) = write_interaction(&self.next_path(req), &interaction) {
+            warn!("failed to write HTTP cassette: {err}");
+        }
+    }
+
+    /// Records a streaming interaction. The cassette file is written once the
+    /// returned byte stream finishes or is dropped.
+    pub(crate) fn record_stream(
+        &self,
+        req: &Request,
+        result: Result<StreamResponse, TransportError>,
+    ) -> Result<StreamResponse, TransportError> {
+        let path = self.next_path(req);
+        let request = RecordedRequest::from(req);
+        let response = match result {
+            Ok(response) => response,
+            Err(err) => {
+                let interaction = Interaction {
+                    request,
+                    response: RecordedResponse::Error {
+                        error: RecordedError::from(&err),
+                    },
+                };
+                if let Err(write_err) = write_interaction(&path, &interaction) {
+                    warn!("failed to write HTTP cassette: {write_err}");
+                }
+                return Err(err);
+            }
+        };
+
+        let mut recorder = StreamRecorder {
+            path,
+            request,
+            status: response.status.as_u16(),
+            headers: record_headers(&response.headers),
+            started: Instant::now(),
+            chunks: Vec::new(),
+            pending: Vec::new(),
+            error: None,
+        };
+        let bytes = response.bytes.map(move |item| {
+            match &item {
+                Ok(bytes) => recorder.push_chunk(bytes),
+                Err(err) => recorder.error = Some(RecordedError::from(err)),
+            }
+            item
+        });
+        Ok(StreamResponse {
+            status: response.status,
+            headers: response.headers,
+            bytes: Box::pin(bytes),
+        })
+    }
+
+    pub(crate) fn replay_response(&self, req: &Request) -> Result<Response, TransportError> {
+        match self.take_interaction(req)?.response {
+            RecordedResponse::Response {
+                status,
+                headers,
+                body,
+            } => Ok(Response {
+                status: replay_status(status)?,
+                headers: replay_headers(&headers),
+                body: Bytes::from(body),
+            }),
+            RecordedResponse::Stream {
+                status,
+                headers,
+                chunks,
+                error,
+            } => match error {
+                Some(error) => Err(error.into_transport_error()),
+                None => Ok(Response {
+                    status: replay_status(status)?,
+                    headers: replay_headers(&headers),
+                    body: Bytes::from(
+                        chunks
+                            .into_iter()
+                            .map(|chunk| chunk.data)
+                            .collect::<String>(),
+                    ),
+                }),
+            },
+            RecordedResponse::Error { error } => Err(error.into_transport_error()),
+        }
+    }
+
+    /// Replays a streaming interaction, delivering each chunk at the offset it
+    /// was originally received.
+    pub(crate) fn replay_stream(&self, req: &Request) -> Result<StreamResponse, TransportError> {
+        let (status, headers, chunks, error) = match self.take_interaction(req)?.response {
+            RecordedResponse::Stream {
+                status,
+                headers,
+                chunks,
+                error,
+            } => (status, headers, chunks, error),
+            RecordedResponse::Response {
+                status,
+                headers,
+                body,
+            } => (
+                status,
+                headers,
+                vec![RecordedChunk {
+                    offset_ms: 0,
+                    data: body,
+                }],
+                None,
+            ),
+            RecordedResponse::Error { error } => return Err(error.into_transport_error()),
+        };
+
+        let started = tokio::time::Instant::now();
+        let bytes = futures::stream::iter(chunks)
+            .then(move |chunk| async move {
+                tokio::time::sleep_until(started + Duration::from_millis(chunk.offset_ms)).await;
+                Ok(Bytes::from(chunk.data))
+            })
+            .chain(futures::stream::iter(
+                error.map(|error| Err(error.into_transport_error())),
+            ));
+        Ok(StreamResponse {
+            status: replay_status(status)?,
+            headers: replay_headers(&headers),
+            bytes: Box::pin(bytes),
+        })
+    }
+
+    /// Returns the first unused interaction recorded for an identical request.
+    /// In [`HttpCassetteMode::ReplayLoose`] a request with a different body
+    /// falls back to the next unused interaction for the same method and URL.
+    fn take_interaction(&self, req: &Request) -> Result<Interaction, TransportError> {
+        let request = RecordedRequest::from(req);
+        let mut interactions = self
+            .interactions
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let exact = interactions
+            .iter()
+            .position(|(recorded, used)| !used && recorded.request == request);
+        let same_url = interactions.iter().position(|(recorded, used)| {
+            !used
+                && recorded.request.method == request.method
+                && recorded.request.url == request.url
+        });
+        let position = match (exact, same_url) {
+            (Some(position), _) => position,
+            (None, Some(position)) if self.mode == HttpCassetteMode::ReplayLoose => {
+                warn!(
+                    "replaying {} {} from a cassette recorded with a different request body",
+                    request.method, request.url
+                );
+                position
+            }
+            (None, Some(_)) => {
+                return Err(TransportError::Build(format!(
+                    "request body for {} {} does not match the recording in {}; \
+                     re-record the cassette or replay with --replay-http-loose",
+                    request.method,
+                    request.url,
+                    self.dir.display()
+                )));
+            }
+            (None, None) => {
+                return Err(TransportError::Build(format!(
+                    "no recorded HTTP interaction for {} {} in {}",
+                    request.method,
+                    request.url,
+                    self.dir.display()
+                )));
+            }
+        };
+        let (interaction, used) = &mut interactions[position];
+        *used = true;
+        Ok(interaction.clone())
+    }
+
+    fn next_path(&self, req: &Request) -> PathBuf {
+        let seq = self.next_seq.fetch_add(1, Ordering::SeqCst);
+        let endpoint = req
+            .url
+            .split('?')
+            .next()
+            .and_then(|path| path.rsplit('/').next())
+            .unwrap_or_default()
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+            .collect::<String>();
+        self.dir.join(format!(
+            "{seq:04}-{}-{endpoint}.json",
+            req.method.as_str().to_ascii_lowercase()
+        ))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Interaction {
+    request: RecordedRequest,
+    response: RecordedResponse,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct RecordedRequest {
+    method: String,
+    url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    body: Option<Value>,
+}
+
+impl From<&Request> for RecordedRequest {
+    fn from(req: &Request) -> Self {
+        let body = req.body.clone().map(|mut body| {
+            if let Some(obj) = body.as_object_mut() {
+                for field in VOLATILE_BODY_FIELDS {
+                    obj.remove(*field);
+                }
+            }
+            body
+        });
+        Self {
+            method: req.method.to_string(),
+            url: req.url.clone(),
+            body,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum RecordedResponse {
+    Response {
+        status: u16,
+        headers: BTreeMap<String, String>,
+        body: String,
+    },
+    Stream {
+        status: u16,
+        headers: BTreeMap<String, String>,
+        chunks: Vec<RecordedChunk>,
+        /// Error that ended the stream, if it did not finish cleanly.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        error: Option<RecordedError>,
+    },
+    Error {
+        error: RecordedError,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct RecordedChunk {
+    /// Milliseconds between the response headers and this chunk.
+    offset_ms: u64,
+    data: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum RecordedError {
+    Http {
+        status: u16,
+        #[serde(default)]
+        headers: BTreeMap<String, String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        body: Option<String>,
+    },
+    RetryLimit,
+    Timeout,
+    Network {
+        message: String,
+    },
+    Build {
+        message: String,
+    },
+}
+
+impl From<&TransportError> for RecordedError {
+    fn from(err: &TransportError) -> Self {
+        match err {
+            TransportError::Http {
+                status,
+                headers,
+                body,
+            } => RecordedError::Http {
+                status: status.as_u16(),
+                headers: headers.as_ref().map(record_headers).unwrap_or_default(),
+                body: body.clone(),
+            },
+            TransportError::RetryLimit => RecordedError::RetryLimit,
+            TransportError::Timeout => RecordedError::Timeout,
+            TransportError::Network(message) => RecordedError::Network {
+                message: message.clone(),
+            },
+            TransportError::Build(message) => RecordedError::Build {
+                message: message.clone(),
+            },
+        }
+    }
+}
+
+impl RecordedError {
+    fn into_transport_error(self) -> TransportError {
+        match self {
+            RecordedError::Http {
+                status,
+                headers,
+                body,
+            } => match StatusCode::from_u16(status) {
+                Ok(status) => TransportError::Http {
+                    status,
+                    headers: Some(replay_headers(&headers)),
+                    body,
+                },
+                Err(err) => TransportError::Build(err.to_string()),
+            },
+            RecordedError::RetryLimit => TransportError::RetryLimit,
+            RecordedError::Timeout => TransportError::Timeout,
+            RecordedError::Network { message } => TransportError::Network(message),
+            RecordedError::Build { message } => TransportError::Build(message),
+        }
+    }
+}
+
+/// Collects the chunks of a streamed response and writes the interaction to
+/// disk when dropped.
+struct StreamRecorder {
+    path: PathBuf,
+    request: RecordedRequest,
+    status: u16,
+    headers: BTreeMap<String, String>,
+    started: Instant,
+    chunks: Vec<RecordedChunk>,
+    /// Trailing bytes of an incomplete UTF-8 sequence, carried over to the
+    /// next chunk.
+    pending: Vec<u8>,
+    error: Option<RecordedError>,
+}
+
+impl StreamRecorder {
+    fn push_chunk(&mut self, bytes: &[u8]) {
+        self.pending.extend_from_slice(bytes);
+        let complete = match std::str::from_utf8(&self.pending) {
+            Ok(_) => self.pending.len(),
+            Err(err) if err.error_len().is_none() => err.valid_up_to(),
+            Err(_) => self.pending.len(),
+        };
+        if complete == 0 {
+            return;
+        }
+        let rest = self.pending.split_off(complete);
+        let data = String::from_utf8_lossy(&self.pending).into_owned();
+        self.pending = rest;
+        self.chunks.push(RecordedChunk {
+            offset_ms: self.started.elapsed().as_millis() as u64,
+            data,
+        });
+    }
+}
+
+impl Drop for StreamRecorder {
+    fn drop(&mut self) {
+        if !self.pending.is_empty() {
+            let data = String::from_utf8_lossy(&self.pending).into_owned();
+            self.pending.clear();
+            self.chunks.push(RecordedChunk {
+                offset_ms: self.started.elapsed().as_millis() as u64,
+                data,
+            });
+        }
+        let interaction = Interaction {
+            request: self.request.clone(),
+            response: RecordedResponse::Stream {
+                status: self.status,
+                headers: std::mem::take(&mut self.headers),
+                chunks: std::mem::take(&mut self.chunks),
+                error: self.error.take(),
+            },
+        };
+        if let Err(err) = write_interaction(&self.path, &interaction) {
+            warn!("failed to write HTTP cassette: {err}");
+        }
+    }
+}
+
+/// Returns the response bodies recorded in `dir` for URLs ending in
+/// `url_suffix`, in recording order. Streamed bodies are joined back
+/// together. Lets tests serve traffic recorded with `--record-http` from a
+/// mock server.
+pub fn read_http_cassette_bodies(dir: &Path, url_suffix: &str) -> io::Result<Vec<String>> {
+    Ok(read_interactions(dir)?
+        .into_iter()
+        .filter(|interaction| {
+            interaction
+                .request
+                .url
+                .split('?')
+                .next()
+                .is_some_and(|path| path.ends_with(url_suffix))
+        })
+        .filter_map(|interaction| match interaction.response {
+            RecordedResponse::Response { body, .. } => Some(body),
+            RecordedResponse::Stream {
+                chunks,
+                error: None,
+                ..
+            } => Some(chunks.into_iter().map(|chunk| chunk.data).collect()),
+            RecordedResponse::Stream { .. } | RecordedResponse::Error { .. } => None,
+        })
+        .collect())
+}
+
+fn read_interactions(dir: &Path) -> io::Result<Vec<Interaction>> {
+    let mut interactions = Vec::new();
+    for path in cassette_files(dir)? {
+        let contents = std::fs::read_to_string(&path)?;
+        let interaction: Interaction = serde_json::from_str(&contents).map_err(|err| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid cassette {}: {err}", path.display()),
+            )
+        })?;
+        interactions.push(interaction);
+    }
+    Ok(interactions)
+}
+
+fn cassette_files(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut files = std::fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect::<Vec<_>>();
+    files.sort();
+    Ok(files)
+}
+
+fn write_interaction(path: &Path, interaction: &Interaction) -> io::Result<()> {
+    let json = serde_json::to_string_pretty(interaction).map_err(io::Error::other)?;
+    std::fs::write(path, json)
+}
+
+fn record_headers(headers: &HeaderMap) -> BTreeMap<String, String> {
+    headers
+        .iter()
+        .filter(|(name, _)| !SKIPPED_RESPONSE_HEADERS.contains(&name.as_str()))
+        .filter_map(|(name, value)| {
+            value
+                .to_str()
+                .ok()
+                .map(|value| (name.as_str().to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+fn replay_headers(headers: &BTreeMap<String, String>) -> HeaderMap {
+    let mut map = HeaderMap::new();
+    for (name, value) in headers {
+        if let (Ok(name), Ok(value)) = (HeaderName::try_from(name), HeaderValue::try_from(value)) {
+            map.insert(name, value);
+        }
+    }
+    map
+}
+
+fn replay_status(status: u16) -> Result<StatusCode, TransportError> {
+    StatusCode::from_u16(status).map_err(|err| TransportError::Build(err.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::Method;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    fn request(body: Value) -> Request {
+        Request::new(Method::POST, "https://example.com/v1/responses".to_string()).with_json(&body)
+    }
+
+    fn sse_response(chunks: &[&'static str]) -> StreamResponse {
+        let chunks = chunks
+            .iter()
+            .map(|chunk| Ok(Bytes::from_static(chunk.as_bytes())))
+            .collect::<Vec<_>>();
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "content-type",
+            HeaderValue::from_static("text/event-stream"),
+        );
+        headers.insert("set-cookie", HeaderValue::from_static("secret"));
+        StreamResponse {
+            status: StatusCode::OK,
+            headers,
+            bytes: Box::pin(futures::stream::iter(chunks)),
+        }
+    }
+
+    async fn collect(response: StreamResponse) -> Vec<String> {
+        response
+            .bytes
+            .map(|chunk| String::from_utf8(chunk.expect("chunk").to_vec()).expect("utf8"))
+            .collect()
+            .await
+    }
+
+    #[tokio::test]
+    async fn records_and_replays_streams() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let recorder = HttpCassette::record(dir.path().to_path_buf()).expect("record");
+        let req = request(json!({"model": "m", "prompt_cache_key": "abc"}));
+
+        let recorded = recorder
+            .record_stream(&req, Ok(sse_response(&["data: one\n\n", "data: two\n\n"])))
+            .expect("stream");
+        assert_eq!(
+            collect(recorded).await,
+            vec!["data: one\n\n".to_string(), "data: two\n\n".to_string()]
+        );
+
+        let replayer = HttpCassette::replay(dir.path().to_path_buf()).expect("replay");
+        // The cache key differs between runs and is ignored when matching.
+        let replayed = replayer
+            .replay_stream(&request(json!({"model": "m", "prompt_cache_key": "xyz"})))
+            .expect("replayed stream");
+        assert_eq!(replayed.status, StatusCode::OK);
+        assert_eq!(
+            replayed.headers.get("content-type"),
+            Some(&HeaderValue::from_static("text/event-stream"))
+        );
+        assert!(replayed.headers.get("set-cookie").is_none());
+        assert_eq!(
+            collect(replayed).await,
+            vec!["data: one\n\n".to_string(), "data: two\n\n".to_string()]
+        );
+
+        // Each interaction is served once.
+        assert!(replayer.replay_stream(&req).is_err());
+    }
+
+    #[tokio::test]
+    async fn replays_recorded_errors() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let recorder = HttpCassette::record(dir.path().to_path_buf()).expect("record");
+        let req = request(json!({"model": "m"}));
+        let result = recorder.record_stream(
+            &req,
+            Err(TransportError::Http {
+                status: StatusCode::TOO_MANY_REQUESTS,
+                headers: None,
+                body: Some("slow down".to_string()),
+            }),
+        );
+        assert!(result.is_err());
+
+        let replayer = HttpCassette::replay(dir.path().to_path_buf()).expect("replay");
+        match replayer.replay_stream(&req) {
+            Err(TransportError::Http { status, body, .. }) => {
+                assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+                assert_eq!(body.as_deref(), Some("slow down"));
+            }
+            other => panic!("unexpected replay result: {:?}", other.map(|r| r.status)),
+        }
+    }
+
+    #[tokio::test]
+    async fn rejects_a_different_body_unless_loose() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let recorder = HttpCassette::record(dir.path().to_path_buf()).expect("record");
+        let recorded = recorder
+            .record_stream(
+                &request(json!({"input": "recorded"})),
+                Ok(sse_response(&["data: one\n\n"])),
+            )
+            .expect("stream");
+        collect(recorded).await;
+        let changed = request(json!({"input": "changed"}));
+
+        let strict = HttpCassette::replay(dir.path().to_path_buf()).expect("replay");
+        match strict.replay_stream(&changed) {
+            Err(TransportError::Build(message)) => {
+                assert!(message.contains("does not match"), "{message}");
+            }
+            other => panic!("unexpected replay result: {:?}", other.map(|r| r.status)),
+        }
+
+        let loose = HttpCassette::replay_loose(dir.path().to_path_buf()).expect("replay");
+        let replayed = loose.replay_stream(&changed).expect("loose replay");
+        assert_eq!(collect(replayed).await, vec!["data: one\n\n".to_string()]);
+
+        assert_eq!(
+            read_http_cassette_bodies(dir.path(), "/responses").expect("bodies"),
+            vec!["data: one\n\n".to_string()]
+        );
+    }
+
+    #[test]
+    fn carries_split_utf8_sequences_to_the_next_chunk() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut recorder = StreamRecorder {
+            path: dir.path().join("0000-post-responses.json"),
+            request: RecordedRequest::from(&request(json!({}))),
+            status: 200,
+            headers: BTreeMap::new(),
+            started: Instant::now(),
+            chunks: Vec::new(),
+            pending: Vec::new(),
+            error: None,
+        };
+        let bytes = "é".as_bytes();
+        recorder.push_chunk(&bytes[..1]);
+        assert!(recorder.chunks.is_empty());
+        recorder.push_chunk(&bytes[1..]);
+        assert_eq!(
+            recorder
+                .chunks
+                .iter()
+                .map(|chunk| chunk.data.as_str())
+                .collect::<Vec<_>>(),
+            vec!["é"]
+        );
+    }
+}
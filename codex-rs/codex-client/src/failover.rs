@@ -0,0 +1,181 @@
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+/// Tracks which targets in an ordered failover chain are healthy.
+///
+/// A target that fails is put in cooldown. While a target is cooling down,
+/// requests go to the next healthy target instead, so a session sticks with
+/// the provider that is working until the cooldown window has passed.
+#[derive(Debug)]
+pub struct ProviderHealth {
+    cooldown: Duration,
+    unhealthy_until: Mutex<Vec<Option<Instant>>>,
+}
+
+impl ProviderHealth {
+    pub fn new(targets: usize, cooldown: Duration) -> Self {
+        Self {
+            cooldown,
+            unhealthy_until: Mutex::new(vec![None; targets.max(1)]),
+        }
+    }
+
+    pub fn cooldown(&self) -> Duration {
+        self.cooldown
+    }
+
+    /// Target indices in the order they should be tried: healthy targets in
+    /// chain order, followed by the ones still cooling down as a last resort.
+    pub fn candidates(&self) -> Vec<usize> {
+        self.candidates_at(Instant::now())
+    }
+
+    fn candidates_at(&self, now: Instant) -> Vec<usize> {
+        let state = self
+            .unhealthy_until
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let (healthy, cooling): (Vec<usize>, Vec<usize>) =
+            (0..state.len()).partition(|&idx| state[idx].is_none_or(|until| until <= now));
+        healthy.into_iter().chain(cooling).collect()
+    }
+
+    pub fn record_success(&self, target: usize) {
+        let mut state = self
+            .unhealthy_until
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if let Some(slot) = state.get_mut(target) {
+            *slot = None;
+        }
+    }
+
+    pub fn record_failure(&self, target: usize) {
+        self.record_failure_at(target, Instant::now());
+    }
+
+    fn record_failure_at(&self, target: usize, now: Instant) {
+        let mut state = self
+            .unhealthy_until
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if let Some(slot) = state.get_mut(target) {
+            *slot = Some(now + self.cooldown);
+        }
+    }
+}
+
+/// Runs `op` against the targets tracked by `health`, moving to the next
+/// target whenever `should_fail_over` accepts the error. `on_fail_over` is
+/// called with `(from, to, error)` before each switch. Returns the index of
+/// the target that produced the result.
+pub async fn run_with_failover<T, E, F, Fut>(
+    health: &ProviderHealth,
+    should_fail_over: impl Fn(&E) -> bool,
+    mut on_fail_over: impl FnMut(usize, usize, &E),
+    mut op: F,
+) -> Result<(usize, T), E>
+where
+    F: FnMut(usize) -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    let mut candidates = health.candidates().into_iter().peekable();
+    while let Some(target) = candidates.next() {
+        match op(target).await {
+            Ok(value) => {
+                health.record_success(target);
+                return Ok((target, value));
+            }
+            Err(err) if should_fail_over(&err) => {
+                health.record_failure(target);
+                match candidates.peek() {
+                    Some(&next) => on_fail_over(target, next, &err),
+                    None => return Err(err),
+                }
+            }
+            Err(err) => return Err(err),
+        }
+    }
+    // `ProviderHealth` always tracks at least one target, so the loop returns.
+    op(0).await.map(|value| (0, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::TransportError;
+    use http::StatusCode;
+    use pretty_assertions::assert_eq;
+
+    fn is_server_error(err: &TransportError) -> bool {
+        matches!(err, TransportError::Http { status, .. } if status.is_server_error())
+    }
+
+    #[test]
+    fn failed_targets_move_to_the_back_until_cooldown_expires() {
+        let health = ProviderHealth::new(3, Duration::from_secs(60));
+        let now = Instant::now();
+
+        health.record_failure_at(0, now);
+        assert_eq!(health.candidates_at(now), vec![1, 2, 0]);
+        assert_eq!(
+            health.candidates_at(now + Duration::from_secs(61)),
+            vec![0, 1, 2]
+        );
+
+        health.record_success(0);
+        assert_eq!(health.candidates_at(now), vec![0, 1, 2]);
+    }
+
+    #[tokio::test]
+    async fn fails_over_to_next_target_and_sticks_to_it() {
+        let health = ProviderHealth::new(2, Duration::from_secs(60));
+        let mut switches = Vec::new();
+
+        let result = run_with_failover(
+            &health,
+            is_server_error,
+            |from, to, _| switches.push((from, to)),
+            |target| async move {
+                if target == 0 {
+                    Err(TransportError::Http {
+                        status: StatusCode::SERVICE_UNAVAILABLE,
+                        headers: None,
+                        body: None,
+                    })
+                } else {
+                    Ok("ok")
+                }
+            },
+        )
+        .await;
+
+        assert_eq!(result.ok(), Some((1, "ok")));
+        assert_eq!(switches, vec![(0, 1)]);
+        assert_eq!(health.candidates(), vec![1, 0]);
+    }
+
+    #[tokio::test]
+    async fn does_not_fail_over_on_client_errors() {
+        let health = ProviderHealth::new(2, Duration::from_secs(60));
+
+        let result: Result<(usize, ()), TransportError> = run_with_failover(
+            &health,
+            is_server_error,
+            |_, _, _| panic!("unexpected failover"),
+            |_| async {
+                Err(TransportError::Http {
+                    status: StatusCode::BAD_REQUEST,
+                    headers: None,
+                    body: None,
+                })
+            },
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(health.candidates(), vec![0, 1]);
+    }
+}
@@ -1,15 +1,23 @@
+mod cassette;
 mod default_client;
 mod error;
+mod failover;
 mod request;
 mod retry;
 mod sse;
 mod telemetry;
 mod transport;
 
+pub use crate::cassette::HttpCassette;
+pub use crate::cassette::HttpCassetteMode;
+pub use crate::cassette::install_http_cassette;
+pub use crate::cassette::read_http_cassette_bodies;
 pub use crate::default_client::CodexHttpClient;
 pub use crate::default_client::CodexRequestBuilder;
 pub use crate::error::StreamError;
 pub use crate::error::TransportError;
+pub use crate::failover::ProviderHealth;
+pub use crate::failover::run_with_failover;
 pub use crate::request::Request;
 pub use crate::request::Response;
 pub use crate::retry::RetryOn;
@@ -1,3 +1,5 @@
+use crate::cassette::HttpCassette;
+use crate::cassette::installed_http_cassette;
 use crate::default_client::CodexHttpClient;
 use crate::default_client::CodexRequestBuilder;
 use crate::error::TransportError;
@@ -10,6 +12,7 @@ use futures::stream::BoxStream;
 use http::HeaderMap;
 use http::Method;
 use http::StatusCode;
+use std::sync::Arc;
 use tracing::Level;
 use tracing::enabled;
 use tracing::trace;
@@ -31,15 +34,24 @@ pub trait HttpTransport: Send + Sync {
 #[derive(Clone, Debug)]
 pub struct ReqwestTransport {
     client: CodexHttpClient,
+    cassette: Option<Arc<HttpCassette>>,
 }
 
 impl ReqwestTransport {
+    /// Creates a transport that records or replays through the cassette
+    /// installed with [`crate::install_http_cassette`], if any.
     pub fn new(client: reqwest::Client) -> Self {
         Self {
             client: CodexHttpClient::new(client),
+            cassette: installed_http_cassette(),
         }
     }
 
+    pub fn with_cassette(mut self, cassette: Option<Arc<HttpCassette>>) -> Self {
+        self.cassette = cassette;
+        self
+    }
+
     fn build(&self, req: Request) -> Result<CodexRequestBuilder, TransportError> {
         let mut builder = self
             .client
@@ -69,6 +81,31 @@ impl ReqwestTransport {
 #[async_trait]
 impl HttpTransport for ReqwestTransport {
     async fn execute(&self, req: Request) -> Result<Response, TransportError> {
+        let Some(cassette) = &self.cassette else {
+            return self.execute_live(req).await;
+        };
+        if cassette.is_replay() {
+            return cassette.replay_response(&req);
+        }
+        let result = self.execute_live(req.clone()).await;
+        cassette.record_response(&req, &result);
+        result
+    }
+
+    async fn stream(&self, req: Request) -> Result<StreamResponse, TransportError> {
+        let Some(cassette) = &self.cassette else {
+            return self.stream_live(req).await;
+        };
+        if cassette.is_replay() {
+            return cassette.replay_stream(&req);
+        }
+        let result = self.stream_live(req.clone()).await;
+        cassette.record_stream(&req, result)
+    }
+}
+
+impl ReqwestTransport {
+    async fn execute_live(&self, req: Request) -> Result<Response, TransportError> {
         let builder = self.build(req)?;
         let resp = builder.send().await.map_err(Self::map_error)?;
         let status = resp.status();
@@ -89,7 +126,7 @@ impl HttpTransport for ReqwestTransport {
         })
     }
 
-    async fn stream(&self, req: Request) -> Result<StreamResponse, TransportError> {
+    async fn stream_live(&self, req: Request) -> Result<StreamResponse, TransportError> {
         if enabled!(Level::TRACE) {
             trace!(
                 "{} to {}: {}",
@@ -3,8 +3,13 @@
 use codex_core::LMSTUDIO_OSS_PROVIDER_ID;
 use codex_core::OLLAMA_OSS_PROVIDER_ID;
 use codex_core::config::Config;
+use codex_core::models_manager::manager::ModelsManager;
+use codex_local_server::LocalServerKind;
 
 /// Returns the default model for a given OSS provider.
+///
+/// llama.cpp and vLLM have no default: the model is whatever the server
+/// serves, discovered via [`register_served_models`].
 pub fn get_default_model_for_oss_provider(provider_id: &str) -> Option<&'static str> {
     match provider_id {
         LMSTUDIO_OSS_PROVIDER_ID => Some(codex_lmstudio::DEFAULT_OSS_MODEL),
@@ -30,12 +35,26 @@ pub async fn ensure_oss_provider_ready(
                 .map_err(|e| std::io::Error::other(format!("OSS setup failed: {e}")))?;
         }
         _ => {
-            // Unknown provider, skip setup
+            if let Some(kind) = LocalServerKind::from_provider_id(provider_id) {
+                codex_local_server::ensure_oss_ready(config, kind)
+                    .await
+                    .map_err(|e| std::io::Error::other(format!("OSS setup failed: {e}")))?;
+            }
+            // Otherwise unknown provider, skip setup
         }
     }
     Ok(())
 }
 
+/// When `config` targets a llama.cpp or vLLM server, lists the models it
+/// serves into `models_manager` so they show up in the model picker and
+/// provide the default model and its context window.
+pub async fn register_served_models(config: &Config, models_manager: &ModelsManager) {
+    if let Some(models) = codex_local_server::fetch_served_models(config).await {
+        models_manager.set_served_models(models).await;
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -52,6 +71,18 @@ mod tests {
         assert_eq!(result, Some(codex_ollama::DEFAULT_OSS_MODEL));
     }
 
+    #[test]
+    fn test_get_default_model_for_local_servers() {
+        assert_eq!(
+            get_default_model_for_oss_provider(codex_core::LLAMA_CPP_OSS_PROVIDER_ID),
+            None
+        );
+        assert_eq!(
+            get_default_model_for_oss_provider(codex_core::VLLM_OSS_PROVIDER_ID),
+            None
+        );
+    }
+
     #[test]
     fn test_get_default_model_for_provider_unknown() {
         let result = get_default_model_for_oss_provider("unknown-provider");
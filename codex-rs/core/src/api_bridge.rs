@@ -8,6 +8,7 @@ use http::HeaderMap;
 use serde::Deserialize;
 
 use crate::auth::CodexAuth;
+use crate::auth_command::auth_command_token;
 use crate::error::CodexErr;
 use crate::error::RetryLimitReachedError;
 use crate::error::UnexpectedResponseError;
@@ -111,6 +112,13 @@ pub(crate) async fn auth_provider_from_auth(
         });
     }
 
+    if let Some(command) = &provider.auth_command {
+        return Ok(CoreAuthProvider {
+            token: Some(auth_command_token(command).await?),
+            account_id: None,
+        });
+    }
+
     if let Some(token) = provider.experimental_bearer_token.clone() {
         return Ok(CoreAuthProvider {
             token: Some(token),
@@ -8,6 +8,7 @@ use crate::safety::assess_patch_safety;
 use codex_apply_patch::ApplyPatchAction;
 use codex_apply_patch::ApplyPatchFileChange;
 use std::collections::HashMap;
+use std::path::Path;
 use std::path::PathBuf;
 
 pub const CODEX_APPLY_PATCH_ARG1: &str = "--codex-run-as-apply-patch";
@@ -31,6 +32,30 @@ pub(crate) enum InternalApplyPatchInvocation {
 pub(crate) struct ApplyPatchExec {
     pub(crate) action: ApplyPatchAction,
     pub(crate) user_explicitly_approved_this_action: bool,
+    /// Files from the proposed patch that the user declined; they are left
+    /// out of `action` and reported back to the model.
+    pub(crate) rejected_paths: Vec<PathBuf>,
+}
+
+impl ApplyPatchExec {
+    /// Adds the files the user declined to the tool output so the model can
+    /// revise them.
+    pub(crate) fn report_rejected_files(
+        &self,
+        result: Result<String, FunctionCallError>,
+    ) -> Result<String, FunctionCallError> {
+        if self.rejected_paths.is_empty() {
+            return result;
+        }
+        let note = rejected_files_note(&self.rejected_paths, &self.action.cwd);
+        match result {
+            Ok(content) => Ok(format!("{content}\n{note}")),
+            Err(FunctionCallError::RespondToModel(content)) => Err(
+                FunctionCallError::RespondToModel(format!("{content}\n{note}")),
+            ),
+            Err(err) => Err(err),
+        }
+    }
 }
 
 pub(crate) async fn apply_patch(
@@ -51,6 +76,7 @@ pub(crate) async fn apply_patch(
         } => InternalApplyPatchInvocation::DelegateToExec(ApplyPatchExec {
             action,
             user_explicitly_approved_this_action: user_explicitly_approved,
+            rejected_paths: Vec::new(),
         }),
         SafetyCheck::AskUser => {
             // Compute a readable summary of path changes to include in the
@@ -76,8 +102,12 @@ pub(crate) async fn apply_patch(
                     InternalApplyPatchInvocation::DelegateToExec(ApplyPatchExec {
                         action,
                         user_explicitly_approved_this_action: true,
+                        rejected_paths: Vec::new(),
                     })
                 }
+                ReviewDecision::ApprovedSubset { approved_paths } => {
+                    apply_approved_subset(action, &approved_paths)
+                }
                 ReviewDecision::Denied | ReviewDecision::Abort => {
                     InternalApplyPatchInvocation::Output(Err(FunctionCallError::RespondToModel(
                         "patch rejected by user".to_string(),
@@ -91,6 +121,50 @@ pub(crate) async fn apply_patch(
     }
 }
 
+/// Narrows `action` to the files the user approved. Approving none of them is
+/// the same as rejecting the whole patch.
+fn apply_approved_subset(
+    action: ApplyPatchAction,
+    approved_paths: &[PathBuf],
+) -> InternalApplyPatchInvocation {
+    let mut rejected_paths: Vec<PathBuf> = action
+        .changes()
+        .keys()
+        .filter(|path| !approved_paths.contains(path))
+        .cloned()
+        .collect();
+    rejected_paths.sort();
+
+    match action.retain_files(approved_paths) {
+        Ok(approved) if !approved.is_empty() => {
+            InternalApplyPatchInvocation::DelegateToExec(ApplyPatchExec {
+                action: approved,
+                user_explicitly_approved_this_action: true,
+                rejected_paths,
+            })
+        }
+        Ok(_) => {
+            InternalApplyPatchInvocation::Output(Err(FunctionCallError::RespondToModel(format!(
+                "patch rejected by user\n{}",
+                rejected_files_note(&rejected_paths, &action.cwd)
+            ))))
+        }
+        Err(err) => InternalApplyPatchInvocation::Output(Err(FunctionCallError::RespondToModel(
+            format!("failed to narrow patch to the approved files: {err}"),
+        ))),
+    }
+}
+
+fn rejected_files_note(rejected_paths: &[PathBuf], cwd: &Path) -> String {
+    let mut note =
+        "The user rejected the changes to the following files; they were not modified:".to_string();
+    for path in rejected_paths {
+        let display = path.strip_prefix(cwd).unwrap_or(path);
+        note.push_str(&format!("\n- {}", display.display()));
+    }
+    note
+}
+
 pub(crate) fn convert_apply_patch_to_protocol(
     action: &ApplyPatchAction,
 ) -> HashMap<PathBuf, FileChange> {
@@ -141,4 +215,32 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn approved_subset_reports_rejected_files() {
+        let tmp = tempdir().expect("tmp");
+        let patch = "*** Begin Patch\n*** Add File: keep.txt\n+keep\n*** Add File: drop.txt\n+drop\n*** End Patch";
+        let argv = vec!["apply_patch".to_string(), patch.to_string()];
+        let codex_apply_patch::MaybeApplyPatchVerified::Body(action) =
+            codex_apply_patch::maybe_parse_apply_patch_verified(&argv, tmp.path())
+        else {
+            panic!("expected a verified patch");
+        };
+        let keep = tmp.path().join("keep.txt");
+
+        let InternalApplyPatchInvocation::DelegateToExec(apply) =
+            apply_approved_subset(action, std::slice::from_ref(&keep))
+        else {
+            panic!("expected the approved file to be applied");
+        };
+        assert_eq!(
+            apply.action.changes().keys().collect::<Vec<_>>(),
+            vec![&keep]
+        );
+        assert_eq!(apply.rejected_paths, vec![tmp.path().join("drop.txt")]);
+        assert_eq!(
+            apply.report_rejected_files(Ok("Success.".to_string())),
+            Ok("Success.\nThe user rejected the changes to the following files; they were not modified:\n- drop.txt".to_string())
+        );
+    }
 }
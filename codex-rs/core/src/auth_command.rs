@@ -0,0 +1,276 @@
+//! Runs user-configured `auth_command` credential helpers.
+//!
+//! A helper is an argv that prints a bearer token to stdout, either as a
+//! bare token or as a JSON object with an optional expiry:
+//!
+//! ```json
+//! {"token": "…", "expires_at": 1767225600}
+//! {"token": "…", "expires_in": 3600}
+//! ```
+//!
+//! Tokens are cached in memory per argv and the helper is run again once the
+//! token is about to expire, or when a caller reports a 401 via
+//! [`invalidate_auth_command_token`].
+
+use std::collections::HashMap;
+use std::process::Stdio;
+use std::sync::Arc;
+use std::sync::LazyLock;
+use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use serde::Deserialize;
+use tokio::process::Command;
+use tokio::sync::Mutex;
+
+/// Upper bound on how long a helper may run before it is killed.
+const AUTH_COMMAND_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Tokens expiring within this window are refreshed ahead of time so that a
+/// request does not race the expiry.
+const EXPIRY_MARGIN: Duration = Duration::from_secs(30);
+
+/// One slot per argv. The outer map lock is only held long enough to find
+/// the slot, so helpers for different argvs never wait on each other.
+type TokenSlot = Arc<Mutex<Option<CachedToken>>>;
+
+static TOKEN_CACHE: LazyLock<std::sync::Mutex<HashMap<Vec<String>, TokenSlot>>> =
+    LazyLock::new(|| std::sync::Mutex::new(HashMap::new()));
+
+#[derive(Debug, thiserror::Error)]
+pub enum AuthCommandError {
+    #[error("auth_command is empty")]
+    Empty,
+
+    #[error("failed to run auth_command `{program}`: {source}")]
+    Spawn {
+        program: String,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("auth_command `{program}` timed out after {}s", AUTH_COMMAND_TIMEOUT.as_secs())]
+    Timeout { program: String },
+
+    #[error("auth_command `{program}` exited with {status}: {stderr}")]
+    Failed {
+        program: String,
+        status: std::process::ExitStatus,
+        stderr: String,
+    },
+
+    #[error("auth_command `{program}` did not print a token")]
+    MissingToken { program: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CachedToken {
+    token: String,
+    expires_at: Option<SystemTime>,
+}
+
+impl CachedToken {
+    fn is_fresh(&self, now: SystemTime) -> bool {
+        self.expires_at
+            .is_none_or(|expires_at| now + EXPIRY_MARGIN < expires_at)
+    }
+}
+
+#[derive(Deserialize)]
+struct AuthCommandOutput {
+    token: String,
+    /// Expiry as seconds since the Unix epoch.
+    #[serde(default)]
+    expires_at: Option<u64>,
+    /// Lifetime in seconds, relative to when the helper ran.
+    #[serde(default)]
+    expires_in: Option<u64>,
+}
+
+/// Returns the token printed by `argv`, running the helper only when there
+/// is no cached token or the cached one has expired.
+pub async fn auth_command_token(argv: &[String]) -> Result<String, AuthCommandError> {
+    // Holding the slot lock while the helper runs means concurrent requests
+    // for the same argv wait for a single invocation instead of each
+    // spawning their own.
+    let slot = token_slot(argv);
+    let mut cached = slot.lock().await;
+    if let Some(token) = cached.as_ref()
+        && token.is_fresh(SystemTime::now())
+    {
+        return Ok(token.token.clone());
+    }
+
+    let fetched = run_auth_command(argv).await?;
+    let token = fetched.token.clone();
+    *cached = Some(fetched);
+    Ok(token)
+}
+
+fn token_slot(argv: &[String]) -> TokenSlot {
+    let mut cache = TOKEN_CACHE
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    Arc::clone(cache.entry(argv.to_vec()).or_default())
+}
+
+/// Drops the cached token for `argv` so the next request runs the helper
+/// again. Called after the server rejected the token with a 401.
+pub async fn invalidate_auth_command_token(argv: &[String]) {
+    *token_slot(argv).lock().await = None;
+}
+
+async fn run_auth_command(argv: &[String]) -> Result<CachedToken, AuthCommandError> {
+    let Some((program, args)) = argv.split_first() else {
+        return Err(AuthCommandError::Empty);
+    };
+
+    let child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|source| AuthCommandError::Spawn {
+            program: program.clone(),
+            source,
+        })?;
+
+    let output = tokio::time::timeout(AUTH_COMMAND_TIMEOUT, child.wait_with_output())
+        .await
+        .map_err(|_| AuthCommandError::Timeout {
+            program: program.clone(),
+        })?
+        .map_err(|source| AuthCommandError::Spawn {
+            program: program.clone(),
+            source,
+        })?;
+
+    if !output.status.success() {
+        return Err(AuthCommandError::Failed {
+            program: program.clone(),
+            status: output.status,
+            stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        });
+    }
+
+    parse_auth_command_output(&String::from_utf8_lossy(&output.stdout), SystemTime::now())
+        .ok_or_else(|| AuthCommandError::MissingToken {
+            program: program.clone(),
+        })
+}
+
+fn parse_auth_command_output(stdout: &str, now: SystemTime) -> Option<CachedToken> {
+    let stdout = stdout.trim();
+    if let Ok(output) = serde_json::from_str::<AuthCommandOutput>(stdout) {
+        let expires_at = match (output.expires_at, output.expires_in) {
+            (Some(at), _) => Some(UNIX_EPOCH + Duration::from_secs(at)),
+            (None, Some(secs)) => Some(now + Duration::from_secs(secs)),
+            (None, None) => None,
+        };
+        let token = output.token.trim().to_string();
+        return (!token.is_empty()).then_some(CachedToken { token, expires_at });
+    }
+
+    stdout
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(|token| CachedToken {
+            token: token.to_string(),
+            expires_at: None,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parses_plain_and_json_output() {
+        let now = UNIX_EPOCH + Duration::from_secs(1_000);
+
+        assert_eq!(
+            parse_auth_command_output("tok-123\n", now),
+            Some(CachedToken {
+                token: "tok-123".to_string(),
+                expires_at: None,
+            })
+        );
+        assert_eq!(
+            parse_auth_command_output(r#"{"token":"abc","expires_in":60}"#, now),
+            Some(CachedToken {
+                token: "abc".to_string(),
+                expires_at: Some(now + Duration::from_secs(60)),
+            })
+        );
+        assert_eq!(
+            parse_auth_command_output(r#"{"token":"abc","expires_at":2000}"#, now),
+            Some(CachedToken {
+                token: "abc".to_string(),
+                expires_at: Some(UNIX_EPOCH + Duration::from_secs(2_000)),
+            })
+        );
+        assert_eq!(parse_auth_command_output("  \n", now), None);
+    }
+
+    #[test]
+    fn tokens_close_to_expiry_are_stale() {
+        let now = UNIX_EPOCH + Duration::from_secs(1_000);
+        let token = |secs| CachedToken {
+            token: "abc".to_string(),
+            expires_at: Some(now + Duration::from_secs(secs)),
+        };
+
+        assert!(token(3_600).is_fresh(now));
+        assert!(!token(10).is_fresh(now));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn caches_token_until_invalidated() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let counter = dir.path().join("count");
+        let script = format!(
+            "echo run >> '{path}'; wc -l < '{path}' | tr -d ' '",
+            path = counter.display()
+        );
+        let argv = vec!["sh".to_string(), "-c".to_string(), script];
+
+        assert_eq!(auth_command_token(&argv).await.expect("token"), "1");
+        assert_eq!(auth_command_token(&argv).await.expect("token"), "1");
+
+        invalidate_auth_command_token(&argv).await;
+        assert_eq!(auth_command_token(&argv).await.expect("token"), "2");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn slow_helper_does_not_block_other_argvs() {
+        let slow = vec![
+            "sh".to_string(),
+            "-c".to_string(),
+            "sleep 5; echo slow".to_string(),
+        ];
+        let fast = vec!["sh".to_string(), "-c".to_string(), "echo fast".to_string()];
+
+        let slow_task = tokio::spawn(async move { auth_command_token(&slow).await });
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        let token = tokio::time::timeout(Duration::from_secs(2), auth_command_token(&fast))
+            .await
+            .expect("fast helper should not wait for the slow one")
+            .expect("token");
+        assert_eq!(token, "fast");
+        slow_task.abort();
+    }
+
+    #[tokio::test]
+    async fn reports_failing_commands() {
+        let err = auth_command_token(&[]).await.expect_err("empty argv");
+        assert!(matches!(err, AuthCommandError::Empty));
+    }
+}
@@ -1,12 +1,17 @@
 use std::sync::Arc;
 
+use crate::api_bridge::CoreAuthProvider;
 use crate::api_bridge::auth_provider_from_auth;
 use crate::api_bridge::map_api_error;
+use crate::auth_command::invalidate_auth_command_token;
 use codex_api::AggregateStreamExt;
 use codex_api::ChatClient as ApiChatClient;
 use codex_api::CompactClient as ApiCompactClient;
 use codex_api::CompactionInput as ApiCompactionInput;
+use codex_api::GeminiClient as ApiGeminiClient;
+use codex_api::MessagesClient as ApiMessagesClient;
 use codex_api::Prompt as ApiPrompt;
+use codex_api::Provider as ApiProvider;
 use codex_api::RequestTelemetry;
 use codex_api::ReqwestTransport;
 use codex_api::ResponseStream as ApiResponseStream;
@@ -18,6 +23,7 @@ use codex_api::common::Reasoning;
 use codex_api::create_text_param_for_request;
 use codex_api::error::ApiError;
 use codex_app_server_protocol::AuthMode;
+use codex_client::run_with_failover;
 use codex_otel::otel_manager::OtelManager;
 use codex_protocol::ConversationId;
 use codex_protocol::config_types::ReasoningSummary as ReasoningSummaryConfig;
@@ -32,7 +38,9 @@ use http::HeaderValue;
 use http::StatusCode as HttpStatusCode;
 use reqwest::StatusCode;
 use serde_json::Value;
+use std::future::Future;
 use std::time::Duration;
+use std::time::Instant;
 use tokio::sync::mpsc;
 use tracing::warn;
 
@@ -50,7 +58,11 @@ use crate::flags::CODEX_RS_SSE_FIXTURE;
 use crate::model_provider_info::ModelProviderInfo;
 use crate::model_provider_info::WireApi;
 use crate::models_manager::model_family::ModelFamily;
+use crate::provider_failover::ProviderFailover;
+use crate::provider_failover::is_provider_unavailable;
 use crate::tools::spec::create_tools_json_for_chat_completions_api;
+use crate::tools::spec::create_tools_json_for_gemini_api;
+use crate::tools::spec::create_tools_json_for_messages_api;
 use crate::tools::spec::create_tools_json_for_responses_api;
 
 #[derive(Debug, Clone)]
@@ -64,6 +76,7 @@ pub struct ModelClient {
     effort: Option<ReasoningEffortConfig>,
     summary: ReasoningSummaryConfig,
     session_source: SessionSource,
+    failover: Option<Arc<ProviderFailover>>,
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -89,9 +102,16 @@ impl ModelClient {
             effort,
             summary,
             session_source,
+            failover: None,
         }
     }
 
+    /// Lets `stream` fail over to the session's fallback providers.
+    pub(crate) fn with_failover(mut self, failover: Option<Arc<ProviderFailover>>) -> Self {
+        self.failover = failover;
+        self
+    }
+
     pub fn get_model_context_window(&self) -> Option<i64> {
         let model_family = self.get_model_family();
         let effective_context_window_percent = model_family.effective_context_window_percent;
@@ -108,14 +128,76 @@ impl ModelClient {
         &self.provider
     }
 
-    /// Streams a single model turn using either the Responses or Chat
-    /// Completions wire API, depending on the configured provider.
+    /// Streams a single model turn.
+    ///
+    /// When fallback providers are configured, a primary provider that
+    /// answers with 5xx/429 or times out is put in cooldown and the turn is
+    /// sent to the next provider in the chain instead.
+    pub async fn stream(&self, prompt: &Prompt) -> Result<ResponseStream> {
+        let Some(failover) = self.failover.as_ref() else {
+            return self.stream_with_provider(prompt).await;
+        };
+
+        let (_, stream) = run_with_failover(
+            failover.health(),
+            is_provider_unavailable,
+            |from, to, err| {
+                let from = self.failover_target(failover, from);
+                let to = self.failover_target(failover, to);
+                let to_model = to.get_model();
+                self.otel_manager.provider_failover(
+                    &from.provider.name,
+                    &to.provider.name,
+                    &to_model,
+                    &err.to_string(),
+                );
+                failover.push_warning(format!(
+                    "Model provider `{}` is unavailable ({err}); switching to `{}` with model `{to_model}` for the next {} minutes.",
+                    from.provider.name,
+                    to.provider.name,
+                    failover.health().cooldown().as_secs() / 60,
+                ));
+            },
+            |target| {
+                let client = self.failover_target(failover, target);
+                async move { client.stream_with_provider(prompt).await }
+            },
+        )
+        .await?;
+        Ok(stream)
+    }
+
+    /// Returns a client that talks to failover `target` (0 is this client's
+    /// own provider).
+    fn failover_target(&self, failover: &ProviderFailover, target: usize) -> ModelClient {
+        let mut client = self.clone();
+        client.failover = None;
+        if let Some(fallback) = failover.fallback(target) {
+            client.provider = fallback.provider.clone();
+            if let Some(model_family) = failover.model_family(target) {
+                client.model_family = model_family.clone();
+            }
+        }
+        client
+    }
+
+    /// Drains warnings about provider switches made by `stream`.
+    pub(crate) fn take_failover_warnings(&self) -> Vec<String> {
+        self.failover
+            .as_ref()
+            .map(|failover| failover.take_warnings())
+            .unwrap_or_default()
+    }
+
+    /// Streams a single model turn using the wire API of the configured
+    /// provider (Responses, Chat Completions, Anthropic Messages or Gemini).
     ///
     /// For Chat providers, the underlying stream is optionally aggregated
     /// based on the `show_raw_agent_reasoning` flag in the config.
-    pub async fn stream(&self, prompt: &Prompt) -> Result<ResponseStream> {
+    async fn stream_with_provider(&self, prompt: &Prompt) -> Result<ResponseStream> {
+        let request_start = Instant::now();
         match self.provider.wire_api {
-            WireApi::Responses => self.stream_responses_api(prompt).await,
+            WireApi::Responses => self.stream_responses_api(prompt, request_start).await,
             WireApi::Chat => {
                 let api_stream = self.stream_chat_completions(prompt).await?;
 
@@ -123,14 +205,32 @@ impl ModelClient {
                     Ok(map_response_stream(
                         api_stream.streaming_mode(),
                         self.otel_manager.clone(),
+                        request_start,
                     ))
                 } else {
                     Ok(map_response_stream(
                         api_stream.aggregate(),
                         self.otel_manager.clone(),
+                        request_start,
                     ))
                 }
             }
+            WireApi::Messages => {
+                let api_stream = self.stream_messages_api(prompt).await?;
+                Ok(map_response_stream(
+                    api_stream,
+                    self.otel_manager.clone(),
+                    request_start,
+                ))
+            }
+            WireApi::Gemini => {
+                let api_stream = self.stream_gemini_api(prompt).await?;
+                Ok(map_response_stream(
+                    api_stream,
+                    self.otel_manager.clone(),
+                    request_start,
+                ))
+            }
         }
     }
 
@@ -145,61 +245,127 @@ impl ModelClient {
             ));
         }
 
-        let auth_manager = self.auth_manager.clone();
         let model_family = self.get_model_family();
         let instructions = prompt.get_full_instructions(&model_family).into_owned();
         let tools_json = create_tools_json_for_chat_completions_api(&prompt.tools)?;
-        let api_prompt = build_api_prompt(prompt, instructions, tools_json);
-        let conversation_id = self.conversation_id.to_string();
-        let session_source = self.session_source.clone();
+        let api_prompt = &build_api_prompt(prompt, instructions, tools_json);
+        let model = &self.get_model();
+        let conversation_id = &self.conversation_id.to_string();
+        let session_source = &self.session_source;
+
+        self.stream_with_auth_retry(|attempt| {
+            let client = ApiChatClient::new(attempt.transport, attempt.provider, attempt.auth)
+                .with_telemetry(Some(attempt.request_telemetry), Some(attempt.sse_telemetry));
+            async move {
+                client
+                    .stream_prompt(
+                        model,
+                        api_prompt,
+                        Some(conversation_id.clone()),
+                        Some(session_source.clone()),
+                    )
+                    .await
+            }
+        })
+        .await
+    }
 
-        let mut refreshed = false;
-        loop {
-            let auth = auth_manager.as_ref().and_then(|m| m.auth());
-            let api_provider = self
-                .provider
-                .to_api_provider(auth.as_ref().map(|a| a.mode))?;
-            let api_auth = auth_provider_from_auth(auth.clone(), &self.provider).await?;
-            let transport = ReqwestTransport::new(build_reqwest_client());
-            let (request_telemetry, sse_telemetry) = self.build_streaming_telemetry();
-            let client = ApiChatClient::new(transport, api_provider, api_auth)
-                .with_telemetry(Some(request_telemetry), Some(sse_telemetry));
-
-            let stream_result = client
-                .stream_prompt(
-                    &self.get_model(),
-                    &api_prompt,
-                    Some(conversation_id.clone()),
-                    Some(session_source.clone()),
-                )
-                .await;
-
-            match stream_result {
-                Ok(stream) => return Ok(stream),
-                Err(ApiError::Transport(TransportError::Http { status, .. }))
-                    if status == StatusCode::UNAUTHORIZED =>
-                {
-                    handle_unauthorized(status, &mut refreshed, &auth_manager, &auth).await?;
-                    continue;
-                }
-                Err(err) => return Err(map_api_error(err)),
+    /// Streams a turn via the Anthropic Messages API.
+    ///
+    /// Reasoning effort maps onto an extended thinking budget; `output_schema`
+    /// is not supported.
+    async fn stream_messages_api(&self, prompt: &Prompt) -> Result<ApiResponseStream> {
+        if prompt.output_schema.is_some() {
+            return Err(CodexErr::UnsupportedOperation(
+                "output_schema is not supported for the Messages API".to_string(),
+            ));
+        }
+
+        let model_family = self.get_model_family();
+        let instructions = prompt.get_full_instructions(&model_family).into_owned();
+        let tools_json = create_tools_json_for_messages_api(&prompt.tools)?;
+        let api_prompt = &build_api_prompt(prompt, instructions, tools_json);
+        let effort = self.effort.or(model_family.default_reasoning_effort);
+        let model = &self.get_model();
+        let conversation_id = &self.conversation_id.to_string();
+        let session_source = &self.session_source;
+
+        self.stream_with_auth_retry(|attempt| {
+            let client = ApiMessagesClient::new(attempt.transport, attempt.provider, attempt.auth)
+                .with_telemetry(Some(attempt.request_telemetry), Some(attempt.sse_telemetry));
+            async move {
+                client
+                    .stream_prompt(
+                        model,
+                        api_prompt,
+                        effort,
+                        Some(conversation_id.clone()),
+                        Some(session_source.clone()),
+                    )
+                    .await
             }
+        })
+        .await
+    }
+
+    /// Streams a turn via the Gemini `streamGenerateContent` API.
+    ///
+    /// Reasoning effort maps onto a thinking budget; `output_schema` is not
+    /// supported.
+    async fn stream_gemini_api(&self, prompt: &Prompt) -> Result<ApiResponseStream> {
+        if prompt.output_schema.is_some() {
+            return Err(CodexErr::UnsupportedOperation(
+                "output_schema is not supported for the Gemini API".to_string(),
+            ));
         }
+
+        let model_family = self.get_model_family();
+        let instructions = prompt.get_full_instructions(&model_family).into_owned();
+        let tools_json = create_tools_json_for_gemini_api(&prompt.tools)?;
+        let api_prompt = &build_api_prompt(prompt, instructions, tools_json);
+        let effort = self.effort.or(model_family.default_reasoning_effort);
+        let model = &self.get_model();
+        let conversation_id = &self.conversation_id.to_string();
+        let session_source = &self.session_source;
+
+        self.stream_with_auth_retry(|attempt| {
+            let client = ApiGeminiClient::new(attempt.transport, attempt.provider, attempt.auth)
+                .with_telemetry(Some(attempt.request_telemetry), Some(attempt.sse_telemetry));
+            async move {
+                client
+                    .stream_prompt(
+                        model,
+                        api_prompt,
+                        effort,
+                        Some(conversation_id.clone()),
+                        Some(session_source.clone()),
+                    )
+                    .await
+            }
+        })
+        .await
     }
 
     /// Streams a turn via the OpenAI Responses API.
     ///
     /// Handles SSE fixtures, reasoning summaries, verbosity, and the
     /// `text` controls used for output schemas.
-    async fn stream_responses_api(&self, prompt: &Prompt) -> Result<ResponseStream> {
+    async fn stream_responses_api(
+        &self,
+        prompt: &Prompt,
+        request_start: Instant,
+    ) -> Result<ResponseStream> {
         if let Some(path) = &*CODEX_RS_SSE_FIXTURE {
             warn!(path, "Streaming from fixture");
             let stream = codex_api::stream_from_fixture(path, self.provider.stream_idle_timeout())
                 .map_err(map_api_error)?;
-            return Ok(map_response_stream(stream, self.otel_manager.clone()));
+            return Ok(map_response_stream(
+                stream,
+                self.otel_manager.clone(),
+                request_start,
+            ));
         }
 
-        let auth_manager = self.auth_manager.clone();
         let model_family = self.get_model_family();
         let instructions = prompt.get_full_instructions(&model_family).into_owned();
         let tools_json: Vec<Value> = create_tools_json_for_responses_api(&prompt.tools)?;
@@ -238,46 +404,79 @@ impl ModelClient {
         };
 
         let text = create_text_param_for_request(verbosity, &prompt.output_schema);
-        let api_prompt = build_api_prompt(prompt, instructions.clone(), tools_json);
+        let api_prompt = &build_api_prompt(prompt, instructions, tools_json);
+        let model = &self.get_model();
         let conversation_id = self.conversation_id.to_string();
-        let session_source = self.session_source.clone();
+        let session_source = &self.session_source;
+
+        let stream = self
+            .stream_with_auth_retry(|attempt| {
+                let client =
+                    ApiResponsesClient::new(attempt.transport, attempt.provider, attempt.auth)
+                        .with_telemetry(
+                            Some(attempt.request_telemetry),
+                            Some(attempt.sse_telemetry),
+                        );
+                let options = ApiResponsesOptions {
+                    reasoning: reasoning.clone(),
+                    include: include.clone(),
+                    prompt_cache_key: Some(conversation_id.clone()),
+                    text: text.clone(),
+                    store_override: None,
+                    conversation_id: Some(conversation_id.clone()),
+                    session_source: Some(session_source.clone()),
+                    extra_headers: beta_feature_headers(&self.config),
+                };
+                async move { client.stream_prompt(model, api_prompt, options).await }
+            })
+            .await?;
+        Ok(map_response_stream(
+            stream,
+            self.otel_manager.clone(),
+            request_start,
+        ))
+    }
 
+    /// Opens a stream with the provider's current credentials, refreshing
+    /// them and retrying once when the server answers 401.
+    ///
+    /// `open` builds the wire-specific client from a [`StreamAttempt`] and
+    /// starts the request; it is called again after a refresh.
+    async fn stream_with_auth_retry<F, Fut>(&self, open: F) -> Result<ApiResponseStream>
+    where
+        F: Fn(StreamAttempt) -> Fut,
+        Fut: Future<Output = std::result::Result<ApiResponseStream, ApiError>>,
+    {
+        let auth_manager = self.auth_manager.clone();
         let mut refreshed = false;
         loop {
             let auth = auth_manager.as_ref().and_then(|m| m.auth());
-            let api_provider = self
+            let provider = self
                 .provider
                 .to_api_provider(auth.as_ref().map(|a| a.mode))?;
             let api_auth = auth_provider_from_auth(auth.clone(), &self.provider).await?;
-            let transport = ReqwestTransport::new(build_reqwest_client());
             let (request_telemetry, sse_telemetry) = self.build_streaming_telemetry();
-            let client = ApiResponsesClient::new(transport, api_provider, api_auth)
-                .with_telemetry(Some(request_telemetry), Some(sse_telemetry));
-
-            let options = ApiResponsesOptions {
-                reasoning: reasoning.clone(),
-                include: include.clone(),
-                prompt_cache_key: Some(conversation_id.clone()),
-                text: text.clone(),
-                store_override: None,
-                conversation_id: Some(conversation_id.clone()),
-                session_source: Some(session_source.clone()),
-                extra_headers: beta_feature_headers(&self.config),
+            let attempt = StreamAttempt {
+                transport: ReqwestTransport::new(build_reqwest_client()),
+                provider,
+                auth: api_auth,
+                request_telemetry,
+                sse_telemetry,
             };
 
-            let stream_result = client
-                .stream_prompt(&self.get_model(), &api_prompt, options)
-                .await;
-
-            match stream_result {
-                Ok(stream) => {
-                    return Ok(map_response_stream(stream, self.otel_manager.clone()));
-                }
+            match open(attempt).await {
+                Ok(stream) => return Ok(stream),
                 Err(ApiError::Transport(TransportError::Http { status, .. }))
                     if status == StatusCode::UNAUTHORIZED =>
                 {
-                    handle_unauthorized(status, &mut refreshed, &auth_manager, &auth).await?;
-                    continue;
+                    handle_unauthorized(
+                        status,
+                        &mut refreshed,
+                        &self.provider,
+                        &auth_manager,
+                        &auth,
+                    )
+                    .await?;
                 }
                 Err(err) => return Err(map_api_error(err)),
             }
@@ -387,6 +586,16 @@ impl ModelClient {
     }
 }
 
+/// Per-attempt inputs for opening a streaming request. They are rebuilt for
+/// each attempt so a retry after a 401 picks up refreshed credentials.
+struct StreamAttempt {
+    transport: ReqwestTransport,
+    provider: ApiProvider,
+    auth: CoreAuthProvider,
+    request_telemetry: Arc<dyn RequestTelemetry>,
+    sse_telemetry: Arc<dyn SseTelemetry>,
+}
+
 /// Adapts the core `Prompt` type into the `codex-api` payload shape.
 fn build_api_prompt(prompt: &Prompt, instructions: String, tools_json: Vec<Value>) -> ApiPrompt {
     ApiPrompt {
@@ -419,7 +628,13 @@ fn beta_feature_headers(config: &Config) -> ApiHeaderMap {
     headers
 }
 
-fn map_response_stream<S>(api_stream: S, otel_manager: OtelManager) -> ResponseStream
+/// Forwards `api_stream` to the turn loop while recording token usage and
+/// time to first token, measured from `request_start`.
+fn map_response_stream<S>(
+    api_stream: S,
+    otel_manager: OtelManager,
+    request_start: Instant,
+) -> ResponseStream
 where
     S: futures::Stream<Item = std::result::Result<ResponseEvent, ApiError>>
         + Unpin
@@ -430,8 +645,16 @@ where
 
     tokio::spawn(async move {
         let mut logged_error = false;
+        let mut first_output_seen = false;
         let mut api_stream = api_stream;
         while let Some(event) = api_stream.next().await {
+            if !first_output_seen
+                && let Ok(event) = &event
+                && is_model_output(event)
+            {
+                first_output_seen = true;
+                otel_manager.record_time_to_first_token(request_start.elapsed());
+            }
             match event {
                 Ok(ResponseEvent::Completed {
                     response_id,
@@ -479,13 +702,29 @@ where
     ResponseStream { rx_event }
 }
 
-/// Handles a 401 response by optionally refreshing ChatGPT tokens once.
+fn is_model_output(event: &ResponseEvent) -> bool {
+    match event {
+        ResponseEvent::OutputItemAdded(_)
+        | ResponseEvent::OutputItemDone(_)
+        | ResponseEvent::OutputTextDelta(_)
+        | ResponseEvent::ReasoningSummaryDelta { .. }
+        | ResponseEvent::ReasoningContentDelta { .. } => true,
+        ResponseEvent::Created
+        | ResponseEvent::Completed { .. }
+        | ResponseEvent::ReasoningSummaryPartAdded { .. }
+        | ResponseEvent::RateLimits(_) => false,
+    }
+}
+
+/// Handles a 401 response by refreshing credentials once: the provider's
+/// `auth_command` is re-run, or ChatGPT tokens are refreshed.
 ///
 /// When refresh succeeds, the caller should retry the API call; otherwise
 /// the mapped `CodexErr` is returned to the caller.
 async fn handle_unauthorized(
     status: StatusCode,
     refreshed: &mut bool,
+    provider: &ModelProviderInfo,
     auth_manager: &Option<Arc<AuthManager>>,
     auth: &Option<crate::auth::CodexAuth>,
 ) -> Result<()> {
@@ -493,6 +732,12 @@ async fn handle_unauthorized(
         return Err(map_unauthorized_status(status));
     }
 
+    if let Some(command) = &provider.auth_command {
+        invalidate_auth_command_token(command).await;
+        *refreshed = true;
+        return Ok(());
+    }
+
     if let Some(manager) = auth_manager.as_ref()
         && let Some(auth) = auth.as_ref()
         && auth.mode == AuthMode::ChatGPT
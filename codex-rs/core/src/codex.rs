@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::fmt::Debug;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -16,6 +17,8 @@ use crate::compact_remote::run_inline_remote_auto_compact_task;
 use crate::exec_policy::load_exec_policy_for_features;
 use crate::features::Feature;
 use crate::features::Features;
+use crate::file_attachments::DEFAULT_FILE_ATTACHMENT_TOKEN_LIMIT;
+use crate::file_attachments::resolve_file_attachments;
 use crate::models_manager::manager::ModelsManager;
 use crate::models_manager::model_family::ModelFamily;
 use crate::parse_command::parse_command;
@@ -118,11 +121,13 @@ use crate::protocol::TokenUsage;
 use crate::protocol::TokenUsageInfo;
 use crate::protocol::TurnDiffEvent;
 use crate::protocol::WarningEvent;
+use crate::provider_failover::ProviderFailover;
 use crate::rollout::RolloutRecorder;
 use crate::rollout::RolloutRecorderParams;
 use crate::rollout::map_session_init_error;
 use crate::shell;
 use crate::shell_snapshot::ShellSnapshot;
+use crate::skills::ActiveSkills;
 use crate::skills::SkillError;
 use crate::skills::SkillInjections;
 use crate::skills::SkillMetadata;
@@ -149,6 +154,7 @@ use crate::user_notification::UserNotification;
 use crate::util::backoff;
 use codex_async_utils::OrCancelExt;
 use codex_execpolicy::Policy as ExecPolicy;
+use codex_otel::otel_manager::CompactionTrigger;
 use codex_otel::otel_manager::OtelManager;
 use codex_protocol::config_types::ReasoningSummary as ReasoningSummaryConfig;
 use codex_protocol::models::ContentItem;
@@ -373,6 +379,8 @@ pub(crate) struct TurnContext {
     pub(crate) tool_call_gate: Arc<ReadinessFlag>,
     pub(crate) exec_policy: Arc<RwLock<ExecPolicy>>,
     pub(crate) truncation_policy: TruncationPolicy,
+    /// Skills mentioned in the turn's input, whose tool restrictions apply to this turn.
+    pub(crate) active_skills: ActiveSkills,
 }
 
 impl TurnContext {
@@ -538,6 +546,7 @@ impl Session {
                 per_turn_config.as_ref(),
                 model_family.truncation_policy,
             ),
+            active_skills: ActiveSkills::default(),
         }
     }
 
@@ -671,6 +680,7 @@ impl Session {
             models_manager: Arc::clone(&models_manager),
             tool_approvals: Mutex::new(ApprovalStore::default()),
             skills_manager,
+            provider_failover: ProviderFailover::from_config(&config, &models_manager).await,
         };
 
         let sess = Arc::new(Session {
@@ -922,6 +932,9 @@ impl Session {
             self.conversation_id,
             sub_id,
         );
+        turn_context.client = turn_context
+            .client
+            .with_failover(self.services.provider_failover.clone());
         if let Some(final_schema) = final_output_json_schema {
             turn_context.final_output_json_schema = final_schema;
         }
@@ -942,6 +955,50 @@ impl Session {
             .await
     }
 
+    /// Rebuilds `turn_context` for the skills activated by its input, applying their model and
+    /// reasoning effort preferences to this turn only.
+    pub(crate) async fn turn_context_for_skills(
+        &self,
+        turn_context: &TurnContext,
+        active_skills: ActiveSkills,
+    ) -> Arc<TurnContext> {
+        let mut session_configuration = {
+            let state = self.state.lock().await;
+            state.session_configuration.clone()
+        };
+        if let Some(model) = active_skills.model() {
+            session_configuration.model = model.to_string();
+        }
+        if let Some(effort) = active_skills.reasoning_effort() {
+            session_configuration.model_reasoning_effort = Some(effort);
+        }
+        session_configuration.cwd = turn_context.cwd.clone();
+
+        let per_turn_config = Self::build_per_turn_config(&session_configuration);
+        let model_family = self
+            .services
+            .models_manager
+            .construct_model_family(session_configuration.model.as_str(), &per_turn_config)
+            .await;
+        let mut skill_turn_context = Self::make_turn_context(
+            Some(Arc::clone(&self.services.auth_manager)),
+            &self.services.otel_manager,
+            session_configuration.provider.clone(),
+            &session_configuration,
+            per_turn_config,
+            model_family,
+            self.conversation_id,
+            turn_context.sub_id.clone(),
+        );
+        skill_turn_context.client = skill_turn_context
+            .client
+            .with_failover(self.services.provider_failover.clone());
+        skill_turn_context.final_output_json_schema = turn_context.final_output_json_schema.clone();
+        skill_turn_context.tool_call_gate = Arc::clone(&turn_context.tool_call_gate);
+        skill_turn_context.active_skills = active_skills;
+        Arc::new(skill_turn_context)
+    }
+
     fn build_environment_update_item(
         &self,
         previous: Option<&Arc<TurnContext>>,
@@ -1197,6 +1254,9 @@ impl Session {
                         turn_context.truncation_policy,
                     );
                 }
+                RolloutItem::EventMsg(EventMsg::ThreadRolledBack(rolled_back)) => {
+                    history.drop_last_user_turns(rolled_back.num_turns as usize);
+                }
                 RolloutItem::Compacted(compacted) => {
                     let snapshot = history.get_history();
                     // TODO(jif) clean
@@ -1634,6 +1694,9 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
                 handlers::get_history_entry_request(&sess, &config, sub.id.clone(), offset, log_id)
                     .await;
             }
+            Op::ListHistoryEntries => {
+                handlers::list_history_entries(&sess, &config, sub.id.clone()).await;
+            }
             Op::ListMcpTools => {
                 handlers::list_mcp_tools(&sess, &config, sub.id.clone()).await;
             }
@@ -1649,6 +1712,13 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
             Op::Compact => {
                 handlers::compact(&sess, sub.id.clone()).await;
             }
+            Op::ThreadRollback {
+                num_turns,
+                restore_snapshots,
+            } => {
+                handlers::thread_rollback(&sess, sub.id.clone(), num_turns, restore_snapshots)
+                    .await;
+            }
             Op::RunUserShellCommand { command } => {
                 handlers::run_user_shell_command(
                     &sess,
@@ -1693,6 +1763,7 @@ mod handlers {
     use crate::review_prompts::resolve_review_request;
     use crate::tasks::CompactTask;
     use crate::tasks::RegularTask;
+    use crate::tasks::RollbackTask;
     use crate::tasks::UndoTask;
     use crate::tasks::UserShellCommandTask;
     use codex_protocol::custom_prompts::CustomPrompt;
@@ -1701,6 +1772,7 @@ mod handlers {
     use codex_protocol::protocol::Event;
     use codex_protocol::protocol::EventMsg;
     use codex_protocol::protocol::ListCustomPromptsResponseEvent;
+    use codex_protocol::protocol::ListHistoryEntriesResponseEvent;
     use codex_protocol::protocol::ListSkillsResponseEvent;
     use codex_protocol::protocol::Op;
     use codex_protocol::protocol::ReviewDecision;
@@ -1780,6 +1852,33 @@ mod handlers {
             .get_otel_manager()
             .user_prompt(&items);
 
+        let items = if items
+            .iter()
+            .any(|item| matches!(item, UserInput::File { .. }))
+        {
+            let token_limit = current_context
+                .client
+                .config()
+                .file_attachment_token_limit
+                .unwrap_or(DEFAULT_FILE_ATTACHMENT_TOKEN_LIMIT);
+            let (items, warnings) = resolve_file_attachments(
+                items,
+                &current_context.cwd,
+                TruncationPolicy::Tokens(token_limit),
+            )
+            .await;
+            for message in warnings {
+                sess.send_event(
+                    &current_context,
+                    EventMsg::Warning(WarningEvent { message }),
+                )
+                .await;
+            }
+            items
+        } else {
+            items
+        };
+
         // Attempt to inject input into current task
         if let Err(items) = sess.inject_input(items).await {
             if let Some(env_item) =
@@ -1875,9 +1974,13 @@ mod handlers {
 
     pub async fn add_to_history(sess: &Arc<Session>, config: &Arc<Config>, text: String) {
         let id = sess.conversation_id;
+        let cwd = {
+            let state = sess.state.lock().await;
+            state.session_configuration.cwd.clone()
+        };
         let config = Arc::clone(config);
         tokio::spawn(async move {
-            if let Err(e) = crate::message_history::append_entry(&text, &id, &config).await {
+            if let Err(e) = crate::message_history::append_entry(&text, &id, &cwd, &config).await {
                 warn!("failed to append to message history: {e}");
             }
         });
@@ -1907,11 +2010,7 @@ mod handlers {
                     crate::protocol::GetHistoryEntryResponseEvent {
                         offset,
                         log_id,
-                        entry: entry_opt.map(|e| codex_protocol::message_history::HistoryEntry {
-                            conversation_id: e.session_id,
-                            ts: e.ts,
-                            text: e.text,
-                        }),
+                        entry: entry_opt.map(into_protocol_history_entry),
                     },
                 ),
             };
@@ -1920,6 +2019,42 @@ mod handlers {
         });
     }
 
+    pub async fn list_history_entries(sess: &Arc<Session>, config: &Arc<Config>, sub_id: String) {
+        let config = Arc::clone(config);
+        let sess_clone = Arc::clone(sess);
+
+        tokio::spawn(async move {
+            // Read in a blocking thread because it does file IO + locking.
+            let entries =
+                tokio::task::spawn_blocking(move || crate::message_history::list_entries(&config))
+                    .await
+                    .unwrap_or_default();
+
+            let event = Event {
+                id: sub_id,
+                msg: EventMsg::ListHistoryEntriesResponse(ListHistoryEntriesResponseEvent {
+                    entries: entries
+                        .into_iter()
+                        .map(into_protocol_history_entry)
+                        .collect(),
+                }),
+            };
+
+            sess_clone.send_event_raw(event).await;
+        });
+    }
+
+    fn into_protocol_history_entry(
+        entry: crate::message_history::HistoryEntry,
+    ) -> codex_protocol::message_history::HistoryEntry {
+        codex_protocol::message_history::HistoryEntry {
+            conversation_id: entry.session_id,
+            ts: entry.ts,
+            text: entry.text,
+            cwd: entry.cwd,
+        }
+    }
+
     pub async fn list_mcp_tools(sess: &Session, config: &Arc<Config>, sub_id: String) {
         let mcp_connection_manager = sess.services.mcp_connection_manager.read().await;
         let snapshot = collect_mcp_snapshot_from_manager(
@@ -2003,6 +2138,21 @@ mod handlers {
             .await;
     }
 
+    pub async fn thread_rollback(
+        sess: &Arc<Session>,
+        sub_id: String,
+        num_turns: u32,
+        restore_snapshots: bool,
+    ) {
+        let turn_context = sess.new_default_turn_with_sub_id(sub_id).await;
+        sess.spawn_task(
+            turn_context,
+            Vec::new(),
+            RollbackTask::new(num_turns, restore_snapshots),
+        )
+        .await;
+    }
+
     pub async fn compact(sess: &Arc<Session>, sub_id: String) {
         let turn_context = sess.new_default_turn_with_sub_id(sub_id).await;
 
@@ -2156,6 +2306,7 @@ async fn spawn_review_thread(
         tool_call_gate: Arc::new(ReadinessFlag::new()),
         exec_policy: parent_turn_context.exec_policy.clone(),
         truncation_policy: TruncationPolicy::new(&per_turn_config, model_family.truncation_policy),
+        active_skills: ActiveSkills::default(),
     };
 
     // Seed the child task with the review prompt as the initial user message.
@@ -2241,16 +2392,41 @@ pub(crate) async fn run_task(
             .skills_for_cwd(&turn_context.cwd)
     });
 
+    let mentions_skills = input
+        .iter()
+        .any(|item| matches!(item, UserInput::Skill { .. }));
+    let available_mcp_servers: HashSet<String> = if skills_outcome.is_some() && mentions_skills {
+        sess.services
+            .mcp_connection_manager
+            .read()
+            .await
+            .list_all_tools()
+            .await
+            .into_values()
+            .map(|tool| tool.server_name)
+            .collect()
+    } else {
+        HashSet::new()
+    };
+
     let SkillInjections {
         items: skill_items,
         warnings: skill_warnings,
-    } = build_skill_injections(&input, skills_outcome.as_ref()).await;
+        active: active_skills,
+    } = build_skill_injections(&input, skills_outcome.as_ref(), &available_mcp_servers).await;
 
     for message in skill_warnings {
         sess.send_event(&turn_context, EventMsg::Warning(WarningEvent { message }))
             .await;
     }
 
+    let turn_context = if active_skills.is_empty() {
+        turn_context
+    } else {
+        sess.turn_context_for_skills(&turn_context, ActiveSkills::new(active_skills))
+            .await
+    };
+
     let initial_input_for_turn: ResponseInputItem = ResponseInputItem::from(input);
     let response_item: ResponseItem = initial_input_for_turn.clone().into();
     sess.record_response_item_and_emit_turn_item(turn_context.as_ref(), response_item)
@@ -2356,6 +2532,10 @@ pub(crate) async fn run_task(
 }
 
 async fn run_auto_compact(sess: &Arc<Session>, turn_context: &Arc<TurnContext>) {
+    turn_context
+        .client
+        .get_otel_manager()
+        .compaction(CompactionTrigger::Auto);
     if should_use_remote_compact_task(sess.as_ref(), &turn_context.client.get_provider()) {
         run_inline_remote_auto_compact_task(Arc::clone(sess), Arc::clone(turn_context)).await;
     } else {
@@ -2386,15 +2566,18 @@ async fn run_turn(
         .list_all_tools()
         .or_cancel(&cancellation_token)
         .await?;
-    let router = Arc::new(ToolRouter::from_config(
-        &turn_context.tools_config,
-        Some(
-            mcp_tools
-                .into_iter()
-                .map(|(name, tool)| (name, tool.tool))
-                .collect(),
-        ),
-    ));
+    let router = Arc::new(
+        ToolRouter::from_config(
+            &turn_context.tools_config,
+            Some(
+                mcp_tools
+                    .into_iter()
+                    .map(|(name, tool)| (name, tool.tool))
+                    .collect(),
+            ),
+        )
+        .with_allowed_tools(turn_context.active_skills.allowed_tools()),
+    );
 
     let model_supports_parallel = turn_context
         .client
@@ -2428,6 +2611,7 @@ async fn run_turn(
             }
             Err(CodexErr::Interrupted) => return Err(CodexErr::Interrupted),
             Err(CodexErr::EnvVar(var)) => return Err(CodexErr::EnvVar(var)),
+            Err(e @ CodexErr::AuthCommand(_)) => return Err(e),
             Err(e @ CodexErr::Fatal(_)) => return Err(e),
             Err(e @ CodexErr::ContextWindowExceeded) => {
                 sess.set_total_tokens_full(&turn_context).await;
@@ -2528,13 +2712,18 @@ async fn try_run_turn(
     });
 
     sess.persist_rollout_items(&[rollout_item]).await;
-    let mut stream = turn_context
+    let stream = turn_context
         .client
         .clone()
         .stream(prompt)
         .instrument(trace_span!("stream_request"))
         .or_cancel(&cancellation_token)
-        .await??;
+        .await?;
+    for message in turn_context.client.take_failover_warnings() {
+        sess.send_event(&turn_context, EventMsg::Warning(WarningEvent { message }))
+            .await;
+    }
+    let mut stream = stream?;
 
     let tool_runtime = ToolCallRuntime::new(
         Arc::clone(&router),
@@ -3147,6 +3336,7 @@ mod tests {
             models_manager,
             tool_approvals: Mutex::new(ApprovalStore::default()),
             skills_manager,
+            provider_failover: None,
         };
 
         let turn_context = Session::make_turn_context(
@@ -3233,6 +3423,7 @@ mod tests {
             models_manager,
             tool_approvals: Mutex::new(ApprovalStore::default()),
             skills_manager,
+            provider_failover: None,
         };
 
         let turn_context = Arc::new(Session::make_turn_context(
@@ -121,6 +121,7 @@ mod document_helpers {
             McpServerTransportConfig::StreamableHttp {
                 url,
                 bearer_token_env_var,
+                auth_command,
                 http_headers,
                 env_http_headers,
             } => {
@@ -128,6 +129,9 @@ mod document_helpers {
                 if let Some(env_var) = bearer_token_env_var {
                     entry["bearer_token_env_var"] = value(env_var.clone());
                 }
+                if let Some(argv) = auth_command {
+                    entry["auth_command"] = array_from_iter(argv.iter().cloned());
+                }
                 if let Some(headers) = http_headers
                     && !headers.is_empty()
                 {
@@ -1136,6 +1140,7 @@ gpt-5 = "gpt-5.1"
                 transport: McpServerTransportConfig::StreamableHttp {
                     url: "https://example.com".to_string(),
                     bearer_token_env_var: Some("TOKEN".to_string()),
+                    auth_command: None,
                     http_headers: Some(
                         [("Z-Header".to_string(), "z".to_string())]
                             .into_iter()
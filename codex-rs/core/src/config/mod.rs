@@ -1,5 +1,8 @@
 use crate::auth::AuthCredentialsStoreMode;
+use crate::config::types::Cues;
 use crate::config::types::DEFAULT_OTEL_ENVIRONMENT;
+use crate::config::types::EditingMode;
+use crate::config::types::FallbackProviderToml;
 use crate::config::types::History;
 use crate::config::types::McpServerConfig;
 use crate::config::types::Notice;
@@ -21,9 +24,12 @@ use crate::features::FeatureOverrides;
 use crate::features::Features;
 use crate::features::FeaturesToml;
 use crate::git_info::resolve_root_git_project_for_trust;
+use crate::model_provider_info::FallbackProvider;
+use crate::model_provider_info::LLAMA_CPP_OSS_PROVIDER_ID;
 use crate::model_provider_info::LMSTUDIO_OSS_PROVIDER_ID;
 use crate::model_provider_info::ModelProviderInfo;
 use crate::model_provider_info::OLLAMA_OSS_PROVIDER_ID;
+use crate::model_provider_info::VLLM_OSS_PROVIDER_ID;
 use crate::model_provider_info::built_in_model_providers;
 use crate::project_doc::DEFAULT_PROJECT_DOC_FILENAME;
 use crate::project_doc::LOCAL_PROJECT_DOC_FILENAME;
@@ -111,6 +117,10 @@ pub struct Config {
     /// Info needed to make an API request to the model.
     pub model_provider: ModelProviderInfo,
 
+    /// Providers to fail over to, in order, when `model_provider` returns
+    /// 5xx/429 errors or times out.
+    pub fallback_providers: Vec<FallbackProvider>,
+
     /// Approval policy for executing commands.
     pub approval_policy: Constrained<AskForApproval>,
 
@@ -173,6 +183,12 @@ pub struct Config {
     /// and turn completions when not focused.
     pub tui_notifications: Notifications,
 
+    /// Audible and spoken TUI cues (`[tui.cues]`).
+    pub tui_cues: Cues,
+
+    /// Composer key bindings (`tui.editing_mode`).
+    pub tui_editing_mode: EditingMode,
+
     /// Enable ASCII animations and shimmer effects in the TUI.
     pub animations: bool,
 
@@ -267,6 +283,9 @@ pub struct Config {
     /// Token budget applied when storing tool/function outputs in the context manager.
     pub tool_output_token_limit: Option<usize>,
 
+    /// Token budget for each file attached to a prompt as `UserInput::File`.
+    pub file_attachment_token_limit: Option<usize>,
+
     /// Directory containing all Codex state (defaults to `~/.codex` but can be
     /// overridden by the `CODEX_HOME` environment variable).
     pub codex_home: PathBuf,
@@ -625,14 +644,17 @@ pub fn set_project_trust_level(
 pub fn set_default_oss_provider(codex_home: &Path, provider: &str) -> std::io::Result<()> {
     // Validate that the provider is one of the known OSS providers
     match provider {
-        LMSTUDIO_OSS_PROVIDER_ID | OLLAMA_OSS_PROVIDER_ID => {
+        LMSTUDIO_OSS_PROVIDER_ID
+        | OLLAMA_OSS_PROVIDER_ID
+        | LLAMA_CPP_OSS_PROVIDER_ID
+        | VLLM_OSS_PROVIDER_ID => {
             // Valid provider, continue
         }
         _ => {
             return Err(std::io::Error::new(
                 std::io::ErrorKind::InvalidInput,
                 format!(
-                    "Invalid OSS provider '{provider}'. Must be one of: {LMSTUDIO_OSS_PROVIDER_ID}, {OLLAMA_OSS_PROVIDER_ID}"
+                    "Invalid OSS provider '{provider}'. Must be one of: {LMSTUDIO_OSS_PROVIDER_ID}, {OLLAMA_OSS_PROVIDER_ID}, {LLAMA_CPP_OSS_PROVIDER_ID}, {VLLM_OSS_PROVIDER_ID}"
                 ),
             ));
         }
@@ -674,6 +696,9 @@ pub struct ConfigToml {
     /// Provider to use from the model_providers map.
     pub model_provider: Option<String>,
 
+    /// Providers to switch to, in order, when `model_provider` is unavailable.
+    pub fallback_providers: Option<Vec<FallbackProviderToml>>,
+
     /// Size of the context window for the model, in tokens.
     pub model_context_window: Option<i64>,
 
@@ -746,6 +771,9 @@ pub struct ConfigToml {
     /// Token budget applied when storing tool/function outputs in the context manager.
     pub tool_output_token_limit: Option<usize>,
 
+    /// Token budget for each file attached to a prompt. Defaults to 20000.
+    pub file_attachment_token_limit: Option<usize>,
+
     /// Profile to use from the `profiles` map.
     pub profile: Option<String>,
 
@@ -824,7 +852,8 @@ pub struct ConfigToml {
     pub experimental_compact_prompt_file: Option<AbsolutePathBuf>,
     pub experimental_use_unified_exec_tool: Option<bool>,
     pub experimental_use_freeform_apply_patch: Option<bool>,
-    /// Preferred OSS provider for local models, e.g. "lmstudio" or "ollama".
+    /// Preferred OSS provider for local models: "lmstudio", "ollama",
+    /// "llamacpp" or "vllm".
     pub oss_provider: Option<String>,
 }
 
@@ -1205,6 +1234,25 @@ impl Config {
                 )
             })?
             .clone();
+        let fallback_providers = config_profile
+            .fallback_providers
+            .or(cfg.fallback_providers)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|fallback| {
+                let provider = model_providers.get(&fallback.provider).ok_or_else(|| {
+                    std::io::Error::new(
+                        std::io::ErrorKind::NotFound,
+                        format!("Fallback model provider `{}` not found", fallback.provider),
+                    )
+                })?;
+                Ok(FallbackProvider {
+                    provider_id: fallback.provider,
+                    provider: provider.clone(),
+                    model: fallback.model,
+                })
+            })
+            .collect::<std::io::Result<Vec<_>>>()?;
 
         let shell_environment_policy = cfg.shell_environment_policy.into();
 
@@ -1314,6 +1362,7 @@ impl Config {
             model_auto_compact_token_limit: cfg.model_auto_compact_token_limit,
             model_provider_id,
             model_provider,
+            fallback_providers,
             cwd: resolved_cwd,
             approval_policy: constrained_approval_policy,
             sandbox_policy: constrained_sandbox_policy,
@@ -1348,6 +1397,7 @@ impl Config {
                 })
                 .collect(),
             tool_output_token_limit: cfg.tool_output_token_limit,
+            file_attachment_token_limit: cfg.file_attachment_token_limit,
             codex_home,
             history,
             file_opener: cfg.file_opener.unwrap_or(UriBasedFileOpener::VsCode),
@@ -1390,6 +1440,8 @@ impl Config {
                 .as_ref()
                 .map(|t| t.notifications.clone())
                 .unwrap_or_default(),
+            tui_cues: cfg.tui.as_ref().map(|t| t.cues.clone()).unwrap_or_default(),
+            tui_editing_mode: cfg.tui.as_ref().map(|t| t.editing_mode).unwrap_or_default(),
             animations: cfg.tui.as_ref().map(|t| t.animations).unwrap_or(true),
             show_tooltips: cfg.tui.as_ref().map(|t| t.show_tooltips).unwrap_or(true),
             tui_scroll_events_per_tick: cfg.tui.as_ref().and_then(|t| t.scroll_events_per_tick),
@@ -1421,11 +1473,13 @@ impl Config {
                     .unwrap_or(DEFAULT_OTEL_ENVIRONMENT.to_string());
                 let exporter = t.exporter.unwrap_or(OtelExporterKind::None);
                 let trace_exporter = t.trace_exporter.unwrap_or_else(|| exporter.clone());
+                let metrics_exporter = t.metrics_exporter.unwrap_or(OtelExporterKind::None);
                 OtelConfig {
                     log_user_prompt,
                     environment,
                     exporter,
                     trace_exporter,
+                    metrics_exporter,
                 }
             },
         };
@@ -1591,6 +1645,7 @@ persistence = "none"
                 notifications: Notifications::Enabled(true),
                 animations: true,
                 show_tooltips: true,
+                editing_mode: EditingMode::Emacs,
                 scroll_events_per_tick: None,
                 scroll_wheel_lines: None,
                 scroll_trackpad_lines: None,
@@ -1600,10 +1655,33 @@ persistence = "none"
                 scroll_wheel_tick_detect_max_ms: None,
                 scroll_wheel_like_max_duration_ms: None,
                 scroll_invert: false,
+                cues: Cues::default(),
             }
         );
     }
 
+    #[test]
+    fn otel_config_parses_offline_exporters() {
+        let cfg = r#"
+[otel]
+exporter = "file"
+metrics_exporter = { prometheus = { listen = "127.0.0.1:9464" } }
+"#;
+
+        let parsed =
+            toml::from_str::<ConfigToml>(cfg).expect("offline OTEL exporters should parse");
+        let otel = parsed.otel.expect("config should include otel section");
+
+        assert_eq!(otel.exporter, Some(OtelExporterKind::File));
+        assert_eq!(
+            otel.metrics_exporter,
+            Some(OtelExporterKind::Prometheus {
+                listen: Some("127.0.0.1:9464".to_string()),
+                textfile: None,
+            })
+        );
+    }
+
     #[test]
     fn test_sandbox_config_parsing() {
         let sandbox_full_access = r#"
@@ -1885,6 +1963,54 @@ trust_level = "trusted"
         Ok(())
     }
 
+    #[test]
+    fn profile_fallback_providers_resolve_against_model_providers() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cfg: ConfigToml = toml::from_str(
+            r#"
+profile = "work"
+
+[profiles.work]
+model_provider = "openai"
+fallback_providers = [
+  { provider = "ollama", model = "gpt-oss:20b" },
+]
+"#,
+        )
+        .expect("TOML deserialization should succeed");
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )?;
+
+        assert_eq!(
+            config
+                .fallback_providers
+                .iter()
+                .map(|fallback| (fallback.provider_id.as_str(), fallback.model.as_deref()))
+                .collect::<Vec<_>>(),
+            vec![("ollama", Some("gpt-oss:20b"))]
+        );
+
+        let unknown: ConfigToml = toml::from_str(
+            r#"
+fallback_providers = [{ provider = "missing" }]
+"#,
+        )
+        .expect("TOML deserialization should succeed");
+        let err = Config::load_from_base_config_with_overrides(
+            unknown,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )
+        .expect_err("unknown fallback provider should be rejected");
+        assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+
+        Ok(())
+    }
+
     #[test]
     fn profile_sandbox_mode_overrides_base() -> std::io::Result<()> {
         let codex_home = TempDir::new()?;
@@ -2412,6 +2538,7 @@ ZIG_VAR = "3"
                 transport: McpServerTransportConfig::StreamableHttp {
                     url: "https://example.com/mcp".to_string(),
                     bearer_token_env_var: Some("MCP_TOKEN".to_string()),
+                    auth_command: None,
                     http_headers: None,
                     env_http_headers: None,
                 },
@@ -2446,10 +2573,12 @@ startup_timeout_sec = 2.0
             McpServerTransportConfig::StreamableHttp {
                 url,
                 bearer_token_env_var,
+                auth_command,
                 http_headers,
                 env_http_headers,
             } => {
                 assert_eq!(url, "https://example.com/mcp");
+                assert!(auth_command.is_none());
                 assert_eq!(bearer_token_env_var.as_deref(), Some("MCP_TOKEN"));
                 assert!(http_headers.is_none());
                 assert!(env_http_headers.is_none());
@@ -2471,6 +2600,7 @@ startup_timeout_sec = 2.0
                 transport: McpServerTransportConfig::StreamableHttp {
                     url: "https://example.com/mcp".to_string(),
                     bearer_token_env_var: Some("MCP_TOKEN".to_string()),
+                    auth_command: None,
                     http_headers: Some(HashMap::from([("X-Doc".to_string(), "42".to_string())])),
                     env_http_headers: Some(HashMap::from([(
                         "X-Auth".to_string(),
@@ -2545,6 +2675,7 @@ X-Auth = "DOCS_AUTH"
                 transport: McpServerTransportConfig::StreamableHttp {
                     url: "https://example.com/mcp".to_string(),
                     bearer_token_env_var: Some("MCP_TOKEN".to_string()),
+                    auth_command: None,
                     http_headers: Some(HashMap::from([("X-Doc".to_string(), "42".to_string())])),
                     env_http_headers: Some(HashMap::from([(
                         "X-Auth".to_string(),
@@ -2575,6 +2706,7 @@ X-Auth = "DOCS_AUTH"
                 transport: McpServerTransportConfig::StreamableHttp {
                     url: "https://example.com/mcp".to_string(),
                     bearer_token_env_var: None,
+                    auth_command: None,
                     http_headers: None,
                     env_http_headers: None,
                 },
@@ -2605,10 +2737,12 @@ url = "https://example.com/mcp"
             McpServerTransportConfig::StreamableHttp {
                 url,
                 bearer_token_env_var,
+                auth_command,
                 http_headers,
                 env_http_headers,
             } => {
                 assert_eq!(url, "https://example.com/mcp");
+                assert!(auth_command.is_none());
                 assert!(bearer_token_env_var.is_none());
                 assert!(http_headers.is_none());
                 assert!(env_http_headers.is_none());
@@ -2634,6 +2768,7 @@ url = "https://example.com/mcp"
                     transport: McpServerTransportConfig::StreamableHttp {
                         url: "https://example.com/mcp".to_string(),
                         bearer_token_env_var: Some("MCP_TOKEN".to_string()),
+                        auth_command: None,
                         http_headers: Some(HashMap::from([(
                             "X-Doc".to_string(),
                             "42".to_string(),
@@ -3085,6 +3220,7 @@ model_verbosity = "high"
             wire_api: crate::WireApi::Chat,
             env_key_instructions: None,
             experimental_bearer_token: None,
+            auth_command: None,
             query_params: None,
             http_headers: None,
             env_http_headers: None,
@@ -3151,6 +3287,7 @@ model_verbosity = "high"
                 model_auto_compact_token_limit: None,
                 model_provider_id: "openai".to_string(),
                 model_provider: fixture.openai_provider.clone(),
+                fallback_providers: Vec::new(),
                 approval_policy: Constrained::allow_any(AskForApproval::Never),
                 sandbox_policy: Constrained::allow_any(SandboxPolicy::new_read_only_policy()),
                 did_user_set_custom_approval_policy_or_sandbox_mode: true,
@@ -3166,6 +3303,7 @@ model_verbosity = "high"
                 project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
                 project_doc_fallback_filenames: Vec::new(),
                 tool_output_token_limit: None,
+                file_attachment_token_limit: None,
                 codex_home: fixture.codex_home(),
                 history: History::default(),
                 file_opener: UriBasedFileOpener::VsCode,
@@ -3195,6 +3333,8 @@ model_verbosity = "high"
                 check_for_update_on_startup: true,
                 disable_paste_burst: false,
                 tui_notifications: Default::default(),
+                tui_cues: Default::default(),
+                tui_editing_mode: EditingMode::Emacs,
                 animations: true,
                 show_tooltips: true,
                 tui_scroll_events_per_tick: None,
@@ -3234,6 +3374,7 @@ model_verbosity = "high"
             model_auto_compact_token_limit: None,
             model_provider_id: "openai-chat-completions".to_string(),
             model_provider: fixture.openai_chat_completions_provider.clone(),
+            fallback_providers: Vec::new(),
             approval_policy: Constrained::allow_any(AskForApproval::UnlessTrusted),
             sandbox_policy: Constrained::allow_any(SandboxPolicy::new_read_only_policy()),
             did_user_set_custom_approval_policy_or_sandbox_mode: true,
@@ -3249,6 +3390,7 @@ model_verbosity = "high"
             project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
             project_doc_fallback_filenames: Vec::new(),
             tool_output_token_limit: None,
+            file_attachment_token_limit: None,
             codex_home: fixture.codex_home(),
             history: History::default(),
             file_opener: UriBasedFileOpener::VsCode,
@@ -3278,6 +3420,8 @@ model_verbosity = "high"
             check_for_update_on_startup: true,
             disable_paste_burst: false,
             tui_notifications: Default::default(),
+            tui_cues: Default::default(),
+            tui_editing_mode: EditingMode::Emacs,
             animations: true,
             show_tooltips: true,
             tui_scroll_events_per_tick: None,
@@ -3332,6 +3476,7 @@ model_verbosity = "high"
             model_auto_compact_token_limit: None,
             model_provider_id: "openai".to_string(),
             model_provider: fixture.openai_provider.clone(),
+            fallback_providers: Vec::new(),
             approval_policy: Constrained::allow_any(AskForApproval::OnFailure),
             sandbox_policy: Constrained::allow_any(SandboxPolicy::new_read_only_policy()),
             did_user_set_custom_approval_policy_or_sandbox_mode: true,
@@ -3347,6 +3492,7 @@ model_verbosity = "high"
             project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
             project_doc_fallback_filenames: Vec::new(),
             tool_output_token_limit: None,
+            file_attachment_token_limit: None,
             codex_home: fixture.codex_home(),
             history: History::default(),
             file_opener: UriBasedFileOpener::VsCode,
@@ -3376,6 +3522,8 @@ model_verbosity = "high"
             check_for_update_on_startup: true,
             disable_paste_burst: false,
             tui_notifications: Default::default(),
+            tui_cues: Default::default(),
+            tui_editing_mode: EditingMode::Emacs,
             animations: true,
             show_tooltips: true,
             tui_scroll_events_per_tick: None,
@@ -3416,6 +3564,7 @@ model_verbosity = "high"
             model_auto_compact_token_limit: None,
             model_provider_id: "openai".to_string(),
             model_provider: fixture.openai_provider.clone(),
+            fallback_providers: Vec::new(),
             approval_policy: Constrained::allow_any(AskForApproval::OnFailure),
             sandbox_policy: Constrained::allow_any(SandboxPolicy::new_read_only_policy()),
             did_user_set_custom_approval_policy_or_sandbox_mode: true,
@@ -3431,6 +3580,7 @@ model_verbosity = "high"
             project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
             project_doc_fallback_filenames: Vec::new(),
             tool_output_token_limit: None,
+            file_attachment_token_limit: None,
             codex_home: fixture.codex_home(),
             history: History::default(),
             file_opener: UriBasedFileOpener::VsCode,
@@ -3460,6 +3610,8 @@ model_verbosity = "high"
             check_for_update_on_startup: true,
             disable_paste_burst: false,
             tui_notifications: Default::default(),
+            tui_cues: Default::default(),
+            tui_editing_mode: EditingMode::Emacs,
             animations: true,
             show_tooltips: true,
             tui_scroll_events_per_tick: None,
@@ -72,6 +72,7 @@ impl<'de> Deserialize<'de> for McpServerConfig {
             url: Option<String>,
             bearer_token: Option<String>,
             bearer_token_env_var: Option<String>,
+            auth_command: Option<Vec<String>>,
 
             // shared
             #[serde(default)]
@@ -123,6 +124,7 @@ impl<'de> Deserialize<'de> for McpServerConfig {
                 raw.bearer_token_env_var.as_ref(),
             )?;
             throw_if_set("stdio", "bearer_token", raw.bearer_token.as_ref())?;
+            throw_if_set("stdio", "auth_command", raw.auth_command.as_ref())?;
             throw_if_set("stdio", "http_headers", raw.http_headers.as_ref())?;
             throw_if_set("stdio", "env_http_headers", raw.env_http_headers.as_ref())?;
             McpServerTransportConfig::Stdio {
@@ -138,9 +140,15 @@ impl<'de> Deserialize<'de> for McpServerConfig {
             throw_if_set("streamable_http", "env_vars", raw.env_vars.as_ref())?;
             throw_if_set("streamable_http", "cwd", raw.cwd.as_ref())?;
             throw_if_set("streamable_http", "bearer_token", raw.bearer_token.as_ref())?;
+            if raw.bearer_token_env_var.is_some() && raw.auth_command.is_some() {
+                return Err(SerdeError::custom(
+                    "auth_command cannot be combined with bearer_token_env_var",
+                ));
+            }
             McpServerTransportConfig::StreamableHttp {
                 url,
                 bearer_token_env_var: raw.bearer_token_env_var.clone(),
+                auth_command: raw.auth_command.take(),
                 http_headers: raw.http_headers.clone(),
                 env_http_headers: raw.env_http_headers.take(),
             }
@@ -186,6 +194,11 @@ pub enum McpServerTransportConfig {
         /// The actual secret value must be provided via the environment.
         #[serde(default, skip_serializing_if = "Option::is_none")]
         bearer_token_env_var: Option<String>,
+        /// Command (program followed by its arguments) that prints a bearer
+        /// token, optionally as JSON with an expiry. The token is cached in
+        /// memory and the command is run again once it expires.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        auth_command: Option<Vec<String>>,
         /// Additional HTTP headers to include in requests to this server.
         #[serde(default, skip_serializing_if = "Option::is_none")]
         http_headers: Option<HashMap<String, String>>,
@@ -252,6 +265,17 @@ impl UriBasedFileOpener {
     }
 }
 
+/// Entry in the ordered `fallback_providers` list used when the primary model
+/// provider is unavailable.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct FallbackProviderToml {
+    /// Key in the `model_providers` map.
+    pub provider: String,
+
+    /// Model to request from this provider. Defaults to the session model.
+    pub model: Option<String>,
+}
+
 /// Settings that govern if and what will be written to `~/.codex/history.jsonl`.
 #[derive(Deserialize, Debug, Clone, PartialEq, Default)]
 pub struct History {
@@ -312,6 +336,20 @@ pub enum OtelExporterKind {
         #[serde(default)]
         tls: Option<OtelTlsConfig>,
     },
+    /// Newline-delimited JSON files under `CODEX_HOME/log`, rotated daily.
+    /// Supported for logs and traces.
+    File,
+    /// Prometheus text exposition of metrics. Only valid for `metrics_exporter`.
+    Prometheus {
+        /// Address to serve metrics on, e.g. `127.0.0.1:9464`.
+        #[serde(default)]
+        listen: Option<String>,
+        /// File rewritten on every export, e.g. for node_exporter's textfile
+        /// collector. Defaults to `CODEX_HOME/log/codex-metrics.prom` when
+        /// `listen` is not set.
+        #[serde(default)]
+        textfile: Option<AbsolutePathBuf>,
+    },
 }
 
 /// OTEL settings loaded from config.toml. Fields are optional so we can apply defaults.
@@ -328,6 +366,11 @@ pub struct OtelConfigToml {
 
     /// Optional trace exporter
     pub trace_exporter: Option<OtelExporterKind>,
+
+    /// Optional metrics exporter. Unlike `trace_exporter` it does not fall back
+    /// to `exporter`, because OTLP/HTTP collectors expect metrics on their own
+    /// endpoint.
+    pub metrics_exporter: Option<OtelExporterKind>,
 }
 
 /// Effective OTEL settings after defaults are applied.
@@ -337,6 +380,7 @@ pub struct OtelConfig {
     pub environment: String,
     pub exporter: OtelExporterKind,
     pub trace_exporter: OtelExporterKind,
+    pub metrics_exporter: OtelExporterKind,
 }
 
 impl Default for OtelConfig {
@@ -346,6 +390,7 @@ impl Default for OtelConfig {
             environment: DEFAULT_OTEL_ENVIRONMENT.to_owned(),
             exporter: OtelExporterKind::None,
             trace_exporter: OtelExporterKind::None,
+            metrics_exporter: OtelExporterKind::None,
         }
     }
 }
@@ -385,6 +430,22 @@ impl Default for ScrollInputMode {
     }
 }
 
+/// Key bindings used by the TUI composer.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum EditingMode {
+    /// Emacs-style bindings (Ctrl+A, Ctrl+E, Ctrl+K, ...).
+    Emacs,
+    /// Modal vi-style editing with normal, insert and visual modes.
+    Vi,
+}
+
+impl Default for EditingMode {
+    fn default() -> Self {
+        Self::Emacs
+    }
+}
+
 /// Collection of settings that are specific to the TUI.
 #[derive(Deserialize, Debug, Clone, PartialEq, Default)]
 pub struct Tui {
@@ -403,6 +464,10 @@ pub struct Tui {
     #[serde(default = "default_true")]
     pub show_tooltips: bool,
 
+    /// Composer key bindings: `emacs` (default) or `vi`.
+    #[serde(default)]
+    pub editing_mode: EditingMode,
+
     /// Override the *wheel* event density used to normalize TUI2 scrolling.
     ///
     /// Terminals generally deliver both mouse wheels and trackpads as discrete `scroll up/down`
@@ -505,6 +570,61 @@ pub struct Tui {
     /// wheel and trackpad input.
     #[serde(default)]
     pub scroll_invert: bool,
+
+    /// Audible and spoken cues for key events. Unlike desktop notifications,
+    /// cues fire even while the terminal is focused unless `unfocused_only`
+    /// is set.
+    #[serde(default)]
+    pub cues: Cues,
+}
+
+/// Cue configuration under `[tui.cues]`.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct Cues {
+    /// Only fire cues while the terminal is unfocused. Defaults to `false`.
+    #[serde(default)]
+    pub unfocused_only: bool,
+
+    /// Command used to speak `say` messages; the message is appended as the
+    /// final argument. Defaults to the first of `say`, `spd-say`,
+    /// `espeak-ng` and `espeak` found on `PATH`.
+    pub tts_command: Option<Vec<String>>,
+
+    /// Command used to play `sound` files; the file path is appended as the
+    /// final argument. Defaults to `afplay` on macOS and the first of
+    /// `paplay`, `pw-play` and `aplay` found on `PATH` elsewhere.
+    pub sound_command: Option<Vec<String>>,
+
+    /// Remaining context, in percent, at or below which the
+    /// `context_nearly_full` cue fires. Defaults to 10.
+    pub context_nearly_full_percent: Option<u8>,
+
+    /// Fired when Codex asks for approval to run a command, edit files or
+    /// answer an MCP elicitation.
+    pub approval_requested: Option<CueAction>,
+
+    /// Fired when a turn finishes.
+    pub turn_complete: Option<CueAction>,
+
+    /// Fired when a command exits with a non-zero status.
+    pub command_failed: Option<CueAction>,
+
+    /// Fired once when remaining context drops to the configured threshold.
+    pub context_nearly_full: Option<CueAction>,
+}
+
+/// What to do when a cue fires. Any combination of actions may be set.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+#[serde(deny_unknown_fields)]
+pub struct CueAction {
+    /// Ring the terminal bell this many times, a fifth of a second apart.
+    pub bell: Option<u8>,
+
+    /// Play this sound file with `sound_command`.
+    pub sound: Option<PathBuf>,
+
+    /// Speak this message with `tts_command`.
+    pub say: Option<String>,
 }
 
 const fn default_true() -> bool {
@@ -816,6 +936,7 @@ mod tests {
             McpServerTransportConfig::StreamableHttp {
                 url: "https://example.com/mcp".to_string(),
                 bearer_token_env_var: None,
+                auth_command: None,
                 http_headers: None,
                 env_http_headers: None,
             }
@@ -838,6 +959,7 @@ mod tests {
             McpServerTransportConfig::StreamableHttp {
                 url: "https://example.com/mcp".to_string(),
                 bearer_token_env_var: Some("GITHUB_TOKEN".to_string()),
+                auth_command: None,
                 http_headers: None,
                 env_http_headers: None,
             }
@@ -845,6 +967,42 @@ mod tests {
         assert!(cfg.enabled);
     }
 
+    #[test]
+    fn deserialize_streamable_http_server_config_with_auth_command() {
+        let cfg: McpServerConfig = toml::from_str(
+            r#"
+            url = "https://example.com/mcp"
+            auth_command = ["gateway-cli", "token"]
+        "#,
+        )
+        .expect("should deserialize http config with auth_command");
+
+        assert_eq!(
+            cfg.transport,
+            McpServerTransportConfig::StreamableHttp {
+                url: "https://example.com/mcp".to_string(),
+                bearer_token_env_var: None,
+                auth_command: Some(vec!["gateway-cli".to_string(), "token".to_string()]),
+                http_headers: None,
+                env_http_headers: None,
+            }
+        );
+
+        let err = toml::from_str::<McpServerConfig>(
+            r#"
+            url = "https://example.com/mcp"
+            bearer_token_env_var = "TOKEN"
+            auth_command = ["gateway-cli", "token"]
+        "#,
+        )
+        .expect_err("should reject auth_command with bearer_token_env_var");
+        assert!(
+            err.to_string()
+                .contains("auth_command cannot be combined with bearer_token_env_var"),
+            "unexpected error: {err}"
+        );
+    }
+
     #[test]
     fn deserialize_streamable_http_server_config_with_headers() {
         let cfg: McpServerConfig = toml::from_str(
@@ -861,6 +1019,7 @@ mod tests {
             McpServerTransportConfig::StreamableHttp {
                 url: "https://example.com/mcp".to_string(),
                 bearer_token_env_var: None,
+                auth_command: None,
                 http_headers: Some(HashMap::from([("X-Foo".to_string(), "bar".to_string())])),
                 env_http_headers: Some(HashMap::from([(
                     "X-Token".to_string(),
@@ -1,10 +1,12 @@
 use crate::codex::TurnContext;
 use crate::context_manager::normalize;
+use crate::event_mapping::parse_turn_item;
 use crate::truncate::TruncationPolicy;
 use crate::truncate::approx_token_count;
 use crate::truncate::approx_tokens_from_byte_count;
 use crate::truncate::truncate_function_output_items_with_policy;
 use crate::truncate::truncate_text;
+use codex_protocol::items::TurnItem;
 use codex_protocol::models::ContentItem;
 use codex_protocol::models::FunctionCallOutputContentItem;
 use codex_protocol::models::FunctionCallOutputPayload;
@@ -121,6 +123,26 @@ impl ContextManager {
         self.items = items;
     }
 
+    /// Removes the last `num_turns` user turns: each of the trailing user
+    /// messages together with everything recorded after it. Returns the removed
+    /// items (oldest first), or `None` when the history holds fewer than
+    /// `num_turns` user turns, in which case nothing is removed.
+    pub(crate) fn drop_last_user_turns(&mut self, num_turns: usize) -> Option<Vec<ResponseItem>> {
+        let user_positions: Vec<usize> = self
+            .items
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| is_user_turn_start(item))
+            .map(|(idx, _)| idx)
+            .collect();
+        if num_turns == 0 || user_positions.len() < num_turns {
+            return None;
+        }
+
+        let cut_idx = user_positions[user_positions.len() - num_turns];
+        Some(self.items.split_off(cut_idx))
+    }
+
     pub(crate) fn replace_last_turn_images(&mut self, placeholder: &str) {
         let Some(last_item) = self.items.last_mut() else {
             return;
@@ -283,6 +305,13 @@ fn is_api_message(message: &ResponseItem) -> bool {
     }
 }
 
+/// Whether `item` is a message typed by the user (as opposed to injected
+/// instructions or environment context), which marks the start of a turn.
+fn is_user_turn_start(item: &ResponseItem) -> bool {
+    matches!(item, ResponseItem::Message { role, .. } if role == "user")
+        && matches!(parse_turn_item(item), Some(TurnItem::UserMessage(_)))
+}
+
 fn estimate_reasoning_length(encoded_len: usize) -> usize {
     encoded_len
         .saturating_mul(3)
@@ -155,6 +155,45 @@ fn get_history_for_prompt_drops_ghost_commits() {
     assert_eq!(filtered, vec![]);
 }
 
+#[test]
+fn drop_last_user_turns_removes_trailing_turns() {
+    let environment_context = ResponseItem::Message {
+        id: None,
+        role: "user".to_string(),
+        content: vec![ContentItem::InputText {
+            text: "<environment_context>cwd</environment_context>".to_string(),
+        }],
+    };
+    let items = vec![
+        environment_context.clone(),
+        user_msg("first"),
+        assistant_msg("first reply"),
+        user_msg("second"),
+        ResponseItem::GhostSnapshot {
+            ghost_commit: GhostCommit::new("ghost-2".to_string(), None, Vec::new(), Vec::new()),
+        },
+        assistant_msg("second reply"),
+        user_msg("third"),
+        assistant_msg("third reply"),
+    ];
+    let mut history = create_history_with_items(items.clone());
+
+    let removed = history.drop_last_user_turns(2);
+
+    assert_eq!(removed, Some(items[3..].to_vec()));
+    assert_eq!(history.get_history(), items[..3].to_vec());
+}
+
+#[test]
+fn drop_last_user_turns_keeps_history_when_out_of_range() {
+    let items = vec![user_msg("only"), assistant_msg("reply")];
+    let mut history = create_history_with_items(items.clone());
+
+    assert_eq!(history.drop_last_user_turns(2), None);
+    assert_eq!(history.drop_last_user_turns(0), None);
+    assert_eq!(history.get_history(), items);
+}
+
 #[test]
 fn remove_first_item_removes_matching_output_for_function_call() {
     let items = vec![
@@ -1,3 +1,4 @@
+use crate::auth_command::AuthCommandError;
 use crate::exec::ExecToolCallOutput;
 use crate::token_data::KnownPlan;
 use crate::token_data::PlanType;
@@ -173,6 +174,9 @@ pub enum CodexErr {
 
     #[error("{0}")]
     EnvVar(EnvVarError),
+
+    #[error(transparent)]
+    AuthCommand(#[from] AuthCommandError),
 }
 
 impl From<CancelErr> for CodexErr {
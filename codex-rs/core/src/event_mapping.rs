@@ -13,6 +13,7 @@ use codex_protocol::user_input::UserInput;
 use tracing::warn;
 use uuid::Uuid;
 
+use crate::file_attachments::parse_attached_file_text;
 use crate::user_instructions::SkillInstructions;
 use crate::user_instructions::UserInstructions;
 use crate::user_shell_command::is_user_shell_command_text;
@@ -38,6 +39,10 @@ fn parse_user_message(message: &[ContentItem]) -> Option<UserMessageItem> {
                 if is_session_prefix(text) || is_user_shell_command_text(text) {
                     return None;
                 }
+                if let Some(file) = parse_attached_file_text(text) {
+                    content.push(file);
+                    continue;
+                }
                 content.push(UserInput::Text { text: text.clone() });
             }
             ContentItem::InputImage { image_url } => {
@@ -0,0 +1,331 @@
+//! Inlines `UserInput::File` attachments into the prompt.
+//!
+//! Each attachment is read from disk (relative paths resolve against the turn
+//! cwd), optionally narrowed to a line range, truncated to the attachment
+//! budget and wrapped in an `<attached_file>` block so the transcript can map
+//! it back to the original attachment. PDFs and DOCX files are converted to
+//! text with `pdftotext` and `pandoc` respectively when those are installed.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::time::Duration;
+
+use codex_protocol::user_input::LineRange;
+use codex_protocol::user_input::UserInput;
+use tokio::process::Command;
+
+use crate::truncate::TruncationPolicy;
+use crate::truncate::formatted_truncate_text;
+
+const ATTACHED_FILE_OPEN: &str = "<attached_file";
+const ATTACHED_FILE_CLOSE: &str = "</attached_file>";
+
+/// Default budget for a single attachment when `file_attachment_token_limit`
+/// is not configured.
+pub(crate) const DEFAULT_FILE_ATTACHMENT_TOKEN_LIMIT: usize = 20_000;
+
+/// Upper bound on how long a document converter may run.
+const CONVERTER_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Attachments larger than this are rejected before being read.
+const MAX_FILE_BYTES: u64 = 50 * 1024 * 1024;
+
+/// Replaces every `UserInput::File` in `items` with a `UserInput::Text`
+/// holding the file contents. Attachments that cannot be read are replaced
+/// with a short note and reported in the returned warnings.
+pub(crate) async fn resolve_file_attachments(
+    items: Vec<UserInput>,
+    cwd: &Path,
+    policy: TruncationPolicy,
+) -> (Vec<UserInput>, Vec<String>) {
+    let mut resolved = Vec::with_capacity(items.len());
+    let mut warnings = Vec::new();
+
+    for item in items {
+        let UserInput::File { path, range } = item else {
+            resolved.push(item);
+            continue;
+        };
+
+        let absolute = if path.is_absolute() {
+            path.clone()
+        } else {
+            cwd.join(&path)
+        };
+        let text = match read_attachment(&absolute, range, policy).await {
+            Ok(contents) => render_attachment(&path, range, &contents),
+            Err(err) => {
+                let message = format!("Could not attach {}: {err}", path.display());
+                let text = render_attachment(&path, range, &format!("[{message}]"));
+                warnings.push(message);
+                text
+            }
+        };
+        resolved.push(UserInput::Text { text });
+    }
+
+    (resolved, warnings)
+}
+
+/// Recovers the attachment that produced `text`, if it was rendered by
+/// [`resolve_file_attachments`].
+pub(crate) fn parse_attached_file_text(text: &str) -> Option<UserInput> {
+    let header = text.trim_start().strip_prefix(ATTACHED_FILE_OPEN)?;
+    let header = &header[..header.find('>')?];
+    let path = attribute(header, "path")?;
+    let range = attribute(header, "lines").and_then(|lines| LineRange::parse(&lines));
+    Some(UserInput::File {
+        path: PathBuf::from(path),
+        range,
+    })
+}
+
+fn render_attachment(path: &Path, range: Option<LineRange>, contents: &str) -> String {
+    let path = escape_attribute(&path.display().to_string());
+    let lines = range
+        .map(|range| format!(" lines=\"{range}\""))
+        .unwrap_or_default();
+    let contents = contents.trim_end_matches('\n');
+    format!("{ATTACHED_FILE_OPEN} path=\"{path}\"{lines}>\n{contents}\n{ATTACHED_FILE_CLOSE}")
+}
+
+async fn read_attachment(
+    path: &Path,
+    range: Option<LineRange>,
+    policy: TruncationPolicy,
+) -> Result<String, String> {
+    let metadata = tokio::fs::metadata(path)
+        .await
+        .map_err(|err| err.to_string())?;
+    if !metadata.is_file() {
+        return Err("not a regular file".to_string());
+    }
+    if metadata.len() > MAX_FILE_BYTES {
+        return Err(format!(
+            "file is {} bytes, larger than the {MAX_FILE_BYTES} byte limit",
+            metadata.len()
+        ));
+    }
+
+    let text = match DocumentKind::from_path(path) {
+        DocumentKind::Pdf => {
+            convert_document("pdftotext", &["-layout", "-enc", "UTF-8"], path, &["-"]).await?
+        }
+        DocumentKind::Docx => convert_document("pandoc", &["--to", "plain"], path, &[]).await?,
+        DocumentKind::Text => {
+            let bytes = tokio::fs::read(path).await.map_err(|err| err.to_string())?;
+            if bytes.contains(&0) {
+                return Err("binary files cannot be attached".to_string());
+            }
+            String::from_utf8_lossy(&bytes).into_owned()
+        }
+    };
+
+    let text = match range {
+        Some(range) => select_lines(&text, range)?,
+        None => text,
+    };
+    Ok(formatted_truncate_text(&text, policy))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DocumentKind {
+    Text,
+    Pdf,
+    Docx,
+}
+
+impl DocumentKind {
+    fn from_path(path: &Path) -> Self {
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(str::to_ascii_lowercase);
+        match extension.as_deref() {
+            Some("pdf") => Self::Pdf,
+            Some("docx") => Self::Docx,
+            _ => Self::Text,
+        }
+    }
+}
+
+async fn convert_document(
+    program: &str,
+    leading_args: &[&str],
+    path: &Path,
+    trailing_args: &[&str],
+) -> Result<String, String> {
+    let child = Command::new(program)
+        .args(leading_args)
+        .arg(path)
+        .args(trailing_args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|err| match err.kind() {
+            std::io::ErrorKind::NotFound => {
+                format!("`{program}` is required to extract text from this file but was not found")
+            }
+            _ => format!("failed to run `{program}`: {err}"),
+        })?;
+
+    let output = tokio::time::timeout(CONVERTER_TIMEOUT, child.wait_with_output())
+        .await
+        .map_err(|_| {
+            format!(
+                "`{program}` timed out after {}s",
+                CONVERTER_TIMEOUT.as_secs()
+            )
+        })?
+        .map_err(|err| format!("failed to run `{program}`: {err}"))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "`{program}` exited with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn select_lines(text: &str, range: LineRange) -> Result<String, String> {
+    let total = text.lines().count();
+    let start = range.start as usize;
+    let end = range.end as usize;
+    let (Some(skip), Some(span)) = (start.checked_sub(1), end.checked_sub(start)) else {
+        return Err(format!(
+            "invalid line range {range}: lines are 1-based and end must not be before start"
+        ));
+    };
+    if start > total {
+        return Err(format!(
+            "line range {range} starts past the end of the file ({total} lines)"
+        ));
+    }
+    Ok(text
+        .lines()
+        .skip(skip)
+        .take(span + 1)
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
+
+fn escape_attribute(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('>', "&gt;")
+}
+
+fn attribute(header: &str, name: &str) -> Option<String> {
+    let needle = format!(" {name}=\"");
+    let start = header.find(&needle)? + needle.len();
+    let len = header[start..].find('"')?;
+    Some(
+        header[start..start + len]
+            .replace("&gt;", ">")
+            .replace("&quot;", "\"")
+            .replace("&amp;", "&"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const POLICY: TruncationPolicy = TruncationPolicy::Tokens(DEFAULT_FILE_ATTACHMENT_TOKEN_LIMIT);
+
+    #[tokio::test]
+    async fn inlines_file_range() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let body = (1..=10).map(|n| format!("line {n}\n")).collect::<String>();
+        std::fs::write(dir.path().join("notes.txt"), body).expect("write");
+
+        let (items, warnings) = resolve_file_attachments(
+            vec![
+                UserInput::Text {
+                    text: "look".to_string(),
+                },
+                UserInput::File {
+                    path: PathBuf::from("notes.txt"),
+                    range: Some(LineRange { start: 3, end: 5 }),
+                },
+            ],
+            dir.path(),
+            POLICY,
+        )
+        .await;
+
+        assert_eq!(warnings, Vec::<String>::new());
+        assert_eq!(
+            items,
+            vec![
+                UserInput::Text {
+                    text: "look".to_string(),
+                },
+                UserInput::Text {
+                    text: "<attached_file path=\"notes.txt\" lines=\"3-5\">\nline 3\nline 4\nline 5\n</attached_file>"
+                        .to_string(),
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn reports_unreadable_attachments() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        std::fs::write(dir.path().join("blob.bin"), [0u8, 1, 2]).expect("write");
+
+        let (items, warnings) = resolve_file_attachments(
+            vec![
+                UserInput::File {
+                    path: PathBuf::from("missing.txt"),
+                    range: None,
+                },
+                UserInput::File {
+                    path: PathBuf::from("blob.bin"),
+                    range: None,
+                },
+            ],
+            dir.path(),
+            POLICY,
+        )
+        .await;
+
+        assert_eq!(items.len(), 2);
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[1].contains("binary files cannot be attached"));
+    }
+
+    #[test]
+    fn round_trips_rendered_attachment() {
+        let path = PathBuf::from("dir/we\"ird>.md");
+        let range = Some(LineRange { start: 2, end: 9 });
+        let text = render_attachment(&path, range, "body");
+
+        assert_eq!(
+            parse_attached_file_text(&text),
+            Some(UserInput::File { path, range })
+        );
+    }
+
+    #[test]
+    fn rejects_ranges_past_end_of_file() {
+        assert_eq!(
+            select_lines("a\nb\n", LineRange { start: 2, end: 99 }),
+            Ok("b".to_string())
+        );
+        assert!(select_lines("a\nb\n", LineRange { start: 5, end: 6 }).is_err());
+    }
+
+    #[test]
+    fn rejects_zero_and_inverted_ranges() {
+        assert!(select_lines("a\nb\n", LineRange { start: 0, end: 1 }).is_err());
+        assert!(select_lines("a\nb\n", LineRange { start: 2, end: 1 }).is_err());
+    }
+}
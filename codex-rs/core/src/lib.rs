@@ -8,6 +8,7 @@
 pub mod api_bridge;
 mod apply_patch;
 pub mod auth;
+pub mod auth_command;
 pub mod bash;
 mod client;
 mod client_common;
@@ -28,6 +29,7 @@ pub mod exec;
 pub mod exec_env;
 mod exec_policy;
 pub mod features;
+mod file_attachments;
 mod flags;
 pub mod git_info;
 pub mod landlock;
@@ -43,6 +45,7 @@ mod model_provider_info;
 pub mod parse_command;
 pub mod path_utils;
 pub mod powershell;
+mod provider_failover;
 pub mod sandboxing;
 mod stream_events_utils;
 mod text_encoding;
@@ -51,11 +54,15 @@ mod truncate;
 mod unified_exec;
 mod user_instructions;
 pub use model_provider_info::CHAT_WIRE_API_DEPRECATION_SUMMARY;
+pub use model_provider_info::DEFAULT_LLAMA_CPP_PORT;
 pub use model_provider_info::DEFAULT_LMSTUDIO_PORT;
 pub use model_provider_info::DEFAULT_OLLAMA_PORT;
+pub use model_provider_info::DEFAULT_VLLM_PORT;
+pub use model_provider_info::LLAMA_CPP_OSS_PROVIDER_ID;
 pub use model_provider_info::LMSTUDIO_OSS_PROVIDER_ID;
 pub use model_provider_info::ModelProviderInfo;
 pub use model_provider_info::OLLAMA_OSS_PROVIDER_ID;
+pub use model_provider_info::VLLM_OSS_PROVIDER_ID;
 pub use model_provider_info::WireApi;
 pub use model_provider_info::built_in_model_providers;
 pub use model_provider_info::create_oss_provider_with_base_url;
@@ -86,6 +93,7 @@ pub use rollout::INTERACTIVE_SESSION_SOURCES;
 pub use rollout::RolloutRecorder;
 pub use rollout::SESSIONS_SUBDIR;
 pub use rollout::SessionMeta;
+pub use rollout::export;
 pub use rollout::find_conversation_path_by_id_str;
 pub use rollout::list::ConversationItem;
 pub use rollout::list::ConversationsPage;
@@ -52,11 +52,16 @@ async fn compute_auth_status(
 ) -> Result<McpAuthStatus> {
     match &config.transport {
         McpServerTransportConfig::Stdio { .. } => Ok(McpAuthStatus::Unsupported),
+        McpServerTransportConfig::StreamableHttp {
+            auth_command: Some(_),
+            ..
+        } => Ok(McpAuthStatus::BearerToken),
         McpServerTransportConfig::StreamableHttp {
             url,
             bearer_token_env_var,
             http_headers,
             env_http_headers,
+            auth_command: None,
         } => {
             determine_streamable_http_auth_status(
                 server_name,
@@ -29,6 +29,7 @@ use codex_protocol::protocol::McpStartupFailure;
 use codex_protocol::protocol::McpStartupStatus;
 use codex_protocol::protocol::McpStartupUpdateEvent;
 use codex_protocol::protocol::SandboxPolicy;
+use codex_rmcp_client::BearerTokenProvider;
 use codex_rmcp_client::ElicitationResponse;
 use codex_rmcp_client::OAuthCredentialsStoreMode;
 use codex_rmcp_client::RmcpClient;
@@ -61,6 +62,8 @@ use tokio_util::sync::CancellationToken;
 use tracing::instrument;
 use tracing::warn;
 
+use crate::auth_command::auth_command_token;
+use crate::auth_command::invalidate_auth_command_token;
 use crate::codex::INITIAL_SUBMIT_ID;
 use crate::config::types::McpServerConfig;
 use crate::config::types::McpServerTransportConfig;
@@ -839,7 +842,35 @@ async fn make_rmcp_client(
             http_headers,
             env_http_headers,
             bearer_token_env_var,
+            auth_command,
         } => {
+            if let Some(argv) = auth_command {
+                // The helper is asked again before every request so expired
+                // tokens are replaced, and re-run after the server answers 401.
+                let token_provider: BearerTokenProvider = Arc::new(move |force_refresh: bool| {
+                    let argv = argv.clone();
+                    async move {
+                        if force_refresh {
+                            invalidate_auth_command_token(&argv).await;
+                        }
+                        Ok::<_, anyhow::Error>(auth_command_token(&argv).await?)
+                    }
+                    .boxed()
+                });
+                return RmcpClient::new_streamable_http_client_with_token_provider(
+                    &url,
+                    token_provider,
+                    http_headers,
+                    env_http_headers,
+                )
+                .await
+                .map_err(|error| {
+                    StartupOutcomeError::from(anyhow!(
+                        "failed to get a token for MCP server '{server_name}': {error}"
+                    ))
+                });
+            }
+
             let resolved_bearer_token =
                 match resolve_bearer_token(server_name, bearer_token_env_var.as_deref()) {
                     Ok(token) => token,
@@ -1110,6 +1141,7 @@ mod tests {
                 transport: McpServerTransportConfig::StreamableHttp {
                     url: "https://api.githubcopilot.com/mcp/".to_string(),
                     bearer_token_env_var: None,
+                    auth_command: None,
                     http_headers: None,
                     env_http_headers: None,
                 },
@@ -1154,6 +1186,7 @@ mod tests {
                 transport: McpServerTransportConfig::StreamableHttp {
                     url: "https://example.com".to_string(),
                     bearer_token_env_var: Some("TOKEN".to_string()),
+                    auth_command: None,
                     http_headers: None,
                     env_http_headers: None,
                 },
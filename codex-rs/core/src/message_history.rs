@@ -5,9 +5,12 @@
 //! JSON-Lines tooling. Each record has the following schema:
 //!
 //! ````text
-//! {"conversation_id":"<uuid>","ts":<unix_seconds>,"text":"<message>"}
+//! {"conversation_id":"<uuid>","ts":<unix_seconds>,"text":"<message>","cwd":"<dir>"}
 //! ````
 //!
+//! `cwd` is the session's working directory when the message was sent; it is
+//! absent from entries written by older versions.
+//!
 //! To minimise the chance of interleaved writes when multiple processes are
 //! appending concurrently, callers should *prepare the full line* (record +
 //! trailing `\n`) and write it with a **single `write(2)` system call** while
@@ -56,6 +59,8 @@ pub struct HistoryEntry {
     pub session_id: String,
     pub ts: u64,
     pub text: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<PathBuf>,
 }
 
 fn history_filepath(config: &Config) -> PathBuf {
@@ -64,12 +69,13 @@ fn history_filepath(config: &Config) -> PathBuf {
     path
 }
 
-/// Append a `text` entry associated with `conversation_id` to the history file. Uses
-/// advisory file locking to ensure that concurrent writes do not interleave,
-/// which entails a small amount of blocking I/O internally.
+/// Append a `text` entry associated with `conversation_id` and the session's `cwd` to the
+/// history file. Uses advisory file locking to ensure that concurrent writes do not
+/// interleave, which entails a small amount of blocking I/O internally.
 pub(crate) async fn append_entry(
     text: &str,
     conversation_id: &ConversationId,
+    cwd: &Path,
     config: &Config,
 ) -> Result<()> {
     match config.history.persistence {
@@ -101,6 +107,7 @@ pub(crate) async fn append_entry(
         session_id: conversation_id.to_string(),
         ts,
         text: text.to_string(),
+        cwd: Some(cwd.to_path_buf()),
     };
     let mut line = serde_json::to_string(&entry)
         .map_err(|e| std::io::Error::other(format!("failed to serialise history entry: {e}")))?;
@@ -261,6 +268,14 @@ pub(crate) fn lookup(log_id: u64, offset: usize, config: &Config) -> Option<Hist
     lookup_history_entry(&path, log_id, offset)
 }
 
+/// Return every parseable entry in the history file, oldest first. Lines that fail to
+/// parse are skipped. Like [`lookup`], this is blocking and should run off the async
+/// runtime.
+pub(crate) fn list_entries(config: &Config) -> Vec<HistoryEntry> {
+    let path = history_filepath(config);
+    read_all_history_entries(&path)
+}
+
 /// On Unix systems, ensure the file permissions are `0o600` (rw-------). If the
 /// permissions cannot be changed the error is propagated to the caller.
 #[cfg(unix)]
@@ -312,6 +327,49 @@ async fn history_metadata_for_file(path: &Path) -> (u64, usize) {
     (log_id, count)
 }
 
+fn read_all_history_entries(path: &Path) -> Vec<HistoryEntry> {
+    let file = match File::open(path) {
+        Ok(f) => f,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Vec::new(),
+        Err(e) => {
+            tracing::warn!(error = %e, "failed to open history file");
+            return Vec::new();
+        }
+    };
+
+    for _ in 0..MAX_RETRIES {
+        match file.try_lock_shared() {
+            Ok(()) => {
+                return BufReader::new(&file)
+                    .lines()
+                    .map_while(|line| {
+                        line.map_err(|e| {
+                            tracing::warn!(error = %e, "failed to read line from history file");
+                        })
+                        .ok()
+                    })
+                    .filter_map(|line| match serde_json::from_str::<HistoryEntry>(&line) {
+                        Ok(entry) => Some(entry),
+                        Err(e) => {
+                            tracing::warn!(error = %e, "failed to parse history entry");
+                            None
+                        }
+                    })
+                    .collect();
+            }
+            Err(std::fs::TryLockError::WouldBlock) => {
+                std::thread::sleep(RETRY_SLEEP);
+            }
+            Err(e) => {
+                tracing::warn!(error = %e, "failed to acquire shared lock on history file");
+                return Vec::new();
+            }
+        }
+    }
+
+    Vec::new()
+}
+
 fn lookup_history_entry(path: &Path, log_id: u64, offset: usize) -> Option<HistoryEntry> {
     use std::io::BufRead;
     use std::io::BufReader;
@@ -418,11 +476,13 @@ mod tests {
                 session_id: "first-session".to_string(),
                 ts: 1,
                 text: "first".to_string(),
+                cwd: None,
             },
             HistoryEntry {
                 session_id: "second-session".to_string(),
                 ts: 2,
                 text: "second".to_string(),
+                cwd: Some(PathBuf::from("/repo")),
             },
         ];
 
@@ -444,6 +504,43 @@ mod tests {
         assert_eq!(second_entry, entries[1]);
     }
 
+    #[test]
+    fn read_all_history_entries_skips_unparseable_lines() {
+        let temp_dir = TempDir::new().expect("create temp dir");
+        let history_path = temp_dir.path().join(HISTORY_FILENAME);
+        std::fs::write(
+            &history_path,
+            concat!(
+                r#"{"session_id":"legacy","ts":1,"text":"no cwd"}"#,
+                "\n",
+                "not json\n",
+                r#"{"session_id":"new","ts":2,"text":"with cwd","cwd":"/repo"}"#,
+                "\n",
+            ),
+        )
+        .expect("write history file");
+
+        let entries = read_all_history_entries(&history_path);
+        assert_eq!(
+            entries,
+            vec![
+                HistoryEntry {
+                    session_id: "legacy".to_string(),
+                    ts: 1,
+                    text: "no cwd".to_string(),
+                    cwd: None,
+                },
+                HistoryEntry {
+                    session_id: "new".to_string(),
+                    ts: 2,
+                    text: "with cwd".to_string(),
+                    cwd: Some(PathBuf::from("/repo")),
+                },
+            ]
+        );
+        assert!(read_all_history_entries(&temp_dir.path().join("missing.jsonl")).is_empty());
+    }
+
     #[tokio::test]
     async fn lookup_uses_stable_log_id_after_appends() {
         let temp_dir = TempDir::new().expect("create temp dir");
@@ -453,11 +550,13 @@ mod tests {
             session_id: "first-session".to_string(),
             ts: 1,
             text: "first".to_string(),
+            cwd: None,
         };
         let appended = HistoryEntry {
             session_id: "second-session".to_string(),
             ts: 2,
             text: "second".to_string(),
+            cwd: None,
         };
 
         let mut file = File::create(&history_path).expect("create history file");
@@ -504,7 +603,7 @@ mod tests {
 
         let history_path = codex_home.path().join("history.jsonl");
 
-        append_entry(&entry_one, &conversation_id, &config)
+        append_entry(&entry_one, &conversation_id, &config.cwd, &config)
             .await
             .expect("write first entry");
 
@@ -514,7 +613,7 @@ mod tests {
         config.history.max_bytes =
             Some(usize::try_from(limit_bytes).expect("limit should fit into usize"));
 
-        append_entry(&entry_two, &conversation_id, &config)
+        append_entry(&entry_two, &conversation_id, &config.cwd, &config)
             .await
             .expect("write second entry");
 
@@ -551,13 +650,13 @@ mod tests {
 
         let history_path = codex_home.path().join("history.jsonl");
 
-        append_entry(&short_entry, &conversation_id, &config)
+        append_entry(&short_entry, &conversation_id, &config.cwd, &config)
             .await
             .expect("write first entry");
 
         let short_entry_len = std::fs::metadata(&history_path).expect("metadata").len();
 
-        append_entry(&long_entry, &conversation_id, &config)
+        append_entry(&long_entry, &conversation_id, &config.cwd, &config)
             .await
             .expect("write second entry");
 
@@ -572,7 +671,7 @@ mod tests {
                 .expect("max bytes should fit into usize"),
         );
 
-        append_entry(&long_entry, &conversation_id, &config)
+        append_entry(&long_entry, &conversation_id, &config.cwd, &config)
             .await
             .expect("write third entry");
 
@@ -45,6 +45,12 @@ pub enum WireApi {
     /// Regular Chat Completions compatible with `/v1/chat/completions`.
     #[default]
     Chat,
+
+    /// The Anthropic Messages API exposed at `/v1/messages`.
+    Messages,
+
+    /// The Google Gemini API (`models/<model>:streamGenerateContent`).
+    Gemini,
 }
 
 /// Serializable representation of a provider definition.
@@ -66,6 +72,12 @@ pub struct ModelProviderInfo {
     /// this may be necessary when using this programmatically.
     pub experimental_bearer_token: Option<String>,
 
+    /// Command (program followed by its arguments) that prints a bearer
+    /// token to stdout, optionally as JSON with an expiry. The token is
+    /// cached in memory and the command is run again when the token expires
+    /// or the provider rejects it with a 401.
+    pub auth_command: Option<Vec<String>>,
+
     /// Which wire protocol this provider expects.
     #[serde(default)]
     pub wire_api: WireApi,
@@ -101,6 +113,17 @@ pub struct ModelProviderInfo {
     pub requires_openai_auth: bool,
 }
 
+/// A provider, and optionally a different model, that requests fail over to
+/// when the primary provider is unavailable.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FallbackProvider {
+    /// Key of the provider in the `model_providers` map.
+    pub provider_id: String,
+    pub provider: ModelProviderInfo,
+    /// Model to request instead of the session model.
+    pub model: Option<String>,
+}
+
 impl ModelProviderInfo {
     fn build_header_map(&self) -> crate::error::Result<HeaderMap> {
         let mut headers = HeaderMap::new();
@@ -157,6 +180,8 @@ impl ModelProviderInfo {
             wire: match self.wire_api {
                 WireApi::Responses => ApiWireApi::Responses,
                 WireApi::Chat => ApiWireApi::Chat,
+                WireApi::Messages => ApiWireApi::Messages,
+                WireApi::Gemini => ApiWireApi::Gemini,
             },
             headers,
             retry,
@@ -224,6 +249,7 @@ impl ModelProviderInfo {
             env_key: None,
             env_key_instructions: None,
             experimental_bearer_token: None,
+            auth_command: None,
             wire_api: WireApi::Responses,
             query_params: None,
             http_headers: Some(
@@ -257,9 +283,13 @@ impl ModelProviderInfo {
 
 pub const DEFAULT_LMSTUDIO_PORT: u16 = 1234;
 pub const DEFAULT_OLLAMA_PORT: u16 = 11434;
+pub const DEFAULT_LLAMA_CPP_PORT: u16 = 8080;
+pub const DEFAULT_VLLM_PORT: u16 = 8000;
 
 pub const LMSTUDIO_OSS_PROVIDER_ID: &str = "lmstudio";
 pub const OLLAMA_OSS_PROVIDER_ID: &str = "ollama";
+pub const LLAMA_CPP_OSS_PROVIDER_ID: &str = "llamacpp";
+pub const VLLM_OSS_PROVIDER_ID: &str = "vllm";
 
 /// Built-in default provider list.
 pub fn built_in_model_providers() -> HashMap<String, ModelProviderInfo> {
@@ -279,6 +309,14 @@ pub fn built_in_model_providers() -> HashMap<String, ModelProviderInfo> {
             LMSTUDIO_OSS_PROVIDER_ID,
             create_oss_provider(DEFAULT_LMSTUDIO_PORT, WireApi::Responses),
         ),
+        (
+            LLAMA_CPP_OSS_PROVIDER_ID,
+            create_oss_provider(DEFAULT_LLAMA_CPP_PORT, WireApi::Chat),
+        ),
+        (
+            VLLM_OSS_PROVIDER_ID,
+            create_oss_provider(DEFAULT_VLLM_PORT, WireApi::Chat),
+        ),
     ]
     .into_iter()
     .map(|(k, v)| (k.to_string(), v))
@@ -312,6 +350,7 @@ pub fn create_oss_provider_with_base_url(base_url: &str, wire_api: WireApi) -> M
         env_key: None,
         env_key_instructions: None,
         experimental_bearer_token: None,
+        auth_command: None,
         wire_api,
         query_params: None,
         http_headers: None,
@@ -340,6 +379,7 @@ base_url = "http://localhost:11434/v1"
             env_key: None,
             env_key_instructions: None,
             experimental_bearer_token: None,
+            auth_command: None,
             wire_api: WireApi::Chat,
             query_params: None,
             http_headers: None,
@@ -368,6 +408,7 @@ query_params = { api-version = "2025-04-01-preview" }
             env_key: Some("AZURE_OPENAI_API_KEY".into()),
             env_key_instructions: None,
             experimental_bearer_token: None,
+            auth_command: None,
             wire_api: WireApi::Chat,
             query_params: Some(maplit::hashmap! {
                 "api-version".to_string() => "2025-04-01-preview".to_string(),
@@ -399,6 +440,7 @@ env_http_headers = { "X-Example-Env-Header" = "EXAMPLE_ENV_VAR" }
             env_key: Some("API_KEY".into()),
             env_key_instructions: None,
             experimental_bearer_token: None,
+            auth_command: None,
             wire_api: WireApi::Chat,
             query_params: None,
             http_headers: Some(maplit::hashmap! {
@@ -417,6 +459,35 @@ env_http_headers = { "X-Example-Env-Header" = "EXAMPLE_ENV_VAR" }
         assert_eq!(expected_provider, provider);
     }
 
+    #[test]
+    fn test_deserialize_messages_and_gemini_wire_apis() {
+        let anthropic: ModelProviderInfo = toml::from_str(
+            r#"
+name = "Anthropic"
+base_url = "https://api.anthropic.com/v1"
+env_key = "ANTHROPIC_API_KEY"
+wire_api = "messages"
+        "#,
+        )
+        .unwrap();
+        assert_eq!(anthropic.wire_api, WireApi::Messages);
+        assert_eq!(
+            anthropic.to_api_provider(None).expect("api provider").wire,
+            ApiWireApi::Messages
+        );
+
+        let gemini: ModelProviderInfo = toml::from_str(
+            r#"
+name = "Gemini"
+base_url = "https://generativelanguage.googleapis.com/v1beta"
+env_key = "GEMINI_API_KEY"
+wire_api = "gemini"
+        "#,
+        )
+        .unwrap();
+        assert_eq!(gemini.wire_api, WireApi::Gemini);
+    }
+
     #[test]
     fn detects_azure_responses_base_urls() {
         let positive_cases = [
@@ -434,6 +505,7 @@ env_http_headers = { "X-Example-Env-Header" = "EXAMPLE_ENV_VAR" }
                 env_key: None,
                 env_key_instructions: None,
                 experimental_bearer_token: None,
+                auth_command: None,
                 wire_api: WireApi::Responses,
                 query_params: None,
                 http_headers: None,
@@ -456,6 +528,7 @@ env_http_headers = { "X-Example-Env-Header" = "EXAMPLE_ENV_VAR" }
             env_key: None,
             env_key_instructions: None,
             experimental_bearer_token: None,
+            auth_command: None,
             wire_api: WireApi::Responses,
             query_params: None,
             http_headers: None,
@@ -480,6 +553,7 @@ env_http_headers = { "X-Example-Env-Header" = "EXAMPLE_ENV_VAR" }
                 env_key: None,
                 env_key_instructions: None,
                 experimental_bearer_token: None,
+                auth_command: None,
                 wire_api: WireApi::Responses,
                 query_params: None,
                 http_headers: None,
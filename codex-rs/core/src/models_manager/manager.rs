@@ -5,6 +5,7 @@ use codex_app_server_protocol::AuthMode;
 use codex_protocol::openai_models::ModelInfo;
 use codex_protocol::openai_models::ModelPreset;
 use codex_protocol::openai_models::ModelsResponse;
+use codex_protocol::openai_models::ReasoningEffort;
 use http::HeaderMap;
 use std::collections::HashSet;
 use std::path::PathBuf;
@@ -33,12 +34,24 @@ const OPENAI_DEFAULT_API_MODEL: &str = "gpt-5.1-codex-max";
 const OPENAI_DEFAULT_CHATGPT_MODEL: &str = "gpt-5.2-codex";
 const CODEX_AUTO_BALANCED_MODEL: &str = "codex-auto-balanced";
 
+/// Model advertised by a local OpenAI-compatible server such as llama.cpp or
+/// vLLM.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServedModel {
+    pub slug: String,
+    /// Context window reported by the server, when it exposes one.
+    pub context_window: Option<i64>,
+}
+
 /// Coordinates remote model discovery plus cached metadata on disk.
 #[derive(Debug)]
 pub struct ModelsManager {
     // todo(aibrahim) merge available_models and model family creation into one struct
     local_models: Vec<ModelPreset>,
     remote_models: RwLock<Vec<ModelInfo>>,
+    /// Models listed by the active local server. When non-empty they replace
+    /// the built-in presets, since a local server can only run what it serves.
+    served_models: RwLock<Vec<ServedModel>>,
     auth_manager: Arc<AuthManager>,
     etag: RwLock<Option<String>>,
     codex_home: PathBuf,
@@ -53,6 +66,7 @@ impl ModelsManager {
         Self {
             local_models: builtin_model_presets(auth_manager.get_auth_mode()),
             remote_models: RwLock::new(Self::load_remote_models_from_file().unwrap_or_default()),
+            served_models: RwLock::new(Vec::new()),
             auth_manager,
             etag: RwLock::new(None),
             codex_home,
@@ -68,6 +82,7 @@ impl ModelsManager {
         Self {
             local_models: builtin_model_presets(auth_manager.get_auth_mode()),
             remote_models: RwLock::new(Self::load_remote_models_from_file().unwrap_or_default()),
+            served_models: RwLock::new(Vec::new()),
             auth_manager,
             etag: RwLock::new(None),
             codex_home,
@@ -107,7 +122,17 @@ impl ModelsManager {
         Ok(())
     }
 
+    /// Replace the models advertised by the active local server.
+    pub async fn set_served_models(&self, models: Vec<ServedModel>) {
+        *self.served_models.write().await = models;
+    }
+
     pub async fn list_models(&self, config: &Config) -> Vec<ModelPreset> {
+        let served_models = self.served_models.read().await;
+        if !served_models.is_empty() {
+            return served_model_presets(&served_models);
+        }
+        drop(served_models);
         if let Err(err) = self.refresh_available_models(config).await {
             error!("failed to refresh available models: {err}");
         }
@@ -116,6 +141,10 @@ impl ModelsManager {
     }
 
     pub fn try_list_models(&self, config: &Config) -> Result<Vec<ModelPreset>, TryLockError> {
+        let served_models = self.served_models.try_read()?;
+        if !served_models.is_empty() {
+            return Ok(served_model_presets(&served_models));
+        }
         let remote_models = self.try_get_remote_models(config)?;
         Ok(self.build_available_models(remote_models))
     }
@@ -126,15 +155,28 @@ impl ModelsManager {
 
     /// Look up the requested model family while applying remote metadata overrides.
     pub async fn construct_model_family(&self, model: &str, config: &Config) -> ModelFamily {
-        Self::find_family_for_model(model)
-            .with_remote_overrides(self.remote_models(config).await)
-            .with_config_overrides(config)
+        let mut family = Self::find_family_for_model(model)
+            .with_remote_overrides(self.remote_models(config).await);
+        if let Some(context_window) = self
+            .served_models
+            .read()
+            .await
+            .iter()
+            .find(|served| served.slug == model)
+            .and_then(|served| served.context_window)
+        {
+            family.context_window = Some(context_window);
+        }
+        family.with_config_overrides(config)
     }
 
     pub async fn get_model(&self, model: &Option<String>, config: &Config) -> String {
         if let Some(model) = model.as_ref() {
             return model.to_string();
         }
+        if let Some(served) = self.served_models.read().await.first() {
+            return served.slug.clone();
+        }
         if let Err(err) = self.refresh_available_models(config).await {
             error!("failed to refresh available models: {err}");
         }
@@ -286,6 +328,30 @@ impl ModelsManager {
     }
 }
 
+/// Picker presets for models served by a local server. Reasoning effort is
+/// left to the server, so no effort options are offered.
+fn served_model_presets(models: &[ServedModel]) -> Vec<ModelPreset> {
+    models
+        .iter()
+        .enumerate()
+        .map(|(idx, served)| ModelPreset {
+            id: served.slug.clone(),
+            model: served.slug.clone(),
+            display_name: served.slug.clone(),
+            description: served
+                .context_window
+                .map(|window| format!("{window} token context"))
+                .unwrap_or_default(),
+            default_reasoning_effort: ReasoningEffort::default(),
+            supported_reasoning_efforts: Vec::new(),
+            is_default: idx == 0,
+            upgrade: None,
+            show_in_picker: true,
+            supported_in_api: true,
+        })
+        .collect()
+}
+
 /// Convert a client version string to a whole version string (e.g. "1.2.3-alpha.4" -> "1.2.3")
 fn format_client_version_to_whole() -> String {
     format_client_version_from_parts(
@@ -367,6 +433,7 @@ mod tests {
             env_key: None,
             env_key_instructions: None,
             experimental_bearer_token: None,
+            auth_command: None,
             wire_api: WireApi::Responses,
             query_params: None,
             http_headers: None,
@@ -657,6 +724,48 @@ mod tests {
         assert_eq!(available, vec![expected]);
     }
 
+    #[tokio::test]
+    async fn served_models_replace_presets_and_context_window() {
+        let codex_home = tempdir().expect("temp dir");
+        let config = ConfigBuilder::default()
+            .codex_home(codex_home.path().to_path_buf())
+            .build()
+            .await
+            .expect("load default test config");
+        let auth_manager =
+            AuthManager::from_auth_for_testing(CodexAuth::from_api_key("Test API Key"));
+        let provider = provider_for("http://example.test".to_string());
+        let manager = ModelsManager::with_provider(auth_manager, provider);
+
+        manager
+            .set_served_models(vec![
+                ServedModel {
+                    slug: "qwen2.5-coder-32b".to_string(),
+                    context_window: Some(32_768),
+                },
+                ServedModel {
+                    slug: "llama-3.1-8b".to_string(),
+                    context_window: None,
+                },
+            ])
+            .await;
+
+        let available = manager.list_models(&config).await;
+        assert_eq!(
+            available
+                .iter()
+                .map(|preset| (preset.model.as_str(), preset.is_default))
+                .collect::<Vec<_>>(),
+            vec![("qwen2.5-coder-32b", true), ("llama-3.1-8b", false)]
+        );
+        assert_eq!(manager.get_model(&None, &config).await, "qwen2.5-coder-32b");
+
+        let family = manager
+            .construct_model_family("qwen2.5-coder-32b", &config)
+            .await;
+        assert_eq!(family.context_window, Some(32_768));
+    }
+
     #[test]
     fn bundled_models_json_roundtrips() {
         let file_contents = include_str!("../../models.json");
@@ -0,0 +1,214 @@
+//! Session-wide state for failing over between the primary model provider
+//! and the `fallback_providers` configured for the active profile.
+
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use codex_client::ProviderHealth;
+
+use crate::config::Config;
+use crate::error::CodexErr;
+use crate::model_provider_info::FallbackProvider;
+use crate::models_manager::manager::ModelsManager;
+use crate::models_manager::model_family::ModelFamily;
+
+/// How long a provider that failed is skipped before it is tried again.
+pub(crate) const PROVIDER_FAILOVER_COOLDOWN: Duration = Duration::from_secs(5 * 60);
+
+/// Failover chain shared by every turn of a session. Target `0` is the
+/// session's own provider; target `n` is `fallbacks[n - 1]`.
+#[derive(Debug)]
+pub(crate) struct ProviderFailover {
+    fallbacks: Vec<FallbackProvider>,
+    /// Model family of each fallback that names its own model.
+    model_families: Vec<Option<ModelFamily>>,
+    health: ProviderHealth,
+    pending_warnings: Mutex<Vec<String>>,
+}
+
+impl ProviderFailover {
+    pub(crate) async fn from_config(
+        config: &Config,
+        models_manager: &ModelsManager,
+    ) -> Option<Arc<Self>> {
+        if config.fallback_providers.is_empty() {
+            return None;
+        }
+        let mut failover = Self::new(
+            config.fallback_providers.clone(),
+            PROVIDER_FAILOVER_COOLDOWN,
+        );
+        for fallback in &config.fallback_providers {
+            let family = match &fallback.model {
+                Some(model) => Some(models_manager.construct_model_family(model, config).await),
+                None => None,
+            };
+            failover.model_families.push(family);
+        }
+        Some(Arc::new(failover))
+    }
+
+    pub(crate) fn new(fallbacks: Vec<FallbackProvider>, cooldown: Duration) -> Self {
+        let targets = fallbacks.len() + 1;
+        Self {
+            fallbacks,
+            model_families: Vec::new(),
+            health: ProviderHealth::new(targets, cooldown),
+            pending_warnings: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub(crate) fn health(&self) -> &ProviderHealth {
+        &self.health
+    }
+
+    /// Returns the fallback for `target`, or `None` for the primary provider.
+    pub(crate) fn fallback(&self, target: usize) -> Option<&FallbackProvider> {
+        target
+            .checked_sub(1)
+            .and_then(|idx| self.fallbacks.get(idx))
+    }
+
+    /// Returns the model family to use for `target` when its fallback names a
+    /// different model than the session's.
+    pub(crate) fn model_family(&self, target: usize) -> Option<&ModelFamily> {
+        target
+            .checked_sub(1)
+            .and_then(|idx| self.model_families.get(idx))
+            .and_then(Option::as_ref)
+    }
+
+    pub(crate) fn push_warning(&self, message: String) {
+        self.pending_warnings
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .push(message);
+    }
+
+    /// Drains warnings about provider switches so the session can surface them.
+    pub(crate) fn take_warnings(&self) -> Vec<String> {
+        std::mem::take(
+            &mut *self
+                .pending_warnings
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner),
+        )
+    }
+}
+
+/// Whether `err` indicates the provider is unavailable (5xx, 429 or a
+/// timeout/connection failure) rather than a problem with the request.
+pub(crate) fn is_provider_unavailable(err: &CodexErr) -> bool {
+    match err {
+        CodexErr::UnexpectedStatus(err) => {
+            err.status.is_server_error() || err.status == http::StatusCode::TOO_MANY_REQUESTS
+        }
+        CodexErr::RetryLimit(_)
+        | CodexErr::InternalServerError
+        | CodexErr::Timeout
+        | CodexErr::Stream(..)
+        | CodexErr::ConnectionFailed(_)
+        | CodexErr::ResponseStreamFailed(_) => true,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AuthManager;
+    use crate::CodexAuth;
+    use crate::config::ConfigBuilder;
+    use crate::error::UnexpectedResponseError;
+    use crate::model_provider_info::built_in_model_providers;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    #[test]
+    fn classifies_unavailable_providers() {
+        let status = |status| {
+            CodexErr::UnexpectedStatus(UnexpectedResponseError {
+                status,
+                body: String::new(),
+                request_id: None,
+            })
+        };
+        assert!(is_provider_unavailable(&status(
+            http::StatusCode::SERVICE_UNAVAILABLE
+        )));
+        assert!(is_provider_unavailable(&status(
+            http::StatusCode::TOO_MANY_REQUESTS
+        )));
+        assert!(is_provider_unavailable(&CodexErr::Timeout));
+        assert!(!is_provider_unavailable(&status(
+            http::StatusCode::BAD_REQUEST
+        )));
+        assert!(!is_provider_unavailable(&CodexErr::ContextWindowExceeded));
+    }
+
+    #[test]
+    fn maps_targets_to_fallbacks() {
+        let ollama = built_in_model_providers()
+            .remove("ollama")
+            .expect("ollama provider");
+        let failover = ProviderFailover::new(
+            vec![FallbackProvider {
+                provider_id: "ollama".to_string(),
+                provider: ollama,
+                model: None,
+            }],
+            Duration::from_secs(1),
+        );
+
+        assert!(failover.fallback(0).is_none());
+        assert_eq!(
+            failover.fallback(1).map(|f| f.provider_id.as_str()),
+            Some("ollama")
+        );
+        assert_eq!(failover.health().candidates(), vec![0, 1]);
+
+        failover.push_warning("switched".to_string());
+        assert_eq!(failover.take_warnings(), vec!["switched".to_string()]);
+        assert!(failover.take_warnings().is_empty());
+    }
+
+    #[tokio::test]
+    async fn derives_model_family_for_fallback_models() {
+        let codex_home = tempdir().expect("temp dir");
+        let mut config = ConfigBuilder::default()
+            .codex_home(codex_home.path().to_path_buf())
+            .build()
+            .await
+            .expect("load default test config");
+        let ollama = built_in_model_providers()
+            .remove("ollama")
+            .expect("ollama provider");
+        config.fallback_providers = vec![
+            FallbackProvider {
+                provider_id: "ollama".to_string(),
+                provider: ollama.clone(),
+                model: Some("gpt-oss:20b".to_string()),
+            },
+            FallbackProvider {
+                provider_id: "ollama".to_string(),
+                provider: ollama,
+                model: None,
+            },
+        ];
+        let models_manager = ModelsManager::new(AuthManager::from_auth_for_testing(
+            CodexAuth::from_api_key("Test API Key"),
+        ));
+
+        let failover = ProviderFailover::from_config(&config, &models_manager)
+            .await
+            .expect("fallbacks are configured");
+
+        let expected = models_manager
+            .construct_model_family("gpt-oss:20b", &config)
+            .await;
+        assert_eq!(failover.model_family(1), Some(&expected));
+        assert!(failover.model_family(0).is_none());
+        assert!(failover.model_family(2).is_none());
+    }
+}
@@ -0,0 +1,211 @@
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::bash::parse_shell_lc_plain_commands;
+use crate::skills::SkillMetadata;
+use crate::tools::sandboxing::ExecApprovalRequirement;
+use codex_protocol::openai_models::ReasoningEffort;
+
+/// Interpreters that may be used to launch a bundled script (`python3 scripts/run.py`).
+const SCRIPT_INTERPRETERS: &[&str] = &[
+    "sh", "bash", "zsh", "python", "python3", "node", "ruby", "perl",
+];
+
+/// Restrictions and preferences contributed by the skills mentioned in a turn.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct ActiveSkills {
+    skills: Vec<SkillMetadata>,
+}
+
+impl ActiveSkills {
+    pub(crate) fn new(skills: Vec<SkillMetadata>) -> Self {
+        Self { skills }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.skills.is_empty()
+    }
+
+    /// The first model preference among the active skills.
+    pub(crate) fn model(&self) -> Option<&str> {
+        self.skills.iter().find_map(|skill| skill.model.as_deref())
+    }
+
+    /// The first reasoning effort preference among the active skills.
+    pub(crate) fn reasoning_effort(&self) -> Option<ReasoningEffort> {
+        self.skills.iter().find_map(|skill| skill.reasoning_effort)
+    }
+
+    /// Tool name patterns the model is limited to, or `None` when unrestricted.
+    ///
+    /// The tool set is only restricted when every active skill declares `allowed_tools`; the
+    /// result is the union of their lists.
+    pub(crate) fn allowed_tools(&self) -> Option<Vec<String>> {
+        if self.skills.is_empty() {
+            return None;
+        }
+        let mut allowed: Vec<String> = Vec::new();
+        for skill in &self.skills {
+            for tool in skill.allowed_tools.as_ref()? {
+                if !allowed.contains(tool) {
+                    allowed.push(tool.clone());
+                }
+            }
+        }
+        Some(allowed)
+    }
+
+    /// Lets bundled scripts of the active skills run without asking for approval. Forbidden
+    /// commands stay forbidden and sandboxing is unchanged.
+    pub(crate) fn exempt_bundled_scripts(
+        &self,
+        command: &[String],
+        cwd: &Path,
+        requirement: ExecApprovalRequirement,
+    ) -> ExecApprovalRequirement {
+        match requirement {
+            ExecApprovalRequirement::NeedsApproval { .. }
+                if self.is_bundled_script_command(command, cwd) =>
+            {
+                ExecApprovalRequirement::Skip {
+                    bypass_sandbox: false,
+                    proposed_execpolicy_amendment: None,
+                }
+            }
+            requirement => requirement,
+        }
+    }
+
+    fn is_bundled_script_command(&self, command: &[String], cwd: &Path) -> bool {
+        if self.skills.iter().all(|skill| skill.scripts.is_empty()) {
+            return false;
+        }
+        let commands =
+            parse_shell_lc_plain_commands(command).unwrap_or_else(|| vec![command.to_vec()]);
+        !commands.is_empty()
+            && commands
+                .iter()
+                .all(|command| self.invokes_bundled_script(command, cwd))
+    }
+
+    fn invokes_bundled_script(&self, command: &[String], cwd: &Path) -> bool {
+        let script = match command {
+            [program, script, ..] if SCRIPT_INTERPRETERS.contains(&program.as_str()) => script,
+            [program, ..] => program,
+            [] => return false,
+        };
+        let script_path = resolve_script_path(script, cwd);
+        self.skills
+            .iter()
+            .flat_map(|skill| skill.scripts.iter())
+            .any(|bundled| *bundled == script_path)
+    }
+}
+
+fn resolve_script_path(script: &str, cwd: &Path) -> PathBuf {
+    let path = cwd.join(script);
+    dunce::canonicalize(&path).unwrap_or(path)
+}
+
+/// Matches a tool name against an `allowed_tools` entry; a trailing `*` matches any suffix.
+pub(crate) fn tool_name_matches(pattern: &str, tool_name: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => tool_name.starts_with(prefix),
+        None => pattern == tool_name,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::protocol::SkillScope;
+    use pretty_assertions::assert_eq;
+    use std::fs;
+
+    fn skill(name: &str, allowed_tools: Option<&[&str]>, scripts: Vec<PathBuf>) -> SkillMetadata {
+        SkillMetadata {
+            name: name.to_string(),
+            description: "desc".to_string(),
+            short_description: None,
+            path: PathBuf::from(format!("/skills/{name}/SKILL.md")),
+            scope: SkillScope::User,
+            allowed_tools: allowed_tools
+                .map(|tools| tools.iter().map(ToString::to_string).collect()),
+            model: None,
+            reasoning_effort: None,
+            required_mcp_servers: Vec::new(),
+            scripts,
+        }
+    }
+
+    fn needs_approval() -> ExecApprovalRequirement {
+        ExecApprovalRequirement::NeedsApproval {
+            reason: None,
+            proposed_execpolicy_amendment: None,
+        }
+    }
+
+    #[test]
+    fn allowed_tools_is_union_when_every_skill_restricts() {
+        let active = ActiveSkills::new(vec![
+            skill("a", Some(&["shell", "read_file"]), Vec::new()),
+            skill("b", Some(&["shell", "mcp__docs__*"]), Vec::new()),
+        ]);
+        assert_eq!(
+            Some(vec![
+                "shell".to_string(),
+                "read_file".to_string(),
+                "mcp__docs__*".to_string(),
+            ]),
+            active.allowed_tools()
+        );
+
+        let unrestricted = ActiveSkills::new(vec![
+            skill("a", Some(&["shell"]), Vec::new()),
+            skill("b", None, Vec::new()),
+        ]);
+        assert_eq!(None, unrestricted.allowed_tools());
+    }
+
+    #[test]
+    fn tool_name_patterns_support_trailing_wildcard() {
+        assert!(tool_name_matches("shell", "shell"));
+        assert!(!tool_name_matches("shell", "shell_command"));
+        assert!(tool_name_matches("mcp__docs__*", "mcp__docs__search"));
+        assert!(!tool_name_matches("mcp__docs__*", "mcp__other__search"));
+    }
+
+    #[test]
+    fn bundled_scripts_skip_approval() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let scripts_dir = dir.path().join("scripts");
+        fs::create_dir_all(&scripts_dir).expect("create scripts dir");
+        let script = scripts_dir.join("build.sh");
+        fs::write(&script, "#!/bin/sh\n").expect("write script");
+        let script = dunce::canonicalize(&script).expect("canonicalize script");
+        let active = ActiveSkills::new(vec![skill("a", None, vec![script])]);
+
+        let command = vec!["bash".to_string(), "scripts/build.sh".to_string()];
+        assert_eq!(
+            ExecApprovalRequirement::Skip {
+                bypass_sandbox: false,
+                proposed_execpolicy_amendment: None,
+            },
+            active.exempt_bundled_scripts(&command, dir.path(), needs_approval())
+        );
+
+        let other = vec!["rm".to_string(), "-rf".to_string(), "scripts".to_string()];
+        assert_eq!(
+            needs_approval(),
+            active.exempt_bundled_scripts(&other, dir.path(), needs_approval())
+        );
+
+        let forbidden = ExecApprovalRequirement::Forbidden {
+            reason: "no".to_string(),
+        };
+        assert_eq!(
+            forbidden.clone(),
+            active.exempt_bundled_scripts(&command, dir.path(), forbidden)
+        );
+    }
+}
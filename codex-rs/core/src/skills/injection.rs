@@ -11,11 +11,14 @@ use tokio::fs;
 pub(crate) struct SkillInjections {
     pub(crate) items: Vec<ResponseItem>,
     pub(crate) warnings: Vec<String>,
+    /// Skills whose instructions were injected and whose restrictions apply to the turn.
+    pub(crate) active: Vec<SkillMetadata>,
 }
 
 pub(crate) async fn build_skill_injections(
     inputs: &[UserInput],
     skills: Option<&SkillLoadOutcome>,
+    available_mcp_servers: &HashSet<String>,
 ) -> SkillInjections {
     if inputs.is_empty() {
         return SkillInjections::default();
@@ -33,16 +36,33 @@ pub(crate) async fn build_skill_injections(
     let mut result = SkillInjections {
         items: Vec::with_capacity(mentioned_skills.len()),
         warnings: Vec::new(),
+        active: Vec::with_capacity(mentioned_skills.len()),
     };
 
     for skill in mentioned_skills {
+        let missing_servers: Vec<&str> = skill
+            .required_mcp_servers
+            .iter()
+            .filter(|server| !available_mcp_servers.contains(server.as_str()))
+            .map(String::as_str)
+            .collect();
+        if !missing_servers.is_empty() {
+            result.warnings.push(format!(
+                "Skill {} requires unavailable MCP server(s): {}",
+                skill.name,
+                missing_servers.join(", ")
+            ));
+            continue;
+        }
+
         match fs::read_to_string(&skill.path).await {
             Ok(contents) => {
                 result.items.push(ResponseItem::from(SkillInstructions {
-                    name: skill.name,
+                    name: skill.name.clone(),
                     path: skill.path.to_string_lossy().into_owned(),
                     contents,
                 }));
+                result.active.push(skill);
             }
             Err(err) => {
                 let message = format!(
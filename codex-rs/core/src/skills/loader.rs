@@ -4,6 +4,7 @@ use crate::skills::model::SkillError;
 use crate::skills::model::SkillLoadOutcome;
 use crate::skills::model::SkillMetadata;
 use crate::skills::system::system_cache_root_dir;
+use codex_protocol::openai_models::ReasoningEffort;
 use codex_protocol::protocol::SkillScope;
 use dunce::canonicalize as normalize_path;
 use serde::Deserialize;
@@ -22,6 +23,16 @@ struct SkillFrontmatter {
     description: String,
     #[serde(default)]
     metadata: SkillFrontmatterMetadata,
+    #[serde(default, alias = "allowed-tools")]
+    allowed_tools: Option<Vec<String>>,
+    #[serde(default)]
+    model: Option<String>,
+    #[serde(default, alias = "reasoning-effort")]
+    reasoning_effort: Option<ReasoningEffort>,
+    #[serde(default, alias = "required-mcp-servers")]
+    required_mcp_servers: Vec<String>,
+    #[serde(default)]
+    scripts: Vec<String>,
 }
 
 #[derive(Debug, Default, Deserialize)]
@@ -37,6 +48,7 @@ const ADMIN_SKILLS_ROOT: &str = "/etc/codex/skills";
 const MAX_NAME_LEN: usize = 64;
 const MAX_DESCRIPTION_LEN: usize = 1024;
 const MAX_SHORT_DESCRIPTION_LEN: usize = MAX_DESCRIPTION_LEN;
+const MAX_MODEL_LEN: usize = MAX_NAME_LEN * 2;
 
 #[derive(Debug)]
 enum SkillParseError {
@@ -259,7 +271,23 @@ fn parse_skill_file(path: &Path, scope: SkillScope) -> Result<SkillMetadata, Ski
         )?;
     }
 
+    let allowed_tools = parsed
+        .allowed_tools
+        .map(|tools| validate_names(tools, "allowed_tools"))
+        .transpose()?;
+    let model = parsed.model.as_deref().map(sanitize_single_line);
+    if let Some(model) = model.as_deref() {
+        validate_field(model, MAX_MODEL_LEN, "model")?;
+    }
+    let required_mcp_servers = validate_names(parsed.required_mcp_servers, "required_mcp_servers")?;
+
     let resolved_path = normalize_path(path).unwrap_or_else(|_| path.to_path_buf());
+    let skill_dir = resolved_path.parent().unwrap_or(&resolved_path);
+    let scripts = parsed
+        .scripts
+        .iter()
+        .map(|script| resolve_bundled_script(skill_dir, script))
+        .collect::<Result<Vec<_>, _>>()?;
 
     Ok(SkillMetadata {
         name,
@@ -267,9 +295,63 @@ fn parse_skill_file(path: &Path, scope: SkillScope) -> Result<SkillMetadata, Ski
         short_description,
         path: resolved_path,
         scope,
+        allowed_tools,
+        model,
+        reasoning_effort: parsed.reasoning_effort,
+        required_mcp_servers,
+        scripts,
     })
 }
 
+fn validate_names(
+    names: Vec<String>,
+    field_name: &'static str,
+) -> Result<Vec<String>, SkillParseError> {
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut validated = Vec::with_capacity(names.len());
+    for name in names {
+        let name = name.trim().to_string();
+        if name.is_empty() || name.chars().any(char::is_whitespace) {
+            return Err(SkillParseError::InvalidField {
+                field: field_name,
+                reason: format!("`{name}` must be a non-empty name without whitespace"),
+            });
+        }
+        if !seen.insert(name.clone()) {
+            return Err(SkillParseError::InvalidField {
+                field: field_name,
+                reason: format!("`{name}` is listed more than once"),
+            });
+        }
+        validated.push(name);
+    }
+    Ok(validated)
+}
+
+/// Bundled scripts are relative paths to files inside the skill directory.
+fn resolve_bundled_script(skill_dir: &Path, script: &str) -> Result<PathBuf, SkillParseError> {
+    let invalid = |reason: String| SkillParseError::InvalidField {
+        field: "scripts",
+        reason,
+    };
+    if Path::new(script).is_absolute() {
+        return Err(invalid(format!(
+            "`{script}` must be relative to the skill directory"
+        )));
+    }
+    let resolved = normalize_path(skill_dir.join(script))
+        .map_err(|err| invalid(format!("`{script}` cannot be resolved: {err}")))?;
+    if !resolved.starts_with(skill_dir) {
+        return Err(invalid(format!(
+            "`{script}` must stay inside the skill directory"
+        )));
+    }
+    if !resolved.is_file() {
+        return Err(invalid(format!("`{script}` is not a file")));
+    }
+    Ok(resolved)
+}
+
 fn sanitize_single_line(raw: &str) -> String {
     raw.split_whitespace().collect::<Vec<_>>().join(" ")
 }
@@ -435,6 +517,79 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn loads_tool_restrictions_and_bundled_scripts() {
+        let codex_home = tempfile::tempdir().expect("tempdir");
+        let skill_dir = codex_home.path().join("skills/demo");
+        fs::create_dir_all(skill_dir.join("scripts")).unwrap();
+        fs::write(skill_dir.join("scripts/build.sh"), "#!/bin/sh\n").unwrap();
+        let contents = "---\nname: demo-skill\ndescription: long description\nallowed_tools:\n  - shell\n  - mcp__docs__*\nmodel: gpt-5.1-codex\nreasoning_effort: high\nrequired_mcp_servers: [docs]\nscripts:\n  - scripts/build.sh\n---\n\n# Body\n";
+        fs::write(skill_dir.join(SKILLS_FILENAME), contents).unwrap();
+
+        let cfg = make_config(&codex_home).await;
+        let outcome = load_skills(&cfg);
+        assert!(
+            outcome.errors.is_empty(),
+            "unexpected errors: {:?}",
+            outcome.errors
+        );
+        assert_eq!(outcome.skills.len(), 1);
+        let skill = &outcome.skills[0];
+        assert_eq!(
+            skill.allowed_tools,
+            Some(vec!["shell".to_string(), "mcp__docs__*".to_string()])
+        );
+        assert_eq!(skill.model, Some("gpt-5.1-codex".to_string()));
+        assert_eq!(skill.reasoning_effort, Some(ReasoningEffort::High));
+        assert_eq!(skill.required_mcp_servers, vec!["docs".to_string()]);
+        assert_eq!(
+            skill.scripts,
+            vec![normalize_path(skill_dir.join("scripts/build.sh")).unwrap()]
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_scripts_outside_skill_dir() {
+        let codex_home = tempfile::tempdir().expect("tempdir");
+        let skill_dir = codex_home.path().join("skills/demo");
+        fs::create_dir_all(&skill_dir).unwrap();
+        fs::write(codex_home.path().join("skills/outside.sh"), "#!/bin/sh\n").unwrap();
+        let contents =
+            "---\nname: demo-skill\ndescription: long description\nscripts: [../outside.sh]\n---\n";
+        fs::write(skill_dir.join(SKILLS_FILENAME), contents).unwrap();
+
+        let cfg = make_config(&codex_home).await;
+        let outcome = load_skills(&cfg);
+        assert_eq!(outcome.skills.len(), 0);
+        assert_eq!(outcome.errors.len(), 1);
+        assert!(
+            outcome.errors[0]
+                .message
+                .contains("must stay inside the skill directory"),
+            "expected scripts error, got: {:?}",
+            outcome.errors
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_duplicate_allowed_tools() {
+        let codex_home = tempfile::tempdir().expect("tempdir");
+        let skill_dir = codex_home.path().join("skills/demo");
+        fs::create_dir_all(&skill_dir).unwrap();
+        let contents = "---\nname: demo-skill\ndescription: long description\nallowed_tools: [shell, shell]\n---\n";
+        fs::write(skill_dir.join(SKILLS_FILENAME), contents).unwrap();
+
+        let cfg = make_config(&codex_home).await;
+        let outcome = load_skills(&cfg);
+        assert_eq!(outcome.skills.len(), 0);
+        assert_eq!(outcome.errors.len(), 1);
+        assert!(
+            outcome.errors[0].message.contains("invalid allowed_tools"),
+            "expected allowed_tools error, got: {:?}",
+            outcome.errors
+        );
+    }
+
     #[tokio::test]
     async fn skips_hidden_and_invalid() {
         let codex_home = tempfile::tempdir().expect("tempdir");
@@ -1,3 +1,4 @@
+pub mod active;
 pub mod injection;
 pub mod loader;
 pub mod manager;
@@ -5,6 +6,7 @@ pub mod model;
 pub mod render;
 pub mod system;
 
+pub(crate) use active::ActiveSkills;
 pub(crate) use injection::SkillInjections;
 pub(crate) use injection::build_skill_injections;
 pub use loader::load_skills;
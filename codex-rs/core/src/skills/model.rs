@@ -1,5 +1,6 @@
 use std::path::PathBuf;
 
+use codex_protocol::openai_models::ReasoningEffort;
 use codex_protocol::protocol::SkillScope;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -9,6 +10,17 @@ pub struct SkillMetadata {
     pub short_description: Option<String>,
     pub path: PathBuf,
     pub scope: SkillScope,
+    /// Tool names (or `prefix*` patterns) the model may call while the skill is active.
+    /// `None` leaves the tool set unrestricted.
+    pub allowed_tools: Option<Vec<String>>,
+    /// Model to run turns with while the skill is active.
+    pub model: Option<String>,
+    /// Reasoning effort to run turns with while the skill is active.
+    pub reasoning_effort: Option<ReasoningEffort>,
+    /// MCP servers that must be available for the skill to activate.
+    pub required_mcp_servers: Vec<String>,
+    /// Absolute paths of scripts bundled with the skill that may run without approval.
+    pub scripts: Vec<PathBuf>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
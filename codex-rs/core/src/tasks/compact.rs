@@ -5,6 +5,7 @@ use super::SessionTaskContext;
 use crate::codex::TurnContext;
 use crate::state::TaskKind;
 use async_trait::async_trait;
+use codex_otel::otel_manager::CompactionTrigger;
 use codex_protocol::user_input::UserInput;
 use tokio_util::sync::CancellationToken;
 
@@ -25,6 +26,9 @@ impl SessionTask for CompactTask {
         _cancellation_token: CancellationToken,
     ) -> Option<String> {
         let session = session.clone_session();
+        ctx.client
+            .get_otel_manager()
+            .compaction(CompactionTrigger::Manual);
         if crate::compact::should_use_remote_compact_task(
             session.as_ref(),
             &ctx.client.get_provider(),
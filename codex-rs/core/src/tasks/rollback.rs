@@ -0,0 +1,122 @@
+use std::sync::Arc;
+
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::protocol::CodexErrorInfo;
+use crate::protocol::ErrorEvent;
+use crate::protocol::EventMsg;
+use crate::protocol::ThreadRolledBackEvent;
+use crate::state::TaskKind;
+use crate::tasks::SessionTask;
+use crate::tasks::SessionTaskContext;
+use async_trait::async_trait;
+use codex_git::RestoreGhostCommitOptions;
+use codex_git::restore_ghost_commit_with_options;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::user_input::UserInput;
+use tokio_util::sync::CancellationToken;
+use tracing::info;
+use tracing::warn;
+
+/// Drops the trailing user turns from the session history and, optionally,
+/// restores the working tree to the ghost snapshot captured before the
+/// earliest dropped turn.
+pub(crate) struct RollbackTask {
+    num_turns: u32,
+    restore_snapshots: bool,
+}
+
+impl RollbackTask {
+    pub(crate) fn new(num_turns: u32, restore_snapshots: bool) -> Self {
+        Self {
+            num_turns,
+            restore_snapshots,
+        }
+    }
+}
+
+#[async_trait]
+impl SessionTask for RollbackTask {
+    fn kind(&self) -> TaskKind {
+        TaskKind::Regular
+    }
+
+    async fn run(
+        self: Arc<Self>,
+        session: Arc<SessionTaskContext>,
+        ctx: Arc<TurnContext>,
+        _input: Vec<UserInput>,
+        cancellation_token: CancellationToken,
+    ) -> Option<String> {
+        let sess = session.clone_session();
+        if cancellation_token.is_cancelled() {
+            send_rollback_error(&sess, ctx.as_ref(), "Rollback cancelled.".to_string()).await;
+            return None;
+        }
+
+        let mut history = sess.clone_history().await;
+        let Some(removed) = history.drop_last_user_turns(self.num_turns as usize) else {
+            send_rollback_error(
+                &sess,
+                ctx.as_ref(),
+                format!(
+                    "Cannot roll back {} turn(s): the thread does not have that many turns.",
+                    self.num_turns
+                ),
+            )
+            .await;
+            return None;
+        };
+
+        if self.restore_snapshots
+            && let Some(ghost_commit) = removed.into_iter().find_map(|item| match item {
+                ResponseItem::GhostSnapshot { ghost_commit } => Some(ghost_commit),
+                _ => None,
+            })
+        {
+            let commit_id = ghost_commit.id().to_string();
+            let repo_path = ctx.cwd.clone();
+            let ghost_snapshot = ctx.ghost_snapshot.clone();
+            let restore_result = tokio::task::spawn_blocking(move || {
+                let options =
+                    RestoreGhostCommitOptions::new(&repo_path).ghost_snapshot(ghost_snapshot);
+                restore_ghost_commit_with_options(&options, &ghost_commit)
+            })
+            .await;
+
+            let error = match restore_result {
+                Ok(Ok(())) => None,
+                Ok(Err(err)) => Some(err.to_string()),
+                Err(err) => Some(err.to_string()),
+            };
+            if let Some(err) = error {
+                let message = format!("Failed to restore snapshot {commit_id}: {err}");
+                warn!("{message}");
+                send_rollback_error(&sess, ctx.as_ref(), message).await;
+                return None;
+            }
+            info!(commit_id = commit_id, "Rollback restored ghost snapshot");
+        }
+
+        sess.replace_history(history.get_history()).await;
+        sess.send_event(
+            ctx.as_ref(),
+            EventMsg::ThreadRolledBack(ThreadRolledBackEvent {
+                num_turns: self.num_turns,
+            }),
+        )
+        .await;
+        None
+    }
+}
+
+async fn send_rollback_error(sess: &Session, ctx: &TurnContext, message: String) {
+    sess.send_event(
+        ctx,
+        EventMsg::Error(ErrorEvent {
+            message,
+            codex_error_info: Some(CodexErrorInfo::ThreadRollbackFailed),
+        }),
+    )
+    .await;
+}
@@ -261,6 +261,11 @@ impl ShellHandler {
             exec_params.sandbox_permissions,
         )
         .await;
+        let exec_approval_requirement = turn.active_skills.exempt_bundled_scripts(
+            &exec_params.command,
+            &exec_params.cwd,
+            exec_approval_requirement,
+        );
 
         let req = ShellRequest {
             command: exec_params.command.clone(),
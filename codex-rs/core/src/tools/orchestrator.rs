@@ -19,6 +19,7 @@ use crate::tools::sandboxing::ToolRuntime;
 use crate::tools::sandboxing::default_exec_approval_requirement;
 use codex_protocol::protocol::AskForApproval;
 use codex_protocol::protocol::ReviewDecision;
+use std::time::Instant;
 
 pub(crate) struct ToolOrchestrator {
     sandbox: SandboxManager,
@@ -68,12 +69,17 @@ impl ToolOrchestrator {
                     call_id: &tool_ctx.call_id,
                     retry_reason: reason,
                 };
+                let approval_start = Instant::now();
                 let decision = tool.start_approval_async(req, approval_ctx).await;
 
                 otel.tool_decision(otel_tn, otel_ci, &decision, otel_user.clone());
+                otel.approval_wait(otel_tn, &decision, approval_start.elapsed());
 
                 match decision {
-                    ReviewDecision::Denied | ReviewDecision::Abort => {
+                    // A file subset only applies to patch approvals, never to a command.
+                    ReviewDecision::Denied
+                    | ReviewDecision::Abort
+                    | ReviewDecision::ApprovedSubset { .. } => {
                         return Err(ToolError::Rejected("rejected by user".to_string()));
                     }
                     ReviewDecision::Approved
@@ -131,11 +137,16 @@ impl ToolOrchestrator {
                         retry_reason: Some(reason_msg),
                     };
 
+                    let approval_start = Instant::now();
                     let decision = tool.start_approval_async(req, approval_ctx).await;
                     otel.tool_decision(otel_tn, otel_ci, &decision, otel_user);
+                    otel.approval_wait(otel_tn, &decision, approval_start.elapsed());
 
                     match decision {
-                        ReviewDecision::Denied | ReviewDecision::Abort => {
+                        // A file subset only applies to patch approvals, never to a command.
+                        ReviewDecision::Denied
+                        | ReviewDecision::Abort
+                        | ReviewDecision::ApprovedSubset { .. } => {
                             return Err(ToolError::Rejected("rejected by user".to_string()));
                         }
                         ReviewDecision::Approved
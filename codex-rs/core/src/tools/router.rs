@@ -3,6 +3,7 @@ use crate::codex::Session;
 use crate::codex::TurnContext;
 use crate::function_tool::FunctionCallError;
 use crate::sandboxing::SandboxPermissions;
+use crate::skills::active::tool_name_matches;
 use crate::tools::context::SharedTurnDiffTracker;
 use crate::tools::context::ToolInvocation;
 use crate::tools::context::ToolPayload;
@@ -28,6 +29,8 @@ pub struct ToolCall {
 pub struct ToolRouter {
     registry: ToolRegistry,
     specs: Vec<ConfiguredToolSpec>,
+    /// Tool name patterns the model is limited to while a restricting skill is active.
+    allowed_tools: Option<Vec<String>>,
 }
 
 impl ToolRouter {
@@ -38,16 +41,35 @@ impl ToolRouter {
         let builder = build_specs(config, mcp_tools);
         let (specs, registry) = builder.build();
 
-        Self { registry, specs }
+        Self {
+            registry,
+            specs,
+            allowed_tools: None,
+        }
+    }
+
+    /// Hide and refuse every tool that matches none of `allowed_tools`.
+    pub fn with_allowed_tools(mut self, allowed_tools: Option<Vec<String>>) -> Self {
+        self.allowed_tools = allowed_tools;
+        self
     }
 
     pub fn specs(&self) -> Vec<ToolSpec> {
         self.specs
             .iter()
+            .filter(|config| self.is_tool_allowed(config.spec.name()))
             .map(|config| config.spec.clone())
             .collect()
     }
 
+    fn is_tool_allowed(&self, tool_name: &str) -> bool {
+        self.allowed_tools.as_ref().is_none_or(|allowed| {
+            allowed
+                .iter()
+                .any(|pattern| tool_name_matches(pattern, tool_name))
+        })
+    }
+
     pub fn tool_supports_parallel(&self, tool_name: &str) -> bool {
         self.specs
             .iter()
@@ -142,6 +164,16 @@ impl ToolRouter {
         let payload_outputs_custom = matches!(payload, ToolPayload::Custom { .. });
         let failure_call_id = call_id.clone();
 
+        if !self.is_tool_allowed(&tool_name) {
+            return Ok(Self::failure_response(
+                failure_call_id,
+                payload_outputs_custom,
+                FunctionCallError::RespondToModel(format!(
+                    "tool `{tool_name}` is not allowed by the active skill(s)"
+                )),
+            ));
+        }
+
         let invocation = ToolInvocation {
             session,
             turn,
@@ -829,6 +829,94 @@ pub(crate) fn create_tools_json_for_chat_completions_api(
     Ok(tools_json)
 }
 
+/// Returns JSON values compatible with the Anthropic Messages API.
+pub(crate) fn create_tools_json_for_messages_api(
+    tools: &[ToolSpec],
+) -> crate::error::Result<Vec<serde_json::Value>> {
+    let tools_json = function_tools_json(tools)?
+        .map(|(name, description, parameters)| {
+            json!({
+                "name": name,
+                "description": description,
+                "input_schema": parameters,
+            })
+        })
+        .collect();
+    Ok(tools_json)
+}
+
+/// Returns Gemini `functionDeclarations`. Gemini rejects some JSON-schema
+/// keywords, so those are stripped from the parameters.
+pub(crate) fn create_tools_json_for_gemini_api(
+    tools: &[ToolSpec],
+) -> crate::error::Result<Vec<serde_json::Value>> {
+    let tools_json = function_tools_json(tools)?
+        .map(|(name, description, mut parameters)| {
+            strip_gemini_unsupported_schema_keywords(&mut parameters);
+            json!({
+                "name": name,
+                "description": description,
+                "parameters": parameters,
+            })
+        })
+        .collect();
+    Ok(tools_json)
+}
+
+/// Yields `(name, description, parameters)` for every function tool; other
+/// tool kinds have no equivalent outside the OpenAI APIs.
+fn function_tools_json(
+    tools: &[ToolSpec],
+) -> crate::error::Result<impl Iterator<Item = (String, String, serde_json::Value)>> {
+    let responses_api_tools_json = create_tools_json_for_responses_api(tools)?;
+    Ok(responses_api_tools_json.into_iter().filter_map(|tool| {
+        if tool.get("type").and_then(serde_json::Value::as_str) != Some("function") {
+            return None;
+        }
+        let name = tool.get("name")?.as_str()?.to_string();
+        let description = tool
+            .get("description")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_default()
+            .to_string();
+        let parameters = tool
+            .get("parameters")
+            .cloned()
+            .unwrap_or_else(|| json!({"type": "object", "properties": {}}));
+        Some((name, description, parameters))
+    }))
+}
+
+/// Removes the keywords Gemini rejects from a schema node and its subschemas.
+/// The keys of `properties` (and similar maps) are parameter names rather than
+/// keywords, so only their values are visited.
+fn strip_gemini_unsupported_schema_keywords(schema: &mut serde_json::Value) {
+    match schema {
+        serde_json::Value::Object(map) => {
+            map.remove("additionalProperties");
+            map.remove("$schema");
+            for (key, value) in map.iter_mut() {
+                match key.as_str() {
+                    "properties" | "patternProperties" | "definitions" | "$defs" => {
+                        if let serde_json::Value::Object(named) = value {
+                            named
+                                .values_mut()
+                                .for_each(strip_gemini_unsupported_schema_keywords);
+                        }
+                    }
+                    // These hold data, not schemas.
+                    "enum" | "const" | "default" | "examples" => {}
+                    _ => strip_gemini_unsupported_schema_keywords(value),
+                }
+            }
+        }
+        serde_json::Value::Array(items) => items
+            .iter_mut()
+            .for_each(strip_gemini_unsupported_schema_keywords),
+        _ => {}
+    }
+}
+
 pub(crate) fn mcp_tool_to_openai_tool(
     fully_qualified_name: String,
     tool: mcp_types::Tool,
@@ -2162,4 +2250,84 @@ Examples of valid command strings:
             })]
         );
     }
+
+    #[test]
+    fn messages_and_gemini_tools_json_use_function_tools_only() {
+        let mut properties = BTreeMap::new();
+        properties.insert("foo".to_string(), JsonSchema::String { description: None });
+        let tools = vec![
+            ToolSpec::Function(ResponsesApiTool {
+                name: "demo".to_string(),
+                description: "A demo tool".to_string(),
+                strict: false,
+                parameters: JsonSchema::Object {
+                    properties,
+                    required: None,
+                    additional_properties: Some(false.into()),
+                },
+            }),
+            ToolSpec::LocalShell {},
+        ];
+
+        assert_eq!(
+            create_tools_json_for_messages_api(&tools).unwrap(),
+            vec![json!({
+                "name": "demo",
+                "description": "A demo tool",
+                "input_schema": {
+                    "type": "object",
+                    "properties": {
+                        "foo": { "type": "string" }
+                    },
+                    "additionalProperties": false,
+                },
+            })]
+        );
+        assert_eq!(
+            create_tools_json_for_gemini_api(&tools).unwrap(),
+            vec![json!({
+                "name": "demo",
+                "description": "A demo tool",
+                "parameters": {
+                    "type": "object",
+                    "properties": {
+                        "foo": { "type": "string" }
+                    },
+                },
+            })]
+        );
+    }
+
+    #[test]
+    fn gemini_tools_json_keeps_parameters_named_like_keywords() {
+        let mut nested = json!({
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "type": "object",
+            "properties": {
+                "additionalProperties": {
+                    "type": "object",
+                    "properties": { "$schema": { "type": "string" } },
+                    "additionalProperties": false,
+                },
+                "mode": { "type": "string", "enum": ["a", "b"] },
+            },
+            "additionalProperties": false,
+        });
+
+        strip_gemini_unsupported_schema_keywords(&mut nested);
+
+        assert_eq!(
+            nested,
+            json!({
+                "type": "object",
+                "properties": {
+                    "additionalProperties": {
+                        "type": "object",
+                        "properties": { "$schema": { "type": "string" } },
+                    },
+                    "mode": { "type": "string", "enum": ["a", "b"] },
+                },
+            })
+        );
+    }
 }
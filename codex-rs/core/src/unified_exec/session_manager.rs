@@ -493,6 +493,11 @@ impl UnifiedExecSessionManager {
             sandbox_permissions,
         )
         .await;
+        let exec_approval_requirement = context.turn.active_skills.exempt_bundled_scripts(
+            command,
+            &cwd,
+            exec_approval_requirement,
+        );
         let req = UnifiedExecToolRequest::new(
             command.to_vec(),
             cwd,
@@ -747,6 +747,22 @@ pub async fn mount_function_call_agent_response(
     }
 }
 
+/// Serves the `/responses` streams recorded in a cassette directory, in
+/// recording order. To regenerate a fixture from real traffic, run the
+/// scenario with `codex exec --record-http <dir>` and check `<dir>` in.
+pub async fn mount_sse_cassette(
+    server: &MockServer,
+    dir: impl AsRef<std::path::Path>,
+) -> Result<ResponseMock> {
+    let bodies = codex_client::read_http_cassette_bodies(dir.as_ref(), "/responses")?;
+    anyhow::ensure!(
+        !bodies.is_empty(),
+        "no recorded /responses streams in {}",
+        dir.as_ref().display()
+    );
+    Ok(mount_sse_sequence(server, bodies).await)
+}
+
 /// Mounts a sequence of SSE response bodies and serves them in order for each
 /// POST to `/v1/responses`. Panics if more requests are received than bodies
 /// provided. Also asserts the exact number of expected calls.
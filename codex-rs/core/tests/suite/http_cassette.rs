@@ -0,0 +1,30 @@
+use std::path::Path;
+
+use anyhow::Result;
+use core_test_support::responses::mount_sse_cassette;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::test_codex;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn replays_turn_from_recorded_cassette() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let cassette = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join("cassettes")
+        .join("assistant_reply");
+    let mock = mount_sse_cassette(&server, cassette).await?;
+
+    let test = test_codex().build(&server).await?;
+    test.submit_turn("hello").await?;
+
+    let request = mock.single_request();
+    assert!(
+        request.body_json().to_string().contains("hello"),
+        "request should carry the user prompt"
+    );
+    Ok(())
+}
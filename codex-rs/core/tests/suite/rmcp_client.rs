@@ -711,6 +711,7 @@ async fn streamable_http_tool_call_round_trip() -> anyhow::Result<()> {
                     transport: McpServerTransportConfig::StreamableHttp {
                         url: server_url,
                         bearer_token_env_var: None,
+                        auth_command: None,
                         http_headers: None,
                         env_http_headers: None,
                     },
@@ -891,6 +892,7 @@ async fn streamable_http_with_oauth_round_trip() -> anyhow::Result<()> {
                     transport: McpServerTransportConfig::StreamableHttp {
                         url: server_url,
                         bearer_token_env_var: None,
+                        auth_command: None,
                         http_headers: None,
                         env_http_headers: None,
                     },
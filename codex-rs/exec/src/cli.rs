@@ -14,6 +14,12 @@ pub struct Cli {
     #[arg(long = "image", short = 'i', value_name = "FILE", value_delimiter = ',', num_args = 1..)]
     pub images: Vec<PathBuf>,
 
+    /// Optional file(s) whose contents are inlined into the initial prompt.
+    /// Append `:START-END` to attach only a range of lines. PDF and DOCX
+    /// files are converted to text.
+    #[arg(long = "attach", value_name = "FILE[:START-END]", value_delimiter = ',', num_args = 1..)]
+    pub attachments: Vec<String>,
+
     /// Model the agent should use.
     #[arg(long, short = 'm')]
     pub model: Option<String>,
@@ -22,7 +28,7 @@ pub struct Cli {
     #[arg(long = "oss", default_value_t = false)]
     pub oss: bool,
 
-    /// Specify which local provider to use (lmstudio or ollama).
+    /// Specify which local provider to use (lmstudio, ollama, llamacpp or vllm).
     /// If not specified with --oss, will use config default or show selection.
     #[arg(long = "local-provider")]
     pub oss_provider: Option<String>,
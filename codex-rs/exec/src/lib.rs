@@ -15,11 +15,14 @@ pub use cli::Command;
 pub use cli::ReviewArgs;
 use codex_common::oss::ensure_oss_provider_ready;
 use codex_common::oss::get_default_model_for_oss_provider;
+use codex_common::oss::register_served_models;
 use codex_core::AuthManager;
 use codex_core::ConversationManager;
+use codex_core::LLAMA_CPP_OSS_PROVIDER_ID;
 use codex_core::LMSTUDIO_OSS_PROVIDER_ID;
 use codex_core::NewConversation;
 use codex_core::OLLAMA_OSS_PROVIDER_ID;
+use codex_core::VLLM_OSS_PROVIDER_ID;
 use codex_core::auth::enforce_login_restrictions;
 use codex_core::config::Config;
 use codex_core::config::ConfigOverrides;
@@ -37,6 +40,7 @@ use codex_core::protocol::SessionSource;
 use codex_protocol::approvals::ElicitationAction;
 use codex_protocol::config_types::SandboxMode;
 use codex_protocol::user_input::UserInput;
+use codex_protocol::user_input::parse_file_attachment;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use event_processor_with_human_output::EventProcessorWithHumanOutput;
 use event_processor_with_jsonl_output::EventProcessorWithJsonOutput;
@@ -75,6 +79,7 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
     let Cli {
         command,
         images,
+        attachments,
         model: model_cli_arg,
         oss,
         oss_provider,
@@ -176,7 +181,7 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
             Some(provider)
         } else {
             return Err(anyhow::anyhow!(
-                "No default OSS provider configured. Use --local-provider=provider or set oss_provider to either {LMSTUDIO_OSS_PROVIDER_ID} or {OLLAMA_OSS_PROVIDER_ID} in config.toml"
+                "No default OSS provider configured. Use --local-provider=provider or set oss_provider to one of {LMSTUDIO_OSS_PROVIDER_ID}, {OLLAMA_OSS_PROVIDER_ID}, {LLAMA_CPP_OSS_PROVIDER_ID} or {VLLM_OSS_PROVIDER_ID} in config.toml"
             ));
         }
     } else {
@@ -287,6 +292,7 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         config.cli_auth_credentials_store_mode,
     );
     let conversation_manager = ConversationManager::new(auth_manager.clone(), SessionSource::Exec);
+    register_served_models(&config, &conversation_manager.get_models_manager()).await;
     let default_model = conversation_manager
         .get_models_manager()
         .get_model(&config.model, &config)
@@ -314,6 +320,13 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
             .new_conversation(config.clone())
             .await?
     };
+    let attachments: Vec<UserInput> = attachments
+        .iter()
+        .map(|spec| {
+            let (path, range) = parse_file_attachment(spec);
+            UserInput::File { path, range }
+        })
+        .collect();
     let (initial_operation, prompt_summary) = match (command, prompt, images) {
         (Some(ExecCommand::Review(review_cli)), _, _) => {
             let review_request = build_review_request(review_cli)?;
@@ -336,6 +349,7 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
             let mut items: Vec<UserInput> = imgs
                 .into_iter()
                 .map(|path| UserInput::LocalImage { path })
+                .chain(attachments)
                 .collect();
             items.push(UserInput::Text {
                 text: prompt_text.clone(),
@@ -354,6 +368,7 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
             let mut items: Vec<UserInput> = imgs
                 .into_iter()
                 .map(|path| UserInput::LocalImage { path })
+                .chain(attachments)
                 .collect();
             items.push(UserInput::Text {
                 text: prompt_text.clone(),
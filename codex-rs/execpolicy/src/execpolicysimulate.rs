@@ -0,0 +1,422 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use clap::Parser;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::Decision;
+use crate::Policy;
+use crate::execpolicycheck::load_policies;
+
+const RULES_DIR_NAME: &str = "rules";
+const RULE_EXTENSION: &str = "rules";
+const SESSIONS_DIR_NAME: &str = "sessions";
+const ROLLOUT_FILE_PREFIX: &str = "rollout-";
+const ROLLOUT_FILE_EXTENSION: &str = "jsonl";
+
+/// Splits a recorded command (e.g. `bash -lc "..."`) into the plain commands it runs.
+pub type CommandSplitter<'a> = &'a dyn Fn(&[String]) -> Option<Vec<Vec<String>>>;
+
+/// Arguments for replaying recorded session commands through a candidate execpolicy.
+#[derive(Debug, Parser, Clone)]
+pub struct ExecPolicySimulateCommand {
+    /// Paths to the candidate execpolicy rule files (repeatable).
+    #[arg(short = 'r', long = "rules", value_name = "PATH", required = true)]
+    pub rules: Vec<PathBuf>,
+
+    /// Paths to the current execpolicy rule files to compare against (repeatable).
+    /// Defaults to the `*.rules` files under `$CODEX_HOME/rules`.
+    #[arg(long = "baseline", value_name = "PATH")]
+    pub baseline: Vec<PathBuf>,
+
+    /// Pretty-print the JSON output.
+    #[arg(long)]
+    pub pretty: bool,
+
+    /// Rollout files or directories containing them. Defaults to `$CODEX_HOME/sessions`.
+    #[arg(value_name = "ROLLOUT")]
+    pub rollouts: Vec<PathBuf>,
+}
+
+impl ExecPolicySimulateCommand {
+    /// Replay the recorded commands through the candidate and baseline policies and render JSON
+    /// output.
+    pub fn run(&self, codex_home: Option<&Path>, split_command: CommandSplitter<'_>) -> Result<()> {
+        let report = self.simulate(codex_home, split_command)?;
+        let json = if self.pretty {
+            serde_json::to_string_pretty(&report)?
+        } else {
+            serde_json::to_string(&report)?
+        };
+        println!("{json}");
+        Ok(())
+    }
+
+    pub fn simulate(
+        &self,
+        codex_home: Option<&Path>,
+        split_command: CommandSplitter<'_>,
+    ) -> Result<SimulationReport> {
+        let candidate = load_policies(&self.rules)?;
+        let baseline = if self.baseline.is_empty() {
+            let codex_home = codex_home.context("CODEX_HOME is unknown; pass --baseline")?;
+            load_policies(&collect_policy_files(&codex_home.join(RULES_DIR_NAME))?)?
+        } else {
+            load_policies(&self.baseline)?
+        };
+
+        let rollout_roots = if self.rollouts.is_empty() {
+            let codex_home = codex_home.context("CODEX_HOME is unknown; pass rollout paths")?;
+            vec![codex_home.join(SESSIONS_DIR_NAME)]
+        } else {
+            self.rollouts.clone()
+        };
+        let rollout_files = collect_rollout_files(&rollout_roots)?;
+
+        let mut commands = Vec::new();
+        for rollout_file in &rollout_files {
+            commands.extend(read_rollout_commands(rollout_file)?);
+        }
+
+        Ok(simulate_commands(
+            &candidate,
+            &baseline,
+            rollout_files.len(),
+            &commands,
+            split_command,
+        ))
+    }
+}
+
+/// How a recorded command would be handled by a policy.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SimulatedOutcome {
+    Allow,
+    Prompt,
+    Forbidden,
+    /// At least one part of the command matched no rule, so the decision is left to the
+    /// built-in safety heuristics.
+    Heuristics,
+}
+
+#[derive(Debug, Default, Eq, PartialEq, Serialize)]
+pub struct OutcomeCounts {
+    pub allow: usize,
+    pub prompt: usize,
+    pub forbidden: usize,
+    pub heuristics: usize,
+}
+
+impl OutcomeCounts {
+    fn record(&mut self, outcome: SimulatedOutcome) {
+        match outcome {
+            SimulatedOutcome::Allow => self.allow += 1,
+            SimulatedOutcome::Prompt => self.prompt += 1,
+            SimulatedOutcome::Forbidden => self.forbidden += 1,
+            SimulatedOutcome::Heuristics => self.heuristics += 1,
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleCoverage {
+    pub rule: String,
+    pub decision: Decision,
+    /// Number of recorded commands this rule matched.
+    pub matched_commands: usize,
+}
+
+#[derive(Debug, Eq, PartialEq, Serialize)]
+pub struct CommandCount {
+    pub command: String,
+    pub count: usize,
+}
+
+#[derive(Debug, Eq, PartialEq, Serialize)]
+pub struct DecisionChange {
+    pub command: String,
+    pub baseline: SimulatedOutcome,
+    pub candidate: SimulatedOutcome,
+    pub count: usize,
+}
+
+#[derive(Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulationReport {
+    pub rollout_files: usize,
+    pub commands: usize,
+    /// Outcome counts for the candidate policy.
+    pub outcomes: OutcomeCounts,
+    /// Outcome counts for the baseline policy.
+    pub baseline_outcomes: OutcomeCounts,
+    /// Every candidate rule, including rules that matched nothing.
+    pub rules: Vec<RuleCoverage>,
+    /// Plain commands that matched no candidate rule, most frequent first.
+    pub heuristics: Vec<CommandCount>,
+    /// Commands whose outcome differs between the baseline and the candidate.
+    pub changes: Vec<DecisionChange>,
+}
+
+pub fn simulate_commands(
+    candidate: &Policy,
+    baseline: &Policy,
+    rollout_files: usize,
+    commands: &[Vec<String>],
+    split_command: CommandSplitter<'_>,
+) -> SimulationReport {
+    let mut coverage: BTreeMap<(String, Decision), usize> = candidate
+        .rules()
+        .iter_all()
+        .flat_map(|(_, rules)| rules.iter())
+        .map(|rule| ((rule.to_string(), rule.decision()), 0))
+        .collect();
+    let mut outcomes = OutcomeCounts::default();
+    let mut baseline_outcomes = OutcomeCounts::default();
+    let mut heuristics: HashMap<String, usize> = HashMap::new();
+    let mut changes: HashMap<(String, SimulatedOutcome, SimulatedOutcome), usize> = HashMap::new();
+
+    for command in commands {
+        let parts = split_command(command).unwrap_or_else(|| vec![command.clone()]);
+
+        let mut matched_rules = BTreeSet::new();
+        let mut candidate_decision = None;
+        let mut candidate_fell_through = false;
+        for part in &parts {
+            let rules = part
+                .first()
+                .and_then(|program| candidate.rules().get_vec(program))
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            let mut matched = false;
+            for rule in rules {
+                if let Some(rule_match) = rule.matches(part) {
+                    matched = true;
+                    candidate_decision = candidate_decision.max(Some(rule_match.decision()));
+                    matched_rules.insert((rule.to_string(), rule.decision()));
+                }
+            }
+            if !matched {
+                candidate_fell_through = true;
+                *heuristics.entry(render_command(part)).or_default() += 1;
+            }
+        }
+        for key in matched_rules {
+            *coverage.entry(key).or_default() += 1;
+        }
+
+        let candidate_outcome = combine(candidate_decision, candidate_fell_through);
+        let baseline_outcome = evaluate(baseline, &parts);
+        outcomes.record(candidate_outcome);
+        baseline_outcomes.record(baseline_outcome);
+        if candidate_outcome != baseline_outcome {
+            *changes
+                .entry((render_command(command), baseline_outcome, candidate_outcome))
+                .or_default() += 1;
+        }
+    }
+
+    let rules = coverage
+        .into_iter()
+        .map(|((rule, decision), matched_commands)| RuleCoverage {
+            rule,
+            decision,
+            matched_commands,
+        })
+        .collect();
+
+    let mut heuristics: Vec<CommandCount> = heuristics
+        .into_iter()
+        .map(|(command, count)| CommandCount { command, count })
+        .collect();
+    heuristics.sort_by(|a, b| {
+        b.count
+            .cmp(&a.count)
+            .then_with(|| a.command.cmp(&b.command))
+    });
+
+    let mut changes: Vec<DecisionChange> = changes
+        .into_iter()
+        .map(|((command, baseline, candidate), count)| DecisionChange {
+            command,
+            baseline,
+            candidate,
+            count,
+        })
+        .collect();
+    changes.sort_by(|a, b| {
+        b.count
+            .cmp(&a.count)
+            .then_with(|| a.command.cmp(&b.command))
+    });
+
+    SimulationReport {
+        rollout_files,
+        commands: commands.len(),
+        outcomes,
+        baseline_outcomes,
+        rules,
+        heuristics,
+        changes,
+    }
+}
+
+fn evaluate(policy: &Policy, parts: &[Vec<String>]) -> SimulatedOutcome {
+    let mut decision = None;
+    let mut fell_through = false;
+    for part in parts {
+        let matched_rules = policy.matches_for_command(part, None);
+        if matched_rules.is_empty() {
+            fell_through = true;
+        }
+        decision = decision.max(matched_rules.iter().map(|rule| rule.decision()).max());
+    }
+    combine(decision, fell_through)
+}
+
+/// A forbidden match wins outright; otherwise any unmatched part defers to the heuristics.
+fn combine(decision: Option<Decision>, fell_through: bool) -> SimulatedOutcome {
+    match (decision, fell_through) {
+        (Some(Decision::Forbidden), _) => SimulatedOutcome::Forbidden,
+        (_, true) | (None, _) => SimulatedOutcome::Heuristics,
+        (Some(Decision::Prompt), false) => SimulatedOutcome::Prompt,
+        (Some(Decision::Allow), false) => SimulatedOutcome::Allow,
+    }
+}
+
+fn render_command(command: &[String]) -> String {
+    shlex::try_join(command.iter().map(String::as_str)).unwrap_or_else(|_| command.join(" "))
+}
+
+fn collect_policy_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => {
+            return Err(err).with_context(|| format!("failed to read {}", dir.display()));
+        }
+    };
+
+    let mut policy_paths = Vec::new();
+    for entry in entries {
+        let path = entry
+            .with_context(|| format!("failed to read {}", dir.display()))?
+            .path();
+        if path.is_file()
+            && path
+                .extension()
+                .is_some_and(|extension| extension == RULE_EXTENSION)
+        {
+            policy_paths.push(path);
+        }
+    }
+    policy_paths.sort();
+    Ok(policy_paths)
+}
+
+fn collect_rollout_files(roots: &[PathBuf]) -> Result<Vec<PathBuf>> {
+    let mut rollout_files = Vec::new();
+    for root in roots {
+        if root.is_dir() {
+            collect_rollout_files_in_dir(root, &mut rollout_files)?;
+        } else {
+            rollout_files.push(root.clone());
+        }
+    }
+    Ok(rollout_files)
+}
+
+fn collect_rollout_files_in_dir(dir: &Path, rollout_files: &mut Vec<PathBuf>) -> Result<()> {
+    let mut entries = fs::read_dir(dir)
+        .with_context(|| format!("failed to read {}", dir.display()))?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<std::io::Result<Vec<_>>>()
+        .with_context(|| format!("failed to read {}", dir.display()))?;
+    entries.sort();
+
+    for path in entries {
+        if path.is_dir() {
+            collect_rollout_files_in_dir(&path, rollout_files)?;
+        } else if is_rollout_file(&path) {
+            rollout_files.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn is_rollout_file(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension == ROLLOUT_FILE_EXTENSION)
+        && path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with(ROLLOUT_FILE_PREFIX))
+}
+
+/// Read every shell command the model ran in a rollout file, in order.
+pub fn read_rollout_commands(path: &Path) -> Result<Vec<Vec<String>>> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("failed to read rollout at {}", path.display()))?;
+    Ok(contents
+        .lines()
+        .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+        .filter_map(|line| command_from_rollout_line(&line))
+        .collect())
+}
+
+/// Extract the command from a persisted `response_item` line, covering the `shell`,
+/// `shell_command` and `exec_command` tools as well as `local_shell_call` items.
+fn command_from_rollout_line(line: &Value) -> Option<Vec<String>> {
+    if line.get("type")?.as_str()? != "response_item" {
+        return None;
+    }
+    let payload = line.get("payload")?;
+    match payload.get("type")?.as_str()? {
+        "local_shell_call" => string_array(payload.get("action")?.get("command")?),
+        "function_call" => {
+            let arguments: Value =
+                serde_json::from_str(payload.get("arguments")?.as_str()?).ok()?;
+            match payload.get("name")?.as_str()? {
+                "shell" | "container.exec" => string_array(arguments.get("command")?),
+                "shell_command" => shell_script(&arguments, "command"),
+                "exec_command" => shell_script(&arguments, "cmd"),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+fn shell_script(arguments: &Value, key: &str) -> Option<Vec<String>> {
+    let script = arguments.get(key)?.as_str()?;
+    let shell = arguments
+        .get("shell")
+        .and_then(Value::as_str)
+        .unwrap_or("bash");
+    let login = arguments
+        .get("login")
+        .and_then(Value::as_bool)
+        .unwrap_or(true);
+    let flag = if login { "-lc" } else { "-c" };
+    Some(vec![
+        shell.to_string(),
+        flag.to_string(),
+        script.to_string(),
+    ])
+}
+
+fn string_array(value: &Value) -> Option<Vec<String>> {
+    value
+        .as_array()?
+        .iter()
+        .map(|token| token.as_str().map(str::to_string))
+        .collect()
+}
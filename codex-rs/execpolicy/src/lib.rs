@@ -2,9 +2,11 @@ pub mod amend;
 pub mod decision;
 pub mod error;
 pub mod execpolicycheck;
+pub mod execpolicysimulate;
 pub mod parser;
 pub mod policy;
 pub mod rule;
+mod token_matcher;
 
 pub use amend::AmendError;
 pub use amend::blocking_append_allow_prefix_rule;
@@ -12,6 +14,7 @@ pub use decision::Decision;
 pub use error::Error;
 pub use error::Result;
 pub use execpolicycheck::ExecPolicyCheckCommand;
+pub use execpolicysimulate::ExecPolicySimulateCommand;
 pub use parser::PolicyParser;
 pub use policy::Evaluation;
 pub use policy::Policy;
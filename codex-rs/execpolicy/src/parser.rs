@@ -8,6 +8,7 @@ use starlark::starlark_module;
 use starlark::syntax::AstModule;
 use starlark::syntax::Dialect;
 use starlark::values::Value;
+use starlark::values::ValueLike;
 use starlark::values::list::ListRef;
 use starlark::values::list::UnpackList;
 use starlark::values::none::NoneType;
@@ -18,12 +19,16 @@ use std::sync::Arc;
 use crate::decision::Decision;
 use crate::error::Error;
 use crate::error::Result;
+use crate::rule::ArgPredicate;
+use crate::rule::ConditionalRule;
 use crate::rule::PatternToken;
 use crate::rule::PrefixPattern;
 use crate::rule::PrefixRule;
 use crate::rule::RuleRef;
+use crate::rule::TokenRegex;
 use crate::rule::validate_match_examples;
 use crate::rule::validate_not_match_examples;
+use crate::token_matcher::TokenMatcher;
 
 pub struct PolicyParser {
     builder: RefCell<PolicyBuilder>,
@@ -106,6 +111,8 @@ fn parse_pattern<'v>(pattern: UnpackList<Value<'v>>) -> Result<Vec<PatternToken>
 fn parse_pattern_token<'v>(value: Value<'v>) -> Result<PatternToken> {
     if let Some(s) = value.unpack_str() {
         Ok(PatternToken::Single(s.to_string()))
+    } else if let Some(matcher) = value.downcast_ref::<TokenMatcher>() {
+        matcher.to_pattern_token()
     } else if let Some(list) = ListRef::from_value(value) {
         let tokens: Vec<String> = list
             .content()
@@ -132,12 +139,29 @@ fn parse_pattern_token<'v>(value: Value<'v>) -> Result<PatternToken> {
         }
     } else {
         Err(Error::InvalidPattern(format!(
-            "pattern element must be a string or list of strings (got {})",
+            "pattern element must be a string, list of strings or token matcher (got {})",
             value.get_type()
         )))
     }
 }
 
+fn parse_arg_predicate<'v>(
+    name: &str,
+    patterns: UnpackList<Value<'v>>,
+    predicate: fn(Vec<PatternToken>) -> ArgPredicate,
+) -> Result<ArgPredicate> {
+    let tokens: Vec<PatternToken> = patterns
+        .items
+        .into_iter()
+        .map(parse_pattern_token)
+        .collect::<Result<_>>()?;
+    if tokens.is_empty() {
+        Err(Error::InvalidPattern(format!("{name} cannot be empty")))
+    } else {
+        Ok(predicate(tokens))
+    }
+}
+
 fn parse_examples<'v>(examples: UnpackList<Value<'v>>) -> Result<Vec<Vec<String>>> {
     examples.items.into_iter().map(parse_example).collect()
 }
@@ -207,11 +231,15 @@ fn policy_builder<'v, 'a>(eval: &Evaluator<'v, 'a, '_>) -> RefMut<'a, PolicyBuil
 
 #[starlark_module]
 fn policy_builtins(builder: &mut GlobalsBuilder) {
+    #[allow(clippy::too_many_arguments)]
     fn prefix_rule<'v>(
         pattern: UnpackList<Value<'v>>,
         decision: Option<&'v str>,
         r#match: Option<UnpackList<Value<'v>>>,
         not_match: Option<UnpackList<Value<'v>>>,
+        args_any: Option<UnpackList<Value<'v>>>,
+        args_none: Option<UnpackList<Value<'v>>>,
+        args_all: Option<UnpackList<Value<'v>>>,
         eval: &mut Evaluator<'v, '_, '_>,
     ) -> anyhow::Result<NoneType> {
         let decision = match decision {
@@ -221,6 +249,30 @@ fn policy_builtins(builder: &mut GlobalsBuilder) {
 
         let pattern_tokens = parse_pattern(pattern)?;
 
+        let mut predicates: Vec<ArgPredicate> = Vec::new();
+        if let Some(patterns) = args_any {
+            predicates.push(parse_arg_predicate(
+                "args_any",
+                patterns,
+                ArgPredicate::Any,
+            )?);
+        }
+        if let Some(patterns) = args_none {
+            predicates.push(parse_arg_predicate(
+                "args_none",
+                patterns,
+                ArgPredicate::None,
+            )?);
+        }
+        if let Some(patterns) = args_all {
+            predicates.push(parse_arg_predicate(
+                "args_all",
+                patterns,
+                ArgPredicate::All,
+            )?);
+        }
+        let predicates: Arc<[ArgPredicate]> = predicates.into();
+
         let matches: Vec<Vec<String>> =
             r#match.map(parse_examples).transpose()?.unwrap_or_default();
         let not_matches: Vec<Vec<String>> = not_match
@@ -236,17 +288,30 @@ fn policy_builtins(builder: &mut GlobalsBuilder) {
 
         let rest: Arc<[PatternToken]> = remaining_tokens.to_vec().into();
 
-        let rules: Vec<RuleRef> = first_token
-            .alternatives()
+        let heads = first_token.alternatives().ok_or_else(|| {
+            Error::InvalidPattern(
+                "first pattern element must be a literal program name".to_string(),
+            )
+        })?;
+
+        let rules: Vec<RuleRef> = heads
             .iter()
             .map(|head| {
-                Arc::new(PrefixRule {
+                let prefix = PrefixRule {
                     pattern: PrefixPattern {
                         first: Arc::from(head.as_str()),
                         rest: rest.clone(),
                     },
                     decision,
-                }) as RuleRef
+                };
+                if predicates.is_empty() {
+                    Arc::new(prefix) as RuleRef
+                } else {
+                    Arc::new(ConditionalRule {
+                        prefix,
+                        predicates: predicates.clone(),
+                    }) as RuleRef
+                }
             })
             .collect();
 
@@ -256,4 +321,27 @@ fn policy_builtins(builder: &mut GlobalsBuilder) {
         rules.into_iter().for_each(|rule| builder.add_rule(rule));
         Ok(NoneType)
     }
+
+    /// Token matcher for a shell-style glob (`*` and `?`) over the whole token.
+    fn glob(pattern: String) -> anyhow::Result<TokenMatcher> {
+        Ok(TokenMatcher::Glob(pattern))
+    }
+
+    /// Token matcher for a regular expression that must match the whole token.
+    fn regex(pattern: String) -> anyhow::Result<TokenMatcher> {
+        TokenRegex::new(&pattern)?;
+        Ok(TokenMatcher::Regex(pattern))
+    }
+
+    /// Token matcher for any flag except the excluded ones (`--name=value` forms included).
+    fn any_flag(exclude: Option<UnpackList<String>>) -> anyhow::Result<TokenMatcher> {
+        Ok(TokenMatcher::AnyFlag {
+            exclude: exclude.map(|list| list.items).unwrap_or_default(),
+        })
+    }
+
+    /// Token matcher for a relative path that stays inside the working directory.
+    fn workspace_path() -> anyhow::Result<TokenMatcher> {
+        Ok(TokenMatcher::WorkspacePath)
+    }
 }
@@ -1,34 +1,183 @@
 use crate::decision::Decision;
 use crate::error::Error;
 use crate::error::Result;
+use regex_lite::Regex;
 use serde::Deserialize;
 use serde::Serialize;
 use shlex::try_join;
 use std::any::Any;
+use std::fmt;
 use std::fmt::Debug;
+use std::fmt::Display;
+use std::path::Component;
+use std::path::Path;
 use std::sync::Arc;
+use wildmatch::WildMatch;
 
-/// Matches a single command token, either a fixed string or one of several allowed alternatives.
+/// Matches a single command token: a fixed string, one of several allowed alternatives, or a
+/// predicate over the token's shape.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum PatternToken {
     Single(String),
     Alts(Vec<String>),
+    /// Shell-style glob (`*` and `?`) that must match the whole token.
+    Glob(String),
+    /// Regular expression that must match the whole token.
+    Regex(TokenRegex),
+    /// Any flag (a token starting with `-`) other than the excluded ones.
+    AnyFlag {
+        exclude: Vec<String>,
+    },
+    /// A path that stays inside the working directory: relative, not starting with `~`, and never
+    /// climbing above its starting point via `..`.
+    WorkspacePath,
 }
 
 impl PatternToken {
-    fn matches(&self, token: &str) -> bool {
+    pub fn matches(&self, token: &str) -> bool {
         match self {
             Self::Single(expected) => expected == token,
             Self::Alts(alternatives) => alternatives.iter().any(|alt| alt == token),
+            Self::Glob(pattern) => WildMatch::new(pattern).matches(token),
+            Self::Regex(regex) => regex.is_match(token),
+            Self::AnyFlag { exclude } => {
+                token.starts_with('-')
+                    && !exclude.iter().any(|excluded| {
+                        token == excluded
+                            || token
+                                .strip_prefix(excluded.as_str())
+                                .is_some_and(|rest| rest.starts_with('='))
+                    })
+            }
+            Self::WorkspacePath => is_workspace_relative_path(token),
+        }
+    }
+
+    /// Literal tokens this pattern accepts, or `None` when it matches by predicate.
+    pub fn alternatives(&self) -> Option<&[String]> {
+        match self {
+            Self::Single(expected) => Some(std::slice::from_ref(expected)),
+            Self::Alts(alternatives) => Some(alternatives),
+            Self::Glob(_) | Self::Regex(_) | Self::AnyFlag { .. } | Self::WorkspacePath => None,
+        }
+    }
+}
+
+impl Display for PatternToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Single(expected) => write!(f, "{expected:?}"),
+            Self::Alts(alternatives) => write!(f, "{alternatives:?}"),
+            Self::Glob(pattern) => write!(f, "glob({pattern:?})"),
+            Self::Regex(regex) => write!(f, "regex({:?})", regex.as_str()),
+            Self::AnyFlag { exclude } if exclude.is_empty() => write!(f, "any_flag()"),
+            Self::AnyFlag { exclude } => write!(f, "any_flag(exclude = {exclude:?})"),
+            Self::WorkspacePath => write!(f, "workspace_path()"),
+        }
+    }
+}
+
+/// Regular expression anchored to match a whole command token.
+#[derive(Clone, Debug)]
+pub struct TokenRegex {
+    source: String,
+    regex: Regex,
+}
+
+impl TokenRegex {
+    pub fn new(source: &str) -> Result<Self> {
+        let regex = Regex::new(&format!("^(?:{source})$"))
+            .map_err(|err| Error::InvalidPattern(format!("invalid regex `{source}`: {err}")))?;
+        Ok(Self {
+            source: source.to_string(),
+            regex,
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    fn is_match(&self, token: &str) -> bool {
+        self.regex.is_match(token)
+    }
+}
+
+impl PartialEq for TokenRegex {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source
+    }
+}
+
+impl Eq for TokenRegex {}
+
+fn is_workspace_relative_path(token: &str) -> bool {
+    if token.is_empty() || token.starts_with('~') || token.starts_with('-') {
+        return false;
+    }
+
+    let mut depth = 0usize;
+    for component in Path::new(token).components() {
+        match component {
+            Component::Normal(_) => depth += 1,
+            Component::CurDir => {}
+            Component::ParentDir => match depth.checked_sub(1) {
+                Some(parent) => depth = parent,
+                None => return false,
+            },
+            Component::RootDir | Component::Prefix(_) => return false,
         }
     }
+    true
+}
 
-    pub fn alternatives(&self) -> &[String] {
+/// Constraint on the arguments that follow a matched prefix.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ArgPredicate {
+    /// At least one remaining argument must match one of the patterns.
+    Any(Vec<PatternToken>),
+    /// No remaining argument may match any of the patterns.
+    None(Vec<PatternToken>),
+    /// Every remaining argument must match one of the patterns.
+    All(Vec<PatternToken>),
+}
+
+impl ArgPredicate {
+    fn holds(&self, args: &[String]) -> bool {
+        let matches_any =
+            |arg: &String, patterns: &[PatternToken]| patterns.iter().any(|p| p.matches(arg));
         match self {
-            Self::Single(expected) => std::slice::from_ref(expected),
-            Self::Alts(alternatives) => alternatives,
+            Self::Any(patterns) => args.iter().any(|arg| matches_any(arg, patterns)),
+            Self::None(patterns) => !args.iter().any(|arg| matches_any(arg, patterns)),
+            Self::All(patterns) => args.iter().all(|arg| matches_any(arg, patterns)),
+        }
+    }
+}
+
+impl Display for ArgPredicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (name, patterns) = match self {
+            Self::Any(patterns) => ("args_any", patterns),
+            Self::None(patterns) => ("args_none", patterns),
+            Self::All(patterns) => ("args_all", patterns),
+        };
+        write!(f, "{name} = ")?;
+        write_token_list(f, patterns.iter())
+    }
+}
+
+fn write_token_list<'a>(
+    f: &mut fmt::Formatter<'_>,
+    tokens: impl Iterator<Item = &'a PatternToken>,
+) -> fmt::Result {
+    write!(f, "[")?;
+    for (index, token) in tokens.enumerate() {
+        if index > 0 {
+            write!(f, ", ")?;
         }
+        write!(f, "{token}")?;
     }
+    write!(f, "]")
 }
 
 /// Prefix matcher for commands with support for alternative match tokens.
@@ -56,6 +205,13 @@ impl PrefixPattern {
     }
 }
 
+impl Display for PrefixPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let first = PatternToken::Single(self.first.to_string());
+        write_token_list(f, std::iter::once(&first).chain(self.rest.iter()))
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum RuleMatch {
@@ -85,9 +241,35 @@ pub struct PrefixRule {
     pub decision: Decision,
 }
 
-pub trait Rule: Any + Debug + Send + Sync {
+/// Prefix rule that additionally constrains the arguments following the prefix.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConditionalRule {
+    pub prefix: PrefixRule,
+    pub predicates: Arc<[ArgPredicate]>,
+}
+
+impl Display for PrefixRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "pattern = {}", self.pattern)
+    }
+}
+
+impl Display for ConditionalRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.prefix)?;
+        for predicate in self.predicates.iter() {
+            write!(f, ", {predicate}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A rule renders itself (via `Display`) in the same shape as its `prefix_rule` arguments.
+pub trait Rule: Any + Debug + Display + Send + Sync {
     fn program(&self) -> &str;
 
+    fn decision(&self) -> Decision;
+
     fn matches(&self, cmd: &[String]) -> Option<RuleMatch>;
 }
 
@@ -98,6 +280,10 @@ impl Rule for PrefixRule {
         self.pattern.first.as_ref()
     }
 
+    fn decision(&self) -> Decision {
+        self.decision
+    }
+
     fn matches(&self, cmd: &[String]) -> Option<RuleMatch> {
         self.pattern
             .matches_prefix(cmd)
@@ -108,6 +294,33 @@ impl Rule for PrefixRule {
     }
 }
 
+impl Rule for ConditionalRule {
+    fn program(&self) -> &str {
+        self.prefix.program()
+    }
+
+    fn decision(&self) -> Decision {
+        self.prefix.decision
+    }
+
+    fn matches(&self, cmd: &[String]) -> Option<RuleMatch> {
+        let matched_prefix = self.prefix.pattern.matches_prefix(cmd)?;
+        let args = &cmd[matched_prefix.len()..];
+        if !self
+            .predicates
+            .iter()
+            .all(|predicate| predicate.holds(args))
+        {
+            return None;
+        }
+
+        Some(RuleMatch::PrefixRuleMatch {
+            matched_prefix,
+            decision: self.prefix.decision,
+        })
+    }
+}
+
 /// Count how many rules match each provided example and error if any example is unmatched.
 pub(crate) fn validate_match_examples(rules: &[RuleRef], matches: &[Vec<String>]) -> Result<()> {
     let mut unmatched_examples = Vec::new();
@@ -0,0 +1,61 @@
+#![allow(clippy::needless_lifetimes)]
+
+use std::fmt;
+
+use allocative::Allocative;
+use starlark::any::ProvidesStaticType;
+use starlark::values::AllocValue;
+use starlark::values::Heap;
+use starlark::values::NoSerialize;
+use starlark::values::StarlarkValue;
+use starlark::values::Value;
+use starlark::values::starlark_value;
+
+use crate::error::Result;
+use crate::rule::PatternToken;
+use crate::rule::TokenRegex;
+
+/// Starlark value produced by the `glob`, `regex`, `any_flag` and `workspace_path` builtins.
+/// It can be used anywhere a pattern element is accepted.
+#[derive(Clone, Debug, PartialEq, Eq, ProvidesStaticType, NoSerialize, Allocative)]
+pub(crate) enum TokenMatcher {
+    Glob(String),
+    Regex(String),
+    AnyFlag { exclude: Vec<String> },
+    WorkspacePath,
+}
+
+impl TokenMatcher {
+    pub(crate) fn to_pattern_token(&self) -> Result<PatternToken> {
+        Ok(match self {
+            Self::Glob(pattern) => PatternToken::Glob(pattern.clone()),
+            Self::Regex(source) => PatternToken::Regex(TokenRegex::new(source)?),
+            Self::AnyFlag { exclude } => PatternToken::AnyFlag {
+                exclude: exclude.clone(),
+            },
+            Self::WorkspacePath => PatternToken::WorkspacePath,
+        })
+    }
+}
+
+impl fmt::Display for TokenMatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Glob(pattern) => write!(f, "glob({pattern:?})"),
+            Self::Regex(source) => write!(f, "regex({source:?})"),
+            Self::AnyFlag { exclude } => write!(f, "any_flag(exclude = {exclude:?})"),
+            Self::WorkspacePath => write!(f, "workspace_path()"),
+        }
+    }
+}
+
+#[starlark_value(type = "TokenMatcher")]
+impl<'v> StarlarkValue<'v> for TokenMatcher {
+    type Canonical = TokenMatcher;
+}
+
+impl<'v> AllocValue<'v> for TokenMatcher {
+    fn alloc_value(self, heap: &'v Heap) -> Value<'v> {
+        heap.alloc_simple(self)
+    }
+}
@@ -452,3 +452,137 @@ fn heuristics_match_is_returned_when_no_policy_matches() {
         evaluation
     );
 }
+
+#[test]
+fn arg_predicates_gate_prefix_rules() -> Result<()> {
+    let policy_src = r#"
+prefix_rule(
+    pattern = ["git", "push"],
+    decision = "allow",
+    args_none = ["--force", "-f", glob("+*")],
+    match = ["git push origin main"],
+    not_match = ["git push --force origin main", "git push origin +main"],
+)
+prefix_rule(
+    pattern = ["git", "push"],
+    decision = "forbidden",
+    args_any = ["--force", "-f"],
+)
+    "#;
+    let mut parser = PolicyParser::new();
+    parser.parse("test.rules", policy_src)?;
+    let policy = parser.build();
+
+    let plain = policy.check(&tokens(&["git", "push", "origin", "main"]), &prompt_all);
+    assert_eq!(
+        Evaluation {
+            decision: Decision::Allow,
+            matched_rules: vec![RuleMatch::PrefixRuleMatch {
+                matched_prefix: tokens(&["git", "push"]),
+                decision: Decision::Allow,
+            }],
+        },
+        plain
+    );
+
+    let forced = policy.check(&tokens(&["git", "push", "-f", "origin"]), &prompt_all);
+    assert_eq!(
+        Evaluation {
+            decision: Decision::Forbidden,
+            matched_rules: vec![RuleMatch::PrefixRuleMatch {
+                matched_prefix: tokens(&["git", "push"]),
+                decision: Decision::Forbidden,
+            }],
+        },
+        forced
+    );
+    Ok(())
+}
+
+#[test]
+fn token_matchers_match_positional_tokens() -> Result<()> {
+    let policy_src = r#"
+prefix_rule(
+    pattern = ["cargo", regex("build|check|test"), any_flag(exclude = ["--manifest-path"])],
+    match = ["cargo test --workspace", "cargo check --locked"],
+    not_match = ["cargo run --release", "cargo test --manifest-path=../x/Cargo.toml", "cargo test"],
+)
+prefix_rule(
+    pattern = ["cat", workspace_path()],
+    match = ["cat src/lib.rs", "cat docs/../README.md"],
+    not_match = ["cat /etc/passwd", "cat ../secret", "cat ~/.ssh/id_rsa", "cat a/../../b"],
+)
+prefix_rule(
+    pattern = ["rm", glob("*.tmp")],
+    decision = "prompt",
+    match = ["rm build.tmp"],
+    not_match = ["rm build.rs"],
+)
+    "#;
+    let mut parser = PolicyParser::new();
+    parser.parse("test.rules", policy_src)?;
+    let policy = parser.build();
+
+    let evaluation = policy.check(&tokens(&["cat", "src/main.rs"]), &prompt_all);
+    assert_eq!(
+        Evaluation {
+            decision: Decision::Allow,
+            matched_rules: vec![RuleMatch::PrefixRuleMatch {
+                matched_prefix: tokens(&["cat", "src/main.rs"]),
+                decision: Decision::Allow,
+            }],
+        },
+        evaluation
+    );
+    Ok(())
+}
+
+#[test]
+fn invalid_regex_is_rejected() {
+    let policy_src = r#"
+prefix_rule(
+    pattern = ["grep", regex("(")],
+)
+    "#;
+    let mut parser = PolicyParser::new();
+    let err = parser
+        .parse("test.rules", policy_src)
+        .expect_err("invalid regex should be rejected");
+    assert!(err.to_string().contains("invalid regex"), "{err}");
+}
+
+#[test]
+fn program_name_must_be_literal() {
+    let policy_src = r#"
+prefix_rule(
+    pattern = [glob("py*"), "-c"],
+)
+    "#;
+    let mut parser = PolicyParser::new();
+    let err = parser
+        .parse("test.rules", policy_src)
+        .expect_err("matcher as program name should be rejected");
+    assert!(
+        err.to_string()
+            .contains("first pattern element must be a literal program name"),
+        "{err}"
+    );
+}
+
+#[test]
+fn empty_arg_predicate_is_rejected() {
+    let policy_src = r#"
+prefix_rule(
+    pattern = ["git", "push"],
+    args_none = [],
+)
+    "#;
+    let mut parser = PolicyParser::new();
+    let err = parser
+        .parse("test.rules", policy_src)
+        .expect_err("empty predicate should be rejected");
+    assert!(
+        err.to_string().contains("args_none cannot be empty"),
+        "{err}"
+    );
+}
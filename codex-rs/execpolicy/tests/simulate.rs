@@ -0,0 +1,184 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::Result;
+use codex_execpolicy::Decision;
+use codex_execpolicy::ExecPolicySimulateCommand;
+use codex_execpolicy::execpolicysimulate::CommandCount;
+use codex_execpolicy::execpolicysimulate::DecisionChange;
+use codex_execpolicy::execpolicysimulate::OutcomeCounts;
+use codex_execpolicy::execpolicysimulate::RuleCoverage;
+use codex_execpolicy::execpolicysimulate::SimulatedOutcome;
+use codex_execpolicy::execpolicysimulate::read_rollout_commands;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use tempfile::TempDir;
+
+fn function_call(name: &str, arguments: serde_json::Value) -> serde_json::Value {
+    json!({
+        "timestamp": "2025-01-01T00:00:00.000Z",
+        "type": "response_item",
+        "payload": {
+            "type": "function_call",
+            "name": name,
+            "arguments": arguments.to_string(),
+            "call_id": "call-1",
+        },
+    })
+}
+
+fn write_rollout(path: &Path, lines: &[serde_json::Value]) -> Result<()> {
+    let contents: Vec<String> = lines.iter().map(ToString::to_string).collect();
+    fs::create_dir_all(path.parent().expect("rollout has a parent"))?;
+    fs::write(path, contents.join("\n"))?;
+    Ok(())
+}
+
+fn tokens(cmd: &[&str]) -> Vec<String> {
+    cmd.iter().map(std::string::ToString::to_string).collect()
+}
+
+#[test]
+fn reads_commands_from_rollout_items() -> Result<()> {
+    let dir = TempDir::new()?;
+    let rollout = dir.path().join("rollout-2025-01-01T00-00-00-test.jsonl");
+    write_rollout(
+        &rollout,
+        &[
+            json!({"timestamp": "2025-01-01T00:00:00.000Z", "type": "session_meta", "payload": {}}),
+            function_call("shell", json!({"command": ["git", "status"]})),
+            function_call(
+                "shell_command",
+                json!({"command": "ls -la", "login": false}),
+            ),
+            function_call("exec_command", json!({"cmd": "cargo test"})),
+            function_call("apply_patch", json!({"input": "*** Begin Patch"})),
+            json!({
+                "timestamp": "2025-01-01T00:00:00.000Z",
+                "type": "response_item",
+                "payload": {
+                    "type": "local_shell_call",
+                    "call_id": "call-2",
+                    "status": "completed",
+                    "action": {"type": "exec", "command": ["pwd"]},
+                },
+            }),
+        ],
+    )?;
+
+    assert_eq!(
+        vec![
+            tokens(&["git", "status"]),
+            tokens(&["bash", "-c", "ls -la"]),
+            tokens(&["bash", "-lc", "cargo test"]),
+            tokens(&["pwd"]),
+        ],
+        read_rollout_commands(&rollout)?
+    );
+    Ok(())
+}
+
+#[test]
+fn reports_coverage_fallthrough_and_changes() -> Result<()> {
+    let dir = TempDir::new()?;
+    let candidate = dir.path().join("candidate.rules");
+    fs::write(
+        &candidate,
+        r#"
+prefix_rule(pattern = ["git", "status"])
+prefix_rule(pattern = ["git", "push"], decision = "forbidden", args_any = ["--force"])
+prefix_rule(pattern = ["rm"], decision = "prompt")
+"#,
+    )?;
+    let baseline = dir.path().join("baseline.rules");
+    fs::write(
+        &baseline,
+        r#"
+prefix_rule(pattern = ["git"], decision = "prompt")
+"#,
+    )?;
+    write_rollout(
+        &dir.path().join("sessions/2025/01/01/rollout-a.jsonl"),
+        &[
+            function_call("shell", json!({"command": ["git", "status"]})),
+            function_call("shell", json!({"command": ["git", "push", "--force"]})),
+            function_call("shell", json!({"command": ["ls"]})),
+            function_call("shell", json!({"command": ["git", "status"]})),
+        ],
+    )?;
+    fs::write(dir.path().join("sessions/notes.jsonl"), "not a rollout")?;
+
+    let command = ExecPolicySimulateCommand {
+        rules: vec![candidate],
+        baseline: vec![baseline],
+        pretty: false,
+        rollouts: vec![dir.path().join("sessions")],
+    };
+    let report = command.simulate(None, &|_| None)?;
+
+    assert_eq!(1, report.rollout_files);
+    assert_eq!(4, report.commands);
+    assert_eq!(
+        OutcomeCounts {
+            allow: 2,
+            prompt: 0,
+            forbidden: 1,
+            heuristics: 1,
+        },
+        report.outcomes
+    );
+    assert_eq!(
+        OutcomeCounts {
+            allow: 0,
+            prompt: 3,
+            forbidden: 0,
+            heuristics: 1,
+        },
+        report.baseline_outcomes
+    );
+    assert_eq!(
+        vec![
+            RuleCoverage {
+                rule: r#"pattern = ["git", "push"], args_any = ["--force"]"#.to_string(),
+                decision: Decision::Forbidden,
+                matched_commands: 1,
+            },
+            RuleCoverage {
+                rule: r#"pattern = ["git", "status"]"#.to_string(),
+                decision: Decision::Allow,
+                matched_commands: 2,
+            },
+            RuleCoverage {
+                rule: r#"pattern = ["rm"]"#.to_string(),
+                decision: Decision::Prompt,
+                matched_commands: 0,
+            },
+        ],
+        report.rules
+    );
+    assert_eq!(
+        vec![CommandCount {
+            command: "ls".to_string(),
+            count: 1,
+        }],
+        report.heuristics
+    );
+    assert_eq!(
+        vec![
+            DecisionChange {
+                command: "git status".to_string(),
+                baseline: SimulatedOutcome::Prompt,
+                candidate: SimulatedOutcome::Allow,
+                count: 2,
+            },
+            DecisionChange {
+                command: "git push --force".to_string(),
+                baseline: SimulatedOutcome::Prompt,
+                candidate: SimulatedOutcome::Forbidden,
+                count: 1,
+            },
+        ],
+        report.changes
+    );
+    Ok(())
+}
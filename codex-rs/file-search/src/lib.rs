@@ -307,6 +307,43 @@ pub fn run(
     })
 }
 
+/// Scores arbitrary strings against a query using the same fuzzy matching rules as
+/// [`run`], for callers that already hold their candidates in memory.
+pub struct FuzzyMatcher {
+    pattern: Pattern,
+    matcher: Matcher,
+    utf32buf: Vec<char>,
+}
+
+impl FuzzyMatcher {
+    pub fn new(pattern_text: &str) -> Self {
+        Self {
+            pattern: create_pattern(pattern_text),
+            matcher: Matcher::new(nucleo_matcher::Config::DEFAULT),
+            utf32buf: Vec::new(),
+        }
+    }
+
+    /// Returns `None` when `haystack` does not match.
+    pub fn score(&mut self, haystack: &str) -> Option<u32> {
+        let haystack = Utf32Str::new(haystack, &mut self.utf32buf);
+        self.pattern.score(haystack, &mut self.matcher)
+    }
+
+    /// Like [`FuzzyMatcher::score`], but also returns the sorted, deduplicated
+    /// character indices that matched, for highlighting.
+    pub fn score_with_indices(&mut self, haystack: &str) -> Option<(u32, Vec<u32>)> {
+        let haystack = Utf32Str::new(haystack, &mut self.utf32buf);
+        let mut indices = Vec::new();
+        let score = self
+            .pattern
+            .indices(haystack, &mut self.matcher, &mut indices)?;
+        indices.sort_unstable();
+        indices.dedup();
+        Some((score, indices))
+    }
+}
+
 /// Sort matches in-place by descending score, then ascending path.
 fn sort_matches(matches: &mut [(u32, String)]) {
     matches.sort_by(cmp_by_score_desc_then_path_asc::<(u32, String), _, _>(
@@ -444,6 +481,18 @@ mod tests {
         assert_eq!(matches, expected);
     }
 
+    #[test]
+    fn fuzzy_matcher_scores_and_reports_indices() {
+        let mut matcher = FuzzyMatcher::new("fmt");
+        assert_eq!(matcher.score("hello"), None);
+
+        let (score, indices) = matcher
+            .score_with_indices("run cargo fmt")
+            .expect("query should match");
+        assert!(score > 0);
+        assert_eq!(indices, vec![10, 11, 12]);
+    }
+
     #[test]
     fn file_name_from_path_uses_basename() {
         assert_eq!(file_name_from_path("foo/bar.txt"), "bar.txt");
@@ -0,0 +1,328 @@
+use codex_core::LLAMA_CPP_OSS_PROVIDER_ID;
+use codex_core::VLLM_OSS_PROVIDER_ID;
+use codex_core::config::Config;
+use codex_core::models_manager::manager::ServedModel;
+use std::io;
+
+/// OpenAI-compatible local inference servers supported by this crate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LocalServerKind {
+    /// llama.cpp's `llama-server`.
+    LlamaCpp,
+    /// vLLM's OpenAI-compatible server (`vllm serve`).
+    Vllm,
+}
+
+impl LocalServerKind {
+    pub fn from_provider_id(provider_id: &str) -> Option<Self> {
+        match provider_id {
+            LLAMA_CPP_OSS_PROVIDER_ID => Some(Self::LlamaCpp),
+            VLLM_OSS_PROVIDER_ID => Some(Self::Vllm),
+            _ => None,
+        }
+    }
+
+    pub fn provider_id(self) -> &'static str {
+        match self {
+            Self::LlamaCpp => LLAMA_CPP_OSS_PROVIDER_ID,
+            Self::Vllm => VLLM_OSS_PROVIDER_ID,
+        }
+    }
+
+    pub fn display_name(self) -> &'static str {
+        match self {
+            Self::LlamaCpp => "llama.cpp",
+            Self::Vllm => "vLLM",
+        }
+    }
+
+    fn connection_error(self) -> &'static str {
+        match self {
+            Self::LlamaCpp => {
+                "llama.cpp server is not responding. Start it with 'llama-server -m <model.gguf> --port 8080'."
+            }
+            Self::Vllm => {
+                "vLLM server is not responding. Start it with 'vllm serve <model> --port 8000'."
+            }
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct LocalServerClient {
+    client: reqwest::Client,
+    base_url: String,
+    kind: LocalServerKind,
+}
+
+impl LocalServerClient {
+    pub async fn try_from_provider(config: &Config, kind: LocalServerKind) -> io::Result<Self> {
+        let provider_id = kind.provider_id();
+        let provider = config.model_providers.get(provider_id).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("Built-in provider {provider_id} not found"),
+            )
+        })?;
+        let base_url = provider.base_url.as_ref().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                "oss provider must have a base_url",
+            )
+        })?;
+
+        let client = Self::new(base_url, kind);
+        client.check_server().await?;
+        Ok(client)
+    }
+
+    fn new(base_url: impl Into<String>, kind: LocalServerKind) -> Self {
+        let client = reqwest::Client::builder()
+            .connect_timeout(std::time::Duration::from_secs(5))
+            .build()
+            .unwrap_or_else(|_| reqwest::Client::new());
+        Self {
+            client,
+            base_url: base_url.into(),
+            kind,
+        }
+    }
+
+    pub fn kind(&self) -> LocalServerKind {
+        self.kind
+    }
+
+    /// Both servers expose `/health` next to (not under) the `/v1` API root.
+    fn host_root(&self) -> &str {
+        let base_url = self.base_url.trim_end_matches('/');
+        base_url.strip_suffix("/v1").unwrap_or(base_url)
+    }
+
+    fn api_url(&self, path: &str) -> String {
+        format!("{}/{path}", self.base_url.trim_end_matches('/'))
+    }
+
+    /// Probe `/health`. llama.cpp answers 503 while it is still loading the
+    /// model, which is reported separately from an unreachable server.
+    pub async fn check_server(&self) -> io::Result<()> {
+        let url = format!("{}/health", self.host_root());
+        let Ok(response) = self.client.get(&url).send().await else {
+            return Err(io::Error::other(self.kind.connection_error()));
+        };
+
+        let status = response.status();
+        if status.is_success() {
+            Ok(())
+        } else if status == reqwest::StatusCode::SERVICE_UNAVAILABLE {
+            Err(io::Error::other(format!(
+                "{} server is still loading the model; try again shortly.",
+                self.kind.display_name()
+            )))
+        } else {
+            Err(io::Error::other(format!(
+                "Server returned error: {status} {}",
+                self.kind.connection_error()
+            )))
+        }
+    }
+
+    /// Return the models listed by `/v1/models`, with their context window
+    /// when the server reports one.
+    pub async fn fetch_models(&self) -> io::Result<Vec<ServedModel>> {
+        let json = self.get_json(&self.api_url("models")).await?;
+        let data = json["data"].as_array().ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidData, "No 'data' array in response")
+        })?;
+
+        let mut models: Vec<ServedModel> = data
+            .iter()
+            .filter_map(|model| {
+                let slug = model["id"].as_str()?.to_string();
+                let context_window = match self.kind {
+                    LocalServerKind::Vllm => model["max_model_len"].as_i64(),
+                    LocalServerKind::LlamaCpp => model["meta"]["n_ctx_train"].as_i64(),
+                };
+                Some(ServedModel {
+                    slug,
+                    context_window,
+                })
+            })
+            .collect();
+
+        // `n_ctx_train` is what the model was trained with; the window the
+        // server actually allocated is reported by `/props`.
+        if self.kind == LocalServerKind::LlamaCpp
+            && let Some(n_ctx) = self.fetch_llama_cpp_context_window().await
+        {
+            for model in &mut models {
+                model.context_window = Some(n_ctx);
+            }
+        }
+
+        Ok(models)
+    }
+
+    async fn fetch_llama_cpp_context_window(&self) -> Option<i64> {
+        let url = format!("{}/props", self.host_root());
+        match self.get_json(&url).await {
+            Ok(props) => props["default_generation_settings"]["n_ctx"]
+                .as_i64()
+                .or_else(|| props["n_ctx"].as_i64()),
+            Err(err) => {
+                tracing::debug!("Failed to read llama.cpp server props: {err}");
+                None
+            }
+        }
+    }
+
+    async fn get_json(&self, url: &str) -> io::Result<serde_json::Value> {
+        let response = self
+            .client
+            .get(url)
+            .send()
+            .await
+            .map_err(|e| io::Error::other(format!("Request failed: {e}")))?;
+
+        if !response.status().is_success() {
+            return Err(io::Error::other(format!(
+                "Failed to fetch {url}: {}",
+                response.status()
+            )));
+        }
+        response.json().await.map_err(|e| {
+            io::Error::new(io::ErrorKind::InvalidData, format!("JSON parse error: {e}"))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used, clippy::unwrap_used)]
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use wiremock::Mock;
+    use wiremock::MockServer;
+    use wiremock::ResponseTemplate;
+    use wiremock::matchers::method;
+    use wiremock::matchers::path;
+
+    fn network_disabled() -> bool {
+        std::env::var(codex_core::spawn::CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR).is_ok()
+    }
+
+    async fn mount_json(server: &MockServer, route: &str, body: serde_json::Value) {
+        Mock::given(method("GET"))
+            .and(path(route))
+            .respond_with(ResponseTemplate::new(200).set_body_json(body))
+            .mount(server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn vllm_models_report_max_model_len() {
+        if network_disabled() {
+            return;
+        }
+
+        let server = MockServer::start().await;
+        mount_json(
+            &server,
+            "/v1/models",
+            serde_json::json!({
+                "data": [{"id": "Qwen/Qwen2.5-Coder-32B-Instruct", "max_model_len": 32768}]
+            }),
+        )
+        .await;
+
+        let client = LocalServerClient::new(format!("{}/v1", server.uri()), LocalServerKind::Vllm);
+        let models = client.fetch_models().await.expect("fetch models");
+        assert_eq!(
+            models,
+            vec![ServedModel {
+                slug: "Qwen/Qwen2.5-Coder-32B-Instruct".to_string(),
+                context_window: Some(32_768),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn llama_cpp_prefers_runtime_context_from_props() {
+        if network_disabled() {
+            return;
+        }
+
+        let server = MockServer::start().await;
+        mount_json(
+            &server,
+            "/v1/models",
+            serde_json::json!({
+                "data": [{"id": "qwen2.5-coder-7b.gguf", "meta": {"n_ctx_train": 131072}}]
+            }),
+        )
+        .await;
+        mount_json(
+            &server,
+            "/props",
+            serde_json::json!({"default_generation_settings": {"n_ctx": 16384}}),
+        )
+        .await;
+
+        let client =
+            LocalServerClient::new(format!("{}/v1", server.uri()), LocalServerKind::LlamaCpp);
+        let models = client.fetch_models().await.expect("fetch models");
+        assert_eq!(
+            models,
+            vec![ServedModel {
+                slug: "qwen2.5-coder-7b.gguf".to_string(),
+                context_window: Some(16_384),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn health_probe_reports_loading_model() {
+        if network_disabled() {
+            return;
+        }
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/health"))
+            .respond_with(ResponseTemplate::new(503))
+            .mount(&server)
+            .await;
+
+        let client =
+            LocalServerClient::new(format!("{}/v1", server.uri()), LocalServerKind::LlamaCpp);
+        let err = client.check_server().await.expect_err("server is loading");
+        assert!(err.to_string().contains("still loading the model"));
+    }
+
+    #[tokio::test]
+    async fn health_probe_succeeds() {
+        if network_disabled() {
+            return;
+        }
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/health"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+
+        let client = LocalServerClient::new(format!("{}/v1/", server.uri()), LocalServerKind::Vllm);
+        client.check_server().await.expect("server is healthy");
+    }
+
+    #[test]
+    fn provider_ids_round_trip() {
+        for kind in [LocalServerKind::LlamaCpp, LocalServerKind::Vllm] {
+            assert_eq!(
+                LocalServerKind::from_provider_id(kind.provider_id()),
+                Some(kind)
+            );
+        }
+        assert_eq!(LocalServerKind::from_provider_id("ollama"), None);
+    }
+}
@@ -0,0 +1,80 @@
+mod client;
+
+pub use client::LocalServerClient;
+pub use client::LocalServerKind;
+use codex_core::config::Config;
+use codex_core::models_manager::manager::ServedModel;
+
+/// Prepare a local llama.cpp or vLLM server when `--oss` is selected.
+///
+/// - Ensures the server is reachable and has finished loading.
+/// - Checks that the requested model (if any) is among the served models.
+///
+/// Neither server can download models on demand, so unlike LM Studio and
+/// Ollama there is no pull step. When no model is requested, the first served
+/// model is used.
+pub async fn ensure_oss_ready(config: &Config, kind: LocalServerKind) -> std::io::Result<()> {
+    let client = LocalServerClient::try_from_provider(config, kind).await?;
+
+    let models = match client.fetch_models().await {
+        Ok(models) => models,
+        Err(err) => {
+            // Not fatal; higher layers may still proceed and surface errors later.
+            tracing::warn!(
+                "Failed to query local models from {}: {err}.",
+                kind.display_name()
+            );
+            return Ok(());
+        }
+    };
+
+    let Some(model) = config.model.as_ref() else {
+        return Ok(());
+    };
+    if models.iter().any(|served| &served.slug == model) {
+        return Ok(());
+    }
+
+    let served = models
+        .iter()
+        .map(|served| served.slug.as_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+    match kind {
+        // llama-server answers with whatever model it loaded regardless of
+        // the requested name, so a mismatch is only worth a warning.
+        LocalServerKind::LlamaCpp => {
+            tracing::warn!("llama.cpp server does not list model '{model}'; it serves: {served}");
+            Ok(())
+        }
+        LocalServerKind::Vllm => Err(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("vLLM server does not serve model '{model}'. Available models: {served}"),
+        )),
+    }
+}
+
+/// Returns the models served by the local server backing `config`'s model
+/// provider, or `None` when that provider is not a llama.cpp or vLLM server.
+pub async fn fetch_served_models(config: &Config) -> Option<Vec<ServedModel>> {
+    let kind = LocalServerKind::from_provider_id(&config.model_provider_id)?;
+    let client = LocalServerClient::try_from_provider(config, kind)
+        .await
+        .inspect_err(|err| {
+            tracing::warn!(
+                "Failed to reach local {} server: {err}",
+                kind.display_name()
+            );
+        })
+        .ok()?;
+    client
+        .fetch_models()
+        .await
+        .inspect_err(|err| {
+            tracing::warn!(
+                "Failed to query local models from {}: {err}",
+                kind.display_name()
+            );
+        })
+        .ok()
+}
@@ -9,6 +9,9 @@ use crate::exec_approval::handle_exec_approval_request;
 use crate::outgoing_message::OutgoingMessageSender;
 use crate::outgoing_message::OutgoingNotificationMeta;
 use crate::patch_approval::handle_patch_approval_request;
+use crate::progress::ProgressReporter;
+use crate::session_tools::ConversationSnapshots;
+use crate::session_tools::record_snapshot;
 use codex_core::CodexConversation;
 use codex_core::ConversationManager;
 use codex_core::NewConversation;
@@ -21,6 +24,8 @@ use codex_core::protocol::ExecApprovalRequestEvent;
 use codex_core::protocol::Op;
 use codex_core::protocol::Submission;
 use codex_core::protocol::TaskCompleteEvent;
+use codex_core::protocol::TurnAbortReason;
+use codex_core::protocol::TurnAbortedEvent;
 use codex_protocol::ConversationId;
 use codex_protocol::user_input::UserInput;
 use mcp_types::CallToolResult;
@@ -36,6 +41,7 @@ pub(crate) const INVALID_PARAMS_ERROR_CODE: i64 = -32602;
 ///
 /// On completion (success or error) the function sends the appropriate
 /// `tools/call` response so the LLM can continue the conversation.
+#[allow(clippy::too_many_arguments)]
 pub async fn run_codex_tool_session(
     id: RequestId,
     initial_prompt: String,
@@ -43,12 +49,10 @@ pub async fn run_codex_tool_session(
     outgoing: Arc<OutgoingMessageSender>,
     conversation_manager: Arc<ConversationManager>,
     running_requests_id_to_codex_uuid: Arc<Mutex<HashMap<RequestId, ConversationId>>>,
+    snapshots: ConversationSnapshots,
+    progress: Option<ProgressReporter>,
 ) {
-    let NewConversation {
-        conversation_id,
-        conversation,
-        session_configured,
-    } = match conversation_manager.new_conversation(config).await {
+    let new_conversation = match conversation_manager.new_conversation(config).await {
         Ok(res) => res,
         Err(e) => {
             let result = CallToolResult {
@@ -65,6 +69,35 @@ pub async fn run_codex_tool_session(
         }
     };
 
+    start_codex_tool_session(
+        id,
+        initial_prompt,
+        new_conversation,
+        outgoing,
+        running_requests_id_to_codex_uuid,
+        snapshots,
+        progress,
+    )
+    .await;
+}
+
+/// Announce a new or resumed conversation to the client and run its first
+/// turn with `initial_prompt`.
+pub(crate) async fn start_codex_tool_session(
+    id: RequestId,
+    initial_prompt: String,
+    new_conversation: NewConversation,
+    outgoing: Arc<OutgoingMessageSender>,
+    running_requests_id_to_codex_uuid: Arc<Mutex<HashMap<RequestId, ConversationId>>>,
+    snapshots: ConversationSnapshots,
+    progress: Option<ProgressReporter>,
+) {
+    let NewConversation {
+        conversation_id,
+        conversation,
+        session_configured,
+    } = new_conversation;
+
     let session_configured_event = Event {
         // Use a fake id value for now.
         id: "".to_string(),
@@ -106,13 +139,17 @@ pub async fn run_codex_tool_session(
 
     run_codex_tool_session_inner(
         conversation,
+        conversation_id,
         outgoing,
         id,
         running_requests_id_to_codex_uuid,
+        snapshots,
+        progress,
     )
     .await;
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn run_codex_tool_session_reply(
     conversation: Arc<CodexConversation>,
     outgoing: Arc<OutgoingMessageSender>,
@@ -120,6 +157,8 @@ pub async fn run_codex_tool_session_reply(
     prompt: String,
     running_requests_id_to_codex_uuid: Arc<Mutex<HashMap<RequestId, ConversationId>>>,
     conversation_id: ConversationId,
+    snapshots: ConversationSnapshots,
+    progress: Option<ProgressReporter>,
 ) {
     running_requests_id_to_codex_uuid
         .lock()
@@ -142,18 +181,24 @@ pub async fn run_codex_tool_session_reply(
 
     run_codex_tool_session_inner(
         conversation,
+        conversation_id,
         outgoing,
         request_id,
         running_requests_id_to_codex_uuid,
+        snapshots,
+        progress,
     )
     .await;
 }
 
 async fn run_codex_tool_session_inner(
     codex: Arc<CodexConversation>,
+    conversation_id: ConversationId,
     outgoing: Arc<OutgoingMessageSender>,
     request_id: RequestId,
     running_requests_id_to_codex_uuid: Arc<Mutex<HashMap<RequestId, ConversationId>>>,
+    snapshots: ConversationSnapshots,
+    mut progress: Option<ProgressReporter>,
 ) {
     let request_id_str = match &request_id {
         RequestId::String(s) => s.clone(),
@@ -171,6 +216,10 @@ async fn run_codex_tool_session_inner(
                         Some(OutgoingNotificationMeta::new(Some(request_id.clone()))),
                     )
                     .await;
+                record_snapshot(&snapshots, conversation_id, &event.msg).await;
+                if let Some(progress) = progress.as_mut() {
+                    progress.report(&event.msg).await;
+                }
 
                 match event.msg {
                     EventMsg::ExecApprovalRequest(ExecApprovalRequestEvent {
@@ -254,6 +303,34 @@ async fn run_codex_tool_session_inner(
                             .remove(&request_id);
                         break;
                     }
+                    EventMsg::TurnAborted(TurnAbortedEvent { reason }) => {
+                        // A cancelled request was already unregistered and
+                        // must not receive a response; an interrupt requested
+                        // through `codex-interrupt` concludes the tool call.
+                        let was_running = running_requests_id_to_codex_uuid
+                            .lock()
+                            .await
+                            .remove(&request_id)
+                            .is_some();
+                        if was_running {
+                            let reason = match reason {
+                                TurnAbortReason::Interrupted => "interrupted",
+                                TurnAbortReason::Replaced => "replaced by a new turn",
+                                TurnAbortReason::ReviewEnded => "ended with the review",
+                            };
+                            let result = CallToolResult {
+                                content: vec![ContentBlock::TextContent(TextContent {
+                                    r#type: "text".to_string(),
+                                    text: format!("Codex turn {reason}"),
+                                    annotations: None,
+                                })],
+                                is_error: Some(true),
+                                structured_content: None,
+                            };
+                            outgoing.send_response(request_id.clone(), result).await;
+                        }
+                        break;
+                    }
                     EventMsg::SessionConfigured(_) => {
                         tracing::error!("unexpected SessionConfigured event");
                     }
@@ -292,8 +369,8 @@ async fn run_codex_tool_session_inner(
                     | EventMsg::WebSearchBegin(_)
                     | EventMsg::WebSearchEnd(_)
                     | EventMsg::GetHistoryEntryResponse(_)
+                    | EventMsg::ListHistoryEntriesResponse(_)
                     | EventMsg::PlanUpdate(_)
-                    | EventMsg::TurnAborted(_)
                     | EventMsg::UserMessage(_)
                     | EventMsg::ShutdownComplete
                     | EventMsg::ViewImageToolCall(_)
@@ -307,6 +384,7 @@ async fn run_codex_tool_session_inner(
                     | EventMsg::SkillsUpdateAvailable
                     | EventMsg::UndoStarted(_)
                     | EventMsg::UndoCompleted(_)
+                    | EventMsg::ThreadRolledBack(_)
                     | EventMsg::ExitedReviewMode(_)
                     | EventMsg::ContextCompacted(_)
                     | EventMsg::DeprecationNotice(_) => {
@@ -332,6 +410,8 @@ async fn run_codex_tool_session_inner(
                     structured_content: None,
                 };
                 outgoing.send_response(request_id.clone(), result).await;
+                // The event stream only fails once the conversation is gone.
+                snapshots.lock().await.remove(&conversation_id);
                 break;
             }
         }
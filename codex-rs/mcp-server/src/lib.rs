@@ -26,6 +26,10 @@ mod exec_approval;
 pub(crate) mod message_processor;
 mod outgoing_message;
 mod patch_approval;
+mod progress;
+mod resources;
+mod session_tool_config;
+mod session_tools;
 
 use crate::message_processor::MessageProcessor;
 use crate::outgoing_message::OutgoingMessage;
@@ -37,6 +41,9 @@ pub use crate::exec_approval::ExecApprovalElicitRequestParams;
 pub use crate::exec_approval::ExecApprovalResponse;
 pub use crate::patch_approval::PatchApprovalElicitRequestParams;
 pub use crate::patch_approval::PatchApprovalResponse;
+pub use crate::session_tool_config::CodexConversationParam;
+pub use crate::session_tool_config::CodexListSessionsParam;
+pub use crate::session_tool_config::CodexResumeParam;
 
 /// Size of the bounded channels used to communicate between tasks. The value
 /// is a balance between throughput and memory usage – 128 messages should be
@@ -101,6 +108,7 @@ pub async fn run_main(
         let mut processor = MessageProcessor::new(
             outgoing_message_sender,
             codex_linux_sandbox_exe,
+            cli_config_overrides,
             std::sync::Arc::new(config),
         );
         async move {
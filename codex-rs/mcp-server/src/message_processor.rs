@@ -7,12 +7,32 @@ use crate::codex_tool_config::create_tool_for_codex_tool_call_param;
 use crate::codex_tool_config::create_tool_for_codex_tool_call_reply_param;
 use crate::error_code::INVALID_REQUEST_ERROR_CODE;
 use crate::outgoing_message::OutgoingMessageSender;
+use crate::progress::ProgressReporter;
+use crate::progress::progress_token_from_params;
+use crate::session_tool_config::CodexConversationParam;
+use crate::session_tool_config::CodexListSessionsParam;
+use crate::session_tool_config::CodexResumeParam;
+use crate::session_tool_config::INTERRUPT_TOOL;
+use crate::session_tool_config::LIST_SESSIONS_TOOL;
+use crate::session_tool_config::PLAN_TOOL;
+use crate::session_tool_config::RESUME_TOOL;
+use crate::session_tool_config::TURN_DIFF_TOOL;
+use crate::session_tool_config::create_tool_for_interrupt_param;
+use crate::session_tool_config::create_tool_for_list_sessions_param;
+use crate::session_tool_config::create_tool_for_plan_param;
+use crate::session_tool_config::create_tool_for_resume_param;
+use crate::session_tool_config::create_tool_for_turn_diff_param;
+use crate::session_tools;
+use crate::session_tools::ConversationSnapshots;
+use codex_common::CliConfigOverrides;
 use codex_protocol::ConversationId;
+use codex_protocol::protocol::SessionMetaLine;
 use codex_protocol::protocol::SessionSource;
 
 use codex_core::AuthManager;
 use codex_core::ConversationManager;
 use codex_core::config::Config;
+use codex_core::config::ConfigOverrides;
 use codex_core::default_client::USER_AGENT_SUFFIX;
 use codex_core::default_client::get_codex_user_agent;
 use codex_core::protocol::Submission;
@@ -27,10 +47,13 @@ use mcp_types::JSONRPCRequest;
 use mcp_types::JSONRPCResponse;
 use mcp_types::ListToolsResult;
 use mcp_types::ModelContextProtocolRequest;
+use mcp_types::ProgressToken;
 use mcp_types::RequestId;
+use mcp_types::ServerCapabilitiesResources;
 use mcp_types::ServerCapabilitiesTools;
 use mcp_types::ServerNotification;
 use mcp_types::TextContent;
+use serde::de::DeserializeOwned;
 use serde_json::json;
 use std::sync::Arc;
 use tokio::sync::Mutex;
@@ -40,8 +63,14 @@ pub(crate) struct MessageProcessor {
     outgoing: Arc<OutgoingMessageSender>,
     initialized: bool,
     codex_linux_sandbox_exe: Option<PathBuf>,
+    /// `-c` overrides the server was started with, reapplied when a session
+    /// is resumed with its own working directory.
+    cli_config_overrides: CliConfigOverrides,
+    config: Arc<Config>,
+    auth_manager: Arc<AuthManager>,
     conversation_manager: Arc<ConversationManager>,
     running_requests_id_to_codex_uuid: Arc<Mutex<HashMap<RequestId, ConversationId>>>,
+    conversation_snapshots: ConversationSnapshots,
 }
 
 impl MessageProcessor {
@@ -50,6 +79,7 @@ impl MessageProcessor {
     pub(crate) fn new(
         outgoing: OutgoingMessageSender,
         codex_linux_sandbox_exe: Option<PathBuf>,
+        cli_config_overrides: CliConfigOverrides,
         config: Arc<Config>,
     ) -> Self {
         let outgoing = Arc::new(outgoing);
@@ -58,20 +88,28 @@ impl MessageProcessor {
             false,
             config.cli_auth_credentials_store_mode,
         );
-        let conversation_manager =
-            Arc::new(ConversationManager::new(auth_manager, SessionSource::Mcp));
+        let conversation_manager = Arc::new(ConversationManager::new(
+            auth_manager.clone(),
+            SessionSource::Mcp,
+        ));
         Self {
             outgoing,
             initialized: false,
             codex_linux_sandbox_exe,
+            cli_config_overrides,
+            config,
+            auth_manager,
             conversation_manager,
             running_requests_id_to_codex_uuid: Arc::new(Mutex::new(HashMap::new())),
+            conversation_snapshots: ConversationSnapshots::default(),
         }
     }
 
     pub(crate) async fn process_request(&mut self, request: JSONRPCRequest) {
         // Hold on to the ID so we can respond.
         let request_id = request.id.clone();
+        // `_meta` is not part of the typed params, so read the token first.
+        let progress_token = progress_token_from_params(request.params.as_ref());
 
         let client_request = match McpClientRequest::try_from(request) {
             Ok(client_request) => client_request,
@@ -90,13 +128,14 @@ impl MessageProcessor {
                 self.handle_ping(request_id, params).await;
             }
             McpClientRequest::ListResourcesRequest(params) => {
-                self.handle_list_resources(params);
+                self.handle_list_resources(request_id, params).await;
             }
             McpClientRequest::ListResourceTemplatesRequest(params) => {
-                self.handle_list_resource_templates(params);
+                self.handle_list_resource_templates(request_id, params)
+                    .await;
             }
             McpClientRequest::ReadResourceRequest(params) => {
-                self.handle_read_resource(params);
+                self.handle_read_resource(request_id, params).await;
             }
             McpClientRequest::SubscribeRequest(params) => {
                 self.handle_subscribe(params);
@@ -114,7 +153,8 @@ impl MessageProcessor {
                 self.handle_list_tools(request_id, params).await;
             }
             McpClientRequest::CallToolRequest(params) => {
-                self.handle_call_tool(request_id, params).await;
+                self.handle_call_tool(request_id, params, progress_token)
+                    .await;
             }
             McpClientRequest::SetLevelRequest(params) => {
                 self.handle_set_level(params);
@@ -209,7 +249,10 @@ impl MessageProcessor {
                 experimental: None,
                 logging: None,
                 prompts: None,
-                resources: None,
+                resources: Some(ServerCapabilitiesResources {
+                    list_changed: None,
+                    subscribe: None,
+                }),
                 tools: Some(ServerCapabilitiesTools {
                     list_changed: Some(true),
                 }),
@@ -246,26 +289,49 @@ impl MessageProcessor {
             .await;
     }
 
-    fn handle_list_resources(
+    async fn handle_list_resources(
         &self,
+        id: RequestId,
         params: <mcp_types::ListResourcesRequest as mcp_types::ModelContextProtocolRequest>::Params,
     ) {
         tracing::info!("resources/list -> params: {:?}", params);
+        let cursor = params.and_then(|params| params.cursor);
+        match crate::resources::list_resources(&self.config, cursor.as_deref()).await {
+            Ok(result) => {
+                self.send_response::<mcp_types::ListResourcesRequest>(id, result)
+                    .await;
+            }
+            Err(error) => self.outgoing.send_error(id, error).await,
+        }
     }
 
-    fn handle_list_resource_templates(
+    async fn handle_list_resource_templates(
         &self,
+        id: RequestId,
         params:
             <mcp_types::ListResourceTemplatesRequest as mcp_types::ModelContextProtocolRequest>::Params,
     ) {
         tracing::info!("resources/templates/list -> params: {:?}", params);
+        self.send_response::<mcp_types::ListResourceTemplatesRequest>(
+            id,
+            crate::resources::list_resource_templates(),
+        )
+        .await;
     }
 
-    fn handle_read_resource(
+    async fn handle_read_resource(
         &self,
+        id: RequestId,
         params: <mcp_types::ReadResourceRequest as mcp_types::ModelContextProtocolRequest>::Params,
     ) {
         tracing::info!("resources/read -> params: {:?}", params);
+        match crate::resources::read_resource(&self.config, &params.uri).await {
+            Ok(result) => {
+                self.send_response::<mcp_types::ReadResourceRequest>(id, result)
+                    .await;
+            }
+            Err(error) => self.outgoing.send_error(id, error).await,
+        }
     }
 
     fn handle_subscribe(
@@ -306,6 +372,11 @@ impl MessageProcessor {
             tools: vec![
                 create_tool_for_codex_tool_call_param(),
                 create_tool_for_codex_tool_call_reply_param(),
+                create_tool_for_list_sessions_param(),
+                create_tool_for_resume_param(),
+                create_tool_for_interrupt_param(),
+                create_tool_for_turn_diff_param(),
+                create_tool_for_plan_param(),
             ],
             next_cursor: None,
         };
@@ -318,16 +389,25 @@ impl MessageProcessor {
         &self,
         id: RequestId,
         params: <mcp_types::CallToolRequest as mcp_types::ModelContextProtocolRequest>::Params,
+        progress_token: Option<ProgressToken>,
     ) {
         tracing::info!("tools/call -> params: {:?}", params);
         let CallToolRequestParams { name, arguments } = params;
+        let progress =
+            progress_token.map(|token| ProgressReporter::new(token, self.outgoing.clone()));
 
         match name.as_str() {
-            "codex" => self.handle_tool_call_codex(id, arguments).await,
+            "codex" => self.handle_tool_call_codex(id, arguments, progress).await,
             "codex-reply" => {
-                self.handle_tool_call_codex_session_reply(id, arguments)
+                self.handle_tool_call_codex_session_reply(id, arguments, progress)
                     .await
             }
+            LIST_SESSIONS_TOOL => self.handle_tool_call_list_sessions(id, arguments).await,
+            RESUME_TOOL => self.handle_tool_call_resume(id, arguments, progress).await,
+            INTERRUPT_TOOL => self.handle_tool_call_interrupt(id, arguments).await,
+            TURN_DIFF_TOOL | PLAN_TOOL => {
+                self.handle_tool_call_snapshot(id, &name, arguments).await
+            }
             _ => {
                 let result = CallToolResult {
                     content: vec![ContentBlock::TextContent(TextContent {
@@ -343,7 +423,12 @@ impl MessageProcessor {
             }
         }
     }
-    async fn handle_tool_call_codex(&self, id: RequestId, arguments: Option<serde_json::Value>) {
+    async fn handle_tool_call_codex(
+        &self,
+        id: RequestId,
+        arguments: Option<serde_json::Value>,
+        progress: Option<ProgressReporter>,
+    ) {
         let (initial_prompt, config): (String, Config) = match arguments {
             Some(json_val) => match serde_json::from_value::<CodexToolCallParam>(json_val) {
                 Ok(tool_cfg) => match tool_cfg
@@ -405,6 +490,7 @@ impl MessageProcessor {
         let outgoing = self.outgoing.clone();
         let conversation_manager = self.conversation_manager.clone();
         let running_requests_id_to_codex_uuid = self.running_requests_id_to_codex_uuid.clone();
+        let snapshots = self.conversation_snapshots.clone();
 
         // Spawn an async task to handle the Codex session so that we do not
         // block the synchronous message-processing loop.
@@ -417,6 +503,8 @@ impl MessageProcessor {
                 outgoing,
                 conversation_manager,
                 running_requests_id_to_codex_uuid,
+                snapshots,
+                progress,
             )
             .await;
         });
@@ -426,6 +514,7 @@ impl MessageProcessor {
         &self,
         request_id: RequestId,
         arguments: Option<serde_json::Value>,
+        progress: Option<ProgressReporter>,
     ) {
         tracing::info!("tools/call -> params: {:?}", arguments);
 
@@ -520,6 +609,7 @@ impl MessageProcessor {
             let outgoing = outgoing.clone();
             let prompt = prompt.clone();
             let running_requests_id_to_codex_uuid = running_requests_id_to_codex_uuid.clone();
+            let snapshots = self.conversation_snapshots.clone();
 
             async move {
                 crate::codex_tool_runner::run_codex_tool_session_reply(
@@ -529,12 +619,233 @@ impl MessageProcessor {
                     prompt,
                     running_requests_id_to_codex_uuid,
                     conversation_id,
+                    snapshots,
+                    progress,
                 )
                 .await;
             }
         });
     }
 
+    async fn handle_tool_call_list_sessions(
+        &self,
+        request_id: RequestId,
+        arguments: Option<serde_json::Value>,
+    ) {
+        let params = match arguments {
+            Some(json_val) => match serde_json::from_value::<CodexListSessionsParam>(json_val) {
+                Ok(params) => params,
+                Err(e) => {
+                    let result = session_tools::error_result(format!(
+                        "Failed to parse arguments for {LIST_SESSIONS_TOOL}: {e}"
+                    ));
+                    self.send_response::<mcp_types::CallToolRequest>(request_id, result)
+                        .await;
+                    return;
+                }
+            },
+            None => CodexListSessionsParam::default(),
+        };
+
+        let result = match session_tools::list_sessions(
+            &self.config,
+            params.limit,
+            params.cursor.as_deref(),
+        )
+        .await
+        {
+            Ok(page) => session_tools::sessions_page_result(page),
+            Err(e) => session_tools::error_result(format!("Failed to list sessions: {e}")),
+        };
+        self.send_response::<mcp_types::CallToolRequest>(request_id, result)
+            .await;
+    }
+
+    async fn handle_tool_call_resume(
+        &self,
+        request_id: RequestId,
+        arguments: Option<serde_json::Value>,
+        progress: Option<ProgressReporter>,
+    ) {
+        let CodexResumeParam {
+            conversation_id,
+            prompt,
+        } = match parse_tool_arguments(RESUME_TOOL, arguments) {
+            Ok(params) => params,
+            Err(result) => {
+                self.send_response::<mcp_types::CallToolRequest>(request_id, result)
+                    .await;
+                return;
+            }
+        };
+
+        match self.resume_conversation(&conversation_id).await {
+            Ok(new_conversation) => {
+                let outgoing = self.outgoing.clone();
+                let running_requests_id_to_codex_uuid =
+                    self.running_requests_id_to_codex_uuid.clone();
+                let snapshots = self.conversation_snapshots.clone();
+                task::spawn(async move {
+                    crate::codex_tool_runner::start_codex_tool_session(
+                        request_id,
+                        prompt,
+                        new_conversation,
+                        outgoing,
+                        running_requests_id_to_codex_uuid,
+                        snapshots,
+                        progress,
+                    )
+                    .await;
+                });
+            }
+            Err(message) => {
+                let result = session_tools::error_result(message);
+                self.send_response::<mcp_types::CallToolRequest>(request_id, result)
+                    .await;
+            }
+        }
+    }
+
+    /// Loads a recorded session so it can continue. Sessions resume in the
+    /// working directory they were recorded in.
+    async fn resume_conversation(
+        &self,
+        conversation_id: &str,
+    ) -> Result<codex_core::NewConversation, String> {
+        let parsed_id = ConversationId::from_string(conversation_id)
+            .map_err(|e| format!("Failed to parse conversation_id: {e}"))?;
+        if self
+            .conversation_manager
+            .get_conversation(parsed_id)
+            .await
+            .is_ok()
+        {
+            return Err(format!(
+                "Conversation {conversation_id} is already running; use codex-reply to continue it."
+            ));
+        }
+
+        let rollout_path =
+            codex_core::find_conversation_path_by_id_str(&self.config.codex_home, conversation_id)
+                .await
+                .map_err(|e| format!("Failed to locate session {conversation_id}: {e}"))?
+                .ok_or_else(|| format!("No recorded session found for {conversation_id}"))?;
+        let cwd = codex_core::read_head_for_summary(&rollout_path)
+            .await
+            .ok()
+            .and_then(|head| head.into_iter().next())
+            .and_then(|first| serde_json::from_value::<SessionMetaLine>(first).ok())
+            .map(|meta_line| meta_line.meta.cwd);
+
+        let overrides = ConfigOverrides {
+            cwd,
+            codex_linux_sandbox_exe: self.codex_linux_sandbox_exe.clone(),
+            ..Default::default()
+        };
+        let cli_overrides = self
+            .cli_config_overrides
+            .parse_overrides()
+            .map_err(|e| format!("Failed to parse -c overrides: {e}"))?;
+        let config =
+            Config::load_with_cli_overrides_and_harness_overrides(cli_overrides, overrides)
+                .await
+                .map_err(|e| format!("Failed to load Codex configuration: {e}"))?;
+
+        self.conversation_manager
+            .resume_conversation_from_rollout(config, rollout_path, self.auth_manager.clone())
+            .await
+            .map_err(|e| format!("Failed to resume session {conversation_id}: {e}"))
+    }
+
+    async fn handle_tool_call_interrupt(
+        &self,
+        request_id: RequestId,
+        arguments: Option<serde_json::Value>,
+    ) {
+        let result = match parse_tool_arguments::<CodexConversationParam>(INTERRUPT_TOOL, arguments)
+        {
+            Ok(params) => self.interrupt_conversation(&params.conversation_id).await,
+            Err(result) => result,
+        };
+        self.send_response::<mcp_types::CallToolRequest>(request_id, result)
+            .await;
+    }
+
+    async fn interrupt_conversation(&self, conversation_id: &str) -> CallToolResult {
+        let conversation_id = match ConversationId::from_string(conversation_id) {
+            Ok(id) => id,
+            Err(e) => {
+                return session_tools::error_result(format!(
+                    "Failed to parse conversation_id: {e}"
+                ));
+            }
+        };
+        let codex = match self
+            .conversation_manager
+            .get_conversation(conversation_id)
+            .await
+        {
+            Ok(codex) => codex,
+            Err(_) => {
+                return session_tools::error_result(format!(
+                    "Session not found for conversation_id: {conversation_id}"
+                ));
+            }
+        };
+        match codex.submit(codex_core::protocol::Op::Interrupt).await {
+            Ok(_) => session_tools::text_result(format!(
+                "Interrupt requested for conversation {conversation_id}."
+            )),
+            Err(e) => session_tools::error_result(format!("Failed to interrupt Codex: {e}")),
+        }
+    }
+
+    async fn handle_tool_call_snapshot(
+        &self,
+        request_id: RequestId,
+        tool_name: &str,
+        arguments: Option<serde_json::Value>,
+    ) {
+        let params = match parse_tool_arguments::<CodexConversationParam>(tool_name, arguments) {
+            Ok(params) => params,
+            Err(result) => {
+                self.send_response::<mcp_types::CallToolRequest>(request_id, result)
+                    .await;
+                return;
+            }
+        };
+        let result = match ConversationId::from_string(&params.conversation_id) {
+            Ok(conversation_id)
+                if self
+                    .conversation_manager
+                    .get_conversation(conversation_id)
+                    .await
+                    .is_ok() =>
+            {
+                let snapshots = self.conversation_snapshots.lock().await;
+                let snapshot = snapshots.get(&conversation_id);
+                if tool_name == PLAN_TOOL {
+                    session_tools::plan_result(snapshot)
+                } else {
+                    session_tools::turn_diff_result(snapshot)
+                }
+            }
+            Ok(conversation_id) => {
+                // The conversation is gone, so its snapshot can never be read again.
+                self.conversation_snapshots
+                    .lock()
+                    .await
+                    .remove(&conversation_id);
+                session_tools::error_result(format!(
+                    "Session not found for conversation_id: {conversation_id}"
+                ))
+            }
+            Err(e) => session_tools::error_result(format!("Failed to parse conversation_id: {e}")),
+        };
+        self.send_response::<mcp_types::CallToolRequest>(request_id, result)
+            .await;
+    }
+
     fn handle_set_level(
         &self,
         params: <mcp_types::SetLevelRequest as mcp_types::ModelContextProtocolRequest>::Params,
@@ -564,11 +875,12 @@ impl MessageProcessor {
             RequestId::Integer(i) => i.to_string(),
         };
 
-        // Obtain the conversation id while holding the first lock, then release.
+        // Unregister the request before interrupting so the tool runner does
+        // not answer a request the client has cancelled.
         let conversation_id = {
-            let map_guard = self.running_requests_id_to_codex_uuid.lock().await;
-            match map_guard.get(&request_id) {
-                Some(id) => *id,
+            let mut map_guard = self.running_requests_id_to_codex_uuid.lock().await;
+            match map_guard.remove(&request_id) {
+                Some(id) => id,
                 None => {
                     tracing::warn!("Session not found for request_id: {}", request_id_string);
                     return;
@@ -599,13 +911,7 @@ impl MessageProcessor {
             .await;
         if let Err(e) = err {
             tracing::error!("Failed to submit interrupt to Codex: {e}");
-            return;
         }
-        // unregister the id so we don't keep it in the map
-        self.running_requests_id_to_codex_uuid
-            .lock()
-            .await
-            .remove(&request_id);
     }
 
     fn handle_progress_notification(
@@ -653,3 +959,20 @@ impl MessageProcessor {
         tracing::info!("notifications/message -> params: {:?}", params);
     }
 }
+
+/// Parses required tool arguments, or returns the error result to send back.
+fn parse_tool_arguments<T: DeserializeOwned>(
+    tool_name: &str,
+    arguments: Option<serde_json::Value>,
+) -> Result<T, CallToolResult> {
+    let Some(arguments) = arguments else {
+        return Err(crate::session_tools::error_result(format!(
+            "Missing arguments for {tool_name} tool-call."
+        )));
+    };
+    serde_json::from_value(arguments).map_err(|e| {
+        crate::session_tools::error_result(format!(
+            "Failed to parse arguments for {tool_name}: {e}"
+        ))
+    })
+}
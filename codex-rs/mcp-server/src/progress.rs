@@ -0,0 +1,165 @@
+//! `notifications/progress` for tool calls that supplied a `progressToken`.
+//!
+//! The raw `codex/event` notifications remain the detailed stream; progress
+//! notifications carry a short human-readable summary of what the running
+//! turn is doing, which generic MCP clients can surface without knowing the
+//! Codex event schema.
+
+use std::sync::Arc;
+
+use codex_core::protocol::EventMsg;
+use codex_protocol::plan_tool::StepStatus;
+use mcp_types::ModelContextProtocolNotification;
+use mcp_types::ProgressNotification;
+use mcp_types::ProgressNotificationParams;
+use mcp_types::ProgressToken;
+
+use crate::outgoing_message::OutgoingMessageSender;
+use crate::outgoing_message::OutgoingNotification;
+
+/// Longest agent message excerpt included in a progress message.
+const MESSAGE_EXCERPT_CHARS: usize = 120;
+
+pub(crate) struct ProgressReporter {
+    token: ProgressToken,
+    outgoing: Arc<OutgoingMessageSender>,
+    progress: f64,
+}
+
+impl ProgressReporter {
+    pub(crate) fn new(token: ProgressToken, outgoing: Arc<OutgoingMessageSender>) -> Self {
+        Self {
+            token,
+            outgoing,
+            progress: 0.0,
+        }
+    }
+
+    /// Sends a progress notification when `msg` marks a step worth reporting.
+    pub(crate) async fn report(&mut self, msg: &EventMsg) {
+        let Some(message) = progress_message(msg) else {
+            return;
+        };
+        // Progress must increase with every notification; the total is unknown.
+        self.progress += 1.0;
+        let params = ProgressNotificationParams {
+            message: Some(message),
+            progress: self.progress,
+            progress_token: self.token.clone(),
+            total: None,
+        };
+        let Ok(params) = serde_json::to_value(params) else {
+            return;
+        };
+        self.outgoing
+            .send_notification(OutgoingNotification {
+                method: ProgressNotification::METHOD.to_string(),
+                params: Some(params),
+            })
+            .await;
+    }
+}
+
+/// Reads `params._meta.progressToken` from a raw request, which the typed
+/// request params do not carry.
+pub(crate) fn progress_token_from_params(
+    params: Option<&serde_json::Value>,
+) -> Option<ProgressToken> {
+    let token = params?.get("_meta")?.get("progressToken")?;
+    serde_json::from_value(token.clone()).ok()
+}
+
+pub(crate) fn progress_message(msg: &EventMsg) -> Option<String> {
+    let message = match msg {
+        EventMsg::TaskStarted(_) => "Turn started".to_string(),
+        EventMsg::AgentMessage(event) => {
+            let excerpt = event.message.lines().next().unwrap_or_default();
+            let mut excerpt: String = excerpt.chars().take(MESSAGE_EXCERPT_CHARS).collect();
+            if excerpt.len() < event.message.len() {
+                excerpt.push('…');
+            }
+            format!("Agent: {excerpt}")
+        }
+        EventMsg::ExecCommandBegin(event) => {
+            let command = shlex::try_join(event.command.iter().map(String::as_str))
+                .unwrap_or_else(|_| event.command.join(" "));
+            format!("Running {command}")
+        }
+        EventMsg::ExecApprovalRequest(_) => "Waiting for command approval".to_string(),
+        EventMsg::ApplyPatchApprovalRequest(_) => "Waiting for patch approval".to_string(),
+        EventMsg::PatchApplyBegin(event) => match event.changes.len() {
+            1 => "Editing 1 file".to_string(),
+            count => format!("Editing {count} files"),
+        },
+        EventMsg::McpToolCallBegin(event) => format!(
+            "Calling {}.{}",
+            event.invocation.server, event.invocation.tool
+        ),
+        EventMsg::WebSearchBegin(_) => "Searching the web".to_string(),
+        EventMsg::PlanUpdate(plan) => {
+            let completed = plan
+                .plan
+                .iter()
+                .filter(|item| matches!(item.status, StepStatus::Completed))
+                .count();
+            format!("Plan: {completed}/{} steps complete", plan.plan.len())
+        }
+        EventMsg::ContextCompacted(_) => "Compacted conversation history".to_string(),
+        EventMsg::TurnAborted(_) => "Turn interrupted".to_string(),
+        EventMsg::TaskComplete(_) => "Turn complete".to_string(),
+        _ => return None,
+    };
+    Some(message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_core::protocol::AgentMessageEvent;
+    use codex_protocol::plan_tool::PlanItemArg;
+    use codex_protocol::plan_tool::UpdatePlanArgs;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    #[test]
+    fn reads_progress_token_from_meta() {
+        let params = json!({
+            "name": "codex",
+            "arguments": { "prompt": "hi" },
+            "_meta": { "progressToken": "tok-1" },
+        });
+        assert_eq!(
+            progress_token_from_params(Some(&params)),
+            Some(ProgressToken::String("tok-1".to_string()))
+        );
+        assert_eq!(progress_token_from_params(Some(&json!({}))), None);
+    }
+
+    #[test]
+    fn summarizes_turn_events() {
+        let plan = UpdatePlanArgs {
+            explanation: None,
+            plan: vec![
+                PlanItemArg {
+                    step: "one".to_string(),
+                    status: StepStatus::Completed,
+                },
+                PlanItemArg {
+                    step: "two".to_string(),
+                    status: StepStatus::Pending,
+                },
+            ],
+        };
+        assert_eq!(
+            progress_message(&EventMsg::PlanUpdate(plan)),
+            Some("Plan: 1/2 steps complete".to_string())
+        );
+        assert_eq!(
+            progress_message(&EventMsg::AgentMessage(AgentMessageEvent {
+                message: "Done.\nDetails follow.".to_string(),
+            })),
+            Some("Agent: Done.…".to_string())
+        );
+        assert_eq!(progress_message(&EventMsg::ShutdownComplete), None);
+    }
+}
@@ -0,0 +1,176 @@
+//! MCP resources exposed by the server: recorded session rollouts and the
+//! `AGENTS.md` files that apply to the server's working directory.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_core::config::Config;
+use codex_core::find_conversation_path_by_id_str;
+use codex_core::project_doc::DEFAULT_PROJECT_DOC_FILENAME;
+use codex_core::project_doc::LOCAL_PROJECT_DOC_FILENAME;
+use codex_core::project_doc::discover_project_doc_paths;
+use mcp_types::JSONRPCErrorError;
+use mcp_types::ListResourceTemplatesResult;
+use mcp_types::ListResourcesResult;
+use mcp_types::ReadResourceResult;
+use mcp_types::ReadResourceResultContents;
+use mcp_types::Resource;
+use mcp_types::ResourceTemplate;
+use mcp_types::TextResourceContents;
+
+use crate::codex_tool_runner::INVALID_PARAMS_ERROR_CODE;
+use crate::error_code::INTERNAL_ERROR_CODE;
+use crate::session_tools::list_sessions;
+
+const ROLLOUT_URI_PREFIX: &str = "codex://rollouts/";
+const FILE_URI_PREFIX: &str = "file://";
+const ROLLOUT_MIME_TYPE: &str = "application/x-ndjson";
+const MARKDOWN_MIME_TYPE: &str = "text/markdown";
+/// Rollouts listed per `resources/list` page.
+const ROLLOUTS_PER_PAGE: u32 = 50;
+
+pub(crate) async fn list_resources(
+    config: &Config,
+    cursor: Option<&str>,
+) -> Result<ListResourcesResult, JSONRPCErrorError> {
+    let mut resources = Vec::new();
+
+    // Instructions are listed once, ahead of the first page of rollouts.
+    if cursor.is_none() {
+        for path in agents_md_paths(config) {
+            let title = path.display().to_string();
+            let size = std::fs::metadata(&path)
+                .ok()
+                .and_then(|metadata| i64::try_from(metadata.len()).ok());
+            resources.push(Resource {
+                annotations: None,
+                description: Some("Instructions Codex follows in this workspace".to_string()),
+                mime_type: Some(MARKDOWN_MIME_TYPE.to_string()),
+                name: file_name(&path),
+                size,
+                title: Some(title),
+                uri: file_uri(&path),
+            });
+        }
+    }
+
+    let page = list_sessions(config, Some(ROLLOUTS_PER_PAGE), cursor)
+        .await
+        .map_err(|err| internal_error(format!("failed to list sessions: {err}")))?;
+    for session in page.sessions {
+        let preview = session.preview.lines().next().unwrap_or_default();
+        resources.push(Resource {
+            annotations: None,
+            description: Some(format!(
+                "Codex session in {}, last updated {}",
+                session.cwd.display(),
+                session.updated_at.as_deref().unwrap_or("unknown")
+            )),
+            mime_type: Some(ROLLOUT_MIME_TYPE.to_string()),
+            name: format!("rollout-{}", session.conversation_id),
+            size: None,
+            title: (!preview.is_empty()).then(|| preview.to_string()),
+            uri: format!("{ROLLOUT_URI_PREFIX}{}", session.conversation_id),
+        });
+    }
+
+    Ok(ListResourcesResult {
+        next_cursor: page.next_cursor,
+        resources,
+    })
+}
+
+pub(crate) fn list_resource_templates() -> ListResourceTemplatesResult {
+    ListResourceTemplatesResult {
+        next_cursor: None,
+        resource_templates: vec![ResourceTemplate {
+            annotations: None,
+            description: Some(
+                "The recorded rollout (JSONL) of a Codex session, by conversation id".to_string(),
+            ),
+            mime_type: Some(ROLLOUT_MIME_TYPE.to_string()),
+            name: "rollout".to_string(),
+            title: Some("Codex session rollout".to_string()),
+            uri_template: format!("{ROLLOUT_URI_PREFIX}{{conversationId}}"),
+        }],
+    }
+}
+
+pub(crate) async fn read_resource(
+    config: &Config,
+    uri: &str,
+) -> Result<ReadResourceResult, JSONRPCErrorError> {
+    let (path, mime_type) = if let Some(conversation_id) = uri.strip_prefix(ROLLOUT_URI_PREFIX) {
+        let path = find_conversation_path_by_id_str(&config.codex_home, conversation_id)
+            .await
+            .map_err(|err| internal_error(format!("failed to locate rollout: {err}")))?
+            .ok_or_else(|| invalid_params(format!("no rollout found for {conversation_id}")))?;
+        (path, ROLLOUT_MIME_TYPE)
+    } else if let Some(path) = uri.strip_prefix(FILE_URI_PREFIX) {
+        // Only the instruction files listed by `resources/list` are readable.
+        let path = PathBuf::from(path);
+        if !agents_md_paths(config).contains(&path) {
+            return Err(invalid_params(format!("unknown resource: {uri}")));
+        }
+        (path, MARKDOWN_MIME_TYPE)
+    } else {
+        return Err(invalid_params(format!("unknown resource: {uri}")));
+    };
+
+    let text = tokio::fs::read_to_string(&path)
+        .await
+        .map_err(|err| internal_error(format!("failed to read {}: {err}", path.display())))?;
+    Ok(ReadResourceResult {
+        contents: vec![ReadResourceResultContents::TextResourceContents(
+            TextResourceContents {
+                mime_type: Some(mime_type.to_string()),
+                text,
+                uri: uri.to_string(),
+            },
+        )],
+    })
+}
+
+/// The global instructions in `CODEX_HOME` followed by the project docs from
+/// the repository root down to the working directory.
+fn agents_md_paths(config: &Config) -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+    if let Some(global) = [LOCAL_PROJECT_DOC_FILENAME, DEFAULT_PROJECT_DOC_FILENAME]
+        .iter()
+        .map(|name| config.codex_home.join(name))
+        .find(|path| path.is_file())
+    {
+        paths.push(global);
+    }
+    match discover_project_doc_paths(config) {
+        Ok(project_docs) => paths.extend(project_docs),
+        Err(err) => tracing::warn!("failed to discover AGENTS.md files: {err}"),
+    }
+    paths
+}
+
+fn file_uri(path: &Path) -> String {
+    format!("{FILE_URI_PREFIX}{}", path.display())
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| DEFAULT_PROJECT_DOC_FILENAME.to_string())
+}
+
+fn invalid_params(message: String) -> JSONRPCErrorError {
+    JSONRPCErrorError {
+        code: INVALID_PARAMS_ERROR_CODE,
+        message,
+        data: None,
+    }
+}
+
+fn internal_error(message: String) -> JSONRPCErrorError {
+    JSONRPCErrorError {
+        code: INTERNAL_ERROR_CODE,
+        message,
+        data: None,
+    }
+}
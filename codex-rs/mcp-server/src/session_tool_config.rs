@@ -0,0 +1,185 @@
+//! Parameters and `Tool` definitions for the session-management tools that
+//! complement `codex` and `codex-reply`.
+
+use mcp_types::Tool;
+use mcp_types::ToolInputSchema;
+use schemars::JsonSchema;
+use schemars::r#gen::SchemaSettings;
+use serde::Deserialize;
+use serde::Serialize;
+
+pub(crate) const LIST_SESSIONS_TOOL: &str = "codex-list-sessions";
+pub(crate) const RESUME_TOOL: &str = "codex-resume";
+pub(crate) const INTERRUPT_TOOL: &str = "codex-interrupt";
+pub(crate) const TURN_DIFF_TOOL: &str = "codex-turn-diff";
+pub(crate) const PLAN_TOOL: &str = "codex-plan";
+
+/// Arguments for `codex-list-sessions`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CodexListSessionsParam {
+    /// Maximum number of sessions to return, newest first. Defaults to 20.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+
+    /// Opaque cursor returned as `nextCursor` by a previous call.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+}
+
+/// Arguments for `codex-resume`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CodexResumeParam {
+    /// The conversation id of a recorded session, as returned by
+    /// `codex-list-sessions`.
+    pub conversation_id: String,
+
+    /// The *next user prompt* to continue the resumed conversation.
+    pub prompt: String,
+}
+
+/// Arguments for the tools that act on a running conversation.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CodexConversationParam {
+    /// The conversation id for this Codex session.
+    pub conversation_id: String,
+}
+
+pub(crate) fn create_tool_for_list_sessions_param() -> Tool {
+    Tool {
+        name: LIST_SESSIONS_TOOL.to_string(),
+        title: Some("List Codex Sessions".to_string()),
+        input_schema: tool_input_schema::<CodexListSessionsParam>(),
+        output_schema: None,
+        description: Some(
+            "List recorded Codex sessions, newest first, with their conversation id, working directory and first prompt."
+                .to_string(),
+        ),
+        annotations: None,
+    }
+}
+
+pub(crate) fn create_tool_for_resume_param() -> Tool {
+    Tool {
+        name: RESUME_TOOL.to_string(),
+        title: Some("Resume Codex Session".to_string()),
+        input_schema: tool_input_schema::<CodexResumeParam>(),
+        output_schema: None,
+        description: Some(
+            "Resume a recorded Codex session from its rollout and continue it with a new prompt."
+                .to_string(),
+        ),
+        annotations: None,
+    }
+}
+
+pub(crate) fn create_tool_for_interrupt_param() -> Tool {
+    conversation_tool(
+        INTERRUPT_TOOL,
+        "Interrupt Codex",
+        "Interrupt the turn currently running in a Codex conversation.",
+    )
+}
+
+pub(crate) fn create_tool_for_turn_diff_param() -> Tool {
+    conversation_tool(
+        TURN_DIFF_TOOL,
+        "Codex Turn Diff",
+        "Return the unified diff of the changes Codex made during the current or most recent turn.",
+    )
+}
+
+pub(crate) fn create_tool_for_plan_param() -> Tool {
+    conversation_tool(
+        PLAN_TOOL,
+        "Codex Plan",
+        "Return the latest plan Codex published for a conversation.",
+    )
+}
+
+fn conversation_tool(name: &str, title: &str, description: &str) -> Tool {
+    Tool {
+        name: name.to_string(),
+        title: Some(title.to_string()),
+        input_schema: tool_input_schema::<CodexConversationParam>(),
+        output_schema: None,
+        description: Some(description.to_string()),
+        annotations: None,
+    }
+}
+
+fn tool_input_schema<T: JsonSchema>() -> ToolInputSchema {
+    let schema = SchemaSettings::draft2019_09()
+        .with(|s| {
+            s.inline_subschemas = true;
+            s.option_add_null_type = false;
+        })
+        .into_generator()
+        .into_root_schema_for::<T>();
+
+    #[expect(clippy::expect_used)]
+    let schema_value = serde_json::to_value(&schema).expect("tool schema should serialise to JSON");
+
+    serde_json::from_value::<ToolInputSchema>(schema_value).unwrap_or_else(|e| {
+        panic!("failed to create Tool from schema: {e}");
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn verify_list_sessions_tool_json_schema() {
+        let tool = create_tool_for_list_sessions_param();
+        let tool_json = serde_json::to_value(&tool).expect("tool serializes");
+        let expected_tool_json = serde_json::json!({
+          "description": "List recorded Codex sessions, newest first, with their conversation id, working directory and first prompt.",
+          "inputSchema": {
+            "properties": {
+              "cursor": {
+                "description": "Opaque cursor returned as `nextCursor` by a previous call.",
+                "type": "string"
+              },
+              "limit": {
+                "description": "Maximum number of sessions to return, newest first. Defaults to 20.",
+                "format": "uint32",
+                "minimum": 0.0,
+                "type": "integer"
+              },
+            },
+            "type": "object",
+          },
+          "name": "codex-list-sessions",
+          "title": "List Codex Sessions",
+        });
+        assert_eq!(expected_tool_json, tool_json);
+    }
+
+    #[test]
+    fn verify_conversation_tool_json_schema() {
+        let tool = create_tool_for_plan_param();
+        let tool_json = serde_json::to_value(&tool).expect("tool serializes");
+        let expected_tool_json = serde_json::json!({
+          "description": "Return the latest plan Codex published for a conversation.",
+          "inputSchema": {
+            "properties": {
+              "conversationId": {
+                "description": "The conversation id for this Codex session.",
+                "type": "string"
+              },
+            },
+            "required": [
+              "conversationId",
+            ],
+            "type": "object",
+          },
+          "name": "codex-plan",
+          "title": "Codex Plan",
+        });
+        assert_eq!(expected_tool_json, tool_json);
+    }
+}
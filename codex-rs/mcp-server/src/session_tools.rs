@@ -0,0 +1,342 @@
+//! Implementations of the session-management tools: listing recorded
+//! sessions and reporting the latest turn diff and plan of a conversation.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use codex_core::RolloutRecorder;
+use codex_core::config::Config;
+use codex_core::parse_cursor;
+use codex_core::parse_turn_item;
+use codex_core::protocol::EventMsg;
+use codex_protocol::ConversationId;
+use codex_protocol::items::TurnItem;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::plan_tool::StepStatus;
+use codex_protocol::plan_tool::UpdatePlanArgs;
+use codex_protocol::protocol::SessionMetaLine;
+use codex_protocol::protocol::SessionSource;
+use codex_protocol::protocol::USER_MESSAGE_BEGIN;
+use mcp_types::CallToolResult;
+use mcp_types::ContentBlock;
+use mcp_types::TextContent;
+use serde::Serialize;
+use serde_json::json;
+use tokio::sync::Mutex;
+
+const DEFAULT_LIST_LIMIT: u32 = 20;
+const MAX_LIST_LIMIT: u32 = 100;
+
+/// Sessions started by interactive clients as well as by this server.
+const LISTED_SESSION_SOURCES: &[SessionSource] = &[
+    SessionSource::Cli,
+    SessionSource::VSCode,
+    SessionSource::Exec,
+    SessionSource::Mcp,
+];
+
+/// The latest turn diff and plan seen for a conversation, kept up to date by
+/// the tool runner so they can be read while a turn is still streaming.
+/// Dropped once the conversation shuts down.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct ConversationSnapshot {
+    pub(crate) turn_diff: Option<String>,
+    pub(crate) plan: Option<UpdatePlanArgs>,
+}
+
+pub(crate) type ConversationSnapshots = Arc<Mutex<HashMap<ConversationId, ConversationSnapshot>>>;
+
+/// Records the parts of `msg` that `codex-turn-diff` and `codex-plan` report.
+pub(crate) async fn record_snapshot(
+    snapshots: &ConversationSnapshots,
+    conversation_id: ConversationId,
+    msg: &EventMsg,
+) {
+    match msg {
+        EventMsg::TaskStarted(_) => {
+            let mut snapshots = snapshots.lock().await;
+            snapshots.entry(conversation_id).or_default().turn_diff = None;
+        }
+        EventMsg::TurnDiff(event) => {
+            let mut snapshots = snapshots.lock().await;
+            snapshots.entry(conversation_id).or_default().turn_diff =
+                Some(event.unified_diff.clone());
+        }
+        EventMsg::PlanUpdate(plan) => {
+            let mut snapshots = snapshots.lock().await;
+            snapshots.entry(conversation_id).or_default().plan = Some(plan.clone());
+        }
+        EventMsg::ShutdownComplete => {
+            snapshots.lock().await.remove(&conversation_id);
+        }
+        _ => {}
+    }
+}
+
+/// One entry in the `codex-list-sessions` result.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SessionSummary {
+    pub(crate) conversation_id: ConversationId,
+    pub(crate) created_at: Option<String>,
+    pub(crate) updated_at: Option<String>,
+    pub(crate) cwd: PathBuf,
+    pub(crate) preview: String,
+    pub(crate) path: PathBuf,
+}
+
+pub(crate) struct SessionsPage {
+    pub(crate) sessions: Vec<SessionSummary>,
+    pub(crate) next_cursor: Option<String>,
+}
+
+/// Lists recorded sessions from `CODEX_HOME/sessions`, newest first.
+pub(crate) async fn list_sessions(
+    config: &Config,
+    limit: Option<u32>,
+    cursor: Option<&str>,
+) -> std::io::Result<SessionsPage> {
+    let limit = limit.unwrap_or(DEFAULT_LIST_LIMIT).clamp(1, MAX_LIST_LIMIT) as usize;
+    let cursor = cursor.and_then(parse_cursor);
+    let page = RolloutRecorder::list_conversations(
+        &config.codex_home,
+        limit,
+        cursor.as_ref(),
+        LISTED_SESSION_SOURCES,
+        None,
+        config.model_provider_id.as_str(),
+    )
+    .await?;
+
+    let sessions =
+        page.items
+            .into_iter()
+            .filter_map(|item| {
+                let meta_line = item.head.first().and_then(|first| {
+                    serde_json::from_value::<SessionMetaLine>(first.clone()).ok()
+                })?;
+                Some(SessionSummary {
+                    conversation_id: meta_line.meta.id,
+                    created_at: item.created_at,
+                    updated_at: item.updated_at,
+                    cwd: meta_line.meta.cwd,
+                    preview: first_user_message(&item.head).unwrap_or_default(),
+                    path: item.path,
+                })
+            })
+            .collect();
+    let next_cursor = page
+        .next_cursor
+        .as_ref()
+        .and_then(|cursor| serde_json::to_value(cursor).ok())
+        .and_then(|value| value.as_str().map(str::to_owned));
+
+    Ok(SessionsPage {
+        sessions,
+        next_cursor,
+    })
+}
+
+/// Returns the first user message in a rollout head, without the legacy
+/// request prefix.
+pub(crate) fn first_user_message(head: &[serde_json::Value]) -> Option<String> {
+    let message = head
+        .iter()
+        .filter_map(|value| serde_json::from_value::<ResponseItem>(value.clone()).ok())
+        .find_map(|item| match parse_turn_item(&item) {
+            Some(TurnItem::UserMessage(user)) => Some(user.message()),
+            _ => None,
+        })?;
+    let message = match message.find(USER_MESSAGE_BEGIN) {
+        Some(idx) => message[idx + USER_MESSAGE_BEGIN.len()..].trim().to_string(),
+        None => message,
+    };
+    Some(message)
+}
+
+pub(crate) fn sessions_page_result(page: SessionsPage) -> CallToolResult {
+    let text = if page.sessions.is_empty() {
+        "No recorded Codex sessions.".to_string()
+    } else {
+        page.sessions
+            .iter()
+            .map(|session| {
+                let preview = session.preview.lines().next().unwrap_or_default();
+                format!(
+                    "{}  {}  {}  {preview}",
+                    session.conversation_id,
+                    session.updated_at.as_deref().unwrap_or("-"),
+                    session.cwd.display(),
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    CallToolResult {
+        content: vec![text_content(text)],
+        is_error: None,
+        structured_content: Some(json!({
+            "sessions": page.sessions,
+            "nextCursor": page.next_cursor,
+        })),
+    }
+}
+
+pub(crate) fn turn_diff_result(snapshot: Option<&ConversationSnapshot>) -> CallToolResult {
+    let diff = snapshot.and_then(|snapshot| snapshot.turn_diff.clone());
+    let text = match &diff {
+        Some(diff) if !diff.is_empty() => diff.clone(),
+        _ => "No changes in the current turn.".to_string(),
+    };
+    CallToolResult {
+        content: vec![text_content(text)],
+        is_error: None,
+        structured_content: Some(json!({ "unifiedDiff": diff })),
+    }
+}
+
+pub(crate) fn plan_result(snapshot: Option<&ConversationSnapshot>) -> CallToolResult {
+    let Some(plan) = snapshot.and_then(|snapshot| snapshot.plan.as_ref()) else {
+        return CallToolResult {
+            content: vec![text_content("No plan has been published yet.".to_string())],
+            is_error: None,
+            structured_content: None,
+        };
+    };
+    CallToolResult {
+        content: vec![text_content(render_plan(plan))],
+        is_error: None,
+        structured_content: serde_json::to_value(plan).ok(),
+    }
+}
+
+/// Renders a plan as a Markdown checklist.
+pub(crate) fn render_plan(plan: &UpdatePlanArgs) -> String {
+    let mut lines = Vec::new();
+    if let Some(explanation) = plan
+        .explanation
+        .as_deref()
+        .filter(|explanation| !explanation.trim().is_empty())
+    {
+        lines.push(explanation.trim().to_string());
+        lines.push(String::new());
+    }
+    for item in &plan.plan {
+        let marker = match item.status {
+            StepStatus::Completed => "[x]",
+            StepStatus::InProgress => "[~]",
+            StepStatus::Pending => "[ ]",
+        };
+        lines.push(format!("- {marker} {}", item.step));
+    }
+    lines.join("\n")
+}
+
+pub(crate) fn error_result(text: String) -> CallToolResult {
+    CallToolResult {
+        content: vec![text_content(text)],
+        is_error: Some(true),
+        structured_content: None,
+    }
+}
+
+pub(crate) fn text_result(text: String) -> CallToolResult {
+    CallToolResult {
+        content: vec![text_content(text)],
+        is_error: None,
+        structured_content: None,
+    }
+}
+
+fn text_content(text: String) -> ContentBlock {
+    ContentBlock::TextContent(TextContent {
+        r#type: "text".to_string(),
+        text,
+        annotations: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::plan_tool::PlanItemArg;
+    use codex_protocol::protocol::TaskStartedEvent;
+    use codex_protocol::protocol::TurnDiffEvent;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn render_plan_as_checklist() {
+        let plan = UpdatePlanArgs {
+            explanation: Some("Fix the parser first.".to_string()),
+            plan: vec![
+                PlanItemArg {
+                    step: "Reproduce the bug".to_string(),
+                    status: StepStatus::Completed,
+                },
+                PlanItemArg {
+                    step: "Patch the tokenizer".to_string(),
+                    status: StepStatus::InProgress,
+                },
+                PlanItemArg {
+                    step: "Add a regression test".to_string(),
+                    status: StepStatus::Pending,
+                },
+            ],
+        };
+
+        assert_eq!(
+            render_plan(&plan),
+            "Fix the parser first.\n\n- [x] Reproduce the bug\n- [~] Patch the tokenizer\n- [ ] Add a regression test"
+        );
+    }
+
+    #[tokio::test]
+    async fn turn_diff_resets_when_a_new_turn_starts() {
+        let snapshots = ConversationSnapshots::default();
+        let conversation_id = ConversationId::new();
+
+        record_snapshot(
+            &snapshots,
+            conversation_id,
+            &EventMsg::TurnDiff(TurnDiffEvent {
+                unified_diff: "diff --git a/a b/a\n".to_string(),
+            }),
+        )
+        .await;
+        assert_eq!(
+            snapshots.lock().await[&conversation_id]
+                .turn_diff
+                .as_deref(),
+            Some("diff --git a/a b/a\n")
+        );
+
+        record_snapshot(
+            &snapshots,
+            conversation_id,
+            &EventMsg::TaskStarted(TaskStartedEvent {
+                model_context_window: None,
+            }),
+        )
+        .await;
+        assert_eq!(snapshots.lock().await[&conversation_id].turn_diff, None);
+    }
+
+    #[tokio::test]
+    async fn snapshot_is_dropped_on_shutdown() {
+        let snapshots = ConversationSnapshots::default();
+        let conversation_id = ConversationId::new();
+
+        record_snapshot(
+            &snapshots,
+            conversation_id,
+            &EventMsg::TurnDiff(TurnDiffEvent {
+                unified_diff: "diff --git a/a b/a\n".to_string(),
+            }),
+        )
+        .await;
+        record_snapshot(&snapshots, conversation_id, &EventMsg::ShutdownComplete).await;
+
+        assert!(snapshots.lock().await.is_empty());
+    }
+}
@@ -22,8 +22,10 @@ use mcp_types::JSONRPCMessage;
 use mcp_types::JSONRPCNotification;
 use mcp_types::JSONRPCRequest;
 use mcp_types::JSONRPCResponse;
+use mcp_types::ListResourcesRequestParams;
 use mcp_types::ModelContextProtocolNotification;
 use mcp_types::ModelContextProtocolRequest;
+use mcp_types::ReadResourceRequestParams;
 use mcp_types::RequestId;
 use pretty_assertions::assert_eq;
 use serde_json::json;
@@ -156,6 +158,7 @@ impl McpProcess {
                 id: RequestId::Integer(request_id),
                 result: json!({
                     "capabilities": {
+                        "resources": {},
                         "tools": {
                             "listChanged": true
                         },
@@ -189,13 +192,43 @@ impl McpProcess {
         &mut self,
         params: CodexToolCallParam,
     ) -> anyhow::Result<i64> {
-        let codex_tool_call_params = CallToolRequestParams {
-            name: "codex".to_string(),
-            arguments: Some(serde_json::to_value(params)?),
+        self.send_tool_call("codex", serde_json::to_value(params)?)
+            .await
+    }
+
+    /// Calls the tool `name` with `arguments` and returns the request id.
+    pub async fn send_tool_call(
+        &mut self,
+        name: &str,
+        arguments: serde_json::Value,
+    ) -> anyhow::Result<i64> {
+        let tool_call_params = CallToolRequestParams {
+            name: name.to_string(),
+            arguments: Some(arguments),
         };
         self.send_request(
             mcp_types::CallToolRequest::METHOD,
-            Some(serde_json::to_value(codex_tool_call_params)?),
+            Some(serde_json::to_value(tool_call_params)?),
+        )
+        .await
+    }
+
+    pub async fn send_list_resources_request(
+        &mut self,
+        params: Option<ListResourcesRequestParams>,
+    ) -> anyhow::Result<i64> {
+        let params = params.map(serde_json::to_value).transpose()?;
+        self.send_request(mcp_types::ListResourcesRequest::METHOD, params)
+            .await
+    }
+
+    pub async fn send_read_resource_request(
+        &mut self,
+        params: ReadResourceRequestParams,
+    ) -> anyhow::Result<i64> {
+        self.send_request(
+            mcp_types::ReadResourceRequest::METHOD,
+            Some(serde_json::to_value(params)?),
         )
         .await
     }
@@ -0,0 +1,177 @@
+use std::path::Path;
+
+use codex_mcp_server::CodexConversationParam;
+use codex_mcp_server::CodexListSessionsParam;
+use mcp_test_support::McpProcess;
+use mcp_test_support::to_response;
+use mcp_types::CallToolResult;
+use mcp_types::ListResourcesResult;
+use mcp_types::ReadResourceRequestParams;
+use mcp_types::ReadResourceResult;
+use mcp_types::ReadResourceResultContents;
+use mcp_types::RequestId;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use tempfile::TempDir;
+use tokio::time::timeout;
+
+const DEFAULT_READ_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+const CONVERSATION_ID: &str = "67e55044-10b1-426f-9247-bb680e5fe0c8";
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn list_sessions_returns_recorded_rollouts() -> anyhow::Result<()> {
+    let codex_home = TempDir::new()?;
+    write_rollout(codex_home.path(), "Fix the flaky test")?;
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let request_id = mcp
+        .send_tool_call(
+            "codex-list-sessions",
+            serde_json::to_value(CodexListSessionsParam::default())?,
+        )
+        .await?;
+    let response = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    let result: CallToolResult = to_response(response)?;
+
+    assert_eq!(result.is_error, None);
+    let structured = result.structured_content.expect("structured sessions");
+    assert_eq!(
+        structured["sessions"][0]["conversationId"],
+        json!(CONVERSATION_ID)
+    );
+    assert_eq!(
+        structured["sessions"][0]["preview"],
+        json!("Fix the flaky test")
+    );
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn rollouts_and_agents_md_are_resources() -> anyhow::Result<()> {
+    let codex_home = TempDir::new()?;
+    write_rollout(codex_home.path(), "Summarize the repo")?;
+    std::fs::write(codex_home.path().join("AGENTS.md"), "Always run tests.\n")?;
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let request_id = mcp.send_list_resources_request(None).await?;
+    let response = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    let ListResourcesResult { resources, .. } = to_response(response)?;
+    let uris: Vec<&str> = resources
+        .iter()
+        .map(|resource| resource.uri.as_str())
+        .collect();
+    let agents_md_uri = format!("file://{}", codex_home.path().join("AGENTS.md").display());
+    let rollout_uri = format!("codex://rollouts/{CONVERSATION_ID}");
+    // Project docs discovered from the test's working directory may be listed
+    // as well, but instructions always come before the rollouts.
+    assert_eq!(uris.first(), Some(&agents_md_uri.as_str()));
+    assert_eq!(uris.last(), Some(&rollout_uri.as_str()));
+
+    assert_eq!(
+        read_resource_text(&mut mcp, &agents_md_uri).await?,
+        "Always run tests.\n"
+    );
+    assert!(
+        read_resource_text(&mut mcp, &rollout_uri)
+            .await?
+            .contains("Summarize the repo")
+    );
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn plan_for_unknown_conversation_is_an_error() -> anyhow::Result<()> {
+    let codex_home = TempDir::new()?;
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let request_id = mcp
+        .send_tool_call(
+            "codex-plan",
+            serde_json::to_value(CodexConversationParam {
+                conversation_id: CONVERSATION_ID.to_string(),
+            })?,
+        )
+        .await?;
+    let response = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    let result: CallToolResult = to_response(response)?;
+
+    assert_eq!(result.is_error, Some(true));
+    Ok(())
+}
+
+async fn read_resource_text(mcp: &mut McpProcess, uri: &str) -> anyhow::Result<String> {
+    let request_id = mcp
+        .send_read_resource_request(ReadResourceRequestParams {
+            uri: uri.to_string(),
+        })
+        .await?;
+    let response = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    let ReadResourceResult { contents } = to_response(response)?;
+    match contents.into_iter().next() {
+        Some(ReadResourceResultContents::TextResourceContents(text)) => Ok(text.text),
+        other => anyhow::bail!("unexpected resource contents: {other:?}"),
+    }
+}
+
+/// Writes a minimal CLI rollout under `CODEX_HOME/sessions`.
+fn write_rollout(codex_home: &Path, prompt: &str) -> std::io::Result<()> {
+    let dir = codex_home.join("sessions/2025/01/02");
+    std::fs::create_dir_all(&dir)?;
+    let timestamp = "2025-01-02T12:00:00Z";
+    let lines = [
+        json!({
+            "timestamp": timestamp,
+            "type": "session_meta",
+            "payload": {
+                "id": CONVERSATION_ID,
+                "timestamp": timestamp,
+                "cwd": "/",
+                "originator": "codex",
+                "cli_version": "0.0.0",
+                "instructions": null,
+                "source": "cli",
+                "model_provider": "openai",
+            },
+        }),
+        json!({
+            "timestamp": timestamp,
+            "type": "response_item",
+            "payload": {
+                "type": "message",
+                "role": "user",
+                "content": [{ "type": "input_text", "text": prompt }],
+            },
+        }),
+        json!({
+            "timestamp": timestamp,
+            "type": "event_msg",
+            "payload": { "type": "user_message", "message": prompt, "kind": "plain" },
+        }),
+    ]
+    .map(|line| line.to_string());
+    std::fs::write(
+        dir.join(format!(
+            "rollout-2025-01-02T12-00-00-{CONVERSATION_ID}.jsonl"
+        )),
+        lines.join("\n") + "\n",
+    )
+}
@@ -11,6 +11,7 @@ pub struct OtelSettings {
     pub codex_home: PathBuf,
     pub exporter: OtelExporter,
     pub trace_exporter: OtelExporter,
+    pub metrics_exporter: OtelExporter,
 }
 
 #[derive(Clone, Debug)]
@@ -42,4 +43,11 @@ pub enum OtelExporter {
         protocol: OtelHttpProtocol,
         tls: Option<OtelTlsConfig>,
     },
+    /// JSON lines under `CODEX_HOME/log`; logs and traces only.
+    File,
+    /// Prometheus text format; metrics only.
+    Prometheus {
+        listen: Option<String>,
+        textfile: Option<AbsolutePathBuf>,
+    },
 }
@@ -0,0 +1,350 @@
+//! Log and span exporters that append newline-delimited JSON to daily-rotated
+//! files under `CODEX_HOME/log`, so telemetry can be inspected without a
+//! collector.
+
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::future::Future;
+use std::io::Write;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use chrono::DateTime;
+use chrono::SecondsFormat;
+use chrono::Utc;
+use opentelemetry::InstrumentationScope;
+use opentelemetry::KeyValue;
+use opentelemetry::Value;
+use opentelemetry::logs::AnyValue;
+use opentelemetry::trace::SpanId;
+use opentelemetry::trace::Status;
+use opentelemetry_sdk::Resource;
+use opentelemetry_sdk::error::OTelSdkError;
+use opentelemetry_sdk::error::OTelSdkResult;
+use opentelemetry_sdk::logs::LogBatch;
+use opentelemetry_sdk::logs::LogExporter;
+use opentelemetry_sdk::logs::SdkLogRecord;
+use opentelemetry_sdk::trace::SpanData;
+use opentelemetry_sdk::trace::SpanExporter;
+use serde_json::Map;
+use serde_json::json;
+use tracing_appender::rolling::RollingFileAppender;
+use tracing_appender::rolling::Rotation;
+
+/// File name prefix for exported log events, e.g. `codex-otel-events.2025-01-31.jsonl`.
+pub const EVENTS_FILE_PREFIX: &str = "codex-otel-events";
+/// File name prefix for exported spans.
+pub const SPANS_FILE_PREFIX: &str = "codex-otel-spans";
+const FILE_SUFFIX: &str = "jsonl";
+/// Number of daily files kept per prefix before the oldest is deleted.
+const MAX_FILES: usize = 7;
+
+/// Appends one JSON document per line to a daily-rotated file.
+struct JsonLinesWriter {
+    appender: Mutex<RollingFileAppender>,
+}
+
+impl JsonLinesWriter {
+    fn new(log_dir: &Path, prefix: &str) -> Result<Self, Box<dyn Error>> {
+        fs::create_dir_all(log_dir)?;
+        let appender = RollingFileAppender::builder()
+            .rotation(Rotation::DAILY)
+            .filename_prefix(prefix)
+            .filename_suffix(FILE_SUFFIX)
+            .max_log_files(MAX_FILES)
+            .build(log_dir)?;
+        Ok(Self {
+            appender: Mutex::new(appender),
+        })
+    }
+
+    fn write_lines(&self, lines: impl IntoIterator<Item = serde_json::Value>) -> OTelSdkResult {
+        let mut buf = Vec::new();
+        for line in lines {
+            serde_json::to_writer(&mut buf, &line).map_err(internal_failure)?;
+            buf.push(b'\n');
+        }
+        if buf.is_empty() {
+            return Ok(());
+        }
+
+        let mut appender = self
+            .appender
+            .lock()
+            .map_err(|_| OTelSdkError::InternalFailure("file exporter lock poisoned".into()))?;
+        appender.write_all(&buf).map_err(internal_failure)?;
+        appender.flush().map_err(internal_failure)
+    }
+}
+
+impl fmt::Debug for JsonLinesWriter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JsonLinesWriter").finish_non_exhaustive()
+    }
+}
+
+/// Writes each log record as a JSON line to `codex-otel-events.<date>.jsonl`.
+#[derive(Debug)]
+pub(crate) struct FileLogExporter {
+    writer: JsonLinesWriter,
+    resource: Resource,
+}
+
+impl FileLogExporter {
+    pub(crate) fn new(log_dir: &Path) -> Result<Self, Box<dyn Error>> {
+        Ok(Self {
+            writer: JsonLinesWriter::new(log_dir, EVENTS_FILE_PREFIX)?,
+            resource: Resource::builder_empty().build(),
+        })
+    }
+}
+
+impl LogExporter for FileLogExporter {
+    fn export(&self, batch: LogBatch<'_>) -> impl Future<Output = OTelSdkResult> + Send {
+        let resource = resource_json(&self.resource);
+        let result = self.writer.write_lines(
+            batch
+                .iter()
+                .map(|(record, scope)| log_record_json(record, scope, &resource)),
+        );
+        std::future::ready(result)
+    }
+
+    fn set_resource(&mut self, resource: &Resource) {
+        self.resource = resource.clone();
+    }
+}
+
+/// Writes each finished span as a JSON line to `codex-otel-spans.<date>.jsonl`.
+#[derive(Debug)]
+pub(crate) struct FileSpanExporter {
+    writer: JsonLinesWriter,
+    resource: Resource,
+}
+
+impl FileSpanExporter {
+    pub(crate) fn new(log_dir: &Path) -> Result<Self, Box<dyn Error>> {
+        Ok(Self {
+            writer: JsonLinesWriter::new(log_dir, SPANS_FILE_PREFIX)?,
+            resource: Resource::builder_empty().build(),
+        })
+    }
+}
+
+impl SpanExporter for FileSpanExporter {
+    fn export(&self, batch: Vec<SpanData>) -> impl Future<Output = OTelSdkResult> + Send {
+        let resource = resource_json(&self.resource);
+        let result = self
+            .writer
+            .write_lines(batch.iter().map(|span| span_json(span, &resource)));
+        std::future::ready(result)
+    }
+
+    fn set_resource(&mut self, resource: &Resource) {
+        self.resource = resource.clone();
+    }
+}
+
+fn log_record_json(
+    record: &SdkLogRecord,
+    scope: &InstrumentationScope,
+    resource: &serde_json::Value,
+) -> serde_json::Value {
+    let attributes: Map<String, serde_json::Value> = record
+        .attributes_iter()
+        .map(|(key, value)| (key.as_str().to_string(), any_value_json(value)))
+        .collect();
+    let trace_context = record.trace_context();
+
+    json!({
+        "timestamp": record
+            .timestamp()
+            .or_else(|| record.observed_timestamp())
+            .map(format_time),
+        "severity": record.severity_text(),
+        "event.name": record.event_name(),
+        "target": record.target(),
+        "scope": scope.name(),
+        "trace_id": trace_context.map(|context| context.trace_id.to_string()),
+        "span_id": trace_context.map(|context| context.span_id.to_string()),
+        "body": record.body().map(any_value_json),
+        "attributes": attributes,
+        "resource": resource,
+    })
+}
+
+fn span_json(span: &SpanData, resource: &serde_json::Value) -> serde_json::Value {
+    let duration_ms = span
+        .end_time
+        .duration_since(span.start_time)
+        .map(|duration| duration.as_secs_f64() * 1000.0)
+        .unwrap_or_default();
+    let parent_span_id =
+        (span.parent_span_id != SpanId::INVALID).then(|| span.parent_span_id.to_string());
+    let status = match &span.status {
+        Status::Unset => serde_json::Value::Null,
+        Status::Ok => json!({ "code": "ok" }),
+        Status::Error { description } => json!({ "code": "error", "description": description }),
+    };
+    let events: Vec<serde_json::Value> = span
+        .events
+        .iter()
+        .map(|event| {
+            json!({
+                "name": event.name,
+                "timestamp": format_time(event.timestamp),
+                "attributes": key_values_json(&event.attributes),
+            })
+        })
+        .collect();
+
+    json!({
+        "name": span.name,
+        "trace_id": span.span_context.trace_id().to_string(),
+        "span_id": span.span_context.span_id().to_string(),
+        "parent_span_id": parent_span_id,
+        "kind": format!("{:?}", span.span_kind).to_lowercase(),
+        "start_time": format_time(span.start_time),
+        "end_time": format_time(span.end_time),
+        "duration_ms": duration_ms,
+        "status": status,
+        "scope": span.instrumentation_scope.name(),
+        "attributes": key_values_json(&span.attributes),
+        "events": events,
+        "resource": resource,
+    })
+}
+
+fn resource_json(resource: &Resource) -> serde_json::Value {
+    let attributes: Map<String, serde_json::Value> = resource
+        .iter()
+        .map(|(key, value)| (key.as_str().to_string(), value_json(value)))
+        .collect();
+    serde_json::Value::Object(attributes)
+}
+
+fn key_values_json(attributes: &[KeyValue]) -> serde_json::Value {
+    let attributes: Map<String, serde_json::Value> = attributes
+        .iter()
+        .map(|kv| (kv.key.as_str().to_string(), value_json(&kv.value)))
+        .collect();
+    serde_json::Value::Object(attributes)
+}
+
+fn value_json(value: &Value) -> serde_json::Value {
+    match value {
+        Value::Bool(value) => json!(value),
+        Value::I64(value) => json!(value),
+        Value::F64(value) => json!(value),
+        other => json!(other.as_str()),
+    }
+}
+
+fn any_value_json(value: &AnyValue) -> serde_json::Value {
+    match value {
+        AnyValue::Boolean(value) => json!(value),
+        AnyValue::Int(value) => json!(value),
+        AnyValue::Double(value) => json!(value),
+        AnyValue::String(value) => json!(value.as_str()),
+        AnyValue::ListAny(values) => values.iter().map(any_value_json).collect(),
+        AnyValue::Map(entries) => serde_json::Value::Object(
+            entries
+                .iter()
+                .map(|(key, value)| (key.as_str().to_string(), any_value_json(value)))
+                .collect(),
+        ),
+        other => json!(format!("{other:?}")),
+    }
+}
+
+fn format_time(time: SystemTime) -> String {
+    DateTime::<Utc>::from(time).to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+fn internal_failure(error: impl fmt::Display) -> OTelSdkError {
+    OTelSdkError::InternalFailure(format!("failed to write OTEL file export: {error}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry::logs::LogRecord as _;
+    use opentelemetry::logs::Logger as _;
+    use opentelemetry::logs::LoggerProvider as _;
+    use opentelemetry::trace::Tracer as _;
+    use opentelemetry::trace::TracerProvider as _;
+    use opentelemetry_sdk::logs::SdkLoggerProvider;
+    use opentelemetry_sdk::trace::SdkTracerProvider;
+    use tempfile::TempDir;
+
+    fn read_lines(dir: &Path, prefix: &str) -> Vec<serde_json::Value> {
+        let mut lines = Vec::new();
+        for entry in fs::read_dir(dir).expect("read log dir") {
+            let path = entry.expect("dir entry").path();
+            let is_match = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(prefix));
+            if !is_match {
+                continue;
+            }
+            let contents = fs::read_to_string(&path).expect("read export file");
+            lines.extend(
+                contents
+                    .lines()
+                    .map(|line| serde_json::from_str(line).expect("json line")),
+            );
+        }
+        lines
+    }
+
+    #[test]
+    fn writes_log_records_as_json_lines() {
+        let dir = TempDir::new().expect("tempdir");
+        let provider = SdkLoggerProvider::builder()
+            .with_resource(Resource::builder().with_service_name("codex_test").build())
+            .with_simple_exporter(FileLogExporter::new(dir.path()).expect("exporter"))
+            .build();
+
+        let logger = provider.logger("codex_otel");
+        let mut record = logger.create_log_record();
+        record.set_event_name("codex.tool_result");
+        record.set_body(AnyValue::from("done"));
+        record.add_attribute("tool_name", "shell");
+        logger.emit(record);
+        provider.shutdown().expect("shutdown logger");
+
+        let lines = read_lines(dir.path(), EVENTS_FILE_PREFIX);
+        assert_eq!(lines.len(), 1);
+        let line = &lines[0];
+        assert_eq!(line["event.name"], "codex.tool_result");
+        assert_eq!(line["body"], "done");
+        assert_eq!(line["attributes"]["tool_name"], "shell");
+        assert_eq!(line["resource"]["service.name"], "codex_test");
+    }
+
+    #[test]
+    fn writes_spans_as_json_lines() {
+        let dir = TempDir::new().expect("tempdir");
+        let provider = SdkTracerProvider::builder()
+            .with_simple_exporter(FileSpanExporter::new(dir.path()).expect("exporter"))
+            .build();
+
+        let tracer = provider.tracer("codex_otel");
+        tracer.in_span("turn", |_| {
+            tracer.in_span("tool_call", |_| {});
+        });
+        provider.shutdown().expect("shutdown tracer");
+
+        let lines = read_lines(dir.path(), SPANS_FILE_PREFIX);
+        let names: Vec<&str> = lines
+            .iter()
+            .filter_map(|line| line["name"].as_str())
+            .collect();
+        assert_eq!(names, vec!["tool_call", "turn"]);
+        assert_eq!(lines[0]["parent_span_id"], lines[1]["span_id"]);
+        assert_eq!(lines[1]["parent_span_id"], serde_json::Value::Null);
+    }
+}
@@ -0,0 +1,206 @@
+use std::fmt;
+use std::time::Duration;
+
+use opentelemetry::KeyValue;
+use opentelemetry::global;
+use opentelemetry::metrics::Counter;
+use opentelemetry::metrics::Histogram;
+use opentelemetry::metrics::Meter;
+
+/// Instrumentation scope used for every Codex metric.
+pub const METER_NAME: &str = "codex_otel";
+
+pub const API_REQUEST_DURATION: &str = "codex.api_request.duration";
+pub const API_REQUESTS: &str = "codex.api_request.count";
+pub const TIME_TO_FIRST_TOKEN: &str = "codex.response.time_to_first_token";
+pub const TOKENS: &str = "codex.token.usage";
+pub const TOOL_CALL_DURATION: &str = "codex.tool_call.duration";
+pub const TOOL_CALLS: &str = "codex.tool_call.count";
+pub const APPROVAL_WAIT: &str = "codex.approval.wait_time";
+pub const COMPACTIONS: &str = "codex.compaction.count";
+
+/// Counters and histograms recorded next to the `codex.*` log events.
+///
+/// Instruments are created from the global meter provider, so they are no-ops
+/// unless a metrics exporter was configured before the session started.
+pub(crate) struct OtelMetrics {
+    api_request_duration: Histogram<f64>,
+    api_requests: Counter<u64>,
+    time_to_first_token: Histogram<f64>,
+    tokens: Counter<u64>,
+    tool_call_duration: Histogram<f64>,
+    tool_calls: Counter<u64>,
+    approval_wait: Histogram<f64>,
+    compactions: Counter<u64>,
+}
+
+impl OtelMetrics {
+    pub(crate) fn from_global() -> Self {
+        Self::new(&global::meter(METER_NAME))
+    }
+
+    pub(crate) fn new(meter: &Meter) -> Self {
+        Self {
+            api_request_duration: meter
+                .f64_histogram(API_REQUEST_DURATION)
+                .with_description("Duration of model API requests")
+                .with_unit("ms")
+                .build(),
+            api_requests: meter
+                .u64_counter(API_REQUESTS)
+                .with_description("Model API requests, by status and outcome")
+                .build(),
+            time_to_first_token: meter
+                .f64_histogram(TIME_TO_FIRST_TOKEN)
+                .with_description("Time from sending a request to the first streamed output")
+                .with_unit("ms")
+                .build(),
+            tokens: meter
+                .u64_counter(TOKENS)
+                .with_description("Tokens used, by token.type (input, output, cached, reasoning)")
+                .with_unit("{token}")
+                .build(),
+            tool_call_duration: meter
+                .f64_histogram(TOOL_CALL_DURATION)
+                .with_description("Duration of tool calls, by tool and outcome")
+                .with_unit("ms")
+                .build(),
+            tool_calls: meter
+                .u64_counter(TOOL_CALLS)
+                .with_description("Tool calls, by tool and outcome")
+                .build(),
+            approval_wait: meter
+                .f64_histogram(APPROVAL_WAIT)
+                .with_description("Time spent waiting for the user to answer an approval request")
+                .with_unit("ms")
+                .build(),
+            compactions: meter
+                .u64_counter(COMPACTIONS)
+                .with_description("Conversation history compactions, by trigger")
+                .build(),
+        }
+    }
+
+    pub(crate) fn api_request(&self, duration: Duration, attributes: &[KeyValue]) {
+        self.api_request_duration
+            .record(duration_ms(duration), attributes);
+        self.api_requests.add(1, attributes);
+    }
+
+    pub(crate) fn time_to_first_token(&self, duration: Duration, attributes: &[KeyValue]) {
+        self.time_to_first_token
+            .record(duration_ms(duration), attributes);
+    }
+
+    pub(crate) fn tokens(&self, token_type: &'static str, count: i64, attributes: &[KeyValue]) {
+        let Ok(count) = u64::try_from(count) else {
+            return;
+        };
+        if count == 0 {
+            return;
+        }
+        let mut attributes = attributes.to_vec();
+        attributes.push(KeyValue::new("token.type", token_type));
+        self.tokens.add(count, &attributes);
+    }
+
+    /// Counts a tool call; the duration histogram is only fed when the
+    /// duration is known.
+    pub(crate) fn tool_call(&self, duration: Option<Duration>, attributes: &[KeyValue]) {
+        if let Some(duration) = duration {
+            self.tool_call_duration
+                .record(duration_ms(duration), attributes);
+        }
+        self.tool_calls.add(1, attributes);
+    }
+
+    pub(crate) fn approval_wait(&self, duration: Duration, attributes: &[KeyValue]) {
+        self.approval_wait.record(duration_ms(duration), attributes);
+    }
+
+    pub(crate) fn compaction(&self, attributes: &[KeyValue]) {
+        self.compactions.add(1, attributes);
+    }
+}
+
+impl fmt::Debug for OtelMetrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OtelMetrics").finish_non_exhaustive()
+    }
+}
+
+fn duration_ms(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry::metrics::MeterProvider as _;
+    use opentelemetry_sdk::metrics::InMemoryMetricExporter;
+    use opentelemetry_sdk::metrics::PeriodicReader;
+    use opentelemetry_sdk::metrics::SdkMeterProvider;
+
+    #[test]
+    fn records_counters_and_histograms() {
+        let exporter = InMemoryMetricExporter::default();
+        let provider = SdkMeterProvider::builder()
+            .with_reader(PeriodicReader::builder(exporter.clone()).build())
+            .build();
+        let metrics = OtelMetrics::new(&provider.meter(METER_NAME));
+        let attributes = [KeyValue::new("model", "gpt-5")];
+
+        metrics.api_request(Duration::from_millis(120), &attributes);
+        metrics.time_to_first_token(Duration::from_millis(80), &attributes);
+        metrics.tokens("input", 1_000, &attributes);
+        metrics.tokens("cached", 0, &attributes);
+        metrics.tool_call(Some(Duration::from_millis(5)), &attributes);
+        metrics.approval_wait(Duration::from_secs(3), &attributes);
+        metrics.compaction(&attributes);
+        provider.force_flush().expect("flush metrics");
+
+        let exported = exporter.get_finished_metrics().expect("finished metrics");
+        let mut names: Vec<String> = exported
+            .iter()
+            .flat_map(|resource| resource.scope_metrics())
+            .flat_map(|scope| scope.metrics())
+            .map(|metric| metric.name().to_string())
+            .collect();
+        names.sort();
+        names.dedup();
+        assert_eq!(
+            names,
+            vec![
+                API_REQUESTS,
+                API_REQUEST_DURATION,
+                APPROVAL_WAIT,
+                COMPACTIONS,
+                TIME_TO_FIRST_TOKEN,
+                TOKENS,
+                TOOL_CALLS,
+                TOOL_CALL_DURATION,
+            ]
+        );
+    }
+
+    #[test]
+    fn tool_call_without_duration_only_counts() {
+        let exporter = InMemoryMetricExporter::default();
+        let provider = SdkMeterProvider::builder()
+            .with_reader(PeriodicReader::builder(exporter.clone()).build())
+            .build();
+        let metrics = OtelMetrics::new(&provider.meter(METER_NAME));
+
+        metrics.tool_call(None, &[KeyValue::new("tool_name", "shell")]);
+        provider.force_flush().expect("flush metrics");
+
+        let exported = exporter.get_finished_metrics().expect("finished metrics");
+        let names: Vec<String> = exported
+            .iter()
+            .flat_map(|resource| resource.scope_metrics())
+            .flat_map(|scope| scope.metrics())
+            .map(|metric| metric.name().to_string())
+            .collect();
+        assert_eq!(names, vec![TOOL_CALLS]);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::metrics::OtelMetrics;
 use crate::otel_provider::traceparent_context_from_env;
 use chrono::SecondsFormat;
 use chrono::Utc;
@@ -14,12 +15,14 @@ use codex_protocol::protocol::SessionSource;
 use codex_protocol::user_input::UserInput;
 use eventsource_stream::Event as StreamEvent;
 use eventsource_stream::EventStreamError as StreamError;
+use opentelemetry::KeyValue;
 use reqwest::Error;
 use reqwest::Response;
 use serde::Serialize;
 use std::borrow::Cow;
 use std::fmt::Display;
 use std::future::Future;
+use std::sync::Arc;
 use std::time::Duration;
 use std::time::Instant;
 use strum_macros::Display;
@@ -35,6 +38,17 @@ pub enum ToolDecisionSource {
     User,
 }
 
+/// What started a conversation history compaction.
+#[derive(Debug, Clone, Copy, Serialize, Display)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum CompactionTrigger {
+    /// The context window filled up during a turn.
+    Auto,
+    /// The user ran `/compact`.
+    Manual,
+}
+
 #[derive(Debug, Clone)]
 pub struct OtelEventMetadata {
     conversation_id: ConversationId,
@@ -52,6 +66,7 @@ pub struct OtelEventMetadata {
 pub struct OtelManager {
     metadata: OtelEventMetadata,
     session_span: Span,
+    metrics: Arc<OtelMetrics>,
 }
 
 impl OtelManager {
@@ -86,6 +101,7 @@ impl OtelManager {
                 terminal_type,
             },
             session_span,
+            metrics: Arc::new(OtelMetrics::from_global()),
         }
     }
 
@@ -199,6 +215,84 @@ impl OtelManager {
             error.message = error,
             attempt = attempt,
         );
+
+        let mut attributes = self.metric_attributes();
+        if let Some(status) = status {
+            attributes.push(KeyValue::new(
+                "http.response.status_code",
+                i64::from(status),
+            ));
+        }
+        let success = error.is_none() && status.is_none_or(|status| (200..300).contains(&status));
+        attributes.push(KeyValue::new("outcome", outcome(success)));
+        self.metrics.api_request(duration, &attributes);
+    }
+
+    /// Records the time between sending a model request and the first output
+    /// streamed back (text, reasoning, or an output item).
+    pub fn record_time_to_first_token(&self, duration: Duration) {
+        self.metrics
+            .time_to_first_token(duration, &self.metric_attributes());
+    }
+
+    /// Records how long an approval request waited for the user's decision.
+    pub fn approval_wait(&self, tool_name: &str, decision: &ReviewDecision, duration: Duration) {
+        let mut attributes = self.metric_attributes();
+        attributes.push(KeyValue::new("tool_name", tool_name.to_string()));
+        attributes.push(KeyValue::new(
+            "decision",
+            decision.clone().to_string().to_lowercase(),
+        ));
+        self.metrics.approval_wait(duration, &attributes);
+    }
+
+    pub fn compaction(&self, trigger: CompactionTrigger) {
+        tracing::event!(
+            tracing::Level::INFO,
+            event.name = "codex.compaction",
+            event.timestamp = %timestamp(),
+            conversation.id = %self.metadata.conversation_id,
+            app.version = %self.metadata.app_version,
+            auth_mode = self.metadata.auth_mode,
+            user.account_id = self.metadata.account_id,
+            user.email = self.metadata.account_email,
+            terminal.type = %self.metadata.terminal_type,
+            model = %self.metadata.model,
+            slug = %self.metadata.slug,
+            trigger = %trigger,
+        );
+
+        let mut attributes = self.metric_attributes();
+        attributes.push(KeyValue::new("trigger", trigger.to_string()));
+        self.metrics.compaction(&attributes);
+    }
+
+    /// Records that requests moved from one model provider to the next one in
+    /// the configured failover chain.
+    pub fn provider_failover(
+        &self,
+        from_provider: &str,
+        to_provider: &str,
+        to_model: &str,
+        error: &str,
+    ) {
+        tracing::event!(
+            tracing::Level::INFO,
+            event.name = "codex.provider_failover",
+            event.timestamp = %timestamp(),
+            conversation.id = %self.metadata.conversation_id,
+            app.version = %self.metadata.app_version,
+            auth_mode = self.metadata.auth_mode,
+            user.account_id = self.metadata.account_id,
+            user.email = self.metadata.account_email,
+            terminal.type = %self.metadata.terminal_type,
+            model = %self.metadata.model,
+            slug = %self.metadata.slug,
+            from_provider = %from_provider,
+            to_provider = %to_provider,
+            to_model = %to_model,
+            error.message = %error,
+        );
     }
 
     pub fn log_sse_event<E>(
@@ -353,6 +447,17 @@ impl OtelManager {
             reasoning_token_count = reasoning_token_count,
             tool_token_count = %tool_token_count,
         );
+
+        let attributes = self.metric_attributes();
+        self.metrics.tokens("input", input_token_count, &attributes);
+        self.metrics
+            .tokens("output", output_token_count, &attributes);
+        if let Some(cached) = cached_token_count {
+            self.metrics.tokens("cached", cached, &attributes);
+        }
+        if let Some(reasoning) = reasoning_token_count {
+            self.metrics.tokens("reasoning", reasoning, &attributes);
+        }
     }
 
     pub fn user_prompt(&self, items: &[UserInput]) {
@@ -464,6 +569,9 @@ impl OtelManager {
             success = %false,
             output = %error,
         );
+
+        // The call never ran, so there is no duration to put in the histogram.
+        self.record_tool_call(tool_name, None, false);
     }
 
     pub fn tool_result(
@@ -496,6 +604,29 @@ impl OtelManager {
             success = %success_str,
             output = %output,
         );
+
+        self.record_tool_call(tool_name, Some(duration), success);
+    }
+
+    fn record_tool_call(&self, tool_name: &str, duration: Option<Duration>, success: bool) {
+        let mut attributes = self.metric_attributes();
+        attributes.push(KeyValue::new("tool_name", tool_name.to_string()));
+        attributes.push(KeyValue::new("outcome", outcome(success)));
+        self.metrics.tool_call(duration, &attributes);
+    }
+
+    /// Low-cardinality attributes shared by every metric. Unlike the log events,
+    /// metrics leave out per-conversation and per-user identifiers.
+    fn metric_attributes(&self) -> Vec<KeyValue> {
+        let mut attributes = vec![
+            KeyValue::new("model", self.metadata.model.clone()),
+            KeyValue::new("slug", self.metadata.slug.clone()),
+            KeyValue::new("app.version", self.metadata.app_version),
+        ];
+        if let Some(auth_mode) = &self.metadata.auth_mode {
+            attributes.push(KeyValue::new("auth_mode", auth_mode.clone()));
+        }
+        attributes
     }
 
     fn responses_type(event: &ResponseEvent) -> String {
@@ -531,6 +662,10 @@ impl OtelManager {
     }
 }
 
+fn outcome(success: bool) -> &'static str {
+    if success { "success" } else { "failure" }
+}
+
 fn timestamp() -> String {
     Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true)
 }
@@ -2,6 +2,10 @@ use crate::config::OtelExporter;
 use crate::config::OtelHttpProtocol;
 use crate::config::OtelSettings;
 use crate::config::OtelTlsConfig;
+use crate::file_exporter::FileLogExporter;
+use crate::file_exporter::FileSpanExporter;
+use crate::prometheus;
+use crate::prometheus::PrometheusExporter;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use http::Uri;
 use opentelemetry::Context;
@@ -13,7 +17,9 @@ use opentelemetry::trace::TraceContextExt;
 use opentelemetry::trace::TracerProvider as _;
 use opentelemetry_appender_tracing::layer::OpenTelemetryTracingBridge;
 use opentelemetry_otlp::LogExporter;
+use opentelemetry_otlp::MetricExporter;
 use opentelemetry_otlp::OTEL_EXPORTER_OTLP_LOGS_TIMEOUT;
+use opentelemetry_otlp::OTEL_EXPORTER_OTLP_METRICS_TIMEOUT;
 use opentelemetry_otlp::OTEL_EXPORTER_OTLP_TIMEOUT;
 use opentelemetry_otlp::OTEL_EXPORTER_OTLP_TIMEOUT_DEFAULT;
 use opentelemetry_otlp::OTEL_EXPORTER_OTLP_TRACES_TIMEOUT;
@@ -24,6 +30,8 @@ use opentelemetry_otlp::WithHttpConfig;
 use opentelemetry_otlp::WithTonicConfig;
 use opentelemetry_sdk::Resource;
 use opentelemetry_sdk::logs::SdkLoggerProvider;
+use opentelemetry_sdk::metrics::PeriodicReader;
+use opentelemetry_sdk::metrics::SdkMeterProvider;
 use opentelemetry_sdk::propagation::TraceContextPropagator;
 use opentelemetry_sdk::trace::BatchSpanProcessor;
 use opentelemetry_sdk::trace::SdkTracerProvider;
@@ -41,6 +49,7 @@ use std::error::Error;
 use std::fs;
 use std::io::ErrorKind;
 use std::io::{self};
+use std::path::Path;
 use std::path::PathBuf;
 use std::sync::OnceLock;
 use std::time::Duration;
@@ -55,6 +64,8 @@ use tracing_subscriber::Layer;
 use tracing_subscriber::registry::LookupSpan;
 
 const ENV_ATTRIBUTE: &str = "env";
+/// Directory under `CODEX_HOME` used by the file and Prometheus exporters.
+const LOG_DIR: &str = "log";
 const TRACEPARENT_ENV_VAR: &str = "TRACEPARENT";
 const TRACESTATE_ENV_VAR: &str = "TRACESTATE";
 static TRACEPARENT_CONTEXT: OnceLock<Option<Context>> = OnceLock::new();
@@ -67,6 +78,7 @@ pub struct OtelProvider {
     pub logger: Option<SdkLoggerProvider>,
     pub tracer_provider: Option<SdkTracerProvider>,
     pub tracer: Option<Tracer>,
+    pub meter_provider: Option<SdkMeterProvider>,
 }
 
 impl OtelProvider {
@@ -77,24 +89,29 @@ impl OtelProvider {
         if let Some(tracer_provider) = &self.tracer_provider {
             let _ = tracer_provider.shutdown();
         }
+        if let Some(meter_provider) = &self.meter_provider {
+            let _ = meter_provider.shutdown();
+        }
     }
 
     pub fn from(settings: &OtelSettings) -> Result<Option<Self>, Box<dyn Error>> {
         let log_enabled = !matches!(settings.exporter, OtelExporter::None);
         let trace_enabled = !matches!(settings.trace_exporter, OtelExporter::None);
+        let metrics_enabled = !matches!(settings.metrics_exporter, OtelExporter::None);
 
-        if !log_enabled && !trace_enabled {
+        if !log_enabled && !trace_enabled && !metrics_enabled {
             debug!("No exporter enabled in OTLP settings.");
             return Ok(None);
         }
 
         let resource = make_resource(settings);
+        let log_dir = settings.codex_home.join(LOG_DIR);
         let logger = log_enabled
-            .then(|| build_logger(&resource, &settings.exporter))
+            .then(|| build_logger(&resource, &settings.exporter, &log_dir))
             .transpose()?;
 
         let tracer_provider = trace_enabled
-            .then(|| build_tracer_provider(&resource, &settings.trace_exporter))
+            .then(|| build_tracer_provider(&resource, &settings.trace_exporter, &log_dir))
             .transpose()?;
 
         let tracer = tracer_provider
@@ -109,10 +126,19 @@ impl OtelProvider {
             attach_traceparent_context();
         }
 
+        let meter_provider = metrics_enabled
+            .then(|| build_meter_provider(&resource, &settings.metrics_exporter, &log_dir))
+            .transpose()?;
+        if let Some(provider) = meter_provider.clone() {
+            // Session metrics are created from the global meter, see `OtelMetrics`.
+            global::set_meter_provider(provider);
+        }
+
         Ok(Some(Self {
             logger,
             tracer_provider,
             tracer,
+            meter_provider,
         }))
     }
 
@@ -151,6 +177,9 @@ impl Drop for OtelProvider {
         if let Some(tracer_provider) = &self.tracer_provider {
             let _ = tracer_provider.shutdown();
         }
+        if let Some(meter_provider) = &self.meter_provider {
+            let _ = meter_provider.shutdown();
+        }
     }
 }
 
@@ -220,6 +249,7 @@ fn make_resource(settings: &OtelSettings) -> Resource {
 fn build_logger(
     resource: &Resource,
     exporter: &OtelExporter,
+    log_dir: &Path,
 ) -> Result<SdkLoggerProvider, Box<dyn Error>> {
     let mut builder = SdkLoggerProvider::builder().with_resource(resource.clone());
 
@@ -280,6 +310,16 @@ fn build_logger(
 
             builder = builder.with_batch_exporter(exporter);
         }
+        OtelExporter::File => {
+            debug!("Using file exporter for logs: {}", log_dir.display());
+
+            builder = builder.with_batch_exporter(FileLogExporter::new(log_dir)?);
+        }
+        OtelExporter::Prometheus { .. } => {
+            return Err(config_error(
+                "the prometheus exporter only supports metrics; set it as otel.metrics_exporter",
+            ));
+        }
     }
 
     Ok(builder.build())
@@ -288,6 +328,7 @@ fn build_logger(
 fn build_tracer_provider(
     resource: &Resource,
     exporter: &OtelExporter,
+    log_dir: &Path,
 ) -> Result<SdkTracerProvider, Box<dyn Error>> {
     let span_exporter = match exporter {
         OtelExporter::None => return Ok(SdkTracerProvider::builder().build()),
@@ -342,13 +383,127 @@ fn build_tracer_provider(
 
             exporter_builder.build()?
         }
+        OtelExporter::File => {
+            debug!("Using file exporter for traces: {}", log_dir.display());
+
+            return Ok(tracer_provider_with_exporter(
+                resource,
+                FileSpanExporter::new(log_dir)?,
+            ));
+        }
+        OtelExporter::Prometheus { .. } => {
+            return Err(config_error(
+                "the prometheus exporter only supports metrics; set it as otel.metrics_exporter",
+            ));
+        }
     };
 
+    Ok(tracer_provider_with_exporter(resource, span_exporter))
+}
+
+fn tracer_provider_with_exporter(
+    resource: &Resource,
+    span_exporter: impl opentelemetry_sdk::trace::SpanExporter + 'static,
+) -> SdkTracerProvider {
     let processor = BatchSpanProcessor::builder(span_exporter).build();
 
-    Ok(SdkTracerProvider::builder()
+    SdkTracerProvider::builder()
         .with_resource(resource.clone())
         .with_span_processor(processor)
+        .build()
+}
+
+fn build_meter_provider(
+    resource: &Resource,
+    exporter: &OtelExporter,
+    log_dir: &Path,
+) -> Result<SdkMeterProvider, Box<dyn Error>> {
+    let metric_exporter = match exporter {
+        OtelExporter::None => return Ok(SdkMeterProvider::builder().build()),
+        OtelExporter::OtlpGrpc {
+            endpoint,
+            headers,
+            tls,
+        } => {
+            debug!("Using OTLP Grpc exporter for metrics: {endpoint}");
+
+            let header_map = build_header_map(headers);
+
+            let base_tls_config = ClientTlsConfig::new()
+                .with_enabled_roots()
+                .assume_http2(true);
+
+            let tls_config = match tls.as_ref() {
+                Some(tls) => build_grpc_tls_config(endpoint, base_tls_config, tls)?,
+                None => base_tls_config,
+            };
+
+            MetricExporter::builder()
+                .with_tonic()
+                .with_endpoint(endpoint)
+                .with_metadata(MetadataMap::from_headers(header_map))
+                .with_tls_config(tls_config)
+                .build()?
+        }
+        OtelExporter::OtlpHttp {
+            endpoint,
+            headers,
+            protocol,
+            tls,
+        } => {
+            debug!("Using OTLP Http exporter for metrics: {endpoint}");
+
+            let protocol = match protocol {
+                OtelHttpProtocol::Binary => Protocol::HttpBinary,
+                OtelHttpProtocol::Json => Protocol::HttpJson,
+            };
+
+            let mut exporter_builder = MetricExporter::builder()
+                .with_http()
+                .with_endpoint(endpoint)
+                .with_protocol(protocol)
+                .with_headers(headers.clone());
+
+            if let Some(tls) = tls.as_ref() {
+                let client = build_http_client(tls, OTEL_EXPORTER_OTLP_METRICS_TIMEOUT)?;
+                exporter_builder = exporter_builder.with_http_client(client);
+            }
+
+            exporter_builder.build()?
+        }
+        OtelExporter::Prometheus { listen, textfile } => {
+            debug!(
+                "Using Prometheus exporter for metrics: listen={listen:?} textfile={textfile:?}"
+            );
+
+            let exporter = PrometheusExporter::new(
+                listen.as_deref(),
+                textfile.as_ref().map(|path| path.to_path_buf()),
+                log_dir.join(prometheus::DEFAULT_TEXTFILE_NAME),
+            )?;
+            // Refresh the snapshot more often than OTLP pushes so scrapes stay current.
+            let reader = PeriodicReader::builder(exporter)
+                .with_interval(prometheus::EXPORT_INTERVAL)
+                .build();
+            return Ok(SdkMeterProvider::builder()
+                .with_resource(resource.clone())
+                .with_reader(reader)
+                .build());
+        }
+        OtelExporter::File => {
+            return Err(config_error(
+                "the file exporter does not support metrics; use prometheus for otel.metrics_exporter",
+            ));
+        }
+    };
+
+    // The export interval honors `OTEL_METRIC_EXPORT_INTERVAL` (default 60s);
+    // pending data points are flushed on shutdown.
+    let reader = PeriodicReader::builder(metric_exporter).build();
+
+    Ok(SdkMeterProvider::builder()
+        .with_resource(resource.clone())
+        .with_reader(reader)
         .build())
 }
 
@@ -0,0 +1,356 @@
+//! Metrics exporter that renders the Prometheus text exposition format and
+//! either serves it over HTTP or rewrites a textfile, so metrics can be
+//! scraped or inspected without an OTLP collector.
+
+use std::error::Error;
+use std::fmt;
+use std::fmt::Write as _;
+use std::fs;
+use std::future::Future;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+use opentelemetry::KeyValue;
+use opentelemetry_sdk::error::OTelSdkError;
+use opentelemetry_sdk::error::OTelSdkResult;
+use opentelemetry_sdk::metrics::Temporality;
+use opentelemetry_sdk::metrics::data::AggregatedMetrics;
+use opentelemetry_sdk::metrics::data::Metric;
+use opentelemetry_sdk::metrics::data::MetricData;
+use opentelemetry_sdk::metrics::data::ResourceMetrics;
+use opentelemetry_sdk::metrics::exporter::PushMetricExporter;
+use tiny_http::Header;
+use tiny_http::Response;
+use tiny_http::Server;
+use tracing::debug;
+use tracing::warn;
+
+/// Default textfile name under `CODEX_HOME/log`.
+pub const DEFAULT_TEXTFILE_NAME: &str = "codex-metrics.prom";
+/// How often the served snapshot or textfile is refreshed.
+pub(crate) const EXPORT_INTERVAL: Duration = Duration::from_secs(15);
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Keeps the latest rendering of cumulative metrics and publishes it to an
+/// HTTP endpoint, a textfile, or both.
+pub(crate) struct PrometheusExporter {
+    snapshot: Option<Arc<Mutex<String>>>,
+    textfile: Option<PathBuf>,
+    /// Why `listen` could not be served. Logged on the first export, once the
+    /// tracing subscriber that carries it has been installed.
+    serve_warning: Mutex<Option<String>>,
+}
+
+impl PrometheusExporter {
+    /// Serves metrics on `listen` and/or writes them to `textfile`. Without a
+    /// textfile, `default_textfile` is written when nothing is served, including
+    /// when `listen` cannot be bound, so a busy port never stops Codex starting.
+    pub(crate) fn new(
+        listen: Option<&str>,
+        textfile: Option<PathBuf>,
+        default_textfile: PathBuf,
+    ) -> Result<Self, Box<dyn Error>> {
+        let (snapshot, serve_error) = match listen.map(serve).transpose() {
+            Ok(snapshot) => (snapshot, None),
+            Err(error) => (None, Some(error)),
+        };
+        let textfile = match textfile {
+            Some(path) => Some(path),
+            None if snapshot.is_none() => Some(default_textfile),
+            None => None,
+        };
+        let serve_warning = serve_error.map(|error| match &textfile {
+            Some(path) => format!("{error}; writing metrics to {} only", path.display()),
+            None => error.to_string(),
+        });
+        if let Some(parent) = textfile.as_ref().and_then(|path| path.parent()) {
+            fs::create_dir_all(parent)?;
+        }
+        Ok(Self {
+            snapshot,
+            textfile,
+            serve_warning: Mutex::new(serve_warning),
+        })
+    }
+
+    fn publish(&self, body: String) -> OTelSdkResult {
+        if let Some(warning) = self
+            .serve_warning
+            .lock()
+            .ok()
+            .and_then(|mut warning| warning.take())
+        {
+            warn!("{warning}");
+        }
+        if let Some(path) = &self.textfile {
+            write_textfile(path, &body).map_err(|error| {
+                OTelSdkError::InternalFailure(format!(
+                    "failed to write {}: {error}",
+                    path.display()
+                ))
+            })?;
+        }
+        if let Some(snapshot) = &self.snapshot
+            && let Ok(mut snapshot) = snapshot.lock()
+        {
+            *snapshot = body;
+        }
+        Ok(())
+    }
+}
+
+impl PushMetricExporter for PrometheusExporter {
+    fn export(&self, metrics: &ResourceMetrics) -> impl Future<Output = OTelSdkResult> + Send {
+        std::future::ready(self.publish(render(metrics)))
+    }
+
+    fn force_flush(&self) -> OTelSdkResult {
+        Ok(())
+    }
+
+    fn shutdown_with_timeout(&self, _timeout: Duration) -> OTelSdkResult {
+        Ok(())
+    }
+
+    fn temporality(&self) -> Temporality {
+        // Prometheus expects counters and histograms to accumulate.
+        Temporality::Cumulative
+    }
+}
+
+impl fmt::Debug for PrometheusExporter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PrometheusExporter")
+            .field("serving", &self.snapshot.is_some())
+            .field("textfile", &self.textfile)
+            .finish()
+    }
+}
+
+/// Starts a background thread answering every request with the latest snapshot.
+fn serve(listen: &str) -> Result<Arc<Mutex<String>>, Box<dyn Error>> {
+    let server = Server::http(listen).map_err(|error| {
+        io::Error::new(
+            io::ErrorKind::AddrNotAvailable,
+            format!("failed to serve Prometheus metrics on {listen}: {error}"),
+        )
+    })?;
+    debug!("Serving Prometheus metrics on {listen}");
+
+    let snapshot = Arc::new(Mutex::new(String::new()));
+    let served = Arc::clone(&snapshot);
+    thread::Builder::new()
+        .name("codex-prometheus".to_string())
+        .spawn(move || {
+            for request in server.incoming_requests() {
+                let body = served.lock().map(|body| body.clone()).unwrap_or_default();
+                let mut response = Response::from_string(body);
+                if let Ok(header) = Header::from_bytes("Content-Type", CONTENT_TYPE) {
+                    response = response.with_header(header);
+                }
+                let _ = request.respond(response);
+            }
+        })?;
+    Ok(snapshot)
+}
+
+/// Replaces the textfile atomically so collectors never read a partial file.
+fn write_textfile(path: &Path, body: &str) -> io::Result<()> {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    let tmp = PathBuf::from(tmp);
+    fs::write(&tmp, body)?;
+    fs::rename(&tmp, path)
+}
+
+pub(crate) fn render(metrics: &ResourceMetrics) -> String {
+    let mut out = String::new();
+    for scope in metrics.scope_metrics() {
+        for metric in scope.metrics() {
+            match metric.data() {
+                AggregatedMetrics::F64(data) => render_metric(&mut out, metric, data),
+                AggregatedMetrics::U64(data) => render_metric(&mut out, metric, data),
+                AggregatedMetrics::I64(data) => render_metric(&mut out, metric, data),
+            }
+        }
+    }
+    out
+}
+
+fn render_metric<T: fmt::Display + Copy>(out: &mut String, metric: &Metric, data: &MetricData<T>) {
+    let base = metric_name(metric.name(), metric.unit());
+    match data {
+        MetricData::Sum(sum) => {
+            let (name, kind) = if sum.is_monotonic() {
+                (format!("{base}_total"), "counter")
+            } else {
+                (base, "gauge")
+            };
+            write_header(out, &name, metric.description(), kind);
+            for point in sum.data_points() {
+                let labels = format_labels(point.attributes(), None);
+                let _ = writeln!(out, "{name}{labels} {}", point.value());
+            }
+        }
+        MetricData::Gauge(gauge) => {
+            write_header(out, &base, metric.description(), "gauge");
+            for point in gauge.data_points() {
+                let labels = format_labels(point.attributes(), None);
+                let _ = writeln!(out, "{base}{labels} {}", point.value());
+            }
+        }
+        MetricData::Histogram(histogram) => {
+            write_header(out, &base, metric.description(), "histogram");
+            for point in histogram.data_points() {
+                let mut cumulative = 0;
+                for (bound, count) in point.bounds().zip(point.bucket_counts()) {
+                    cumulative += count;
+                    let labels = format_labels(point.attributes(), Some(&bound.to_string()));
+                    let _ = writeln!(out, "{base}_bucket{labels} {cumulative}");
+                }
+                let labels_inf = format_labels(point.attributes(), Some("+Inf"));
+                let _ = writeln!(out, "{base}_bucket{labels_inf} {}", point.count());
+                let labels = format_labels(point.attributes(), None);
+                let _ = writeln!(out, "{base}_sum{labels} {}", point.sum());
+                let _ = writeln!(out, "{base}_count{labels} {}", point.count());
+            }
+        }
+        // Codex does not record exponential histograms.
+        _ => {}
+    }
+}
+
+fn write_header(out: &mut String, name: &str, description: &str, kind: &str) {
+    if !description.is_empty() {
+        let help = description.replace('\\', "\\\\").replace('\n', "\\n");
+        let _ = writeln!(out, "# HELP {name} {help}");
+    }
+    let _ = writeln!(out, "# TYPE {name} {kind}");
+}
+
+/// `codex.api_request.duration` with unit `ms` becomes
+/// `codex_api_request_duration_milliseconds`.
+fn metric_name(name: &str, unit: &str) -> String {
+    let mut name = sanitize(name);
+    let suffix = match unit {
+        "ms" => Some("milliseconds"),
+        "s" => Some("seconds"),
+        "By" => Some("bytes"),
+        _ => None,
+    };
+    if let Some(suffix) = suffix {
+        name.push('_');
+        name.push_str(suffix);
+    }
+    name
+}
+
+fn format_labels<'a>(attributes: impl Iterator<Item = &'a KeyValue>, le: Option<&str>) -> String {
+    let mut pairs: Vec<String> = attributes
+        .map(|kv| {
+            format!(
+                "{}=\"{}\"",
+                sanitize(kv.key.as_str()),
+                escape_label_value(&kv.value.as_str())
+            )
+        })
+        .collect();
+    if let Some(le) = le {
+        pairs.push(format!("le=\"{le}\""));
+    }
+    if pairs.is_empty() {
+        String::new()
+    } else {
+        format!("{{{}}}", pairs.join(","))
+    }
+}
+
+fn sanitize(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::METER_NAME;
+    use crate::metrics::OtelMetrics;
+    use opentelemetry::metrics::MeterProvider as _;
+    use opentelemetry_sdk::metrics::PeriodicReader;
+    use opentelemetry_sdk::metrics::SdkMeterProvider;
+    use tempfile::TempDir;
+
+    #[test]
+    fn writes_textfile_in_exposition_format() {
+        let dir = TempDir::new().expect("tempdir");
+        let path = dir.path().join(DEFAULT_TEXTFILE_NAME);
+        let exporter = PrometheusExporter::new(None, Some(path.clone()), dir.path().join("unused"))
+            .expect("exporter");
+        let provider = SdkMeterProvider::builder()
+            .with_reader(PeriodicReader::builder(exporter).build())
+            .build();
+        let metrics = OtelMetrics::new(&provider.meter(METER_NAME));
+        let attributes = [KeyValue::new("model", "gpt-\"5\"")];
+
+        metrics.api_request(Duration::from_millis(120), &attributes);
+        metrics.api_request(Duration::from_millis(30), &attributes);
+        provider.force_flush().expect("flush metrics");
+
+        let text = fs::read_to_string(&path).expect("read textfile");
+        assert!(text.contains("# TYPE codex_api_request_count_total counter\n"));
+        assert!(text.contains("codex_api_request_count_total{model=\"gpt-\\\"5\\\"\"} 2\n"));
+        assert!(text.contains("# TYPE codex_api_request_duration_milliseconds histogram\n"));
+        assert!(text.contains(
+            "codex_api_request_duration_milliseconds_bucket{model=\"gpt-\\\"5\\\"\",le=\"+Inf\"} 2\n"
+        ));
+        assert!(text.contains(
+            "codex_api_request_duration_milliseconds_sum{model=\"gpt-\\\"5\\\"\"} 150\n"
+        ));
+    }
+
+    #[test]
+    fn falls_back_to_textfile_when_listen_address_is_taken() {
+        let dir = TempDir::new().expect("tempdir");
+        let path = dir.path().join(DEFAULT_TEXTFILE_NAME);
+        let taken = std::net::TcpListener::bind("127.0.0.1:0").expect("bind");
+        let listen = taken.local_addr().expect("local addr").to_string();
+
+        let exporter =
+            PrometheusExporter::new(Some(&listen), None, path.clone()).expect("exporter");
+        assert!(exporter.snapshot.is_none());
+        assert_eq!(exporter.textfile, Some(path.clone()));
+
+        exporter
+            .publish("codex_up 1\n".to_string())
+            .expect("publish");
+        assert_eq!(
+            fs::read_to_string(&path).expect("read textfile"),
+            "codex_up 1\n"
+        );
+    }
+
+    #[test]
+    fn metric_names_follow_prometheus_conventions() {
+        assert_eq!(
+            metric_name("codex.tool_call.duration", "ms"),
+            "codex_tool_call_duration_milliseconds"
+        );
+        assert_eq!(
+            metric_name("codex.token.usage", "{token}"),
+            "codex_token_usage"
+        );
+    }
+}
@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
@@ -8,4 +10,8 @@ pub struct HistoryEntry {
     pub conversation_id: String,
     pub ts: u64,
     pub text: String,
+    /// Working directory of the session that recorded the entry, when known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub cwd: Option<PathBuf>,
 }
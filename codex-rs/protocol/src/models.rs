@@ -331,6 +331,7 @@ impl From<Vec<UserInput>> for ResponseInputItem {
                         }
                     },
                     UserInput::Skill { .. } => None, // Skill bodies are injected later in core
+                    UserInput::File { .. } => None,  // File contents are inlined earlier in core
                 })
                 .collect::<Vec<ContentItem>>(),
         }
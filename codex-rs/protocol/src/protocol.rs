@@ -179,6 +179,10 @@ pub enum Op {
     /// Request a single history entry identified by `log_id` + `offset`.
     GetHistoryEntryRequest { offset: usize, log_id: u64 },
 
+    /// Request every entry in the persistent message history, oldest first.
+    /// Reply is delivered via `EventMsg::ListHistoryEntriesResponse`.
+    ListHistoryEntries,
+
     /// Request the list of MCP tools available across all configured servers.
     /// Reply is delivered via `EventMsg::McpListToolsResponse`.
     ListMcpTools,
@@ -207,6 +211,15 @@ pub enum Op {
     /// Request Codex to undo a turn (turn are stacked so it is the same effect as CMD + Z).
     Undo,
 
+    /// Drop the last `num_turns` user turns from the conversation history.
+    /// When `restore_snapshots` is set, the working tree is also restored to
+    /// the ghost snapshot taken before the earliest dropped turn.
+    ThreadRollback {
+        num_turns: u32,
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        restore_snapshots: bool,
+    },
+
     /// Request a code review from the agent.
     Review { review_request: ReviewRequest },
 
@@ -618,6 +631,10 @@ pub enum EventMsg {
 
     UndoCompleted(UndoCompletedEvent),
 
+    /// Notification that the last turns of the conversation were dropped in
+    /// response to `Op::ThreadRollback`.
+    ThreadRolledBack(ThreadRolledBackEvent),
+
     /// Notification that a model stream experienced an error or disconnect
     /// and the system is handling it (e.g., retrying with backoff).
     StreamError(StreamErrorEvent),
@@ -634,6 +651,9 @@ pub enum EventMsg {
     /// Response to GetHistoryEntryRequest.
     GetHistoryEntryResponse(GetHistoryEntryResponseEvent),
 
+    /// Response to ListHistoryEntries.
+    ListHistoryEntriesResponse(ListHistoryEntriesResponseEvent),
+
     /// List of MCP tools available to the agent.
     McpListToolsResponse(McpListToolsResponseEvent),
 
@@ -695,6 +715,8 @@ pub enum CodexErrorInfo {
     ResponseTooManyFailedAttempts {
         http_status_code: Option<u16>,
     },
+    /// A thread rollback could not be applied.
+    ThreadRollbackFailed,
     Other,
 }
 
@@ -1578,6 +1600,12 @@ pub struct UndoCompletedEvent {
     pub message: Option<String>,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct ThreadRolledBackEvent {
+    /// Number of user turns removed from the end of the conversation.
+    pub num_turns: u32,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct StreamErrorEvent {
     pub message: String,
@@ -1637,6 +1665,12 @@ pub struct GetHistoryEntryResponseEvent {
     pub entry: Option<HistoryEntry>,
 }
 
+/// Response payload for `Op::ListHistoryEntries`.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct ListHistoryEntriesResponseEvent {
+    pub entries: Vec<HistoryEntry>,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct McpListToolsResponseEvent {
     /// Fully qualified tool name -> tool definition.
@@ -1804,6 +1838,11 @@ pub enum ReviewDecision {
     /// remainder of the session.
     ApprovedForSession,
 
+    /// User has approved only the changes to `approved_paths` from a proposed
+    /// patch. Changes to the remaining files are rejected and reported back to
+    /// the model.
+    ApprovedSubset { approved_paths: Vec<PathBuf> },
+
     /// User has denied this command and the agent should not execute it, but
     /// it should continue the session and try something else.
     #[default]
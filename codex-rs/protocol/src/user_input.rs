@@ -27,4 +27,126 @@ pub enum UserInput {
         name: String,
         path: std::path::PathBuf,
     },
+
+    /// Local file whose contents (optionally limited to `range`) are inlined
+    /// into the prompt by core. PDFs and DOCX files are converted to text.
+    File {
+        path: std::path::PathBuf,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[ts(optional)]
+        range: Option<LineRange>,
+    },
+}
+
+/// Inclusive, 1-based range of lines within an attached file. Deserializing
+/// rejects a zero `start` or an `end` before `start`.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, TS, JsonSchema)]
+pub struct LineRange {
+    pub start: u32,
+    pub end: u32,
+}
+
+impl LineRange {
+    /// Returns `None` unless `1 <= start <= end`.
+    pub fn new(start: u32, end: u32) -> Option<Self> {
+        (start >= 1 && start <= end).then_some(Self { start, end })
+    }
+
+    /// Parses `START-END` or a single line number.
+    pub fn parse(spec: &str) -> Option<Self> {
+        let (start, end) = spec.split_once('-').unwrap_or((spec, spec));
+        let start = start.trim().parse::<u32>().ok()?;
+        let end = end.trim().parse::<u32>().ok()?;
+        Self::new(start, end)
+    }
+}
+
+impl<'de> Deserialize<'de> for LineRange {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct RawLineRange {
+            start: u32,
+            end: u32,
+        }
+
+        let RawLineRange { start, end } = RawLineRange::deserialize(deserializer)?;
+        Self::new(start, end).ok_or_else(|| {
+            serde::de::Error::custom(format!(
+                "invalid line range {start}-{end}: lines are 1-based and end must not be before start"
+            ))
+        })
+    }
+}
+
+impl std::fmt::Display for LineRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+/// Splits an attachment spec such as `logs/app.log:10-40` into the path and
+/// the optional line range. A suffix that is not a valid range is treated as
+/// part of the path.
+pub fn parse_file_attachment(spec: &str) -> (std::path::PathBuf, Option<LineRange>) {
+    if let Some((path, range)) = spec.rsplit_once(':')
+        && !path.is_empty()
+        && let Some(range) = LineRange::parse(range)
+    {
+        return (std::path::PathBuf::from(path), Some(range));
+    }
+    (std::path::PathBuf::from(spec), None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    #[test]
+    fn parses_file_attachment_specs() {
+        assert_eq!(
+            parse_file_attachment("logs/app.log:10-40"),
+            (
+                PathBuf::from("logs/app.log"),
+                Some(LineRange { start: 10, end: 40 })
+            )
+        );
+        assert_eq!(
+            parse_file_attachment("notes.md:7"),
+            (
+                PathBuf::from("notes.md"),
+                Some(LineRange { start: 7, end: 7 })
+            )
+        );
+        assert_eq!(
+            parse_file_attachment("spec.pdf"),
+            (PathBuf::from("spec.pdf"), None)
+        );
+        assert_eq!(
+            parse_file_attachment("C:\\logs\\app.log"),
+            (PathBuf::from("C:\\logs\\app.log"), None)
+        );
+        assert_eq!(
+            parse_file_attachment("file.txt:40-10"),
+            (PathBuf::from("file.txt:40-10"), None)
+        );
+        assert_eq!(
+            parse_file_attachment("file.txt:0-3"),
+            (PathBuf::from("file.txt:0-3"), None)
+        );
+    }
+
+    #[test]
+    fn rejects_zero_and_inverted_ranges_when_deserializing() {
+        assert_eq!(
+            serde_json::from_str::<LineRange>(r#"{"start":2,"end":5}"#).ok(),
+            Some(LineRange { start: 2, end: 5 })
+        );
+        assert!(serde_json::from_str::<LineRange>(r#"{"start":0,"end":5}"#).is_err());
+        assert!(serde_json::from_str::<LineRange>(r#"{"start":5,"end":2}"#).is_err());
+    }
 }
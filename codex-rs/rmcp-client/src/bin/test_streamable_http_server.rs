@@ -2,6 +2,7 @@ use std::borrow::Cow;
 use std::collections::HashMap;
 use std::io::ErrorKind;
 use std::net::SocketAddr;
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use axum::Router;
@@ -292,9 +293,18 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             ),
         );
 
-    let router = if let Ok(token) = std::env::var("MCP_EXPECT_BEARER") {
-        let expected = Arc::new(format!("Bearer {token}"));
-        router.layer(middleware::from_fn_with_state(expected, require_bearer))
+    let expected = if let Ok(path) = std::env::var("MCP_EXPECT_BEARER_FILE") {
+        Some(ExpectedBearer::File(PathBuf::from(path)))
+    } else {
+        std::env::var("MCP_EXPECT_BEARER")
+            .ok()
+            .map(ExpectedBearer::Fixed)
+    };
+    let router = if let Some(expected) = expected {
+        router.layer(middleware::from_fn_with_state(
+            Arc::new(expected),
+            require_bearer,
+        ))
     } else {
         router
     };
@@ -304,18 +314,37 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+/// The token clients must send. `File` is re-read on every request so tests
+/// can rotate the token while the server is running.
+enum ExpectedBearer {
+    Fixed(String),
+    File(PathBuf),
+}
+
+impl ExpectedBearer {
+    fn header_value(&self) -> Option<String> {
+        let token = match self {
+            ExpectedBearer::Fixed(token) => token.clone(),
+            ExpectedBearer::File(path) => std::fs::read_to_string(path).ok()?.trim().to_string(),
+        };
+        Some(format!("Bearer {token}"))
+    }
+}
+
 async fn require_bearer(
-    State(expected): State<Arc<String>>,
+    State(expected): State<Arc<ExpectedBearer>>,
     request: Request<Body>,
     next: Next,
 ) -> Result<Response, StatusCode> {
     if request.uri().path().contains("/.well-known/") {
         return Ok(next.run(request).await);
     }
-    if request
-        .headers()
-        .get(AUTHORIZATION)
-        .is_some_and(|value| value.as_bytes() == expected.as_bytes())
+    let expected = expected.header_value();
+    if let Some(expected) = expected
+        && request
+            .headers()
+            .get(AUTHORIZATION)
+            .is_some_and(|value| value.as_bytes() == expected.as_bytes())
     {
         Ok(next.run(request).await)
     } else {
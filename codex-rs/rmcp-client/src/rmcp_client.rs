@@ -95,10 +95,28 @@ pub type SendElicitation = Box<
     dyn Fn(RequestId, Elicitation) -> BoxFuture<'static, Result<ElicitationResponse>> + Send + Sync,
 >;
 
+/// Supplies the bearer token for a streamable HTTP server whose token can
+/// change while the client is running. `force_refresh` is set after the
+/// server rejected the current token.
+pub type BearerTokenProvider =
+    Arc<dyn Fn(bool) -> BoxFuture<'static, Result<String>> + Send + Sync>;
+
+/// State needed to reconnect a streamable HTTP client with a new bearer token.
+struct BearerTokenRefresh {
+    url: String,
+    http_client: reqwest::Client,
+    provider: BearerTokenProvider,
+    /// The token the running transport was built with.
+    current: Mutex<String>,
+    /// Set by `initialize` so that reconnects can repeat the handshake.
+    handler: Mutex<Option<LoggingClientHandler>>,
+}
+
 /// MCP client implemented on top of the official `rmcp` SDK.
 /// https://github.com/modelcontextprotocol/rust-sdk
 pub struct RmcpClient {
     state: Mutex<ClientState>,
+    bearer_refresh: Option<BearerTokenRefresh>,
 }
 
 impl RmcpClient {
@@ -155,6 +173,7 @@ impl RmcpClient {
             state: Mutex::new(ClientState::Connecting {
                 transport: Some(PendingTransport::ChildProcess(transport)),
             }),
+            bearer_refresh: None,
         })
     }
 
@@ -209,6 +228,40 @@ impl RmcpClient {
             state: Mutex::new(ClientState::Connecting {
                 transport: Some(transport),
             }),
+            bearer_refresh: None,
+        })
+    }
+
+    /// Like [`Self::new_streamable_http_client`], but asks `token_provider`
+    /// for the bearer token before every request. When the token changes
+    /// (because it expired, or because the server answered 401) the client
+    /// reconnects with the new token.
+    pub async fn new_streamable_http_client_with_token_provider(
+        url: &str,
+        token_provider: BearerTokenProvider,
+        http_headers: Option<HashMap<String, String>>,
+        env_http_headers: Option<HashMap<String, String>>,
+    ) -> Result<Self> {
+        let default_headers = build_default_headers(http_headers, env_http_headers)?;
+        let http_client =
+            apply_default_headers(reqwest::Client::builder(), &default_headers).build()?;
+        let token = token_provider(false).await?;
+
+        let http_config = StreamableHttpClientTransportConfig::with_uri(url.to_string())
+            .auth_header(token.clone());
+        let transport =
+            StreamableHttpClientTransport::with_client(http_client.clone(), http_config);
+        Ok(Self {
+            state: Mutex::new(ClientState::Connecting {
+                transport: Some(PendingTransport::StreamableHttp { transport }),
+            }),
+            bearer_refresh: Some(BearerTokenRefresh {
+                url: url.to_string(),
+                http_client,
+                provider: token_provider,
+                current: Mutex::new(token),
+                handler: Mutex::new(None),
+            }),
         })
     }
 
@@ -222,6 +275,9 @@ impl RmcpClient {
     ) -> Result<InitializeResult> {
         let rmcp_params: InitializeRequestParam = convert_to_rmcp(params.clone())?;
         let client_handler = LoggingClientHandler::new(rmcp_params, send_elicitation);
+        if let Some(refresh) = &self.bearer_refresh {
+            *refresh.handler.lock().await = Some(client_handler.clone());
+        }
 
         let (transport, oauth_persistor) = {
             let mut guard = self.state.lock().await;
@@ -286,14 +342,18 @@ impl RmcpClient {
         params: Option<ListToolsRequestParams>,
         timeout: Option<Duration>,
     ) -> Result<ListToolsResult> {
-        self.refresh_oauth_if_needed().await;
-        let service = self.service().await?;
         let rmcp_params = params
             .map(convert_to_rmcp::<_, PaginatedRequestParam>)
             .transpose()?;
 
-        let fut = service.list_tools(rmcp_params);
-        let result = run_with_timeout(fut, timeout, "tools/list").await?;
+        let result = self
+            .run_service_operation(timeout, |service| {
+                let rmcp_params = rmcp_params.clone();
+                async move {
+                    run_with_timeout(service.list_tools(rmcp_params), timeout, "tools/list").await
+                }
+            })
+            .await?;
         let converted = convert_to_mcp(result)?;
         self.persist_oauth_tokens().await;
         Ok(converted)
@@ -304,14 +364,23 @@ impl RmcpClient {
         params: Option<ListResourcesRequestParams>,
         timeout: Option<Duration>,
     ) -> Result<ListResourcesResult> {
-        self.refresh_oauth_if_needed().await;
-        let service = self.service().await?;
         let rmcp_params = params
             .map(convert_to_rmcp::<_, PaginatedRequestParam>)
             .transpose()?;
 
-        let fut = service.list_resources(rmcp_params);
-        let result = run_with_timeout(fut, timeout, "resources/list").await?;
+        let result = self
+            .run_service_operation(timeout, |service| {
+                let rmcp_params = rmcp_params.clone();
+                async move {
+                    run_with_timeout(
+                        service.list_resources(rmcp_params),
+                        timeout,
+                        "resources/list",
+                    )
+                    .await
+                }
+            })
+            .await?;
         let converted = convert_to_mcp(result)?;
         self.persist_oauth_tokens().await;
         Ok(converted)
@@ -322,14 +391,23 @@ impl RmcpClient {
         params: Option<ListResourceTemplatesRequestParams>,
         timeout: Option<Duration>,
     ) -> Result<ListResourceTemplatesResult> {
-        self.refresh_oauth_if_needed().await;
-        let service = self.service().await?;
         let rmcp_params = params
             .map(convert_to_rmcp::<_, PaginatedRequestParam>)
             .transpose()?;
 
-        let fut = service.list_resource_templates(rmcp_params);
-        let result = run_with_timeout(fut, timeout, "resources/templates/list").await?;
+        let result = self
+            .run_service_operation(timeout, |service| {
+                let rmcp_params = rmcp_params.clone();
+                async move {
+                    run_with_timeout(
+                        service.list_resource_templates(rmcp_params),
+                        timeout,
+                        "resources/templates/list",
+                    )
+                    .await
+                }
+            })
+            .await?;
         let converted = convert_to_mcp(result)?;
         self.persist_oauth_tokens().await;
         Ok(converted)
@@ -340,11 +418,20 @@ impl RmcpClient {
         params: ReadResourceRequestParams,
         timeout: Option<Duration>,
     ) -> Result<ReadResourceResult> {
-        self.refresh_oauth_if_needed().await;
-        let service = self.service().await?;
         let rmcp_params: ReadResourceRequestParam = convert_to_rmcp(params)?;
-        let fut = service.read_resource(rmcp_params);
-        let result = run_with_timeout(fut, timeout, "resources/read").await?;
+        let result = self
+            .run_service_operation(timeout, |service| {
+                let rmcp_params = rmcp_params.clone();
+                async move {
+                    run_with_timeout(
+                        service.read_resource(rmcp_params),
+                        timeout,
+                        "resources/read",
+                    )
+                    .await
+                }
+            })
+            .await?;
         let converted = convert_to_mcp(result)?;
         self.persist_oauth_tokens().await;
         Ok(converted)
@@ -356,12 +443,16 @@ impl RmcpClient {
         arguments: Option<serde_json::Value>,
         timeout: Option<Duration>,
     ) -> Result<CallToolResult> {
-        self.refresh_oauth_if_needed().await;
-        let service = self.service().await?;
         let params = CallToolRequestParams { arguments, name };
         let rmcp_params: CallToolRequestParam = convert_to_rmcp(params)?;
-        let fut = service.call_tool(rmcp_params);
-        let rmcp_result = run_with_timeout(fut, timeout, "tools/call").await?;
+        let rmcp_result = self
+            .run_service_operation(timeout, |service| {
+                let rmcp_params = rmcp_params.clone();
+                async move {
+                    run_with_timeout(service.call_tool(rmcp_params), timeout, "tools/call").await
+                }
+            })
+            .await?;
         let converted = convert_call_tool_result(rmcp_result)?;
         self.persist_oauth_tokens().await;
         Ok(converted)
@@ -397,6 +488,75 @@ impl RmcpClient {
         Ok(response)
     }
 
+    /// Runs `operation` against the current service, refreshing credentials
+    /// first. A request rejected with 401 is retried once after the bearer
+    /// token provider hands out a new token.
+    async fn run_service_operation<T, F, Fut>(
+        &self,
+        timeout: Option<Duration>,
+        operation: F,
+    ) -> Result<T>
+    where
+        F: Fn(Arc<RunningService<RoleClient, LoggingClientHandler>>) -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        self.refresh_oauth_if_needed().await;
+        self.refresh_bearer_token(false, timeout).await?;
+        match operation(self.service().await?).await {
+            Err(error) if self.bearer_refresh.is_some() && is_unauthorized(&error) => {
+                warn!("MCP server rejected the bearer token, refreshing: {error:#}");
+                self.refresh_bearer_token(true, timeout).await?;
+                operation(self.service().await?).await
+            }
+            result => result,
+        }
+    }
+
+    /// Asks the token provider for the current token and reconnects when it
+    /// differs from the one the running transport was built with.
+    async fn refresh_bearer_token(
+        &self,
+        force_refresh: bool,
+        timeout: Option<Duration>,
+    ) -> Result<()> {
+        let Some(refresh) = &self.bearer_refresh else {
+            return Ok(());
+        };
+        let token = (refresh.provider)(force_refresh).await?;
+
+        // Holding `current` across the reconnect keeps concurrent requests
+        // from reconnecting more than once for the same token.
+        let mut current = refresh.current.lock().await;
+        if *current == token {
+            return Ok(());
+        }
+        let Some(handler) = refresh.handler.lock().await.clone() else {
+            return Err(anyhow!("MCP client not initialized"));
+        };
+
+        let http_config = StreamableHttpClientTransportConfig::with_uri(refresh.url.clone())
+            .auth_header(token.clone());
+        let transport =
+            StreamableHttpClientTransport::with_client(refresh.http_client.clone(), http_config);
+        let handshake = service::serve_client(handler, transport);
+        let service = match timeout {
+            Some(duration) => time::timeout(duration, handshake)
+                .await
+                .map_err(|_| anyhow!("timed out reconnecting to MCP server after {duration:?}"))?
+                .map_err(|err| anyhow!("reconnecting to MCP server failed: {err}"))?,
+            None => handshake
+                .await
+                .map_err(|err| anyhow!("reconnecting to MCP server failed: {err}"))?,
+        };
+
+        *self.state.lock().await = ClientState::Ready {
+            service: Arc::new(service),
+            oauth: None,
+        };
+        *current = token;
+        Ok(())
+    }
+
     async fn service(&self) -> Result<Arc<RunningService<RoleClient, LoggingClientHandler>>> {
         let guard = self.state.lock().await;
         match &*guard {
@@ -435,6 +595,13 @@ impl RmcpClient {
     }
 }
 
+/// `run_with_timeout` flattens transport errors into their message, so a
+/// rejected token is recognised by the HTTP status in that message.
+fn is_unauthorized(error: &anyhow::Error) -> bool {
+    let message = format!("{error:#}").to_ascii_lowercase();
+    message.contains("401") || message.contains("unauthorized") || message.contains("auth required")
+}
+
 async fn create_oauth_transport_and_runtime(
     server_name: &str,
     url: &str,
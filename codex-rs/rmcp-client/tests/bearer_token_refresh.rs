@@ -0,0 +1,133 @@
+use std::net::TcpListener;
+use std::net::TcpStream;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use codex_rmcp_client::BearerTokenProvider;
+use codex_rmcp_client::ElicitationAction;
+use codex_rmcp_client::ElicitationResponse;
+use codex_rmcp_client::RmcpClient;
+use escargot::CargoBuild;
+use futures::FutureExt as _;
+use mcp_types::ClientCapabilities;
+use mcp_types::Implementation;
+use mcp_types::InitializeRequestParams;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use tokio::process::Command;
+
+fn streamable_http_server_bin() -> anyhow::Result<PathBuf> {
+    let build = CargoBuild::new()
+        .package("codex-rmcp-client")
+        .bin("test_streamable_http_server")
+        .run()?;
+    Ok(build.path().to_path_buf())
+}
+
+fn init_params() -> InitializeRequestParams {
+    InitializeRequestParams {
+        capabilities: ClientCapabilities {
+            experimental: None,
+            roots: None,
+            sampling: None,
+            elicitation: Some(json!({})),
+        },
+        client_info: Implementation {
+            name: "codex-test".into(),
+            version: "0.0.0-test".into(),
+            title: Some("Codex rmcp bearer refresh test".into()),
+            user_agent: None,
+        },
+        protocol_version: mcp_types::MCP_SCHEMA_VERSION.to_string(),
+    }
+}
+
+/// Hands out the token in `path`, caching it until asked to refresh, the way
+/// an `auth_command` helper is cached.
+fn file_token_provider(path: PathBuf, forced_refreshes: Arc<AtomicUsize>) -> BearerTokenProvider {
+    let cached = Arc::new(Mutex::new(None::<String>));
+    Arc::new(move |force_refresh: bool| {
+        let path = path.clone();
+        let cached = Arc::clone(&cached);
+        let forced_refreshes = Arc::clone(&forced_refreshes);
+        async move {
+            if force_refresh {
+                forced_refreshes.fetch_add(1, Ordering::SeqCst);
+            }
+            let mut cached = cached
+                .lock()
+                .map_err(|_| anyhow::anyhow!("token cache poisoned"))?;
+            if force_refresh || cached.is_none() {
+                *cached = Some(std::fs::read_to_string(&path)?.trim().to_string());
+            }
+            cached
+                .clone()
+                .ok_or_else(|| anyhow::anyhow!("no token cached"))
+        }
+        .boxed()
+    })
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn rejected_bearer_token_is_refreshed_and_retried() -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let token_file = temp.path().join("token");
+    std::fs::write(&token_file, "first-token")?;
+
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let bind_addr = listener.local_addr()?.to_string();
+    drop(listener);
+    let _server = Command::new(streamable_http_server_bin()?)
+        .kill_on_drop(true)
+        .env("MCP_STREAMABLE_HTTP_BIND_ADDR", &bind_addr)
+        .env("MCP_EXPECT_BEARER_FILE", &token_file)
+        .spawn()?;
+    let mut attempts = 0;
+    while TcpStream::connect(&bind_addr).is_err() {
+        attempts += 1;
+        anyhow::ensure!(attempts < 100, "test server did not start");
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+
+    let forced_refreshes = Arc::new(AtomicUsize::new(0));
+    let client = RmcpClient::new_streamable_http_client_with_token_provider(
+        &format!("http://{bind_addr}/mcp"),
+        file_token_provider(token_file.clone(), Arc::clone(&forced_refreshes)),
+        None,
+        None,
+    )
+    .await?;
+    client
+        .initialize(
+            init_params(),
+            Some(Duration::from_secs(5)),
+            Box::new(|_, _| {
+                async {
+                    Ok(ElicitationResponse {
+                        action: ElicitationAction::Accept,
+                        content: Some(json!({})),
+                    })
+                }
+                .boxed()
+            }),
+        )
+        .await?;
+    client
+        .list_tools(None, Some(Duration::from_secs(5)))
+        .await?;
+    assert_eq!(forced_refreshes.load(Ordering::SeqCst), 0);
+
+    // Rotate the token on the server; the cached one is now rejected.
+    std::fs::write(&token_file, "second-token")?;
+    let tools = client
+        .list_tools(None, Some(Duration::from_secs(5)))
+        .await?;
+
+    assert!(!tools.tools.is_empty());
+    assert_eq!(forced_refreshes.load(Ordering::SeqCst), 1);
+    Ok(())
+}
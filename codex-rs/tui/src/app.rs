@@ -3,8 +3,12 @@ use crate::app_event::AppEvent;
 use crate::app_event_sender::AppEventSender;
 use crate::bottom_pane::ApprovalRequest;
 use crate::chatwidget::ChatWidget;
+use crate::clipboard_copy;
+use crate::diff_browser::DiffBrowserOverlay;
+use crate::diff_browser::apply_hunk_and_reload;
 use crate::diff_render::DiffSummary;
 use crate::exec_command::strip_bash_lc_and_escape;
+use crate::external_editor;
 use crate::file_search::FileSearchManager;
 use crate::history_cell::HistoryCell;
 use crate::model_migration::ModelMigrationOutcome;
@@ -18,11 +22,15 @@ use crate::tui;
 use crate::tui::TuiEvent;
 use crate::update_action::UpdateAction;
 use codex_ansi_escape::ansi_escape_line;
+use codex_common::oss::register_served_models;
 use codex_core::AuthManager;
 use codex_core::ConversationManager;
 use codex_core::config::Config;
 use codex_core::config::edit::ConfigEdit;
 use codex_core::config::edit::ConfigEditsBuilder;
+use codex_core::export::ExportOptions;
+use codex_core::export::export_dir;
+use codex_core::export::export_rollout;
 #[cfg(target_os = "windows")]
 use codex_core::features::Feature;
 use codex_core::models_manager::manager::ModelsManager;
@@ -347,6 +355,7 @@ impl App {
             auth_manager.clone(),
             SessionSource::Cli,
         ));
+        register_served_models(&config, &conversation_manager.get_models_manager()).await;
         let mut model = conversation_manager
             .get_models_manager()
             .get_model(&config.model, &config)
@@ -725,6 +734,28 @@ impl App {
             AppEvent::ExitRequest => {
                 return Ok(false);
             }
+            AppEvent::OpenExternalEditor => {
+                let command = match external_editor::editor_command() {
+                    Ok(command) => command,
+                    Err(err) => {
+                        self.chat_widget
+                            .add_error_message(format!("Cannot open external editor: {err}"));
+                        return Ok(true);
+                    }
+                };
+                let draft = self.chat_widget.composer_text();
+                let edited = tui
+                    .with_released_terminal(external_editor::edit_in_external_editor(
+                        &command, &draft,
+                    ))
+                    .await?;
+                match edited {
+                    Ok(text) => self.chat_widget.apply_external_edit(text),
+                    Err(err) => self
+                        .chat_widget
+                        .add_error_message(format!("External editor failed: {err}")),
+                }
+            }
             AppEvent::CodexOp(op) => self.chat_widget.submit_op(op),
             AppEvent::DiffResult(text) => {
                 // Clear the in-progress state in the bottom pane
@@ -742,6 +773,69 @@ impl App {
                 ));
                 tui.frame_requester().schedule_frame();
             }
+            AppEvent::OpenDiffBrowser(diffs) => {
+                self.chat_widget.on_diff_complete();
+                let _ = tui.enter_alt_screen();
+                self.overlay = Some(Overlay::new_diff_browser(DiffBrowserOverlay::new(
+                    diffs,
+                    self.config.file_opener,
+                    self.app_event_tx.clone(),
+                )));
+                tui.frame_requester().schedule_frame();
+            }
+            AppEvent::ApplyDiffHunk { patch, action } => {
+                let cwd = self.config.cwd.clone();
+                let app_event_tx = self.app_event_tx.clone();
+                tokio::spawn(async move {
+                    let result = apply_hunk_and_reload(cwd, patch, action).await;
+                    app_event_tx.send(AppEvent::DiffBrowserUpdated(result));
+                });
+            }
+            AppEvent::DiffBrowserUpdated(result) => {
+                if let Some(Overlay::DiffBrowser(browser)) = &mut self.overlay {
+                    browser.update(result);
+                    tui.frame_requester().schedule_frame();
+                }
+            }
+            AppEvent::ExportSession(format) => {
+                let Some(rollout_path) = self.chat_widget.rollout_path() else {
+                    self.chat_widget
+                        .add_info_message("Nothing to export yet.".to_string(), None);
+                    return Ok(true);
+                };
+                let file_name = match self.chat_widget.conversation_id() {
+                    Some(id) => format!("codex-session-{id}.{}", format.extension()),
+                    None => format!("codex-session.{}", format.extension()),
+                };
+                let dir = export_dir(&self.config.codex_home);
+                let output = dir.join(file_name);
+                let options = ExportOptions {
+                    format,
+                    redact_secrets: true,
+                };
+                match export_rollout(&rollout_path, options)
+                    .await
+                    .and_then(|transcript| {
+                        std::fs::create_dir_all(&dir)?;
+                        std::fs::write(&output, transcript)
+                    }) {
+                    Ok(()) => self.chat_widget.add_info_message(
+                        format!("Exported session to {}", output.display()),
+                        None,
+                    ),
+                    Err(err) => self
+                        .chat_widget
+                        .add_error_message(format!("Failed to export session: {err}")),
+                }
+            }
+            AppEvent::CopyToClipboard { text, label } => match clipboard_copy::copy_text(text) {
+                Ok(()) => self
+                    .chat_widget
+                    .add_info_message(format!("Copied {label} to the clipboard."), None),
+                Err(err) => self
+                    .chat_widget
+                    .add_error_message(format!("Failed to copy {label}: {err}")),
+            },
             AppEvent::StartFileSearch(query) => {
                 if !query.is_empty() {
                     self.file_search.on_user_query(query);
@@ -1085,7 +1179,7 @@ impl App {
                 ApprovalRequest::ApplyPatch { cwd, changes, .. } => {
                     let _ = tui.enter_alt_screen();
                     let diff_summary = DiffSummary::new(changes, cwd);
-                    self.overlay = Some(Overlay::new_static_with_renderables(
+                    self.overlay = Some(Overlay::new_static_diff(
                         vec![diff_summary.into()],
                         "P A T C H".to_string(),
                     ));
@@ -1,13 +1,16 @@
 use std::path::PathBuf;
 
 use codex_common::approval_presets::ApprovalPreset;
+use codex_core::export::ExportFormat;
 use codex_core::protocol::ConversationPathResponseEvent;
 use codex_core::protocol::Event;
 use codex_core::protocol::RateLimitSnapshot;
 use codex_file_search::FileMatch;
+use codex_git::HunkAction;
 use codex_protocol::openai_models::ModelPreset;
 
 use crate::bottom_pane::ApprovalRequest;
+use crate::get_git_diff::GitDiffs;
 use crate::history_cell::HistoryCell;
 
 use codex_core::features::Feature;
@@ -29,6 +32,9 @@ pub(crate) enum AppEvent {
     /// Request to exit the application gracefully.
     ExitRequest,
 
+    /// Suspend the TUI and edit the composer draft in `$VISUAL`/`$EDITOR`.
+    OpenExternalEditor,
+
     /// Forward an `Op` to the Agent. Using an `AppEvent` for this avoids
     /// bubbling channels through layers of widgets.
     CodexOp(codex_core::protocol::Op),
@@ -49,9 +55,33 @@ pub(crate) enum AppEvent {
     /// Result of refreshing rate limits
     RateLimitSnapshotFetched(RateLimitSnapshot),
 
-    /// Result of computing a `/diff` command.
+    /// Result of computing a `/diff` command that has nothing to browse: no
+    /// changes, not a repository, or an error message.
     DiffResult(String),
 
+    /// Open the diff browser on the repository's current changes.
+    OpenDiffBrowser(GitDiffs),
+
+    /// Stage, unstage or discard `patch` from the diff browser, then reload it.
+    ApplyDiffHunk {
+        patch: String,
+        action: HunkAction,
+    },
+
+    /// Reloaded diffs for the open diff browser, or why the last action or
+    /// reload failed.
+    DiffBrowserUpdated(Result<GitDiffs, String>),
+
+    /// Write the current session transcript to a file in the working
+    /// directory.
+    ExportSession(ExportFormat),
+
+    /// Copy `text` to the clipboard; `label` names it in the confirmation.
+    CopyToClipboard {
+        text: String,
+        label: String,
+    },
+
     InsertHistoryCell(Box<dyn HistoryCell>),
 
     StartCommitAnimation,
@@ -27,9 +27,12 @@ use super::footer::footer_height;
 use super::footer::render_footer;
 use super::footer::reset_mode_after_activity;
 use super::footer::toggle_shortcut_mode;
+use super::history_search_popup::HistorySearchPopup;
 use super::paste_burst::CharDecision;
 use super::paste_burst::PasteBurst;
 use super::skill_popup::SkillPopup;
+use super::vi_mode::ViOutcome;
+use super::vi_mode::ViState;
 use crate::bottom_pane::paste_burst::FlushResult;
 use crate::bottom_pane::prompt_args::expand_custom_prompt;
 use crate::bottom_pane::prompt_args::expand_if_numeric_with_positional_args;
@@ -55,8 +58,11 @@ use crate::clipboard_paste::normalize_pasted_path;
 use crate::clipboard_paste::pasted_image_format;
 use crate::history_cell;
 use crate::ui_consts::LIVE_PREFIX_COLS;
+use codex_core::config::types::EditingMode;
+use codex_core::protocol::Op;
 use codex_core::skills::model::SkillMetadata;
 use codex_file_search::FileMatch;
+use codex_protocol::message_history::HistoryEntry;
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::path::Path;
@@ -82,6 +88,61 @@ struct AttachedImage {
     path: PathBuf,
 }
 
+/// Maximum number of removed pastes and images kept around for undo.
+const MAX_DETACHED_ATTACHMENTS: usize = 32;
+
+/// Pastes and images whose placeholders were removed from the text. They are kept
+/// so that undoing the removal restores the placeholder together with its content.
+#[derive(Debug, Default)]
+struct DetachedAttachments {
+    pastes: Vec<(String, String)>,
+    images: Vec<AttachedImage>,
+}
+
+impl DetachedAttachments {
+    /// Move back any detached paste or image whose placeholder reappeared in `text`.
+    fn reattach(
+        &mut self,
+        text: &str,
+        pending_pastes: &mut Vec<(String, String)>,
+        attached_images: &mut Vec<AttachedImage>,
+    ) {
+        let mut idx = 0;
+        while idx < self.pastes.len() {
+            let placeholder = &self.pastes[idx].0;
+            if text.contains(placeholder.as_str())
+                && !pending_pastes.iter().any(|(ph, _)| ph == placeholder)
+            {
+                pending_pastes.push(self.pastes.remove(idx));
+            } else {
+                idx += 1;
+            }
+        }
+
+        let mut idx = 0;
+        while idx < self.images.len() {
+            let placeholder = &self.images[idx].placeholder;
+            let in_text = text.matches(placeholder.as_str()).count();
+            let attached = attached_images
+                .iter()
+                .filter(|img| &img.placeholder == placeholder)
+                .count();
+            if in_text > attached {
+                attached_images.push(self.images.remove(idx));
+            } else {
+                idx += 1;
+            }
+        }
+    }
+
+    fn truncate(&mut self) {
+        let excess = self.pastes.len().saturating_sub(MAX_DETACHED_ATTACHMENTS);
+        self.pastes.drain(..excess);
+        let excess = self.images.len().saturating_sub(MAX_DETACHED_ATTACHMENTS);
+        self.images.drain(..excess);
+    }
+}
+
 enum PromptSelectionMode {
     Completion,
     Submit,
@@ -107,6 +168,7 @@ pub(crate) struct ChatComposer {
     large_paste_counters: HashMap<usize, usize>,
     has_focus: bool,
     attached_images: Vec<AttachedImage>,
+    detached_attachments: DetachedAttachments,
     placeholder_text: String,
     is_task_running: bool,
     // Non-bracketed paste burst tracker.
@@ -120,6 +182,8 @@ pub(crate) struct ChatComposer {
     context_window_used_tokens: Option<i64>,
     skills: Option<Vec<SkillMetadata>>,
     dismissed_skill_popup_token: Option<String>,
+    /// Modal editing state; `None` for the default emacs-style bindings.
+    vi: Option<ViState>,
 }
 
 /// Popup state – at most one can be visible at any time.
@@ -128,6 +192,7 @@ enum ActivePopup {
     Command(CommandPopup),
     File(FileSearchPopup),
     Skill(SkillPopup),
+    History(HistorySearchPopup),
 }
 
 const FOOTER_SPACING_HEIGHT: u16 = 0;
@@ -157,6 +222,7 @@ impl ChatComposer {
             large_paste_counters: HashMap::new(),
             has_focus: has_input_focus,
             attached_images: Vec::new(),
+            detached_attachments: DetachedAttachments::default(),
             placeholder_text,
             is_task_running: false,
             paste_burst: PasteBurst::default(),
@@ -168,6 +234,7 @@ impl ChatComposer {
             context_window_used_tokens: None,
             skills: None,
             dismissed_skill_popup_token: None,
+            vi: None,
         };
         // Apply configuration via the setter to keep side-effects centralized.
         this.set_disable_paste_burst(disable_paste_burst);
@@ -178,6 +245,20 @@ impl ChatComposer {
         self.skills = skills;
     }
 
+    pub(crate) fn set_editing_mode(&mut self, mode: EditingMode) {
+        self.vi = match mode {
+            EditingMode::Emacs => None,
+            EditingMode::Vi => Some(ViState::new()),
+        };
+        self.textarea.set_highlight(None);
+    }
+
+    /// True when Esc belongs to vi mode (leaving insert/visual mode or cancelling
+    /// a pending command) rather than to interrupt or backtrack handling.
+    pub(crate) fn consumes_esc(&self) -> bool {
+        self.vi.as_ref().is_some_and(ViState::consumes_esc)
+    }
+
     fn layout_areas(&self, area: Rect) -> [Rect; 3] {
         let footer_props = self.footer_props();
         let footer_hint_height = self
@@ -193,6 +274,7 @@ impl ChatComposer {
             ActivePopup::Skill(popup) => {
                 Constraint::Max(popup.calculate_required_height(area.width))
             }
+            ActivePopup::History(popup) => Constraint::Max(popup.calculate_required_height()),
             ActivePopup::None => Constraint::Max(footer_total_height),
         };
         let [composer_rect, popup_rect] =
@@ -223,6 +305,19 @@ impl ChatComposer {
     /// Integrate an asynchronous response to an on-demand history lookup. If
     /// the entry is present and the offset matches the current cursor we
     /// immediately populate the textarea.
+    /// Integrate the full history requested when the Ctrl+R search opened.
+    pub(crate) fn on_history_entries_response(
+        &mut self,
+        entries: Vec<HistoryEntry>,
+        workspace_root: PathBuf,
+    ) -> bool {
+        let ActivePopup::History(popup) = &mut self.active_popup else {
+            return false;
+        };
+        popup.set_entries(entries, workspace_root);
+        true
+    }
+
     pub(crate) fn on_history_entry_response(
         &mut self,
         log_id: u64,
@@ -290,15 +385,72 @@ impl ChatComposer {
 
     /// Replace the entire composer content with `text` and reset cursor.
     pub(crate) fn set_text_content(&mut self, text: String) {
-        // Clear any existing content, placeholders, and attachments first.
-        self.textarea.set_text("");
-        self.pending_pastes.clear();
-        self.attached_images.clear();
+        // Clear any existing content, placeholders, and attachments first. The
+        // attachments stay detached so undoing the replacement brings them back.
+        self.detached_attachments
+            .pastes
+            .extend(self.pending_pastes.drain(..));
+        self.detached_attachments
+            .images
+            .extend(self.attached_images.drain(..));
+        self.detached_attachments.truncate();
         self.textarea.set_text(&text);
         self.textarea.set_cursor(0);
         self.sync_popups();
     }
 
+    /// Replace the composer content with text edited outside the TUI.
+    ///
+    /// Paste and image placeholders that survived the edit are turned back into
+    /// atomic elements so their pending content and attachments are submitted
+    /// as before; attachments whose placeholder was deleted are dropped.
+    pub(crate) fn apply_external_edit(&mut self, text: String) {
+        let mut placeholders: Vec<String> = self
+            .pending_pastes
+            .iter()
+            .map(|(placeholder, _)| placeholder.clone())
+            .chain(
+                self.attached_images
+                    .iter()
+                    .map(|img| img.placeholder.clone()),
+            )
+            .collect();
+        // Prefer the longest placeholder when several start at the same offset.
+        placeholders.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+        placeholders.dedup();
+
+        // Rebuild the buffer as one undo step so the whole edit can be reverted.
+        self.textarea.transact(|textarea| {
+            textarea.replace_range(0..textarea.text().len(), "");
+            let mut rest = text.as_str();
+            while !rest.is_empty() {
+                let next = placeholders
+                    .iter()
+                    .filter_map(|placeholder| {
+                        rest.find(placeholder.as_str())
+                            .map(|idx| (idx, placeholder))
+                    })
+                    .min_by_key(|(idx, _)| *idx);
+                let Some((idx, placeholder)) = next else {
+                    textarea.insert_str(rest);
+                    break;
+                };
+                textarea.insert_str(&rest[..idx]);
+                textarea.insert_element(placeholder);
+                rest = &rest[idx + placeholder.len()..];
+            }
+        });
+
+        Self::sync_attachments_with_text(
+            &text,
+            &mut self.pending_pastes,
+            &mut self.attached_images,
+            &mut self.detached_attachments,
+        );
+        self.textarea.set_cursor(self.textarea.text().len());
+        self.sync_popups();
+    }
+
     pub(crate) fn clear_for_ctrl_c(&mut self) -> Option<String> {
         if self.is_empty() {
             return None;
@@ -391,16 +543,43 @@ impl ChatComposer {
 
     /// Handle a key event coming from the main UI.
     pub fn handle_key_event(&mut self, key_event: KeyEvent) -> (InputResult, bool) {
+        if matches!(self.active_popup, ActivePopup::None)
+            && let Some(vi) = self.vi.as_mut()
+        {
+            let outcome = vi.handle_key(&mut self.textarea, key_event);
+            self.textarea.set_highlight(vi.selection(&self.textarea));
+            if outcome == ViOutcome::Handled {
+                self.footer_mode = reset_mode_after_activity(self.footer_mode);
+                Self::sync_attachments_with_text(
+                    self.textarea.text(),
+                    &mut self.pending_pastes,
+                    &mut self.attached_images,
+                    &mut self.detached_attachments,
+                );
+                self.sync_popups();
+                return (InputResult::None, true);
+            }
+        }
+
         let result = match &mut self.active_popup {
             ActivePopup::Command(_) => self.handle_key_event_with_slash_popup(key_event),
             ActivePopup::File(_) => self.handle_key_event_with_file_popup(key_event),
             ActivePopup::Skill(_) => self.handle_key_event_with_skill_popup(key_event),
+            ActivePopup::History(_) => self.handle_key_event_with_history_popup(key_event),
             ActivePopup::None => self.handle_key_event_without_popup(key_event),
         };
 
         // Update (or hide/show) popup after processing the key.
         self.sync_popups();
 
+        if matches!(
+            result.0,
+            InputResult::Submitted(_) | InputResult::Command(_)
+        ) && let Some(vi) = self.vi.as_mut()
+        {
+            vi.reset();
+        }
+
         result
     }
 
@@ -793,6 +972,89 @@ impl ChatComposer {
         }
     }
 
+    /// Handle key event when the Ctrl+R history search is visible. Printable
+    /// characters edit the search query; the draft itself is only replaced when
+    /// a prompt is accepted.
+    fn handle_key_event_with_history_popup(&mut self, key_event: KeyEvent) -> (InputResult, bool) {
+        let ActivePopup::History(popup) = &mut self.active_popup else {
+            unreachable!();
+        };
+
+        match key_event {
+            KeyEvent {
+                code: KeyCode::Up, ..
+            }
+            | KeyEvent {
+                code: KeyCode::Char('p'),
+                modifiers: KeyModifiers::CONTROL,
+                ..
+            } => {
+                popup.move_up();
+            }
+            // Like readline, pressing Ctrl+R again steps to the next (older) match.
+            KeyEvent {
+                code: KeyCode::Down,
+                ..
+            }
+            | KeyEvent {
+                code: KeyCode::Char('n' | 'r'),
+                modifiers: KeyModifiers::CONTROL,
+                ..
+            } => {
+                popup.move_down();
+            }
+            KeyEvent {
+                code: KeyCode::Tab, ..
+            } => {
+                popup.toggle_scope();
+            }
+            KeyEvent {
+                code: KeyCode::Backspace,
+                ..
+            } => {
+                popup.pop_char();
+            }
+            KeyEvent {
+                code: KeyCode::Enter,
+                modifiers: KeyModifiers::NONE,
+                ..
+            } => {
+                let selected = popup.selected_text().map(str::to_string);
+                self.active_popup = ActivePopup::None;
+                if let Some(text) = selected {
+                    self.set_text_content(text);
+                    self.textarea.set_cursor(self.textarea.text().len());
+                }
+            }
+            KeyEvent {
+                code: KeyCode::Esc, ..
+            }
+            | KeyEvent {
+                code: KeyCode::Char('c' | 'g'),
+                modifiers: KeyModifiers::CONTROL,
+                ..
+            } => {
+                self.active_popup = ActivePopup::None;
+            }
+            KeyEvent {
+                code: KeyCode::Char(ch),
+                modifiers,
+                kind: KeyEventKind::Press | KeyEventKind::Repeat,
+                ..
+            } if !has_ctrl_or_alt(modifiers) => {
+                popup.push_char(ch);
+            }
+            _ => return (InputResult::None, false),
+        }
+        (InputResult::None, true)
+    }
+
+    fn open_history_search(&mut self) {
+        self.active_popup = ActivePopup::History(HistorySearchPopup::new());
+        self.app_event_tx
+            .send(AppEvent::CodexOp(Op::ListHistoryEntries));
+    }
+
     fn is_image_path(path: &str) -> bool {
         let lower = path.to_ascii_lowercase();
         lower.ends_with(".png") || lower.ends_with(".jpg") || lower.ends_with(".jpeg")
@@ -1038,6 +1300,24 @@ impl ChatComposer {
                 self.app_event_tx.send(AppEvent::ExitRequest);
                 (InputResult::None, true)
             }
+            KeyEvent {
+                code: KeyCode::Char('g'),
+                modifiers: KeyModifiers::CONTROL,
+                kind: KeyEventKind::Press,
+                ..
+            } => {
+                self.app_event_tx.send(AppEvent::OpenExternalEditor);
+                (InputResult::None, true)
+            }
+            KeyEvent {
+                code: KeyCode::Char('r'),
+                modifiers: KeyModifiers::CONTROL,
+                kind: KeyEventKind::Press,
+                ..
+            } => {
+                self.open_history_search();
+                (InputResult::None, true)
+            }
             // -------------------------------------------------------------
             // History navigation (Up / Down) – only when the composer is not
             // empty or when the cursor is at the correct position, to avoid
@@ -1111,6 +1391,7 @@ impl ChatComposer {
                 }
                 // If we have pending placeholder pastes, replace them in the textarea text
                 // and continue to the normal submission flow to handle slash commands.
+                // The pastes stay detached so undoing the expansion brings them back.
                 if !self.pending_pastes.is_empty() {
                     let mut text = self.textarea.text().to_string();
                     for (placeholder, actual) in &self.pending_pastes {
@@ -1119,7 +1400,10 @@ impl ChatComposer {
                         }
                     }
                     self.textarea.set_text(&text);
-                    self.pending_pastes.clear();
+                    self.detached_attachments
+                        .pastes
+                        .extend(self.pending_pastes.drain(..));
+                    self.detached_attachments.truncate();
                 }
 
                 // During a paste-like burst, treat Enter as a newline instead of submit.
@@ -1198,6 +1482,10 @@ impl ChatComposer {
                 if !text.is_empty() {
                     self.history.record_local_submission(&text);
                 }
+                // The submitted text and its attachments are gone, so nothing before
+                // this point can be undone back into the composer.
+                self.textarea.clear_undo_history();
+                self.detached_attachments = DetachedAttachments::default();
                 // Do not clear attached_images here; ChatWidget drains them via take_recent_submission_images().
                 (InputResult::Submitted(text), true)
             }
@@ -1332,34 +1620,76 @@ impl ChatComposer {
             }
         }
 
-        // Check if any placeholders were removed and remove their corresponding pending pastes
-        self.pending_pastes
-            .retain(|(placeholder, _)| text_after.contains(placeholder));
+        Self::sync_attachments_with_text(
+            text_after,
+            &mut self.pending_pastes,
+            &mut self.attached_images,
+            &mut self.detached_attachments,
+        );
+
+        (InputResult::None, true)
+    }
+
+    /// Detach pending pastes and attached images whose placeholders no longer appear in
+    /// `text`, and reattach detached ones that reappeared (e.g. after undo).
+    fn sync_attachments_with_text(
+        text: &str,
+        pending_pastes: &mut Vec<(String, String)>,
+        attached_images: &mut Vec<AttachedImage>,
+        detached: &mut DetachedAttachments,
+    ) {
+        detached.reattach(text, pending_pastes, attached_images);
+
+        // Check if any placeholders were removed and detach their corresponding pending pastes
+        let (kept, removed): (Vec<_>, Vec<_>) = pending_pastes
+            .drain(..)
+            .partition(|(placeholder, _)| text.contains(placeholder));
+        *pending_pastes = kept;
+        detached.pastes.extend(removed);
 
         // Keep attached images in proportion to how many matching placeholders exist in the text.
         // This handles duplicate placeholders that share the same visible label.
-        if !self.attached_images.is_empty() {
+        if !attached_images.is_empty() {
             let mut needed: HashMap<String, usize> = HashMap::new();
-            for img in &self.attached_images {
+            for img in attached_images.iter() {
                 needed
                     .entry(img.placeholder.clone())
-                    .or_insert_with(|| text_after.matches(&img.placeholder).count());
+                    .or_insert_with(|| text.matches(&img.placeholder).count());
             }
 
             let mut used: HashMap<String, usize> = HashMap::new();
-            let mut kept: Vec<AttachedImage> = Vec::with_capacity(self.attached_images.len());
-            for img in self.attached_images.drain(..) {
+            let mut kept: Vec<AttachedImage> = Vec::with_capacity(attached_images.len());
+            for img in attached_images.drain(..) {
                 let total_needed = *needed.get(&img.placeholder).unwrap_or(&0);
                 let used_count = used.entry(img.placeholder.clone()).or_insert(0);
                 if *used_count < total_needed {
                     kept.push(img);
                     *used_count += 1;
+                } else {
+                    detached.images.push(img);
                 }
             }
-            self.attached_images = kept;
+            *attached_images = kept;
         }
+        detached.truncate();
+    }
 
-        (InputResult::None, true)
+    fn detach_image(&mut self, idx: usize) {
+        let image = self.attached_images.remove(idx);
+        self.detached_attachments.images.push(image);
+        self.detached_attachments.truncate();
+    }
+
+    fn detach_paste(&mut self, placeholder: &str) {
+        if let Some(idx) = self
+            .pending_pastes
+            .iter()
+            .position(|(ph, _)| ph == placeholder)
+        {
+            let paste = self.pending_pastes.remove(idx);
+            self.detached_attachments.pastes.push(paste);
+            self.detached_attachments.truncate();
+        }
     }
 
     /// Attempts to remove an image or paste placeholder if the cursor is at the end of one.
@@ -1415,7 +1745,7 @@ impl ChatComposer {
         }
         if let Some((idx, placeholder)) = out {
             self.textarea.replace_range(p - placeholder.len()..p, "");
-            self.attached_images.remove(idx);
+            self.detach_image(idx);
             return true;
         }
 
@@ -1464,7 +1794,7 @@ impl ChatComposer {
 
         if let Some((idx, placeholder)) = out {
             self.textarea.replace_range(p..p + placeholder.len(), "");
-            self.attached_images.remove(idx);
+            self.detach_image(idx);
             return true;
         }
 
@@ -1481,7 +1811,7 @@ impl ChatComposer {
             }
         }) {
             self.textarea.replace_range(p - placeholder.len()..p, "");
-            self.pending_pastes.retain(|(ph, _)| ph != &placeholder);
+            self.detach_paste(&placeholder);
             return true;
         }
 
@@ -1497,7 +1827,7 @@ impl ChatComposer {
             }
         }) {
             self.textarea.replace_range(p..p + placeholder.len(), "");
-            self.pending_pastes.retain(|(ph, _)| ph != &placeholder);
+            self.detach_paste(&placeholder);
             return true;
         }
 
@@ -1532,6 +1862,7 @@ impl ChatComposer {
             is_task_running: self.is_task_running,
             context_window_percent: self.context_window_percent,
             context_window_used_tokens: self.context_window_used_tokens,
+            vi_mode: self.vi.as_ref().map(ViState::mode),
         }
     }
 
@@ -1553,6 +1884,10 @@ impl ChatComposer {
     }
 
     fn sync_popups(&mut self) {
+        // The history search owns its own query and is only closed explicitly.
+        if matches!(self.active_popup, ActivePopup::History(_)) {
+            return;
+        }
         let file_token = Self::current_at_token(&self.textarea);
         let skill_token = self.current_skill_token();
 
@@ -1804,6 +2139,7 @@ impl Renderable for ChatComposer {
                 ActivePopup::Command(c) => c.calculate_required_height(width),
                 ActivePopup::File(c) => c.calculate_required_height(),
                 ActivePopup::Skill(c) => c.calculate_required_height(width),
+                ActivePopup::History(c) => c.calculate_required_height(),
             }
     }
 
@@ -1819,6 +2155,9 @@ impl Renderable for ChatComposer {
             ActivePopup::Skill(popup) => {
                 popup.render_ref(popup_rect, buf);
             }
+            ActivePopup::History(popup) => {
+                popup.render_ref(popup_rect, buf);
+            }
             ActivePopup::None => {
                 let footer_props = self.footer_props();
                 let custom_height = self.custom_footer_height();
@@ -3155,6 +3494,197 @@ mod tests {
         assert_eq!(vec![path], imgs);
     }
 
+    #[test]
+    fn external_edit_preserves_surviving_placeholders() {
+        let (tx, _rx) = unbounded_channel::<AppEvent>();
+        let sender = AppEventSender::new(tx);
+        let mut composer = ChatComposer::new(
+            true,
+            sender,
+            false,
+            "Ask Codex to do anything".to_string(),
+            false,
+        );
+        let kept = PathBuf::from("/tmp/kept.png");
+        composer.attach_image(kept.clone(), 32, 16, "PNG");
+        composer.attach_image(PathBuf::from("/tmp/dropped.png"), 8, 8, "PNG");
+        let large = "z".repeat(LARGE_PASTE_CHAR_THRESHOLD + 5);
+        composer.handle_paste(large.clone());
+        let paste_placeholder = format!("[Pasted Content {} chars]", large.chars().count());
+
+        composer.apply_external_edit(format!(
+            "Look at [kept.png 32x16]\nthen use {paste_placeholder} here"
+        ));
+
+        assert_eq!(composer.pending_pastes.len(), 1);
+        assert_eq!(composer.attached_images.len(), 1);
+        assert_eq!(composer.textarea.cursor(), composer.textarea.text().len());
+        let (result, _) =
+            composer.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        match result {
+            InputResult::Submitted(text) => {
+                assert_eq!(
+                    text,
+                    format!("Look at [kept.png 32x16]\nthen use {large} here")
+                )
+            }
+            _ => panic!("expected Submitted"),
+        }
+        assert_eq!(composer.take_recent_submission_images(), vec![kept]);
+    }
+
+    #[test]
+    fn vi_mode_normal_keys_edit_instead_of_inserting() {
+        use crate::bottom_pane::vi_mode::ViMode;
+
+        let (tx, _rx) = unbounded_channel::<AppEvent>();
+        let sender = AppEventSender::new(tx);
+        let mut composer = ChatComposer::new(
+            true,
+            sender,
+            false,
+            "Ask Codex to do anything".to_string(),
+            true,
+        );
+        composer.set_editing_mode(EditingMode::Vi);
+        assert!(composer.consumes_esc());
+
+        for ch in "fix the bug".chars() {
+            let _ = composer.handle_key_event(KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE));
+        }
+        let _ = composer.handle_key_event(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        assert_eq!(composer.footer_props().vi_mode, Some(ViMode::Normal));
+        // In normal mode Esc is left for interrupt / backtrack.
+        assert!(!composer.consumes_esc());
+
+        for ch in "0dwq".chars() {
+            let _ = composer.handle_key_event(KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE));
+        }
+        assert_eq!(composer.textarea.text(), "the bug");
+
+        let (result, _) =
+            composer.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert!(matches!(result, InputResult::Submitted(text) if text == "the bug"));
+        assert_eq!(composer.footer_props().vi_mode, Some(ViMode::Insert));
+    }
+
+    #[test]
+    fn ctrl_r_searches_history_and_inserts_selected_prompt() {
+        let (tx, mut rx) = unbounded_channel::<AppEvent>();
+        let sender = AppEventSender::new(tx);
+        let mut composer = ChatComposer::new(
+            true,
+            sender,
+            false,
+            "Ask Codex to do anything".to_string(),
+            false,
+        );
+        composer.set_text_content("draft".to_string());
+
+        let _ = composer.handle_key_event(KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL));
+        assert!(matches!(composer.active_popup, ActivePopup::History(_)));
+        match rx.try_recv() {
+            Ok(AppEvent::CodexOp(Op::ListHistoryEntries)) => {}
+            other => panic!("expected ListHistoryEntries request, got {other:?}"),
+        }
+
+        let entry = |text: &str| HistoryEntry {
+            conversation_id: "session".to_string(),
+            ts: 0,
+            text: text.to_string(),
+            cwd: None,
+        };
+        assert!(composer.on_history_entries_response(
+            vec![
+                entry("review the diff\nand list risky changes"),
+                entry("run the tests"),
+            ],
+            PathBuf::from("/repo"),
+        ));
+
+        for ch in "risky".chars() {
+            let _ = composer.handle_key_event(KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE));
+        }
+        // The query edits the search, not the draft.
+        assert_eq!(composer.textarea.text(), "draft");
+
+        let _ = composer.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert!(matches!(composer.active_popup, ActivePopup::None));
+        assert_eq!(
+            composer.textarea.text(),
+            "review the diff\nand list risky changes"
+        );
+        assert_eq!(composer.textarea.cursor(), composer.textarea.text().len());
+
+        let _ = composer.handle_key_event(KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL));
+        let _ = composer.handle_key_event(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        assert!(matches!(composer.active_popup, ActivePopup::None));
+        assert_eq!(
+            composer.textarea.text(),
+            "review the diff\nand list risky changes"
+        );
+    }
+
+    #[test]
+    fn undo_restores_deleted_paste_and_image_placeholders() {
+        let (tx, _rx) = unbounded_channel::<AppEvent>();
+        let sender = AppEventSender::new(tx);
+        let mut composer = ChatComposer::new(
+            true,
+            sender,
+            false,
+            "Ask Codex to do anything".to_string(),
+            false,
+        );
+        let path = PathBuf::from("/tmp/undo.png");
+        composer.attach_image(path.clone(), 4, 4, "PNG");
+        let large = "u".repeat(LARGE_PASTE_CHAR_THRESHOLD + 3);
+        composer.handle_paste(large.clone());
+
+        let undo = KeyEvent::new(KeyCode::Char('/'), KeyModifiers::CONTROL);
+        let _ = composer.handle_key_event(KeyEvent::new(KeyCode::Backspace, KeyModifiers::NONE));
+        let _ = composer.handle_key_event(KeyEvent::new(KeyCode::Backspace, KeyModifiers::NONE));
+        assert!(composer.textarea.is_empty());
+        assert!(composer.pending_pastes.is_empty());
+        assert!(composer.attached_images.is_empty());
+
+        // Consecutive backspaces form one undo step.
+        let _ = composer.handle_key_event(undo);
+        assert_eq!(composer.pending_pastes.len(), 1);
+        assert_eq!(composer.attached_images.len(), 1);
+
+        let (result, _) =
+            composer.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        match result {
+            InputResult::Submitted(text) => assert_eq!(text, format!("[undo.png 4x4]{large}")),
+            _ => panic!("expected Submitted"),
+        }
+        assert_eq!(composer.take_recent_submission_images(), vec![path]);
+    }
+
+    #[test]
+    fn undo_after_submit_does_not_restore_submitted_pastes() {
+        let (tx, _rx) = unbounded_channel::<AppEvent>();
+        let sender = AppEventSender::new(tx);
+        let mut composer = ChatComposer::new(
+            true,
+            sender,
+            false,
+            "Ask Codex to do anything".to_string(),
+            false,
+        );
+        let large = "p".repeat(LARGE_PASTE_CHAR_THRESHOLD + 5);
+        composer.handle_paste(large.clone());
+
+        let (result, _) =
+            composer.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert_eq!(result, InputResult::Submitted(large));
+
+        let _ = composer.handle_key_event(KeyEvent::new(KeyCode::Char('/'), KeyModifiers::CONTROL));
+        assert!(composer.textarea.is_empty());
+        assert!(composer.pending_pastes.is_empty());
+    }
+
     #[test]
     fn attach_image_without_text_submits_empty_text_and_images() {
         let (tx, _rx) = unbounded_channel::<AppEvent>();
@@ -1,3 +1,4 @@
+use crate::bottom_pane::vi_mode::ViMode;
 #[cfg(target_os = "linux")]
 use crate::clipboard_paste::is_probably_wsl;
 use crate::key_hint;
@@ -22,6 +23,8 @@ pub(crate) struct FooterProps {
     pub(crate) is_task_running: bool,
     pub(crate) context_window_percent: Option<i64>,
     pub(crate) context_window_used_tokens: Option<i64>,
+    /// Current vi editing mode, when `tui.editing_mode = "vi"`.
+    pub(crate) vi_mode: Option<ViMode>,
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -80,7 +83,7 @@ fn footer_lines(props: FooterProps) -> Vec<Line<'static>> {
     // (e.g., "? for shortcuts"). Keep it visible even when typing (i.e., when
     // the shortcut hint is hidden). Hide it only for the multi-line
     // ShortcutOverlay.
-    match props.mode {
+    let mut lines = match props.mode {
         FooterMode::CtrlCReminder => vec![ctrl_c_reminder_line(CtrlCReminderState {
             is_task_running: props.is_task_running,
         })],
@@ -114,7 +117,17 @@ fn footer_lines(props: FooterProps) -> Vec<Line<'static>> {
             props.context_window_percent,
             props.context_window_used_tokens,
         )],
+    };
+    // The vi mode is spelled out (not just styled) so screen readers announce it.
+    if let Some(vi_mode) = props.vi_mode
+        && props.mode != FooterMode::ShortcutOverlay
+        && let Some(line) = lines.first_mut()
+    {
+        line.spans.insert(0, " · ".dim());
+        line.spans
+            .insert(0, format!("-- {} --", vi_mode.label()).bold());
     }
+    lines
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -164,7 +177,11 @@ fn shortcut_overlay_lines(state: ShortcutsState) -> Vec<Line<'static>> {
     let mut paste_image = Line::from("");
     let mut edit_previous = Line::from("");
     let mut quit = Line::from("");
+    let mut external_editor = Line::from("");
     let mut show_transcript = Line::from("");
+    let mut undo = Line::from("");
+    let mut redo = Line::from("");
+    let mut history_search = Line::from("");
 
     for descriptor in SHORTCUTS {
         if let Some(text) = descriptor.overlay_entry(state) {
@@ -175,7 +192,11 @@ fn shortcut_overlay_lines(state: ShortcutsState) -> Vec<Line<'static>> {
                 ShortcutId::PasteImage => paste_image = text,
                 ShortcutId::EditPrevious => edit_previous = text,
                 ShortcutId::Quit => quit = text,
+                ShortcutId::ExternalEditor => external_editor = text,
                 ShortcutId::ShowTranscript => show_transcript = text,
+                ShortcutId::Undo => undo = text,
+                ShortcutId::Redo => redo = text,
+                ShortcutId::HistorySearch => history_search = text,
             }
         }
     }
@@ -187,8 +208,11 @@ fn shortcut_overlay_lines(state: ShortcutsState) -> Vec<Line<'static>> {
         paste_image,
         edit_previous,
         quit,
-        Line::from(""),
+        external_editor,
         show_transcript,
+        undo,
+        redo,
+        history_search,
     ];
 
     build_columns(ordered)
@@ -263,7 +287,11 @@ enum ShortcutId {
     PasteImage,
     EditPrevious,
     Quit,
+    ExternalEditor,
     ShowTranscript,
+    Undo,
+    Redo,
+    HistorySearch,
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -399,6 +427,15 @@ const SHORTCUTS: &[ShortcutDescriptor] = &[
         prefix: "",
         label: " to exit",
     },
+    ShortcutDescriptor {
+        id: ShortcutId::ExternalEditor,
+        bindings: &[ShortcutBinding {
+            key: key_hint::ctrl(KeyCode::Char('g')),
+            condition: DisplayCondition::Always,
+        }],
+        prefix: "",
+        label: " to open external editor",
+    },
     ShortcutDescriptor {
         id: ShortcutId::ShowTranscript,
         bindings: &[ShortcutBinding {
@@ -408,6 +445,33 @@ const SHORTCUTS: &[ShortcutDescriptor] = &[
         prefix: "",
         label: " to view transcript",
     },
+    ShortcutDescriptor {
+        id: ShortcutId::Undo,
+        bindings: &[ShortcutBinding {
+            key: key_hint::ctrl(KeyCode::Char('/')),
+            condition: DisplayCondition::Always,
+        }],
+        prefix: "",
+        label: " to undo",
+    },
+    ShortcutDescriptor {
+        id: ShortcutId::Redo,
+        bindings: &[ShortcutBinding {
+            key: key_hint::alt(KeyCode::Char('/')),
+            condition: DisplayCondition::Always,
+        }],
+        prefix: "",
+        label: " to redo",
+    },
+    ShortcutDescriptor {
+        id: ShortcutId::HistorySearch,
+        bindings: &[ShortcutBinding {
+            key: key_hint::ctrl(KeyCode::Char('r')),
+            condition: DisplayCondition::Always,
+        }],
+        prefix: "",
+        label: " to search history",
+    },
 ];
 
 #[cfg(test)]
@@ -440,6 +504,7 @@ mod tests {
                 is_task_running: false,
                 context_window_percent: None,
                 context_window_used_tokens: None,
+                vi_mode: None,
             },
         );
 
@@ -452,6 +517,7 @@ mod tests {
                 is_task_running: false,
                 context_window_percent: None,
                 context_window_used_tokens: None,
+                vi_mode: None,
             },
         );
 
@@ -464,6 +530,7 @@ mod tests {
                 is_task_running: false,
                 context_window_percent: None,
                 context_window_used_tokens: None,
+                vi_mode: None,
             },
         );
 
@@ -476,6 +543,7 @@ mod tests {
                 is_task_running: true,
                 context_window_percent: None,
                 context_window_used_tokens: None,
+                vi_mode: None,
             },
         );
 
@@ -488,6 +556,7 @@ mod tests {
                 is_task_running: false,
                 context_window_percent: None,
                 context_window_used_tokens: None,
+                vi_mode: None,
             },
         );
 
@@ -500,6 +569,7 @@ mod tests {
                 is_task_running: false,
                 context_window_percent: None,
                 context_window_used_tokens: None,
+                vi_mode: None,
             },
         );
 
@@ -512,6 +582,7 @@ mod tests {
                 is_task_running: true,
                 context_window_percent: Some(72),
                 context_window_used_tokens: None,
+                vi_mode: None,
             },
         );
 
@@ -524,6 +595,20 @@ mod tests {
                 is_task_running: false,
                 context_window_percent: None,
                 context_window_used_tokens: Some(123_456),
+                vi_mode: None,
+            },
+        );
+
+        snapshot_footer(
+            "footer_vi_normal_mode",
+            FooterProps {
+                mode: FooterMode::ShortcutSummary,
+                esc_backtrack_hint: false,
+                use_shift_enter_hint: false,
+                is_task_running: false,
+                context_window_percent: None,
+                context_window_used_tokens: None,
+                vi_mode: Some(ViMode::Normal),
             },
         );
     }
@@ -0,0 +1,366 @@
+use std::collections::HashSet;
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_file_search::FuzzyMatcher;
+use codex_protocol::message_history::HistoryEntry;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::widgets::Widget;
+use ratatui::widgets::WidgetRef;
+
+use super::popup_consts::MAX_POPUP_ROWS;
+use super::scroll_state::ScrollState;
+use super::selection_popup_common::GenericDisplayRow;
+use super::selection_popup_common::render_rows_single_line;
+use crate::render::Insets;
+use crate::render::RectExt;
+
+/// Which history entries the search considers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum HistorySearchScope {
+    All,
+    /// Only prompts sent from inside the current repository (or cwd outside a repo).
+    Workspace,
+}
+
+/// A prompt from the history file.
+struct SearchEntry {
+    text: String,
+    /// `text` on a single line; whitespace is mapped one-to-one so match indices
+    /// computed against it line up with its characters.
+    display: String,
+    cwd: Option<PathBuf>,
+}
+
+struct HistoryMatch {
+    entry_idx: usize,
+    indices: Option<Vec<usize>>,
+}
+
+/// Ctrl+R search over the persistent message history (`~/.codex/history.jsonl`).
+///
+/// The query is owned by the popup rather than the composer textarea so the
+/// draft is left untouched until a prompt is chosen.
+pub(crate) struct HistorySearchPopup {
+    query: String,
+    /// Prompts, newest first; `None` until the history has loaded.
+    entries: Option<Vec<SearchEntry>>,
+    workspace_root: Option<PathBuf>,
+    scope: HistorySearchScope,
+    matches: Vec<HistoryMatch>,
+    state: ScrollState,
+}
+
+impl HistorySearchPopup {
+    pub(crate) fn new() -> Self {
+        Self {
+            query: String::new(),
+            entries: None,
+            workspace_root: None,
+            scope: HistorySearchScope::All,
+            matches: Vec::new(),
+            state: ScrollState::new(),
+        }
+    }
+
+    /// Load the history (oldest first, as stored) and the root used by the
+    /// workspace scope.
+    pub(crate) fn set_entries(&mut self, entries: Vec<HistoryEntry>, workspace_root: PathBuf) {
+        let entries = entries
+            .into_iter()
+            .rev()
+            .filter(|entry| !entry.text.trim().is_empty())
+            .map(|entry| SearchEntry {
+                display: entry
+                    .text
+                    .chars()
+                    .map(|ch| if ch.is_whitespace() { ' ' } else { ch })
+                    .collect(),
+                text: entry.text,
+                cwd: entry.cwd,
+            })
+            .collect();
+        self.entries = Some(entries);
+        self.workspace_root = Some(workspace_root);
+        self.refresh();
+    }
+
+    pub(crate) fn push_char(&mut self, ch: char) {
+        self.query.push(ch);
+        self.refresh();
+    }
+
+    pub(crate) fn pop_char(&mut self) {
+        if self.query.pop().is_some() {
+            self.refresh();
+        }
+    }
+
+    pub(crate) fn toggle_scope(&mut self) {
+        self.scope = match self.scope {
+            HistorySearchScope::All => HistorySearchScope::Workspace,
+            HistorySearchScope::Workspace => HistorySearchScope::All,
+        };
+        self.refresh();
+    }
+
+    pub(crate) fn move_up(&mut self) {
+        let len = self.matches.len();
+        self.state.move_up_wrap(len);
+        self.state.ensure_visible(len, len.min(MAX_POPUP_ROWS));
+    }
+
+    pub(crate) fn move_down(&mut self) {
+        let len = self.matches.len();
+        self.state.move_down_wrap(len);
+        self.state.ensure_visible(len, len.min(MAX_POPUP_ROWS));
+    }
+
+    pub(crate) fn selected_text(&self) -> Option<&str> {
+        let entries = self.entries.as_ref()?;
+        let selected = self.matches.get(self.state.selected_idx?)?;
+        entries
+            .get(selected.entry_idx)
+            .map(|entry| entry.text.as_str())
+    }
+
+    /// One header row with the query plus the result rows.
+    pub(crate) fn calculate_required_height(&self) -> u16 {
+        1 + self.matches.len().clamp(1, MAX_POPUP_ROWS) as u16
+    }
+
+    fn in_scope(&self, entry: &SearchEntry) -> bool {
+        match (self.scope, self.workspace_root.as_deref()) {
+            (HistorySearchScope::All, _) | (HistorySearchScope::Workspace, None) => true,
+            (HistorySearchScope::Workspace, Some(root)) => entry
+                .cwd
+                .as_deref()
+                .is_some_and(|cwd| cwd.starts_with(root)),
+        }
+    }
+
+    /// Recompute matches for the current query and scope, best score first and
+    /// newest first among equal scores. Repeated prompts are listed once, at
+    /// their newest in-scope occurrence.
+    fn refresh(&mut self) {
+        let Some(entries) = self.entries.as_ref() else {
+            return;
+        };
+        let query = self.query.trim();
+        let mut matches: Vec<(u32, HistoryMatch)> = Vec::new();
+        let mut matcher = (!query.is_empty()).then(|| FuzzyMatcher::new(query));
+        let mut seen = HashSet::new();
+        for (entry_idx, entry) in entries.iter().enumerate() {
+            if !self.in_scope(entry) || !seen.insert(entry.text.as_str()) {
+                continue;
+            }
+            match matcher.as_mut() {
+                None => matches.push((
+                    0,
+                    HistoryMatch {
+                        entry_idx,
+                        indices: None,
+                    },
+                )),
+                Some(matcher) => {
+                    if let Some((score, indices)) = matcher.score_with_indices(&entry.display) {
+                        matches.push((
+                            score,
+                            HistoryMatch {
+                                entry_idx,
+                                indices: Some(indices.into_iter().map(|i| i as usize).collect()),
+                            },
+                        ));
+                    }
+                }
+            }
+        }
+        matches.sort_by(|(a_score, a), (b_score, b)| {
+            b_score.cmp(a_score).then(a.entry_idx.cmp(&b.entry_idx))
+        });
+        self.matches = matches.into_iter().map(|(_, m)| m).collect();
+
+        let len = self.matches.len();
+        self.state.reset();
+        self.state.clamp_selection(len);
+        self.state.ensure_visible(len, len.min(MAX_POPUP_ROWS));
+    }
+
+    fn workspace_name(&self) -> String {
+        self.workspace_root
+            .as_deref()
+            .and_then(Path::file_name)
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "this directory".to_string())
+    }
+
+    fn header_line(&self) -> Line<'static> {
+        let (scope_label, toggle_hint) = match self.scope {
+            HistorySearchScope::All => (
+                "all history".to_string(),
+                format!("tab to search only {}", self.workspace_name()),
+            ),
+            HistorySearchScope::Workspace => (
+                format!("in {}", self.workspace_name()),
+                "tab to search all history".to_string(),
+            ),
+        };
+        Line::from(vec![
+            "search ".dim(),
+            scope_label.dim(),
+            " › ".dim(),
+            self.query.clone().into(),
+            "   ".into(),
+            toggle_hint.dim(),
+        ])
+    }
+}
+
+impl WidgetRef for HistorySearchPopup {
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        let area = area.inset(Insets::tlbr(0, 2, 0, 0));
+        if area.height == 0 {
+            return;
+        }
+        let header_area = Rect { height: 1, ..area };
+        self.header_line().render(header_area, buf);
+
+        let rows_area = Rect {
+            y: area.y + 1,
+            height: area.height - 1,
+            ..area
+        };
+        let rows: Vec<GenericDisplayRow> = match self.entries.as_ref() {
+            Some(entries) => self
+                .matches
+                .iter()
+                .map(|m| GenericDisplayRow {
+                    name: entries[m.entry_idx].display.clone(),
+                    match_indices: m.indices.clone(),
+                    display_shortcut: None,
+                    description: None,
+                    disabled_reason: None,
+                    wrap_indent: None,
+                })
+                .collect(),
+            None => Vec::new(),
+        };
+        let empty_message = if self.entries.is_none() {
+            "loading history..."
+        } else {
+            "no matching prompts"
+        };
+        render_rows_single_line(
+            rows_area,
+            buf,
+            &rows,
+            &self.state,
+            MAX_POPUP_ROWS,
+            empty_message,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn entry(text: &str, cwd: Option<&str>) -> HistoryEntry {
+        HistoryEntry {
+            conversation_id: "session".to_string(),
+            ts: 0,
+            text: text.to_string(),
+            cwd: cwd.map(PathBuf::from),
+        }
+    }
+
+    fn matched_texts(popup: &HistorySearchPopup) -> Vec<String> {
+        let entries = popup.entries.as_ref().expect("entries loaded");
+        popup
+            .matches
+            .iter()
+            .map(|m| entries[m.entry_idx].text.clone())
+            .collect()
+    }
+
+    #[test]
+    fn lists_unique_prompts_newest_first_and_filters_fuzzily() {
+        let mut popup = HistorySearchPopup::new();
+        popup.set_entries(
+            vec![
+                entry("run cargo fmt", None),
+                entry("explain this\nmulti-line prompt", None),
+                entry("run cargo fmt", None),
+                entry("fix the tests", None),
+            ],
+            PathBuf::from("/repo"),
+        );
+        assert_eq!(
+            matched_texts(&popup),
+            vec![
+                "fix the tests",
+                "run cargo fmt",
+                "explain this\nmulti-line prompt"
+            ]
+        );
+
+        for ch in "mlp".chars() {
+            popup.push_char(ch);
+        }
+        assert_eq!(
+            popup.selected_text(),
+            Some("explain this\nmulti-line prompt")
+        );
+
+        popup.pop_char();
+        popup.pop_char();
+        popup.pop_char();
+        popup.push_char('z');
+        popup.push_char('q');
+        assert_eq!(popup.selected_text(), None);
+    }
+
+    #[test]
+    fn workspace_scope_keeps_prompts_sent_inside_the_repo() {
+        let mut popup = HistorySearchPopup::new();
+        popup.set_entries(
+            vec![
+                entry("legacy prompt", None),
+                entry("elsewhere", Some("/other")),
+                entry("at the root", Some("/repo")),
+                entry("in a subdirectory", Some("/repo/crates/tui")),
+            ],
+            PathBuf::from("/repo"),
+        );
+
+        popup.toggle_scope();
+        assert_eq!(popup.scope, HistorySearchScope::Workspace);
+        assert_eq!(
+            matched_texts(&popup),
+            vec!["in a subdirectory", "at the root"]
+        );
+
+        popup.toggle_scope();
+        assert_eq!(matched_texts(&popup).len(), 4);
+    }
+
+    #[test]
+    fn repeated_prompt_stays_in_workspace_scope_when_last_sent_elsewhere() {
+        let mut popup = HistorySearchPopup::new();
+        popup.set_entries(
+            vec![
+                entry("run the tests", Some("/repo")),
+                entry("run the tests", Some("/other")),
+            ],
+            PathBuf::from("/repo"),
+        );
+        assert_eq!(matched_texts(&popup), vec!["run the tests"]);
+
+        popup.toggle_scope();
+        assert_eq!(matched_texts(&popup), vec!["run the tests"]);
+    }
+}
@@ -9,6 +9,7 @@ use crate::render::renderable::Renderable;
 use crate::render::renderable::RenderableItem;
 use crate::tui::FrameRequester;
 use bottom_pane_view::BottomPaneView;
+use codex_core::config::types::EditingMode;
 use codex_core::features::Features;
 use codex_core::skills::model::SkillMetadata;
 use codex_file_search::FileMatch;
@@ -29,6 +30,7 @@ pub mod custom_prompt_view;
 mod experimental_features_view;
 mod file_search_popup;
 mod footer;
+mod history_search_popup;
 mod list_selection_view;
 mod prompt_args;
 mod skill_popup;
@@ -43,6 +45,7 @@ mod scroll_state;
 mod selection_popup_common;
 mod textarea;
 mod unified_exec_footer;
+mod vi_mode;
 pub(crate) use feedback_view::FeedbackNoteView;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -54,6 +57,7 @@ pub(crate) enum CancellationEvent {
 pub(crate) use chat_composer::ChatComposer;
 pub(crate) use chat_composer::InputResult;
 use codex_protocol::custom_prompts::CustomPrompt;
+use codex_protocol::message_history::HistoryEntry;
 
 use crate::status_indicator_widget::StatusIndicatorWidget;
 pub(crate) use experimental_features_view::BetaFeatureItem;
@@ -97,6 +101,7 @@ pub(crate) struct BottomPaneParams {
     pub(crate) placeholder_text: String,
     pub(crate) disable_paste_burst: bool,
     pub(crate) animations_enabled: bool,
+    pub(crate) editing_mode: EditingMode,
     pub(crate) skills: Option<Vec<SkillMetadata>>,
 }
 
@@ -110,6 +115,7 @@ impl BottomPane {
             placeholder_text,
             disable_paste_burst,
             animations_enabled,
+            editing_mode,
             skills,
         } = params;
         let mut composer = ChatComposer::new(
@@ -120,6 +126,7 @@ impl BottomPane {
             disable_paste_burst,
         );
         composer.set_skill_mentions(skills);
+        composer.set_editing_mode(editing_mode);
 
         Self {
             composer,
@@ -195,6 +202,7 @@ impl BottomPane {
             // send an interrupt even while the composer has focus.
             if matches!(key_event.code, crossterm::event::KeyCode::Esc)
                 && self.is_task_running
+                && !self.composer.consumes_esc()
                 && let Some(status) = &self.status
             {
                 // Send Op::Interrupt
@@ -264,6 +272,12 @@ impl BottomPane {
         self.request_redraw();
     }
 
+    /// Load text edited in an external editor, keeping placeholders that survived.
+    pub(crate) fn apply_external_edit(&mut self, text: String) {
+        self.composer.apply_external_edit(text);
+        self.request_redraw();
+    }
+
     pub(crate) fn clear_composer_for_ctrl_c(&mut self) {
         self.composer.clear_for_ctrl_c();
         self.request_redraw();
@@ -425,7 +439,10 @@ impl BottomPane {
     /// overlays or popups and not running a task. This is the safe context to
     /// use Esc-Esc for backtracking from the main view.
     pub(crate) fn is_normal_backtrack_mode(&self) -> bool {
-        !self.is_task_running && self.view_stack.is_empty() && !self.composer.popup_active()
+        !self.is_task_running
+            && self.view_stack.is_empty()
+            && !self.composer.popup_active()
+            && !self.composer.consumes_esc()
     }
 
     pub(crate) fn show_view(&mut self, view: Box<dyn BottomPaneView>) {
@@ -491,6 +508,19 @@ impl BottomPane {
         self.composer.is_in_paste_burst()
     }
 
+    pub(crate) fn on_history_entries_response(
+        &mut self,
+        entries: Vec<HistoryEntry>,
+        workspace_root: PathBuf,
+    ) {
+        if self
+            .composer
+            .on_history_entries_response(entries, workspace_root)
+        {
+            self.request_redraw();
+        }
+    }
+
     pub(crate) fn on_history_entry_response(
         &mut self,
         log_id: u64,
@@ -616,6 +646,7 @@ mod tests {
             placeholder_text: "Ask Codex to do anything".to_string(),
             disable_paste_burst: false,
             animations_enabled: true,
+            editing_mode: EditingMode::default(),
             skills: Some(Vec::new()),
         });
         pane.push_approval_request(exec_request(), &features);
@@ -639,6 +670,7 @@ mod tests {
             placeholder_text: "Ask Codex to do anything".to_string(),
             disable_paste_burst: false,
             animations_enabled: true,
+            editing_mode: EditingMode::default(),
             skills: Some(Vec::new()),
         });
 
@@ -673,6 +705,7 @@ mod tests {
             placeholder_text: "Ask Codex to do anything".to_string(),
             disable_paste_burst: false,
             animations_enabled: true,
+            editing_mode: EditingMode::default(),
             skills: Some(Vec::new()),
         });
 
@@ -740,6 +773,7 @@ mod tests {
             placeholder_text: "Ask Codex to do anything".to_string(),
             disable_paste_burst: false,
             animations_enabled: true,
+            editing_mode: EditingMode::default(),
             skills: Some(Vec::new()),
         });
 
@@ -767,6 +801,7 @@ mod tests {
             placeholder_text: "Ask Codex to do anything".to_string(),
             disable_paste_burst: false,
             animations_enabled: true,
+            editing_mode: EditingMode::default(),
             skills: Some(Vec::new()),
         });
 
@@ -798,6 +833,7 @@ mod tests {
             placeholder_text: "Ask Codex to do anything".to_string(),
             disable_paste_burst: false,
             animations_enabled: true,
+            editing_mode: EditingMode::default(),
             skills: Some(Vec::new()),
         });
 
@@ -826,6 +862,7 @@ mod tests {
             placeholder_text: "Ask Codex to do anything".to_string(),
             disable_paste_burst: false,
             animations_enabled: true,
+            editing_mode: EditingMode::default(),
             skills: Some(Vec::new()),
         });
 
@@ -5,11 +5,13 @@ use crossterm::event::KeyModifiers;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
 use ratatui::style::Color;
+use ratatui::style::Modifier;
 use ratatui::style::Style;
 use ratatui::widgets::StatefulWidgetRef;
 use ratatui::widgets::WidgetRef;
 use std::cell::Ref;
 use std::cell::RefCell;
+use std::collections::VecDeque;
 use std::ops::Range;
 use textwrap::Options;
 use unicode_segmentation::UnicodeSegmentation;
@@ -17,15 +19,38 @@ use unicode_width::UnicodeWidthStr;
 
 const WORD_SEPARATORS: &str = "`~!@#$%^&*()-=+[{]}\\|;:'\",.<>/?";
 
+/// Maximum number of undo steps kept; the oldest steps are dropped first.
+const MAX_UNDO_STEPS: usize = 100;
+
 fn is_word_separator(ch: char) -> bool {
     WORD_SEPARATORS.contains(ch)
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 struct TextElement {
     range: Range<usize>,
 }
 
+/// Buffer state captured before an edit so it can be undone. Elements are part of
+/// the snapshot, so placeholders come back as atomic elements rather than plain text.
+#[derive(Debug, Clone)]
+struct EditSnapshot {
+    text: String,
+    cursor_pos: usize,
+    elements: Vec<TextElement>,
+}
+
+/// How an edit participates in undo coalescing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EditKind {
+    /// A single typed character; consecutive ones form one step per word.
+    Typing,
+    /// A single grapheme or element removed by backspace/delete.
+    Deleting,
+    /// Anything else (paste, kill, yank, replacement) is its own step.
+    Other,
+}
+
 #[derive(Debug)]
 pub(crate) struct TextArea {
     text: String,
@@ -34,6 +59,15 @@ pub(crate) struct TextArea {
     preferred_col: Option<usize>,
     elements: Vec<TextElement>,
     kill_buffer: String,
+    undo_stack: VecDeque<EditSnapshot>,
+    redo_stack: Vec<EditSnapshot>,
+    /// Kind of the last edit and the cursor position it left behind; a following
+    /// edit of the same kind at that position extends the same undo step.
+    last_edit: Option<(EditKind, usize)>,
+    /// True while [`TextArea::transact`] runs, so nested edits share one step.
+    in_transaction: bool,
+    /// Byte range drawn reversed, e.g. the vi visual-mode selection.
+    highlight: Option<Range<usize>>,
 }
 
 #[derive(Debug, Clone)]
@@ -57,10 +91,19 @@ impl TextArea {
             preferred_col: None,
             elements: Vec::new(),
             kill_buffer: String::new(),
+            undo_stack: VecDeque::new(),
+            redo_stack: Vec::new(),
+            last_edit: None,
+            in_transaction: false,
+            highlight: None,
         }
     }
 
     pub fn set_text(&mut self, text: &str) {
+        if text != self.text || !self.elements.is_empty() {
+            self.record_edit(EditKind::Other);
+        }
+        self.last_edit = None;
         self.text = text.to_string();
         self.cursor_pos = self.cursor_pos.clamp(0, self.text.len());
         self.wrap_cache.replace(None);
@@ -79,6 +122,11 @@ impl TextArea {
 
     pub fn insert_str_at(&mut self, pos: usize, text: &str) {
         let pos = self.clamp_pos_for_insertion(pos);
+        if text.is_empty() {
+            return;
+        }
+        let kind = self.insert_edit_kind(pos, text);
+        self.record_edit(kind);
         self.text.insert_str(pos, text);
         self.wrap_cache.replace(None);
         if pos <= self.cursor_pos {
@@ -86,6 +134,7 @@ impl TextArea {
         }
         self.shift_elements(pos, 0, text.len());
         self.preferred_col = None;
+        self.finish_edit(kind);
     }
 
     pub fn replace_range(&mut self, range: std::ops::Range<usize>, text: &str) {
@@ -102,6 +151,8 @@ impl TextArea {
         if removed_len == 0 && inserted_len == 0 {
             return;
         }
+        let kind = self.replace_edit_kind(start..end, text);
+        self.record_edit(kind);
         let diff = inserted_len as isize - removed_len as isize;
 
         self.text.replace_range(range, text);
@@ -124,6 +175,126 @@ impl TextArea {
 
         // Ensure cursor is not inside an element
         self.cursor_pos = self.clamp_pos_to_nearest_boundary(self.cursor_pos);
+        self.finish_edit(kind);
+    }
+
+    // ===== Undo / redo =====
+
+    /// Revert the most recent edit step. Returns false when there is nothing to undo.
+    pub fn undo(&mut self) -> bool {
+        let Some(snapshot) = self.undo_stack.pop_back() else {
+            return false;
+        };
+        let current = self.snapshot();
+        self.redo_stack.push(current);
+        self.restore_snapshot(snapshot);
+        true
+    }
+
+    /// Reapply the most recently undone step. Returns false when there is nothing to redo.
+    pub fn redo(&mut self) -> bool {
+        let Some(snapshot) = self.redo_stack.pop() else {
+            return false;
+        };
+        let current = self.snapshot();
+        self.push_undo(current);
+        self.restore_snapshot(snapshot);
+        true
+    }
+
+    /// Forget all undo and redo steps, e.g. once the text has been submitted.
+    pub fn clear_undo_history(&mut self) {
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+        self.last_edit = None;
+    }
+
+    /// Run several edits as a single undo step.
+    pub fn transact(&mut self, edits: impl FnOnce(&mut Self)) {
+        if self.in_transaction {
+            edits(self);
+            return;
+        }
+        self.record_edit(EditKind::Other);
+        self.in_transaction = true;
+        edits(self);
+        self.in_transaction = false;
+        self.last_edit = None;
+    }
+
+    fn snapshot(&self) -> EditSnapshot {
+        EditSnapshot {
+            text: self.text.clone(),
+            cursor_pos: self.cursor_pos,
+            elements: self.elements.clone(),
+        }
+    }
+
+    fn restore_snapshot(&mut self, snapshot: EditSnapshot) {
+        self.text = snapshot.text;
+        self.elements = snapshot.elements;
+        self.cursor_pos = snapshot.cursor_pos.min(self.text.len());
+        self.wrap_cache.replace(None);
+        self.preferred_col = None;
+        self.last_edit = None;
+    }
+
+    fn push_undo(&mut self, snapshot: EditSnapshot) {
+        if self.undo_stack.len() == MAX_UNDO_STEPS {
+            self.undo_stack.pop_front();
+        }
+        self.undo_stack.push_back(snapshot);
+    }
+
+    /// Capture the pre-edit state unless this edit continues the previous step.
+    fn record_edit(&mut self, kind: EditKind) {
+        if self.in_transaction {
+            return;
+        }
+        let continues_step =
+            kind != EditKind::Other && self.last_edit == Some((kind, self.cursor_pos));
+        if !continues_step {
+            let snapshot = self.snapshot();
+            self.push_undo(snapshot);
+        }
+        self.redo_stack.clear();
+    }
+
+    fn finish_edit(&mut self, kind: EditKind) {
+        if self.in_transaction {
+            return;
+        }
+        self.last_edit = (kind != EditKind::Other).then_some((kind, self.cursor_pos));
+    }
+
+    fn insert_edit_kind(&mut self, pos: usize, text: &str) -> EditKind {
+        let mut chars = text.chars();
+        let (Some(ch), None) = (chars.next(), chars.next()) else {
+            return EditKind::Other;
+        };
+        if ch == '\n' || pos != self.cursor_pos {
+            return EditKind::Other;
+        }
+        // Start a new step at the beginning of each word so undo removes one word at a time.
+        let after_whitespace = self.text[..pos]
+            .chars()
+            .next_back()
+            .is_some_and(char::is_whitespace);
+        if after_whitespace && !ch.is_whitespace() {
+            self.last_edit = None;
+        }
+        EditKind::Typing
+    }
+
+    fn replace_edit_kind(&self, range: Range<usize>, text: &str) -> EditKind {
+        let at_cursor = range.start == self.cursor_pos || range.end == self.cursor_pos;
+        let single_unit = self.text[range.clone()].graphemes(true).count() == 1
+            || self.elements.iter().any(|e| e.range == range);
+        if text.is_empty() && at_cursor && single_unit {
+            EditKind::Deleting
+        } else {
+            EditKind::Other
+        }
     }
 
     pub fn cursor(&self) -> usize {
@@ -136,6 +307,10 @@ impl TextArea {
         self.preferred_col = None;
     }
 
+    pub(crate) fn set_highlight(&mut self, range: Option<Range<usize>>) {
+        self.highlight = range;
+    }
+
     pub fn desired_height(&self, width: u16) -> u16 {
         self.wrapped_lines(width).len() as u16
     }
@@ -230,6 +405,24 @@ impl TextArea {
             KeyEvent { code: KeyCode::Char('\u{000e}'), modifiers: KeyModifiers::NONE, .. } /* ^N */ => {
                 self.move_cursor_down();
             }
+            // Undo: Ctrl+/ and Ctrl+_ (legacy terminals report both as Ctrl+7). Ctrl+Z is
+            // reserved for suspending the process.
+            KeyEvent {
+                code: KeyCode::Char('/' | '_' | '7'),
+                modifiers,
+                ..
+            } if modifiers == KeyModifiers::CONTROL
+                || modifiers == KeyModifiers::CONTROL | KeyModifiers::SHIFT =>
+            {
+                self.undo();
+            }
+            KeyEvent {
+                code: KeyCode::Char('/'),
+                modifiers: KeyModifiers::ALT,
+                ..
+            } => {
+                self.redo();
+            }
             KeyEvent {
                 code: KeyCode::Char(c),
                 // Insert plain characters (and Shift-modified). Do NOT insert when ALT is held,
@@ -821,7 +1014,7 @@ impl TextArea {
         self.shift_elements(start, end.saturating_sub(start), inserted_len);
     }
 
-    fn prev_atomic_boundary(&self, pos: usize) -> usize {
+    pub(crate) fn prev_atomic_boundary(&self, pos: usize) -> usize {
         if pos == 0 {
             return 0;
         }
@@ -847,7 +1040,7 @@ impl TextArea {
         }
     }
 
-    fn next_atomic_boundary(&self, pos: usize) -> usize {
+    pub(crate) fn next_atomic_boundary(&self, pos: usize) -> usize {
         if pos >= self.text.len() {
             return self.text.len();
         }
@@ -1033,6 +1226,19 @@ impl TextArea {
                 let style = Style::default().fg(Color::Cyan);
                 buf.set_string(area.x + x_off, y, styled, style);
             }
+
+            if let Some(highlight) = &self.highlight {
+                let overlap_start = highlight.start.max(line_range.start);
+                let overlap_end = highlight.end.min(line_range.end);
+                // The range may be stale after an edit, so slice defensively.
+                if overlap_start < overlap_end
+                    && let Some(styled) = self.text.get(overlap_start..overlap_end)
+                    && let Some(prefix) = self.text.get(line_range.start..overlap_start)
+                {
+                    let style = Style::default().add_modifier(Modifier::REVERSED);
+                    buf.set_string(area.x + prefix.width() as u16, y, styled, style);
+                }
+            }
         }
     }
 }
@@ -1249,6 +1455,93 @@ mod tests {
         assert_eq!(t.cursor(), t.text().len());
     }
 
+    fn type_str(t: &mut TextArea, text: &str) {
+        for ch in text.chars() {
+            t.input(KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE));
+        }
+    }
+
+    #[test]
+    fn undo_coalesces_typing_per_word_and_redo_reapplies() {
+        let mut t = TextArea::new();
+        type_str(&mut t, "hello world");
+
+        assert!(t.undo());
+        assert_eq!(t.text(), "hello ");
+        assert!(t.undo());
+        assert_eq!(t.text(), "");
+        assert!(!t.undo());
+
+        assert!(t.redo());
+        assert_eq!(t.text(), "hello ");
+        t.input(KeyEvent::new(KeyCode::Char('/'), KeyModifiers::ALT));
+        assert_eq!(t.text(), "hello world");
+        assert_eq!(t.cursor(), t.text().len());
+        assert!(!t.redo());
+    }
+
+    #[test]
+    fn undo_restores_kill_and_deletion_runs() {
+        let mut t = ta_with("keep this line");
+        t.set_cursor(5);
+        t.input(KeyEvent::new(KeyCode::Char('k'), KeyModifiers::CONTROL));
+        assert_eq!(t.text(), "keep ");
+        t.input(KeyEvent::new(KeyCode::Char('/'), KeyModifiers::CONTROL));
+        assert_eq!(t.text(), "keep this line");
+        assert_eq!(t.cursor(), 5);
+
+        t.set_cursor(t.text().len());
+        for _ in 0..4 {
+            t.input(KeyEvent::new(KeyCode::Backspace, KeyModifiers::NONE));
+        }
+        assert_eq!(t.text(), "keep this ");
+        // Legacy terminals report Ctrl+_ / Ctrl+/ as Ctrl+7.
+        t.input(KeyEvent::new(KeyCode::Char('7'), KeyModifiers::CONTROL));
+        assert_eq!(t.text(), "keep this line");
+    }
+
+    #[test]
+    fn undo_restores_elements_atomically() {
+        let mut t = TextArea::new();
+        t.insert_str("see ");
+        t.insert_element("[Pasted Content 2000 chars]");
+        t.input(KeyEvent::new(KeyCode::Backspace, KeyModifiers::NONE));
+        assert_eq!(t.text(), "see ");
+        assert!(t.elements.is_empty());
+
+        assert!(t.undo());
+        assert_eq!(t.text(), "see [Pasted Content 2000 chars]");
+        assert_eq!(t.elements.len(), 1);
+        assert_eq!(t.elements[0].range, 4..t.text().len());
+    }
+
+    #[test]
+    fn transact_records_a_single_step() {
+        let mut t = ta_with("draft");
+        t.transact(|t| {
+            t.replace_range(0..t.text().len(), "");
+            t.insert_str("one ");
+            t.insert_element("<two>");
+        });
+        assert_eq!(t.text(), "one <two>");
+        assert!(t.undo());
+        assert_eq!(t.text(), "draft");
+    }
+
+    #[test]
+    fn undo_history_is_bounded() {
+        let mut t = TextArea::new();
+        for _ in 0..(MAX_UNDO_STEPS + 10) {
+            t.insert_str("\n");
+        }
+        let mut steps = 0;
+        while t.undo() {
+            steps += 1;
+        }
+        assert_eq!(steps, MAX_UNDO_STEPS);
+        assert_eq!(t.text(), "\n".repeat(10));
+    }
+
     #[test]
     fn delete_forward_word_handles_atomic_elements() {
         let mut t = TextArea::new();
@@ -0,0 +1,895 @@
+//! Optional vi-style modal editing for the composer (`tui.editing_mode = "vi"`).
+//!
+//! Insert mode keeps the regular composer bindings. Normal and visual mode
+//! interpret keys as counts, motions and operators applied to the [`TextArea`];
+//! keys with Ctrl/Alt, Enter and (when nothing is pending) Up/Down/Esc still
+//! reach the composer so submission, history and app shortcuts keep working.
+
+use std::ops::Range;
+
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyEventKind;
+use crossterm::event::KeyModifiers;
+
+use super::textarea::TextArea;
+
+/// Upper bound for typed counts so `99999999dd` cannot stall the UI.
+const MAX_COUNT: usize = 9_999;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ViMode {
+    Normal,
+    Insert,
+    Visual,
+}
+
+impl ViMode {
+    /// Mode name shown (as text, not only styling) in the footer.
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            ViMode::Normal => "NORMAL",
+            ViMode::Insert => "INSERT",
+            ViMode::Visual => "VISUAL",
+        }
+    }
+}
+
+/// Whether [`ViState::handle_key`] consumed a key.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ViOutcome {
+    Handled,
+    /// Let the composer's regular key handling process the key.
+    PassThrough,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Operator {
+    Delete,
+    Change,
+    Yank,
+}
+
+impl Operator {
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            'd' => Some(Operator::Delete),
+            'c' => Some(Operator::Change),
+            'y' => Some(Operator::Yank),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Motion {
+    Left,
+    Right,
+    Up,
+    Down,
+    WordForward,
+    WordBackward,
+    WordEnd,
+    LineStart,
+    LineEnd,
+    /// `gg`: the first line, or line N with a count.
+    FirstLine,
+    /// `G`: the last line, or line N with a count.
+    LastLine,
+}
+
+impl Motion {
+    fn is_linewise(self) -> bool {
+        matches!(
+            self,
+            Motion::Up | Motion::Down | Motion::FirstLine | Motion::LastLine
+        )
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct PendingOperator {
+    operator: Operator,
+    count: Option<usize>,
+}
+
+/// Text captured by the last delete, change or yank.
+#[derive(Debug, Default)]
+struct Register {
+    text: String,
+    linewise: bool,
+}
+
+#[derive(Debug)]
+pub(crate) struct ViState {
+    mode: ViMode,
+    count: Option<usize>,
+    operator: Option<PendingOperator>,
+    pending_g: bool,
+    visual_anchor: usize,
+    register: Register,
+}
+
+impl ViState {
+    /// Starts in insert mode so typing works immediately, as in shell vi modes.
+    pub(crate) fn new() -> Self {
+        Self {
+            mode: ViMode::Insert,
+            count: None,
+            operator: None,
+            pending_g: false,
+            visual_anchor: 0,
+            register: Register::default(),
+        }
+    }
+
+    pub(crate) fn mode(&self) -> ViMode {
+        self.mode
+    }
+
+    /// Return to insert mode, e.g. after the draft was submitted.
+    pub(crate) fn reset(&mut self) {
+        self.clear_pending();
+        self.mode = ViMode::Insert;
+    }
+
+    /// True when Esc has a vi meaning right now (leave insert/visual mode or
+    /// cancel a pending command) and must not be used for interrupt/backtrack.
+    pub(crate) fn consumes_esc(&self) -> bool {
+        self.mode != ViMode::Normal || self.has_pending()
+    }
+
+    /// Byte range highlighted in visual mode.
+    pub(crate) fn selection(&self, textarea: &TextArea) -> Option<Range<usize>> {
+        (self.mode == ViMode::Visual).then(|| self.visual_range(textarea))
+    }
+
+    pub(crate) fn handle_key(&mut self, textarea: &mut TextArea, key: KeyEvent) -> ViOutcome {
+        if key.kind == KeyEventKind::Release {
+            return ViOutcome::PassThrough;
+        }
+        match self.mode {
+            ViMode::Insert => self.handle_insert_key(textarea, key),
+            ViMode::Normal | ViMode::Visual => self.handle_command_key(textarea, key),
+        }
+    }
+
+    fn handle_insert_key(&mut self, textarea: &mut TextArea, key: KeyEvent) -> ViOutcome {
+        if key.code != KeyCode::Esc || key.modifiers != KeyModifiers::NONE {
+            return ViOutcome::PassThrough;
+        }
+        self.mode = ViMode::Normal;
+        // Like vi, leaving insert mode steps back onto the last inserted character.
+        if textarea.cursor() > line_start(textarea.text(), textarea.cursor()) {
+            textarea.move_cursor_left();
+        }
+        ViOutcome::Handled
+    }
+
+    fn handle_command_key(&mut self, textarea: &mut TextArea, key: KeyEvent) -> ViOutcome {
+        let KeyEvent {
+            code, modifiers, ..
+        } = key;
+        if modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) {
+            if code == KeyCode::Char('r') && modifiers == KeyModifiers::CONTROL {
+                let count = self.take_count(None);
+                for _ in 0..count {
+                    textarea.redo();
+                }
+                self.finish_command(textarea);
+                return ViOutcome::Handled;
+            }
+            self.clear_pending();
+            return ViOutcome::PassThrough;
+        }
+
+        match code {
+            KeyCode::Esc => {
+                if self.mode == ViMode::Normal && !self.has_pending() {
+                    return ViOutcome::PassThrough;
+                }
+                self.clear_pending();
+                self.mode = ViMode::Normal;
+                self.finish_command(textarea);
+                ViOutcome::Handled
+            }
+            KeyCode::Enter => {
+                self.clear_pending();
+                self.mode = ViMode::Normal;
+                ViOutcome::PassThrough
+            }
+            KeyCode::Up | KeyCode::Down if self.mode == ViMode::Normal && !self.has_pending() => {
+                ViOutcome::PassThrough
+            }
+            KeyCode::Up => self.apply_motion(textarea, Motion::Up),
+            KeyCode::Down => self.apply_motion(textarea, Motion::Down),
+            KeyCode::Left | KeyCode::Backspace => self.apply_motion(textarea, Motion::Left),
+            KeyCode::Right => self.apply_motion(textarea, Motion::Right),
+            KeyCode::Home => self.apply_motion(textarea, Motion::LineStart),
+            KeyCode::End => self.apply_motion(textarea, Motion::LineEnd),
+            KeyCode::Char(c) => self.handle_command_char(textarea, c),
+            // Everything else is swallowed so stray keys never edit the draft.
+            _ => {
+                self.clear_pending();
+                ViOutcome::Handled
+            }
+        }
+    }
+
+    fn handle_command_char(&mut self, textarea: &mut TextArea, c: char) -> ViOutcome {
+        if let Some(digit) = c.to_digit(10)
+            && (digit != 0 || self.count.is_some())
+            && !self.pending_g
+        {
+            let count = self.count.unwrap_or(0) * 10 + digit as usize;
+            self.count = Some(count.min(MAX_COUNT));
+            return ViOutcome::Handled;
+        }
+
+        if std::mem::take(&mut self.pending_g) {
+            if c == 'g' {
+                return self.apply_motion(textarea, Motion::FirstLine);
+            }
+            self.clear_pending();
+            return ViOutcome::Handled;
+        }
+
+        let motion = match c {
+            'h' => Some(Motion::Left),
+            'l' | ' ' => Some(Motion::Right),
+            'j' => Some(Motion::Down),
+            'k' => Some(Motion::Up),
+            'w' => Some(Motion::WordForward),
+            'b' => Some(Motion::WordBackward),
+            'e' => Some(Motion::WordEnd),
+            '0' => Some(Motion::LineStart),
+            '$' => Some(Motion::LineEnd),
+            'G' => Some(Motion::LastLine),
+            _ => None,
+        };
+        if let Some(motion) = motion {
+            return self.apply_motion(textarea, motion);
+        }
+        if c == 'g' {
+            self.pending_g = true;
+            return ViOutcome::Handled;
+        }
+
+        match self.mode {
+            ViMode::Visual => self.visual_command(textarea, c),
+            ViMode::Normal | ViMode::Insert => self.normal_command(textarea, c),
+        }
+        ViOutcome::Handled
+    }
+
+    fn normal_command(&mut self, textarea: &mut TextArea, c: char) {
+        if let Some(operator) = Operator::from_char(c) {
+            match self.operator.take() {
+                // `dd`, `cc`, `yy`: operate on whole lines.
+                Some(pending) if pending.operator == operator => {
+                    let count = self.take_count(pending.count);
+                    let first = line_index(textarea.text(), textarea.cursor());
+                    self.apply_linewise(textarea, operator, first, first + count - 1);
+                }
+                Some(_) => self.clear_pending(),
+                None => {
+                    self.operator = Some(PendingOperator {
+                        operator,
+                        count: self.count.take(),
+                    });
+                }
+            }
+            return;
+        }
+        if self.operator.is_some() {
+            self.clear_pending();
+            return;
+        }
+
+        let count = self.take_count(None);
+        let text = textarea.text();
+        let cursor = textarea.cursor();
+        match c {
+            'i' => self.mode = ViMode::Insert,
+            'a' => {
+                if cursor < line_end(text, cursor) {
+                    textarea.move_cursor_right();
+                }
+                self.mode = ViMode::Insert;
+            }
+            'I' => {
+                let at = first_non_blank(text, cursor);
+                textarea.set_cursor(at);
+                self.mode = ViMode::Insert;
+            }
+            'A' => {
+                let at = line_end(text, cursor);
+                textarea.set_cursor(at);
+                self.mode = ViMode::Insert;
+            }
+            'o' => {
+                let at = line_end(text, cursor);
+                textarea.insert_str_at(at, "\n");
+                textarea.set_cursor(at + 1);
+                self.mode = ViMode::Insert;
+            }
+            'O' => {
+                let at = line_start(text, cursor);
+                textarea.insert_str_at(at, "\n");
+                textarea.set_cursor(at);
+                self.mode = ViMode::Insert;
+            }
+            'x' | 's' => {
+                let end = step_right(textarea, cursor, count);
+                let operator = if c == 'x' {
+                    Operator::Delete
+                } else {
+                    Operator::Change
+                };
+                self.apply_charwise(textarea, operator, cursor..end);
+            }
+            'X' => {
+                let start = step_left(textarea, cursor, count);
+                self.apply_charwise(textarea, Operator::Delete, start..cursor);
+            }
+            'D' | 'C' => {
+                let end = line_end(text, cursor);
+                let operator = if c == 'D' {
+                    Operator::Delete
+                } else {
+                    Operator::Change
+                };
+                self.apply_charwise(textarea, operator, cursor..end);
+            }
+            'Y' => {
+                let first = line_index(text, cursor);
+                self.apply_linewise(textarea, Operator::Yank, first, first + count - 1);
+            }
+            'p' | 'P' => self.put(textarea, c == 'p', count),
+            'u' => {
+                for _ in 0..count {
+                    textarea.undo();
+                }
+            }
+            'v' => {
+                self.visual_anchor = cursor;
+                self.mode = ViMode::Visual;
+            }
+            _ => {}
+        }
+        self.finish_command(textarea);
+    }
+
+    fn visual_command(&mut self, textarea: &mut TextArea, c: char) {
+        let range = self.visual_range(textarea);
+        self.clear_pending();
+        match c {
+            'd' | 'x' => self.apply_charwise(textarea, Operator::Delete, range),
+            'c' | 's' => self.apply_charwise(textarea, Operator::Change, range),
+            'y' => self.apply_charwise(textarea, Operator::Yank, range),
+            'v' => self.mode = ViMode::Normal,
+            'o' => {
+                let cursor = textarea.cursor();
+                textarea.set_cursor(self.visual_anchor);
+                self.visual_anchor = cursor;
+            }
+            _ => {}
+        }
+        self.finish_command(textarea);
+    }
+
+    fn apply_motion(&mut self, textarea: &mut TextArea, motion: Motion) -> ViOutcome {
+        let pending = self.operator.take();
+        let explicit_count = match (pending.and_then(|p| p.count), self.count.take()) {
+            (Some(a), Some(b)) => Some((a * b).min(MAX_COUNT)),
+            (a, b) => a.or(b),
+        };
+        let count = explicit_count.unwrap_or(1);
+        self.pending_g = false;
+
+        let Some(PendingOperator { operator, .. }) = pending else {
+            self.move_cursor(textarea, motion, count, explicit_count);
+            self.finish_command(textarea);
+            return ViOutcome::Handled;
+        };
+
+        let text = textarea.text();
+        let cursor = textarea.cursor();
+        if motion.is_linewise() {
+            let current = line_index(text, cursor);
+            let target = match motion {
+                Motion::Up => current.saturating_sub(count),
+                Motion::Down => current + count,
+                _ => target_line(text, motion, explicit_count),
+            };
+            self.apply_linewise(textarea, operator, current.min(target), current.max(target));
+            return ViOutcome::Handled;
+        }
+
+        // `cw` on a word behaves like `ce`, as in vi.
+        let motion = if operator == Operator::Change
+            && motion == Motion::WordForward
+            && text[cursor..]
+                .chars()
+                .next()
+                .is_some_and(|ch| !ch.is_whitespace())
+        {
+            Motion::WordEnd
+        } else {
+            motion
+        };
+        let target = self.charwise_target(textarea, motion, count);
+        let range = match motion {
+            Motion::WordEnd => cursor..textarea.next_atomic_boundary(target),
+            Motion::LineEnd => cursor..target,
+            // An operator with `w` stops at the end of the line rather than
+            // joining it with the next one.
+            Motion::WordForward => {
+                let end = match text[cursor..target].find('\n') {
+                    Some(offset) if offset > 0 => cursor + offset,
+                    _ => target,
+                };
+                cursor..end
+            }
+            _ => cursor.min(target)..cursor.max(target),
+        };
+        self.apply_charwise(textarea, operator, range);
+        ViOutcome::Handled
+    }
+
+    fn move_cursor(
+        &mut self,
+        textarea: &mut TextArea,
+        motion: Motion,
+        count: usize,
+        explicit_count: Option<usize>,
+    ) {
+        match motion {
+            Motion::Up => (0..count).for_each(|_| textarea.move_cursor_up()),
+            Motion::Down => (0..count).for_each(|_| textarea.move_cursor_down()),
+            Motion::FirstLine | Motion::LastLine => {
+                let text = textarea.text();
+                let line = target_line(text, motion, explicit_count);
+                let target = line_start_of_index(text, line);
+                textarea.set_cursor(target);
+            }
+            _ => {
+                let target = self.charwise_target(textarea, motion, count);
+                textarea.set_cursor(target);
+            }
+        }
+    }
+
+    fn charwise_target(&self, textarea: &TextArea, motion: Motion, count: usize) -> usize {
+        let text = textarea.text();
+        let cursor = textarea.cursor();
+        match motion {
+            Motion::Left => step_left(textarea, cursor, count),
+            Motion::Right => step_right(textarea, cursor, count),
+            Motion::WordForward => (0..count).fold(cursor, |pos, _| word_forward(text, pos)),
+            Motion::WordBackward => (0..count).fold(cursor, |pos, _| word_backward(text, pos)),
+            Motion::WordEnd => (0..count).fold(cursor, |pos, _| word_end(text, pos)),
+            Motion::LineStart => line_start(text, cursor),
+            Motion::LineEnd => {
+                let line = line_index(text, cursor) + count - 1;
+                line_end(text, line_start_of_index(text, line))
+            }
+            Motion::Up | Motion::Down | Motion::FirstLine | Motion::LastLine => cursor,
+        }
+    }
+
+    fn apply_charwise(&mut self, textarea: &mut TextArea, operator: Operator, range: Range<usize>) {
+        if range.start >= range.end {
+            if operator == Operator::Change {
+                self.mode = ViMode::Insert;
+            }
+            return;
+        }
+        self.register = Register {
+            text: textarea.text()[range.clone()].to_string(),
+            linewise: false,
+        };
+        match operator {
+            Operator::Yank => {
+                textarea.set_cursor(range.start);
+                self.mode = ViMode::Normal;
+            }
+            Operator::Delete => {
+                textarea.replace_range(range, "");
+                self.mode = ViMode::Normal;
+            }
+            Operator::Change => {
+                textarea.replace_range(range, "");
+                self.mode = ViMode::Insert;
+            }
+        }
+        self.finish_command(textarea);
+    }
+
+    /// Apply `operator` to logical lines `first..=last` (clamped to the buffer).
+    fn apply_linewise(
+        &mut self,
+        textarea: &mut TextArea,
+        operator: Operator,
+        first: usize,
+        last: usize,
+    ) {
+        let text = textarea.text();
+        let start = line_start_of_index(text, first);
+        let end = line_end(text, line_start_of_index(text, last));
+        self.register = Register {
+            text: text[start..end].to_string(),
+            linewise: true,
+        };
+        match operator {
+            Operator::Yank => {
+                textarea.set_cursor(start);
+                self.mode = ViMode::Normal;
+            }
+            Operator::Delete => {
+                // Remove one adjoining newline so the remaining lines close up.
+                let range = if end < text.len() {
+                    start..end + 1
+                } else {
+                    start.saturating_sub(1)..end
+                };
+                textarea.replace_range(range, "");
+                let cursor = line_start(textarea.text(), textarea.cursor());
+                textarea.set_cursor(cursor);
+                self.mode = ViMode::Normal;
+            }
+            Operator::Change => {
+                textarea.replace_range(start..end, "");
+                textarea.set_cursor(start);
+                self.mode = ViMode::Insert;
+            }
+        }
+        self.finish_command(textarea);
+    }
+
+    /// `p` / `P`: put the register after / before the cursor (or line, when linewise).
+    fn put(&mut self, textarea: &mut TextArea, after: bool, count: usize) {
+        if self.register.text.is_empty() {
+            return;
+        }
+        let text = textarea.text();
+        let cursor = textarea.cursor();
+        let repeated = vec![self.register.text.as_str(); count];
+        if self.register.linewise {
+            let body = repeated.join("\n");
+            if after {
+                let at = line_end(text, cursor);
+                textarea.insert_str_at(at, &format!("\n{body}"));
+                textarea.set_cursor(at + 1);
+            } else {
+                let at = line_start(text, cursor);
+                textarea.insert_str_at(at, &format!("{body}\n"));
+                textarea.set_cursor(at);
+            }
+        } else {
+            let body = repeated.concat();
+            let at = if after && cursor < line_end(text, cursor) {
+                textarea.next_atomic_boundary(cursor)
+            } else {
+                cursor
+            };
+            textarea.insert_str_at(at, &body);
+            // Leave the cursor on the last inserted character.
+            let end = at + body.len();
+            textarea.set_cursor(textarea.prev_atomic_boundary(end));
+        }
+    }
+
+    fn visual_range(&self, textarea: &TextArea) -> Range<usize> {
+        let cursor = textarea.cursor();
+        let len = textarea.text().len();
+        let anchor = self.visual_anchor.min(len);
+        let start = anchor.min(cursor);
+        let end = textarea.next_atomic_boundary(anchor.max(cursor));
+        start..end
+    }
+
+    /// Keep the normal-mode cursor on a character rather than past the end of the line.
+    fn finish_command(&self, textarea: &mut TextArea) {
+        if self.mode != ViMode::Normal {
+            return;
+        }
+        let text = textarea.text();
+        let cursor = textarea.cursor();
+        let start = line_start(text, cursor);
+        if cursor > start && cursor == line_end(text, cursor) {
+            textarea.set_cursor(textarea.prev_atomic_boundary(cursor));
+        }
+    }
+
+    fn take_count(&mut self, operator_count: Option<usize>) -> usize {
+        let count = self.count.take().unwrap_or(1) * operator_count.unwrap_or(1);
+        count.clamp(1, MAX_COUNT)
+    }
+
+    fn has_pending(&self) -> bool {
+        self.count.is_some() || self.operator.is_some() || self.pending_g
+    }
+
+    fn clear_pending(&mut self) {
+        self.count = None;
+        self.operator = None;
+        self.pending_g = false;
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum CharClass {
+    Whitespace,
+    Word,
+    Punctuation,
+}
+
+fn char_class(ch: char) -> CharClass {
+    if ch.is_whitespace() {
+        CharClass::Whitespace
+    } else if ch.is_alphanumeric() || ch == '_' {
+        CharClass::Word
+    } else {
+        CharClass::Punctuation
+    }
+}
+
+/// `w`: start of the next word, where a word is a run of word characters or a
+/// run of punctuation.
+fn word_forward(text: &str, pos: usize) -> usize {
+    let mut chars = text[pos..]
+        .char_indices()
+        .map(|(idx, ch)| (pos + idx, char_class(ch)))
+        .peekable();
+    let Some(&(_, class)) = chars.peek() else {
+        return text.len();
+    };
+    if class != CharClass::Whitespace {
+        while chars.next_if(|&(_, c)| c == class).is_some() {}
+    }
+    while chars
+        .next_if(|&(_, c)| c == CharClass::Whitespace)
+        .is_some()
+    {}
+    chars.peek().map_or(text.len(), |&(idx, _)| idx)
+}
+
+/// `b`: start of the current or previous word.
+fn word_backward(text: &str, pos: usize) -> usize {
+    let mut chars = text[..pos]
+        .char_indices()
+        .rev()
+        .map(|(idx, ch)| (idx, char_class(ch)))
+        .peekable();
+    while chars
+        .next_if(|&(_, c)| c == CharClass::Whitespace)
+        .is_some()
+    {}
+    let Some(&(mut start, class)) = chars.peek() else {
+        return 0;
+    };
+    while let Some((idx, _)) = chars.next_if(|&(_, c)| c == class) {
+        start = idx;
+    }
+    start
+}
+
+/// `e`: last character of the current or next word. Always advances at least
+/// one character when possible.
+fn word_end(text: &str, pos: usize) -> usize {
+    let mut chars = text[pos..]
+        .char_indices()
+        .map(|(idx, ch)| (pos + idx, char_class(ch)))
+        .skip(1)
+        .peekable();
+    while chars
+        .next_if(|&(_, c)| c == CharClass::Whitespace)
+        .is_some()
+    {}
+    let Some(&(mut end, class)) = chars.peek() else {
+        return pos;
+    };
+    while let Some((idx, _)) = chars.next_if(|&(_, c)| c == class) {
+        end = idx;
+    }
+    end
+}
+
+fn step_left(textarea: &TextArea, pos: usize, count: usize) -> usize {
+    let floor = line_start(textarea.text(), pos);
+    (0..count).fold(pos, |pos, _| textarea.prev_atomic_boundary(pos).max(floor))
+}
+
+fn step_right(textarea: &TextArea, pos: usize, count: usize) -> usize {
+    let ceiling = line_end(textarea.text(), pos);
+    (0..count).fold(pos, |pos, _| {
+        textarea.next_atomic_boundary(pos).min(ceiling)
+    })
+}
+
+fn line_start(text: &str, pos: usize) -> usize {
+    text[..pos].rfind('\n').map_or(0, |idx| idx + 1)
+}
+
+fn line_end(text: &str, pos: usize) -> usize {
+    text[pos..].find('\n').map_or(text.len(), |idx| pos + idx)
+}
+
+fn first_non_blank(text: &str, pos: usize) -> usize {
+    let start = line_start(text, pos);
+    let end = line_end(text, pos);
+    text[start..end]
+        .find(|ch: char| !ch.is_whitespace())
+        .map_or(end, |idx| start + idx)
+}
+
+/// Zero-based logical line containing `pos`.
+fn line_index(text: &str, pos: usize) -> usize {
+    text[..pos].matches('\n').count()
+}
+
+fn line_count(text: &str) -> usize {
+    text.matches('\n').count() + 1
+}
+
+/// Start of zero-based logical line `line`, clamped to the last line.
+fn line_start_of_index(text: &str, line: usize) -> usize {
+    if line == 0 {
+        return 0;
+    }
+    text.match_indices('\n')
+        .nth(line - 1)
+        .map_or_else(|| line_start(text, text.len()), |(idx, _)| idx + 1)
+}
+
+/// Zero-based target line for `gg` / `G`, honoring a one-based count.
+fn target_line(text: &str, motion: Motion, explicit_count: Option<usize>) -> usize {
+    let last = line_count(text) - 1;
+    match (motion, explicit_count) {
+        (_, Some(n)) => (n.max(1) - 1).min(last),
+        (Motion::FirstLine, None) => 0,
+        _ => last,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn setup(text: &str, cursor: usize) -> (ViState, TextArea) {
+        let mut textarea = TextArea::new();
+        textarea.insert_str(text);
+        textarea.set_cursor(cursor);
+        let mut vi = ViState::new();
+        press(&mut vi, &mut textarea, KeyCode::Esc);
+        textarea.set_cursor(cursor);
+        (vi, textarea)
+    }
+
+    fn press(vi: &mut ViState, textarea: &mut TextArea, code: KeyCode) -> ViOutcome {
+        vi.handle_key(textarea, KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    /// Type `keys`, letting the textarea handle whatever vi passes through
+    /// (as the composer does in insert mode).
+    fn keys(vi: &mut ViState, textarea: &mut TextArea, keys: &str) {
+        for c in keys.chars() {
+            let event = KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+            if vi.handle_key(textarea, event) == ViOutcome::PassThrough {
+                textarea.input(event);
+            }
+        }
+    }
+
+    #[test]
+    fn escape_enters_normal_mode_and_steps_back() {
+        let mut textarea = TextArea::new();
+        let mut vi = ViState::new();
+        assert_eq!(vi.mode(), ViMode::Insert);
+        textarea.insert_str("hello");
+        assert_eq!(
+            press(&mut vi, &mut textarea, KeyCode::Esc),
+            ViOutcome::Handled
+        );
+        assert_eq!(vi.mode(), ViMode::Normal);
+        assert_eq!(textarea.cursor(), 4);
+        // Plain keys no longer insert text in normal mode.
+        keys(&mut vi, &mut textarea, "z");
+        assert_eq!(textarea.text(), "hello");
+        // A second Esc is left for the composer (interrupt / backtrack).
+        assert_eq!(
+            press(&mut vi, &mut textarea, KeyCode::Esc),
+            ViOutcome::PassThrough
+        );
+    }
+
+    #[test]
+    fn word_motions_with_counts() {
+        let (mut vi, mut textarea) = setup("let foo = bar.baz(1);", 0);
+        keys(&mut vi, &mut textarea, "w");
+        assert_eq!(textarea.cursor(), 4);
+        keys(&mut vi, &mut textarea, "2w");
+        assert_eq!(textarea.cursor(), 10);
+        keys(&mut vi, &mut textarea, "e");
+        assert_eq!(textarea.cursor(), 12);
+        keys(&mut vi, &mut textarea, "b");
+        assert_eq!(textarea.cursor(), 10);
+        keys(&mut vi, &mut textarea, "$");
+        assert_eq!(textarea.cursor(), 20);
+        keys(&mut vi, &mut textarea, "0");
+        assert_eq!(textarea.cursor(), 0);
+    }
+
+    #[test]
+    fn line_motions_gg_and_counted_g() {
+        let (mut vi, mut textarea) = setup("one\ntwo\nthree", 0);
+        keys(&mut vi, &mut textarea, "G");
+        assert_eq!(textarea.cursor(), 8);
+        keys(&mut vi, &mut textarea, "gg");
+        assert_eq!(textarea.cursor(), 0);
+        keys(&mut vi, &mut textarea, "2G");
+        assert_eq!(textarea.cursor(), 4);
+    }
+
+    #[test]
+    fn operators_with_motions() {
+        let (mut vi, mut textarea) = setup("alpha beta gamma", 0);
+        keys(&mut vi, &mut textarea, "dw");
+        assert_eq!(textarea.text(), "beta gamma");
+
+        keys(&mut vi, &mut textarea, "cwdelta");
+        assert_eq!(vi.mode(), ViMode::Insert);
+        assert_eq!(textarea.text(), "delta gamma");
+
+        press(&mut vi, &mut textarea, KeyCode::Esc);
+        keys(&mut vi, &mut textarea, "0yep");
+        assert_eq!(textarea.text(), "ddeltaelta gamma");
+
+        keys(&mut vi, &mut textarea, "0d$");
+        assert_eq!(textarea.text(), "");
+    }
+
+    #[test]
+    fn counted_linewise_delete_and_put() {
+        let (mut vi, mut textarea) = setup("one\ntwo\nthree\nfour", 0);
+        keys(&mut vi, &mut textarea, "2dd");
+        assert_eq!(textarea.text(), "three\nfour");
+        keys(&mut vi, &mut textarea, "p");
+        assert_eq!(textarea.text(), "three\none\ntwo\nfour");
+        assert_eq!(textarea.cursor(), 6);
+
+        keys(&mut vi, &mut textarea, "Gyyggp");
+        assert_eq!(textarea.text(), "three\nfour\none\ntwo\nfour");
+
+        keys(&mut vi, &mut textarea, "d2j");
+        assert_eq!(textarea.text(), "three\nfour");
+    }
+
+    #[test]
+    fn visual_mode_selects_and_deletes() {
+        let (mut vi, mut textarea) = setup("select some text", 7);
+        keys(&mut vi, &mut textarea, "ve");
+        assert_eq!(vi.mode(), ViMode::Visual);
+        assert_eq!(vi.selection(&textarea), Some(7..11));
+        keys(&mut vi, &mut textarea, "d");
+        assert_eq!(vi.mode(), ViMode::Normal);
+        assert_eq!(vi.selection(&textarea), None);
+        assert_eq!(textarea.text(), "select  text");
+    }
+
+    #[test]
+    fn undo_and_redo_in_normal_mode() {
+        let (mut vi, mut textarea) = setup("keep drop", 5);
+        keys(&mut vi, &mut textarea, "D");
+        assert_eq!(textarea.text(), "keep ");
+        keys(&mut vi, &mut textarea, "u");
+        assert_eq!(textarea.text(), "keep drop");
+        vi.handle_key(
+            &mut textarea,
+            KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL),
+        );
+        assert_eq!(textarea.text(), "keep ");
+    }
+}
@@ -10,9 +10,11 @@ use codex_backend_client::Client as BackendClient;
 use codex_core::config::Config;
 use codex_core::config::ConstraintResult;
 use codex_core::config::types::Notifications;
+use codex_core::export::ExportFormat;
 use codex_core::features::FEATURES;
 use codex_core::features::Feature;
 use codex_core::git_info::current_branch_name;
+use codex_core::git_info::get_git_repo_root;
 use codex_core::git_info::local_git_branches;
 use codex_core::models_manager::manager::ModelsManager;
 use codex_core::models_manager::model_family::ModelFamily;
@@ -36,6 +38,7 @@ use codex_core::protocol::ExecCommandEndEvent;
 use codex_core::protocol::ExecCommandSource;
 use codex_core::protocol::ExitedReviewModeEvent;
 use codex_core::protocol::ListCustomPromptsResponseEvent;
+use codex_core::protocol::ListHistoryEntriesResponseEvent;
 use codex_core::protocol::ListSkillsResponseEvent;
 use codex_core::protocol::McpListToolsResponseEvent;
 use codex_core::protocol::McpStartupCompleteEvent;
@@ -68,7 +71,9 @@ use codex_protocol::ConversationId;
 use codex_protocol::account::PlanType;
 use codex_protocol::approvals::ElicitationRequestEvent;
 use codex_protocol::parse_command::ParsedCommand;
+use codex_protocol::user_input::LineRange;
 use codex_protocol::user_input::UserInput;
+use codex_protocol::user_input::parse_file_attachment;
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
 use crossterm::event::KeyEventKind;
@@ -99,13 +104,16 @@ use crate::bottom_pane::SelectionItem;
 use crate::bottom_pane::SelectionViewParams;
 use crate::bottom_pane::custom_prompt_view::CustomPromptView;
 use crate::bottom_pane::popup_consts::standard_popup_hint_line;
+use crate::clipboard_copy::fenced_code_blocks;
 use crate::clipboard_paste::paste_image_to_temp_png;
+use crate::cues;
+use crate::cues::CueEvent;
 use crate::diff_render::display_path_for;
 use crate::exec_cell::CommandOutput;
 use crate::exec_cell::ExecCell;
 use crate::exec_cell::new_active_exec_command;
 use crate::exec_command::strip_bash_lc_and_escape;
-use crate::get_git_diff::get_git_diff;
+use crate::get_git_diff::get_git_diffs;
 use crate::history_cell;
 use crate::history_cell::AgentMessageCell;
 use crate::history_cell::HistoryCell;
@@ -348,6 +356,10 @@ pub(crate) struct ChatWidget {
     queued_user_messages: VecDeque<UserMessage>,
     // Pending notification to show when unfocused on next Draw
     pending_notification: Option<Notification>,
+    // Cues to fire on next Draw, once terminal focus is known
+    pending_cues: Vec<CueEvent>,
+    // Whether the context-nearly-full cue fired since context was last above the threshold
+    context_nearly_full_cued: bool,
     // Simple review mode flag; used to adjust layout and banners.
     is_review_mode: bool,
     // Snapshot of token usage to restore after review mode exits.
@@ -360,6 +372,9 @@ pub(crate) struct ChatWidget {
     feedback: codex_feedback::CodexFeedback,
     // Current session rollout path (if known)
     current_rollout_path: Option<PathBuf>,
+    // Most recent final agent message and command, for /copy
+    last_agent_message: Option<String>,
+    last_command: Option<String>,
 }
 
 struct UserMessage {
@@ -491,6 +506,9 @@ impl ChatWidget {
     }
 
     fn on_agent_message(&mut self, message: String) {
+        if !message.trim().is_empty() {
+            self.last_agent_message = Some(message.clone());
+        }
         // If we have a stream_controller, then the final agent message is redundant and will be a
         // duplicate of what has already been streamed.
         if self.stream_controller.is_none() {
@@ -592,6 +610,13 @@ impl ChatWidget {
         let used_tokens = self.context_used_tokens(&info, percent.is_some());
         self.bottom_pane.set_context_window(percent, used_tokens);
         self.token_info = Some(info);
+        if let Some(percent) = percent {
+            let nearly_full = percent <= cues::context_nearly_full_threshold(&self.config.tui_cues);
+            if nearly_full && !self.context_nearly_full_cued {
+                self.cue(CueEvent::ContextNearlyFull);
+            }
+            self.context_nearly_full_cued = nearly_full;
+        }
     }
 
     fn context_remaining_percent(&self, info: &TokenUsageInfo) -> Option<i64> {
@@ -858,6 +883,9 @@ impl ChatWidget {
 
     fn on_exec_command_begin(&mut self, ev: ExecCommandBeginEvent) {
         self.flush_answer_stream_with_separator();
+        if ev.source != ExecCommandSource::UnifiedExecInteraction {
+            self.last_command = Some(strip_bash_lc_and_escape(&ev.command));
+        }
         if is_unified_exec_source(ev.source) {
             self.track_unified_exec_session_begin(&ev);
             if !is_standard_tool_call(&ev.parsed_cmd) {
@@ -1039,6 +1067,15 @@ impl ChatWidget {
             .on_history_entry_response(log_id, offset, entry.map(|e| e.text));
     }
 
+    fn on_list_history_entries(&mut self, ev: ListHistoryEntriesResponseEvent) {
+        // The workspace scope of the history search covers the whole repository,
+        // falling back to the cwd outside a git checkout.
+        let workspace_root =
+            get_git_repo_root(&self.config.cwd).unwrap_or_else(|| self.config.cwd.clone());
+        self.bottom_pane
+            .on_history_entries_response(ev.entries, workspace_root);
+    }
+
     fn on_shutdown_complete(&mut self) {
         self.request_exit();
     }
@@ -1176,6 +1213,9 @@ impl ChatWidget {
         };
         let is_unified_exec_interaction =
             matches!(source, ExecCommandSource::UnifiedExecInteraction);
+        if ev.exit_code != 0 && !is_unified_exec_interaction {
+            self.cue(CueEvent::CommandFailed);
+        }
 
         let needs_new = self
             .active_cell
@@ -1428,6 +1468,7 @@ impl ChatWidget {
                 placeholder_text: placeholder,
                 disable_paste_burst: config.disable_paste_burst,
                 animations_enabled: config.animations,
+                editing_mode: config.tui_editing_mode,
                 skills: None,
             }),
             active_cell: None,
@@ -1463,12 +1504,16 @@ impl ChatWidget {
             show_welcome_banner: is_first_run,
             suppress_session_configured_redraw: false,
             pending_notification: None,
+            pending_cues: Vec::new(),
+            context_nearly_full_cued: false,
             is_review_mode: false,
             pre_review_token_info: None,
             needs_final_message_separator: false,
             last_rendered_width: std::cell::Cell::new(None),
             feedback,
             current_rollout_path: None,
+            last_agent_message: None,
+            last_command: None,
         };
 
         widget.prefetch_rate_limits();
@@ -1514,6 +1559,7 @@ impl ChatWidget {
                 placeholder_text: placeholder,
                 disable_paste_burst: config.disable_paste_burst,
                 animations_enabled: config.animations,
+                editing_mode: config.tui_editing_mode,
                 skills: None,
             }),
             active_cell: None,
@@ -1549,12 +1595,16 @@ impl ChatWidget {
             show_welcome_banner: false,
             suppress_session_configured_redraw: true,
             pending_notification: None,
+            pending_cues: Vec::new(),
+            context_nearly_full_cued: false,
             is_review_mode: false,
             pre_review_token_info: None,
             needs_final_message_separator: false,
             last_rendered_width: std::cell::Cell::new(None),
             feedback,
             current_rollout_path: None,
+            last_agent_message: None,
+            last_command: None,
         };
 
         widget.prefetch_rate_limits();
@@ -1723,20 +1773,29 @@ impl ChatWidget {
             SlashCommand::Diff => {
                 self.add_diff_in_progress();
                 let tx = self.app_event_tx.clone();
+                let cwd = self.config.cwd.clone();
                 tokio::spawn(async move {
-                    let text = match get_git_diff().await {
-                        Ok((is_git_repo, diff_text)) => {
-                            if is_git_repo {
-                                diff_text
-                            } else {
-                                "`/diff` — _not inside a git repository_".to_string()
-                            }
+                    let text = match get_git_diffs(&cwd).await {
+                        Ok(Some(diffs)) if !diffs.is_empty() => {
+                            tx.send(AppEvent::OpenDiffBrowser(diffs));
+                            return;
                         }
+                        Ok(Some(_)) => String::new(),
+                        Ok(None) => "`/diff` — _not inside a git repository_".to_string(),
                         Err(e) => format!("Failed to compute diff: {e}"),
                     };
                     tx.send(AppEvent::DiffResult(text));
                 });
             }
+            SlashCommand::Copy => {
+                self.open_copy_popup();
+            }
+            SlashCommand::Export => {
+                self.open_export_popup();
+            }
+            SlashCommand::Edit => {
+                self.app_event_tx.send(AppEvent::OpenExternalEditor);
+            }
             SlashCommand::Mention => {
                 self.insert_str("@");
             }
@@ -1920,6 +1979,10 @@ impl ChatWidget {
             }
         }
 
+        for (path, range) in find_file_mentions(&text, &self.config.cwd) {
+            items.push(UserInput::File { path, range });
+        }
+
         self.codex_op_tx
             .send(Op::UserInput { items })
             .unwrap_or_else(|e| {
@@ -2045,6 +2108,7 @@ impl ChatWidget {
             EventMsg::WebSearchBegin(ev) => self.on_web_search_begin(ev),
             EventMsg::WebSearchEnd(ev) => self.on_web_search_end(ev),
             EventMsg::GetHistoryEntryResponse(ev) => self.on_get_history_entry_response(ev),
+            EventMsg::ListHistoryEntriesResponse(ev) => self.on_list_history_entries(ev),
             EventMsg::McpListToolsResponse(ev) => self.on_list_mcp_tools(ev),
             EventMsg::ListCustomPromptsResponse(ev) => self.on_list_custom_prompts(ev),
             EventMsg::ListSkillsResponse(ev) => self.on_list_skills(ev),
@@ -2076,6 +2140,7 @@ impl ChatWidget {
             EventMsg::ExitedReviewMode(review) => self.on_exited_review_mode(review),
             EventMsg::ContextCompacted(_) => self.on_agent_message("Context compacted".to_owned()),
             EventMsg::RawResponseItem(_)
+            | EventMsg::ThreadRolledBack(_)
             | EventMsg::ItemStarted(_)
             | EventMsg::ItemCompleted(_)
             | EventMsg::AgentMessageContentDelta(_)
@@ -2148,7 +2213,13 @@ impl ChatWidget {
         self.frame_requester.schedule_frame();
     }
 
+    fn cue(&mut self, event: CueEvent) {
+        self.pending_cues.push(event);
+        self.request_redraw();
+    }
+
     fn notify(&mut self, notification: Notification) {
+        self.cue(notification.cue_event());
         if !notification.allowed_for(&self.config.tui_notifications) {
             return;
         }
@@ -2160,6 +2231,13 @@ impl ChatWidget {
         if let Some(notif) = self.pending_notification.take() {
             tui.notify(notif.display());
         }
+        let focused = tui.is_terminal_focused();
+        for event in self.pending_cues.drain(..) {
+            let bells = cues::fire(&self.config.tui_cues, event, focused);
+            if bells > 0 {
+                tui.ring_bell(bells);
+            }
+        }
     }
 
     /// Mark the active cell as failed (✗) and flush it into history.
@@ -2197,6 +2275,120 @@ impl ChatWidget {
         self.bottom_pane.set_queued_user_messages(messages);
     }
 
+    /// Offer the formats the current session can be exported in.
+    pub(crate) fn open_export_popup(&mut self) {
+        if self.current_rollout_path.is_none() {
+            self.add_info_message("Nothing to export yet.".to_string(), None);
+            return;
+        }
+        let items = [
+            ("Markdown", ExportFormat::Markdown),
+            ("HTML", ExportFormat::Html),
+            ("Plain text", ExportFormat::Text),
+        ]
+        .into_iter()
+        .map(|(name, format)| {
+            let actions: Vec<SelectionAction> = vec![Box::new(move |tx| {
+                tx.send(AppEvent::ExportSession(format));
+            })];
+            SelectionItem {
+                name: name.to_string(),
+                description: Some(format!("codex-session-<id>.{}", format.extension())),
+                actions,
+                dismiss_on_select: true,
+                ..Default::default()
+            }
+        })
+        .collect();
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some("Export session".to_string()),
+            subtitle: Some(
+                "Saved under CODEX_HOME/exports with secrets redacted. \
+                 Use `codex sessions export` for a raw transcript."
+                    .to_string(),
+            ),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+        self.request_redraw();
+    }
+
+    /// Offer the last agent message, the last command, and each code block
+    /// in the last agent message for copying to the clipboard.
+    pub(crate) fn open_copy_popup(&mut self) {
+        let mut entries: Vec<(String, Option<String>, String)> = Vec::new();
+        if let Some(message) = self.last_agent_message.as_ref() {
+            entries.push((
+                "Last agent message".to_string(),
+                Some(first_line_preview(message)),
+                message.clone(),
+            ));
+        }
+        if let Some(command) = self.last_command.as_ref() {
+            entries.push((
+                "Last command".to_string(),
+                Some(first_line_preview(command)),
+                command.clone(),
+            ));
+        }
+        let blocks = self
+            .last_agent_message
+            .as_deref()
+            .map(fenced_code_blocks)
+            .unwrap_or_default();
+        for (idx, block) in blocks.into_iter().enumerate() {
+            let lines = block.code.lines().count();
+            let name = match block.language.as_deref() {
+                Some(language) => format!("Code block {} ({language})", idx + 1),
+                None => format!("Code block {}", idx + 1),
+            };
+            let description = format!(
+                "{lines} line{} · {}",
+                if lines == 1 { "" } else { "s" },
+                first_line_preview(&block.code)
+            );
+            entries.push((name, Some(description), block.code));
+        }
+
+        if entries.is_empty() {
+            self.add_info_message(
+                "Nothing to copy yet.".to_string(),
+                Some("/copy becomes available after Codex replies or runs a command.".to_string()),
+            );
+            return;
+        }
+
+        let items = entries
+            .into_iter()
+            .map(|(name, description, text)| {
+                let label = name.to_lowercase();
+                let actions: Vec<SelectionAction> = vec![Box::new(move |tx| {
+                    tx.send(AppEvent::CopyToClipboard {
+                        text: text.clone(),
+                        label: label.clone(),
+                    });
+                })];
+                SelectionItem {
+                    name,
+                    description,
+                    actions,
+                    dismiss_on_select: true,
+                    ..Default::default()
+                }
+            })
+            .collect();
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some("Copy to clipboard".to_string()),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+        self.request_redraw();
+    }
+
     pub(crate) fn add_diff_in_progress(&mut self) {
         self.request_redraw();
     }
@@ -3272,6 +3464,14 @@ impl ChatWidget {
         self.bottom_pane.set_composer_text(text);
     }
 
+    pub(crate) fn composer_text(&self) -> String {
+        self.bottom_pane.composer_text()
+    }
+
+    pub(crate) fn apply_external_edit(&mut self, text: String) {
+        self.bottom_pane.apply_external_edit(text);
+    }
+
     pub(crate) fn show_esc_backtrack_hint(&mut self) {
         self.bottom_pane.show_esc_backtrack_hint();
     }
@@ -3572,6 +3772,15 @@ impl Notification {
         }
     }
 
+    fn cue_event(&self) -> CueEvent {
+        match self {
+            Notification::AgentTurnComplete { .. } => CueEvent::TurnComplete,
+            Notification::ExecApprovalRequested { .. }
+            | Notification::EditApprovalRequested { .. }
+            | Notification::ElicitationRequested { .. } => CueEvent::ApprovalRequested,
+        }
+    }
+
     fn allowed_for(&self, settings: &Notifications) -> bool {
         match settings {
             Notifications::Enabled(enabled) => *enabled,
@@ -3607,6 +3816,22 @@ const EXAMPLE_PROMPTS: [&str; 6] = [
     "Improve documentation in @filename",
 ];
 
+/// First non-empty line of `text`, shortened for a one-line popup description.
+fn first_line_preview(text: &str) -> String {
+    const MAX_CHARS: usize = 60;
+    let line = text
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or_default();
+    if line.chars().count() > MAX_CHARS {
+        let truncated: String = line.chars().take(MAX_CHARS - 1).collect();
+        format!("{truncated}…")
+    } else {
+        line.to_string()
+    }
+}
+
 // Extract the first bold (Markdown) element in the form **...** from `s`.
 // Returns the inner text if found; otherwise `None`.
 fn extract_first_bold(s: &str) -> Option<String> {
@@ -3707,6 +3932,11 @@ fn skills_for_cwd(cwd: &Path, skills_entries: &[SkillsListEntry]) -> Vec<SkillMe
                     short_description: skill.short_description.clone(),
                     path: skill.path.clone(),
                     scope: skill.scope,
+                    allowed_tools: None,
+                    model: None,
+                    reasoning_effort: None,
+                    required_mcp_servers: Vec::new(),
+                    scripts: Vec::new(),
                 })
                 .collect()
         })
@@ -3729,5 +3959,25 @@ fn find_skill_mentions(text: &str, skills: &[SkillMetadata]) -> Vec<SkillMetadat
     matches
 }
 
+/// Returns the `@path` and `@path:START-END` mentions in `text` that name an
+/// existing file relative to `cwd`, so they can be attached to the prompt.
+fn find_file_mentions(text: &str, cwd: &Path) -> Vec<(PathBuf, Option<LineRange>)> {
+    let mut mentions: Vec<(PathBuf, Option<LineRange>)> = Vec::new();
+    for token in text.split_whitespace() {
+        let Some(spec) = token.strip_prefix('@') else {
+            continue;
+        };
+        let spec = spec.trim_end_matches([',', '.', ';', ')']);
+        if spec.is_empty() {
+            continue;
+        }
+        let mention = parse_file_attachment(spec);
+        if cwd.join(&mention.0).is_file() && !mentions.contains(&mention) {
+            mentions.push(mention);
+        }
+    }
+    mentions
+}
+
 #[cfg(test)]
 pub(crate) mod tests;
@@ -0,0 +1,332 @@
+//! Copying text to the system clipboard.
+//!
+//! A native clipboard (via `arboard`) is used when a display server is
+//! reachable. Over SSH, inside containers, or on headless hosts, text is
+//! instead sent to the user's terminal with an OSC 52 escape sequence, which
+//! most modern terminal emulators place on their own clipboard. The sequence
+//! is wrapped for tmux and GNU screen so it reaches the outer terminal.
+
+use std::io::Write;
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use tracing::error;
+
+#[derive(Debug)]
+pub enum ClipboardError {
+    ClipboardUnavailable(String),
+    WriteFailed(String),
+}
+
+impl std::fmt::Display for ClipboardError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClipboardError::ClipboardUnavailable(msg) => {
+                write!(f, "clipboard unavailable: {msg}")
+            }
+            ClipboardError::WriteFailed(msg) => write!(f, "failed to write to clipboard: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for ClipboardError {}
+
+pub trait ClipboardManager {
+    fn set_text(&mut self, text: String) -> Result<(), ClipboardError>;
+}
+
+#[cfg(not(target_os = "android"))]
+pub struct ArboardClipboardManager {
+    inner: Option<arboard::Clipboard>,
+}
+
+#[cfg(not(target_os = "android"))]
+impl ArboardClipboardManager {
+    pub fn new() -> Self {
+        match arboard::Clipboard::new() {
+            Ok(cb) => Self { inner: Some(cb) },
+            Err(err) => {
+                error!(error = %err, "failed to initialize clipboard");
+                Self { inner: None }
+            }
+        }
+    }
+}
+
+#[cfg(not(target_os = "android"))]
+impl ClipboardManager for ArboardClipboardManager {
+    fn set_text(&mut self, text: String) -> Result<(), ClipboardError> {
+        let Some(cb) = &mut self.inner else {
+            return Err(ClipboardError::ClipboardUnavailable(
+                "clipboard is not available in this environment".to_string(),
+            ));
+        };
+        cb.set_text(text)
+            .map_err(|e| ClipboardError::WriteFailed(e.to_string()))
+    }
+}
+
+#[cfg(target_os = "android")]
+pub struct ArboardClipboardManager;
+
+#[cfg(target_os = "android")]
+impl ArboardClipboardManager {
+    pub fn new() -> Self {
+        ArboardClipboardManager
+    }
+}
+
+#[cfg(target_os = "android")]
+impl ClipboardManager for ArboardClipboardManager {
+    fn set_text(&mut self, _text: String) -> Result<(), ClipboardError> {
+        Err(ClipboardError::ClipboardUnavailable(
+            "clipboard text copy is unsupported on Android".to_string(),
+        ))
+    }
+}
+
+/// Many terminals drop OSC 52 payloads above this size (hterm and xterm
+/// among them), so larger copies are refused rather than silently lost.
+const MAX_OSC52_ENCODED_BYTES: usize = 74_994;
+
+/// GNU screen truncates DCS strings longer than this, so passthrough payloads
+/// are split into chunks of at most this many bytes.
+const SCREEN_DCS_CHUNK_BYTES: usize = 76;
+
+/// Terminal multiplexer that an OSC 52 sequence must be tunneled through.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Multiplexer {
+    None,
+    Tmux,
+    Screen,
+}
+
+impl Multiplexer {
+    fn detect() -> Self {
+        if std::env::var_os("TMUX").is_some() {
+            Self::Tmux
+        } else if std::env::var("TERM").is_ok_and(|term| term.starts_with("screen"))
+            || std::env::var_os("STY").is_some()
+        {
+            Self::Screen
+        } else {
+            Self::None
+        }
+    }
+}
+
+/// Writes the clipboard contents to the controlling terminal as OSC 52.
+pub struct Osc52ClipboardManager {
+    multiplexer: Multiplexer,
+}
+
+impl Osc52ClipboardManager {
+    pub fn new() -> Self {
+        Self {
+            multiplexer: Multiplexer::detect(),
+        }
+    }
+}
+
+impl ClipboardManager for Osc52ClipboardManager {
+    fn set_text(&mut self, text: String) -> Result<(), ClipboardError> {
+        let sequence = osc52_sequence(&text, self.multiplexer)?;
+        let mut stdout = std::io::stdout().lock();
+        stdout
+            .write_all(sequence.as_bytes())
+            .and_then(|()| stdout.flush())
+            .map_err(|e| ClipboardError::WriteFailed(e.to_string()))
+    }
+}
+
+fn osc52_sequence(text: &str, multiplexer: Multiplexer) -> Result<String, ClipboardError> {
+    let encoded = BASE64_STANDARD.encode(text);
+    if encoded.len() > MAX_OSC52_ENCODED_BYTES {
+        return Err(ClipboardError::WriteFailed(format!(
+            "{} bytes is too large to copy through the terminal (limit is about {} bytes)",
+            text.len(),
+            MAX_OSC52_ENCODED_BYTES / 4 * 3
+        )));
+    }
+    let osc = format!("\x1b]52;c;{encoded}\x07");
+    Ok(match multiplexer {
+        Multiplexer::None => osc,
+        // tmux forwards DCS passthrough when `allow-passthrough` is on; every
+        // ESC inside the payload must be doubled.
+        Multiplexer::Tmux => format!("\x1bPtmux;{}\x1b\\", osc.replace('\x1b', "\x1b\x1b")),
+        Multiplexer::Screen => {
+            let mut wrapped = String::with_capacity(osc.len() * 2);
+            // The payload is ASCII, so byte offsets are char boundaries.
+            for start in (0..osc.len()).step_by(SCREEN_DCS_CHUNK_BYTES) {
+                let end = (start + SCREEN_DCS_CHUNK_BYTES).min(osc.len());
+                wrapped.push_str("\x1bP");
+                wrapped.push_str(&osc[start..end]);
+                wrapped.push_str("\x1b\\");
+            }
+            wrapped
+        }
+    })
+}
+
+/// Whether a native clipboard is likely to reach the user. On a remote or
+/// headless machine the "system" clipboard belongs to the wrong host (or does
+/// not exist), so OSC 52 is preferred there.
+fn native_clipboard_reachable() -> bool {
+    if cfg!(target_os = "android") {
+        return false;
+    }
+    let has_display = std::env::var_os("DISPLAY").is_some_and(|v| !v.is_empty())
+        || std::env::var_os("WAYLAND_DISPLAY").is_some_and(|v| !v.is_empty());
+    if cfg!(any(target_os = "macos", target_os = "windows")) {
+        let over_ssh =
+            std::env::var_os("SSH_CONNECTION").is_some() || std::env::var_os("SSH_TTY").is_some();
+        // X11 forwarding still reaches the local machine.
+        !over_ssh || has_display
+    } else {
+        has_display
+    }
+}
+
+/// Copies `text`, using the native clipboard when a display server is
+/// reachable and falling back to OSC 52 otherwise or when it fails.
+pub fn copy_text(text: String) -> Result<(), ClipboardError> {
+    if native_clipboard_reachable() {
+        let mut manager = ArboardClipboardManager::new();
+        match manager.set_text(text.clone()) {
+            Ok(()) => return Ok(()),
+            Err(err) => {
+                tracing::debug!(error = %err, "native clipboard failed; falling back to OSC 52");
+            }
+        }
+    }
+    Osc52ClipboardManager::new().set_text(text)
+}
+
+/// A fenced code block found in markdown, with its info-string language.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CodeBlock {
+    pub language: Option<String>,
+    pub code: String,
+}
+
+/// Extracts the fenced (``` or ~~~) code blocks from `markdown`, in order.
+pub(crate) fn fenced_code_blocks(markdown: &str) -> Vec<CodeBlock> {
+    let mut blocks = Vec::new();
+    let mut open: Option<(String, Option<String>, Vec<&str>)> = None;
+    for line in markdown.lines() {
+        let trimmed = line.trim_start();
+        match open.as_mut() {
+            Some((fence, _, lines)) => {
+                let closes = trimmed.starts_with(fence.as_str())
+                    && trimmed
+                        .trim_start_matches(fence.chars().next().unwrap_or('`'))
+                        .trim()
+                        .is_empty();
+                if closes {
+                    if let Some((_, language, lines)) = open.take() {
+                        blocks.push(CodeBlock {
+                            language,
+                            code: lines.join("\n"),
+                        });
+                    }
+                } else {
+                    lines.push(line);
+                }
+            }
+            None => {
+                let marker = trimmed.chars().next();
+                if let Some(marker @ ('`' | '~')) = marker {
+                    let fence_len = trimmed.chars().take_while(|c| *c == marker).count();
+                    if fence_len >= 3 {
+                        let info = trimmed[fence_len..].trim();
+                        let language = info
+                            .split_whitespace()
+                            .next()
+                            .filter(|lang| !lang.is_empty())
+                            .map(str::to_string);
+                        open = Some((marker.to_string().repeat(fence_len), language, Vec::new()));
+                    }
+                }
+            }
+        }
+    }
+    // An unterminated fence runs to the end of the message.
+    if let Some((_, language, lines)) = open {
+        blocks.push(CodeBlock {
+            language,
+            code: lines.join("\n"),
+        });
+    }
+    blocks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn osc52_plain_sequence() {
+        let seq = osc52_sequence("hi", Multiplexer::None).expect("sequence");
+        assert_eq!(seq, "\x1b]52;c;aGk=\x07");
+    }
+
+    #[test]
+    fn osc52_tmux_passthrough_doubles_escapes() {
+        let seq = osc52_sequence("hi", Multiplexer::Tmux).expect("sequence");
+        assert_eq!(seq, "\x1bPtmux;\x1b\x1b]52;c;aGk=\x07\x1b\\");
+    }
+
+    #[test]
+    fn osc52_screen_passthrough_is_chunked() {
+        let text = "x".repeat(200);
+        let seq = osc52_sequence(&text, Multiplexer::Screen).expect("sequence");
+        let chunks: Vec<&str> = seq
+            .split("\x1b\\")
+            .filter(|chunk| !chunk.is_empty())
+            .collect();
+        assert!(chunks.len() > 1);
+        assert!(
+            chunks.iter().all(
+                |chunk| chunk.starts_with("\x1bP") && chunk.len() <= SCREEN_DCS_CHUNK_BYTES + 2
+            )
+        );
+        let joined: String = chunks
+            .iter()
+            .map(|chunk| chunk.trim_start_matches("\x1bP"))
+            .collect();
+        assert_eq!(
+            joined,
+            osc52_sequence(&text, Multiplexer::None).expect("plain")
+        );
+    }
+
+    #[test]
+    fn osc52_rejects_oversized_payloads() {
+        let text = "x".repeat(MAX_OSC52_ENCODED_BYTES);
+        assert!(osc52_sequence(&text, Multiplexer::None).is_err());
+    }
+
+    #[test]
+    fn extracts_fenced_code_blocks() {
+        let markdown =
+            "Try this:\n\n```rust\nfn main() {}\n```\n\nthen\n~~~\nls -la\n  pwd\n~~~\n````\nopen";
+        assert_eq!(
+            fenced_code_blocks(markdown),
+            vec![
+                CodeBlock {
+                    language: Some("rust".to_string()),
+                    code: "fn main() {}".to_string(),
+                },
+                CodeBlock {
+                    language: None,
+                    code: "ls -la\n  pwd".to_string(),
+                },
+                CodeBlock {
+                    language: None,
+                    code: "open".to_string(),
+                },
+            ]
+        );
+    }
+}
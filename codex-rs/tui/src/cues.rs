@@ -0,0 +1,175 @@
+//! Audible and spoken cues configured under `[tui.cues]`.
+//!
+//! Desktop notifications are only posted while the terminal is unfocused and
+//! are silent, which makes them easy to miss and invisible to screen-reader
+//! users. Cues ring the terminal bell, play a sound file, or speak a short
+//! message through a text-to-speech command, and fire regardless of focus
+//! unless `unfocused_only` is set.
+//!
+//! tui2 uses this module as well. Bells are rung by each crate's `Tui`
+//! through its terminal writer, so this module never writes to the terminal.
+
+use std::path::Path;
+use std::process::Command;
+use std::process::Stdio;
+use std::time::Duration;
+
+use codex_core::config::types::CueAction;
+use codex_core::config::types::Cues;
+
+/// Spacing between bells so terminals do not collapse them into one.
+pub const BELL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Upper bound on the bell pattern length.
+const MAX_BELLS: u8 = 5;
+
+const DEFAULT_CONTEXT_NEARLY_FULL_PERCENT: u8 = 10;
+
+const TTS_CANDIDATES: &[&str] = &["say", "spd-say", "espeak-ng", "espeak"];
+
+#[cfg(target_os = "macos")]
+const SOUND_CANDIDATES: &[&str] = &["afplay"];
+#[cfg(not(target_os = "macos"))]
+const SOUND_CANDIDATES: &[&str] = &["paplay", "pw-play", "aplay"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CueEvent {
+    ApprovalRequested,
+    TurnComplete,
+    CommandFailed,
+    ContextNearlyFull,
+}
+
+impl CueEvent {
+    fn action(self, cues: &Cues) -> Option<&CueAction> {
+        match self {
+            CueEvent::ApprovalRequested => cues.approval_requested.as_ref(),
+            CueEvent::TurnComplete => cues.turn_complete.as_ref(),
+            CueEvent::CommandFailed => cues.command_failed.as_ref(),
+            CueEvent::ContextNearlyFull => cues.context_nearly_full.as_ref(),
+        }
+    }
+}
+
+/// Remaining-context percentage at or below which `ContextNearlyFull` fires.
+pub fn context_nearly_full_threshold(cues: &Cues) -> i64 {
+    i64::from(
+        cues.context_nearly_full_percent
+            .unwrap_or(DEFAULT_CONTEXT_NEARLY_FULL_PERCENT),
+    )
+}
+
+/// Performs the sound and speech actions configured for `event` and returns
+/// how many times the caller should ring the terminal bell. Failures are
+/// logged rather than surfaced, since a missing cue should never interrupt a
+/// session.
+pub fn fire(cues: &Cues, event: CueEvent, terminal_focused: bool) -> u8 {
+    if cues.unfocused_only && terminal_focused {
+        return 0;
+    }
+    let Some(action) = event.action(cues) else {
+        return 0;
+    };
+
+    if let Some(sound) = action.sound.as_deref() {
+        let command = configured_or_detected(cues.sound_command.as_deref(), SOUND_CANDIDATES);
+        run_with_arg(command, "sound_command", &sound.to_string_lossy());
+    }
+    if let Some(message) = action.say.as_deref().filter(|m| !m.trim().is_empty()) {
+        let command = configured_or_detected(cues.tts_command.as_deref(), TTS_CANDIDATES);
+        run_with_arg(command, "tts_command", message);
+    }
+    action.bell.unwrap_or(0).min(MAX_BELLS)
+}
+
+fn configured_or_detected(configured: Option<&[String]>, candidates: &[&str]) -> Vec<String> {
+    match configured {
+        Some(command) => command.to_vec(),
+        None => candidates
+            .iter()
+            .find(|candidate| which::which(candidate).is_ok())
+            .map(|candidate| vec![(*candidate).to_string()])
+            .unwrap_or_default(),
+    }
+}
+
+/// Spawns `command arg` in the background and reaps it on a helper thread.
+fn run_with_arg(command: Vec<String>, setting: &str, arg: &str) {
+    let Some((program, args)) = command.split_first() else {
+        tracing::warn!("no {setting} configured or found on PATH; skipping cue");
+        return;
+    };
+    let spawned = Command::new(program)
+        .args(args)
+        .arg(arg)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn();
+    match spawned {
+        Ok(mut child) => {
+            std::thread::spawn(move || {
+                let _ = child.wait();
+            });
+        }
+        Err(err) => {
+            let program = Path::new(program).display();
+            tracing::warn!("failed to run {setting} `{program}`: {err}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn configured_command_takes_precedence() {
+        let configured = vec!["espeak-ng".to_string(), "-s".to_string(), "180".to_string()];
+        assert_eq!(
+            configured_or_detected(Some(configured.as_slice()), &["definitely-not-a-real-tts"]),
+            configured
+        );
+        assert_eq!(
+            configured_or_detected(None, &["definitely-not-a-real-tts"]),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn events_map_to_their_actions() {
+        let cues = Cues {
+            command_failed: Some(CueAction {
+                bell: Some(2),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        assert_eq!(
+            CueEvent::CommandFailed.action(&cues).and_then(|a| a.bell),
+            Some(2)
+        );
+        assert_eq!(CueEvent::TurnComplete.action(&cues), None);
+        assert_eq!(context_nearly_full_threshold(&cues), 10);
+    }
+
+    #[test]
+    fn fire_returns_capped_bell_count() {
+        let cues = Cues {
+            turn_complete: Some(CueAction {
+                bell: Some(9),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        assert_eq!(fire(&cues, CueEvent::TurnComplete, true), MAX_BELLS);
+        assert_eq!(fire(&cues, CueEvent::CommandFailed, true), 0);
+
+        let unfocused_only = Cues {
+            unfocused_only: true,
+            ..cues
+        };
+        assert_eq!(fire(&unfocused_only, CueEvent::TurnComplete, true), 0);
+    }
+}
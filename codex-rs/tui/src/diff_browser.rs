@@ -0,0 +1,1007 @@
+//! Interactive browser behind `/diff`.
+//!
+//! Changed files are listed as a tree with their added/removed line counts.
+//! Expanding a file shows its hunks, which can be staged, unstaged or
+//! discarded one at a time through `codex-git`, or opened in the configured
+//! `file_opener`. Unstaged and staged changes are shown as two views.
+
+use std::collections::HashSet;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_core::config::types::UriBasedFileOpener;
+use codex_core::protocol::FileChange;
+use codex_git::HunkAction;
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+use ratatui::widgets::Clear;
+use ratatui::widgets::Paragraph;
+use ratatui::widgets::Widget;
+use ratatui::widgets::WidgetRef;
+
+use crate::app_event::AppEvent;
+use crate::app_event_sender::AppEventSender;
+use crate::diff_render;
+use crate::diff_render::SIDE_BY_SIDE_KEY;
+use crate::get_git_diff::GitDiffs;
+use crate::get_git_diff::get_git_diffs;
+use crate::get_git_diff::split_file_diffs;
+use crate::key_hint;
+use crate::key_hint::KeyBinding;
+use crate::pager_overlay::render_key_hints;
+use crate::render::line_utils::prefix_lines;
+use crate::tui;
+use crate::tui::TuiEvent;
+
+/// Files start expanded when there are at most this many in a view.
+const EXPAND_ALL_MAX_FILES: usize = 5;
+
+const KEY_UP: KeyBinding = key_hint::plain(KeyCode::Up);
+const KEY_DOWN: KeyBinding = key_hint::plain(KeyCode::Down);
+const KEY_K: KeyBinding = key_hint::plain(KeyCode::Char('k'));
+const KEY_J: KeyBinding = key_hint::plain(KeyCode::Char('j'));
+const KEY_LEFT: KeyBinding = key_hint::plain(KeyCode::Left);
+const KEY_RIGHT: KeyBinding = key_hint::plain(KeyCode::Right);
+const KEY_H: KeyBinding = key_hint::plain(KeyCode::Char('h'));
+const KEY_L: KeyBinding = key_hint::plain(KeyCode::Char('l'));
+const KEY_PAGE_UP: KeyBinding = key_hint::plain(KeyCode::PageUp);
+const KEY_PAGE_DOWN: KeyBinding = key_hint::plain(KeyCode::PageDown);
+const KEY_HOME: KeyBinding = key_hint::plain(KeyCode::Home);
+const KEY_END: KeyBinding = key_hint::plain(KeyCode::End);
+const KEY_PREV_FILE: KeyBinding = key_hint::plain(KeyCode::Char('['));
+const KEY_NEXT_FILE: KeyBinding = key_hint::plain(KeyCode::Char(']'));
+const KEY_PREV_HUNK: KeyBinding = key_hint::plain(KeyCode::Char('p'));
+const KEY_NEXT_HUNK: KeyBinding = key_hint::plain(KeyCode::Char('n'));
+const KEY_ENTER: KeyBinding = key_hint::plain(KeyCode::Enter);
+const KEY_SPACE: KeyBinding = key_hint::plain(KeyCode::Char(' '));
+const KEY_TAB: KeyBinding = key_hint::plain(KeyCode::Tab);
+const KEY_STAGE: KeyBinding = key_hint::plain(KeyCode::Char('s'));
+const KEY_UNSTAGE: KeyBinding = key_hint::plain(KeyCode::Char('u'));
+const KEY_DISCARD: KeyBinding = key_hint::plain(KeyCode::Char('d'));
+const KEY_OPEN: KeyBinding = key_hint::plain(KeyCode::Char('o'));
+const KEY_Q: KeyBinding = key_hint::plain(KeyCode::Char('q'));
+const KEY_ESC: KeyBinding = key_hint::plain(KeyCode::Esc);
+const KEY_CTRL_C: KeyBinding = key_hint::ctrl(KeyCode::Char('c'));
+
+const NAVIGATION_HINTS: &[(&[KeyBinding], &str)] = &[
+    (&[KEY_UP, KEY_DOWN], "to move"),
+    (&[KEY_PREV_FILE, KEY_NEXT_FILE], "files"),
+    (&[KEY_PREV_HUNK, KEY_NEXT_HUNK], "hunks"),
+    (&[KEY_ENTER], "to expand/collapse"),
+    (&[SIDE_BY_SIDE_KEY], "side-by-side"),
+];
+const UNSTAGED_HINTS: &[(&[KeyBinding], &str)] = &[
+    (&[KEY_STAGE], "to stage"),
+    (&[KEY_DISCARD], "to discard"),
+    (&[KEY_OPEN], "to open"),
+    (&[KEY_TAB], "for staged"),
+    (&[KEY_Q], "to quit"),
+];
+const STAGED_HINTS: &[(&[KeyBinding], &str)] = &[
+    (&[KEY_UNSTAGE], "to unstage"),
+    (&[KEY_OPEN], "to open"),
+    (&[KEY_TAB], "for unstaged"),
+    (&[KEY_Q], "to quit"),
+];
+
+/// Applies `action` to `patch` in the repository containing `cwd` and reloads
+/// the diffs for the browser.
+pub(crate) async fn apply_hunk_and_reload(
+    cwd: PathBuf,
+    patch: String,
+    action: HunkAction,
+) -> Result<GitDiffs, String> {
+    let apply_cwd = cwd.clone();
+    tokio::task::spawn_blocking(move || codex_git::apply_hunk(&apply_cwd, &patch, action))
+        .await
+        .map_err(|err| format!("Failed to apply change: {err}"))?
+        .map_err(|err| err.to_string())?;
+    match get_git_diffs(&cwd).await {
+        Ok(Some(diffs)) => Ok(diffs),
+        Ok(None) => Err("No longer inside a git repository.".to_string()),
+        Err(err) => Err(format!("Failed to compute diff: {err}")),
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum DiffView {
+    Unstaged,
+    Staged,
+}
+
+/// A selectable row: a file, or one of its hunks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Item {
+    File(usize),
+    Hunk(usize, usize),
+}
+
+impl Item {
+    fn file(self) -> usize {
+        match self {
+            Item::File(file) | Item::Hunk(file, _) => file,
+        }
+    }
+
+    fn hunk(self) -> Option<usize> {
+        match self {
+            Item::File(_) => None,
+            Item::Hunk(_, hunk) => Some(hunk),
+        }
+    }
+}
+
+/// A changed file, split so each hunk can be rendered and applied on its own.
+struct BrowserFile {
+    path: PathBuf,
+    /// The `diff --git` line and extended headers.
+    git_header: String,
+    /// The `---`/`+++` lines.
+    file_header: String,
+    hunks: Vec<String>,
+    added: usize,
+    removed: usize,
+    /// What changed when there is no textual diff to show, e.g. `binary`.
+    note: Option<String>,
+}
+
+impl BrowserFile {
+    fn parse_all(diff: &str) -> Vec<Self> {
+        let mut files: Vec<Self> = split_file_diffs(diff)
+            .into_iter()
+            .map(|file| {
+                let mut file_header = String::new();
+                let mut hunks: Vec<String> = Vec::new();
+                for line in file.unified_diff.split_inclusive('\n') {
+                    if line.starts_with("@@") {
+                        hunks.push(line.to_string());
+                    } else if let Some(hunk) = hunks.last_mut() {
+                        hunk.push_str(line);
+                    } else {
+                        file_header.push_str(line);
+                    }
+                }
+                let (added, removed) =
+                    diff_render::calculate_add_remove_from_diff(&file.unified_diff);
+                let note = hunks.is_empty().then(|| header_only_note(&file.header));
+                Self {
+                    path: file.path,
+                    git_header: file.header,
+                    file_header,
+                    hunks,
+                    added,
+                    removed,
+                    note,
+                }
+            })
+            .collect();
+        // Untracked files are appended after tracked ones; the tree needs
+        // them in path order.
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        files
+    }
+
+    /// A patch for one hunk, or for the whole file when `hunk` is `None`.
+    fn patch(&self, hunk: Option<usize>) -> String {
+        let hunks = match hunk {
+            Some(hunk) => self.hunks.get(hunk).cloned().unwrap_or_default(),
+            None => self.hunks.concat(),
+        };
+        format!("{}{}{hunks}", self.git_header, self.file_header)
+    }
+}
+
+pub(crate) struct DiffBrowserOverlay {
+    app_event_tx: AppEventSender,
+    file_opener: UriBasedFileOpener,
+    repo_root: PathBuf,
+    unstaged: Vec<BrowserFile>,
+    staged: Vec<BrowserFile>,
+    view: DiffView,
+    expanded: HashSet<PathBuf>,
+    selected: Option<Item>,
+    scroll_offset: usize,
+    /// First content row of each selectable item in the last render.
+    item_rows: Vec<(Item, usize)>,
+    page_height: usize,
+    /// Set by the first `d`; a second `d` on the same item discards it.
+    pending_discard: Option<Item>,
+    /// True while a stage/unstage/discard is running.
+    busy: bool,
+    status: Option<Line<'static>>,
+    is_done: bool,
+}
+
+impl DiffBrowserOverlay {
+    pub(crate) fn new(
+        diffs: GitDiffs,
+        file_opener: UriBasedFileOpener,
+        app_event_tx: AppEventSender,
+    ) -> Self {
+        let mut browser = Self {
+            app_event_tx,
+            file_opener,
+            repo_root: PathBuf::new(),
+            unstaged: Vec::new(),
+            staged: Vec::new(),
+            view: DiffView::Unstaged,
+            expanded: HashSet::new(),
+            selected: None,
+            scroll_offset: 0,
+            item_rows: Vec::new(),
+            page_height: 0,
+            pending_discard: None,
+            busy: false,
+            status: None,
+            is_done: false,
+        };
+        browser.set_diffs(diffs);
+        if browser.unstaged.is_empty() && !browser.staged.is_empty() {
+            browser.view = DiffView::Staged;
+        }
+        for files in [&browser.unstaged, &browser.staged] {
+            if files.len() <= EXPAND_ALL_MAX_FILES {
+                browser
+                    .expanded
+                    .extend(files.iter().map(|file| file.path.clone()));
+            }
+        }
+        browser.selected = browser.items().first().copied();
+        browser
+    }
+
+    /// Replaces the diffs after a stage/unstage/discard, keeping the
+    /// selection on the same file where possible.
+    pub(crate) fn update(&mut self, result: Result<GitDiffs, String>) {
+        self.busy = false;
+        match result {
+            Ok(diffs) => {
+                let previous_path = self
+                    .selected
+                    .and_then(|item| self.files().get(item.file()))
+                    .map(|file| file.path.clone());
+                let previous_hunk = self.selected.and_then(Item::hunk);
+                let previous_index = self.selected_index().unwrap_or(0);
+                self.set_diffs(diffs);
+                self.status = None;
+
+                let file = previous_path
+                    .and_then(|path| self.files().iter().position(|file| file.path == path));
+                self.selected = match (file, previous_hunk) {
+                    (Some(file), Some(hunk)) if !self.files()[file].hunks.is_empty() => Some(
+                        Item::Hunk(file, hunk.min(self.files()[file].hunks.len() - 1)),
+                    ),
+                    (Some(file), _) => Some(Item::File(file)),
+                    (None, _) => {
+                        let items = self.items();
+                        items
+                            .get(previous_index.min(items.len().saturating_sub(1)))
+                            .copied()
+                    }
+                };
+            }
+            Err(err) => self.status = Some(Line::from(err).red()),
+        }
+    }
+
+    pub(crate) fn handle_event(&mut self, tui: &mut tui::Tui, event: TuiEvent) -> io::Result<()> {
+        match event {
+            TuiEvent::Key(key_event) => {
+                self.handle_key_event(key_event);
+                tui.frame_requester().schedule_frame();
+                Ok(())
+            }
+            TuiEvent::Draw => {
+                tui.draw(u16::MAX, |frame| {
+                    self.render(frame.area(), frame.buffer);
+                })?;
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub(crate) fn is_done(&self) -> bool {
+        self.is_done
+    }
+
+    fn handle_key_event(&mut self, key_event: KeyEvent) {
+        // Any other key cancels a pending discard; Esc does only that.
+        if !KEY_DISCARD.is_press(key_event) && self.pending_discard.take().is_some() {
+            self.status = None;
+            if KEY_ESC.is_press(key_event) {
+                return;
+            }
+        }
+        match key_event {
+            e if KEY_Q.is_press(e) || KEY_ESC.is_press(e) || KEY_CTRL_C.is_press(e) => {
+                self.is_done = true;
+            }
+            e if KEY_UP.is_press(e) || KEY_K.is_press(e) => self.move_selection(-1),
+            e if KEY_DOWN.is_press(e) || KEY_J.is_press(e) => self.move_selection(1),
+            e if KEY_PAGE_UP.is_press(e) => self.page(false),
+            e if KEY_PAGE_DOWN.is_press(e) => self.page(true),
+            e if KEY_HOME.is_press(e) => self.selected = self.items().first().copied(),
+            e if KEY_END.is_press(e) => self.selected = self.items().last().copied(),
+            e if KEY_NEXT_FILE.is_press(e) => self.jump_file(true),
+            e if KEY_PREV_FILE.is_press(e) => self.jump_file(false),
+            e if KEY_NEXT_HUNK.is_press(e) => self.jump_hunk(true),
+            e if KEY_PREV_HUNK.is_press(e) => self.jump_hunk(false),
+            e if KEY_ENTER.is_press(e) || KEY_SPACE.is_press(e) => self.toggle_expanded(),
+            e if KEY_RIGHT.is_press(e) || KEY_L.is_press(e) => self.expand_selected(),
+            e if KEY_LEFT.is_press(e) || KEY_H.is_press(e) => self.collapse_selected(),
+            e if KEY_TAB.is_press(e) => self.switch_view(),
+            e if KEY_STAGE.is_press(e) => self.apply_to_selection(HunkAction::Stage),
+            e if KEY_UNSTAGE.is_press(e) => self.apply_to_selection(HunkAction::Unstage),
+            e if KEY_DISCARD.is_press(e) => self.apply_to_selection(HunkAction::Revert),
+            e if KEY_OPEN.is_press(e) => self.open_selected(),
+            e if SIDE_BY_SIDE_KEY.is_press(e) => {
+                diff_render::toggle_side_by_side();
+            }
+            _ => {}
+        }
+    }
+
+    fn set_diffs(&mut self, diffs: GitDiffs) {
+        self.repo_root = diffs.repo_root;
+        self.unstaged = BrowserFile::parse_all(&diffs.unstaged);
+        self.staged = BrowserFile::parse_all(&diffs.staged);
+    }
+
+    fn files(&self) -> &[BrowserFile] {
+        match self.view {
+            DiffView::Unstaged => &self.unstaged,
+            DiffView::Staged => &self.staged,
+        }
+    }
+
+    /// Selectable items in display order: each file, then its hunks when
+    /// expanded.
+    fn items(&self) -> Vec<Item> {
+        let mut items = Vec::new();
+        for (index, file) in self.files().iter().enumerate() {
+            items.push(Item::File(index));
+            if self.expanded.contains(&file.path) {
+                items.extend((0..file.hunks.len()).map(|hunk| Item::Hunk(index, hunk)));
+            }
+        }
+        items
+    }
+
+    fn selected_index(&self) -> Option<usize> {
+        let selected = self.selected?;
+        self.items().iter().position(|item| *item == selected)
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        let items = self.items();
+        let Some(last) = items.len().checked_sub(1) else {
+            return;
+        };
+        let current = self.selected_index().unwrap_or(0);
+        let next = current.saturating_add_signed(delta).min(last);
+        self.selected = items.get(next).copied();
+    }
+
+    /// Moves by roughly one screen, using the row positions of the last render.
+    fn page(&mut self, forward: bool) {
+        let Some(selected) = self.selected else {
+            return;
+        };
+        let Some(start) = self
+            .item_rows
+            .iter()
+            .find(|(item, _)| *item == selected)
+            .map(|(_, row)| *row)
+        else {
+            return;
+        };
+        let page = self.page_height.max(1);
+        let target = if forward {
+            self.item_rows
+                .iter()
+                .rev()
+                .find(|(_, row)| *row <= start + page)
+        } else {
+            self.item_rows
+                .iter()
+                .find(|(_, row)| *row >= start.saturating_sub(page))
+        };
+        if let Some((item, _)) = target {
+            self.selected = Some(*item);
+        }
+    }
+
+    fn jump_file(&mut self, forward: bool) {
+        let Some(selected) = self.selected else {
+            return;
+        };
+        let file = selected.file();
+        let target = match (forward, selected) {
+            (true, _) => file + 1,
+            // From a hunk, `[` first returns to the hunk's own file.
+            (false, Item::Hunk(..)) => file,
+            (false, Item::File(_)) => match file.checked_sub(1) {
+                Some(previous) => previous,
+                None => return,
+            },
+        };
+        if target < self.files().len() {
+            self.selected = Some(Item::File(target));
+        }
+    }
+
+    /// Moves to the next or previous hunk across all files, expanding the
+    /// file it lands in.
+    fn jump_hunk(&mut self, forward: bool) {
+        let hunks: Vec<Item> = self
+            .files()
+            .iter()
+            .enumerate()
+            .flat_map(|(file, f)| (0..f.hunks.len()).map(move |hunk| Item::Hunk(file, hunk)))
+            .collect();
+        let target = match self.selected {
+            Some(Item::Hunk(file, hunk)) => {
+                let position = hunks
+                    .iter()
+                    .position(|item| *item == Item::Hunk(file, hunk));
+                match (position, forward) {
+                    (Some(position), true) => hunks.get(position + 1),
+                    (Some(position), false) => position.checked_sub(1).and_then(|p| hunks.get(p)),
+                    (None, _) => None,
+                }
+            }
+            Some(Item::File(file)) if forward => hunks.iter().find(|item| item.file() >= file),
+            Some(Item::File(file)) => hunks.iter().rev().find(|item| item.file() < file),
+            None => None,
+        };
+        if let Some(item) = target.copied() {
+            let path = self.files()[item.file()].path.clone();
+            self.expanded.insert(path);
+            self.selected = Some(item);
+        }
+    }
+
+    fn toggle_expanded(&mut self) {
+        match self.selected {
+            Some(Item::File(file)) => {
+                let path = self.files()[file].path.clone();
+                if !self.expanded.remove(&path) {
+                    self.expanded.insert(path);
+                }
+            }
+            Some(Item::Hunk(..)) => self.collapse_selected(),
+            None => {}
+        }
+    }
+
+    fn expand_selected(&mut self) {
+        let Some(Item::File(file)) = self.selected else {
+            return;
+        };
+        let path = self.files()[file].path.clone();
+        if self.expanded.insert(path) {
+            return;
+        }
+        if !self.files()[file].hunks.is_empty() {
+            self.selected = Some(Item::Hunk(file, 0));
+        }
+    }
+
+    fn collapse_selected(&mut self) {
+        let Some(selected) = self.selected else {
+            return;
+        };
+        let file = selected.file();
+        let path = self.files()[file].path.clone();
+        self.expanded.remove(&path);
+        self.selected = Some(Item::File(file));
+    }
+
+    fn switch_view(&mut self) {
+        self.view = match self.view {
+            DiffView::Unstaged => DiffView::Staged,
+            DiffView::Staged => DiffView::Unstaged,
+        };
+        self.selected = self.items().first().copied();
+        self.scroll_offset = 0;
+    }
+
+    fn apply_to_selection(&mut self, action: HunkAction) {
+        let unavailable = match (action, self.view) {
+            (HunkAction::Stage, DiffView::Staged) => {
+                Some("Already staged. Press tab for unstaged changes.")
+            }
+            (HunkAction::Revert, DiffView::Staged) => {
+                Some("Unstage changes before discarding them.")
+            }
+            (HunkAction::Unstage, DiffView::Unstaged) => {
+                Some("Not staged. Press tab for staged changes.")
+            }
+            _ => None,
+        };
+        if let Some(message) = unavailable {
+            self.status = Some(message.dim().into());
+            return;
+        }
+        let Some(item) = self.selected else {
+            return;
+        };
+        let Some(file) = self.files().get(item.file()) else {
+            return;
+        };
+        if self.busy {
+            return;
+        }
+        let target = match item {
+            Item::File(_) => file.path.display().to_string(),
+            Item::Hunk(..) => "this hunk".to_string(),
+        };
+        let patch = file.patch(item.hunk());
+        if action == HunkAction::Revert && self.pending_discard != Some(item) {
+            self.pending_discard = Some(item);
+            self.status = Some(
+                Line::from(format!(
+                    "Discard {target}? Press d again to confirm; this cannot be undone."
+                ))
+                .red(),
+            );
+            return;
+        }
+
+        let verb = match action {
+            HunkAction::Stage => "Staging",
+            HunkAction::Unstage => "Unstaging",
+            HunkAction::Revert => "Discarding",
+        };
+        self.pending_discard = None;
+        self.busy = true;
+        self.status = Some(format!("{verb} {target}…").dim().into());
+        self.app_event_tx
+            .send(AppEvent::ApplyDiffHunk { patch, action });
+    }
+
+    fn open_selected(&mut self) {
+        let Some(item) = self.selected else {
+            return;
+        };
+        let Some(file) = self.files().get(item.file()) else {
+            return;
+        };
+        let Some(scheme) = self.file_opener.get_scheme() else {
+            self.status = Some(
+                "Set `file_opener` in config.toml to open files."
+                    .dim()
+                    .into(),
+            );
+            return;
+        };
+        let line = item
+            .hunk()
+            .and_then(|hunk| file.hunks.get(hunk))
+            .and_then(|hunk| hunk_new_start(hunk))
+            .unwrap_or(1)
+            .max(1);
+        let uri = file_uri(scheme, &self.repo_root.join(&file.path), line);
+        let display_path = file.path.display().to_string();
+        self.status = Some(match webbrowser::open(&uri) {
+            Ok(()) => format!("Opened {display_path}").dim().into(),
+            Err(err) => Line::from(format!("Failed to open {display_path}: {err}")).red(),
+        });
+    }
+
+    pub(crate) fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+        // Header, tabs, separator, status and two hint rows.
+        if area.height < 6 {
+            return;
+        }
+        let row = |offset: u16| Rect::new(area.x, area.y + offset, area.width, 1);
+
+        Span::from("/ ".repeat(area.width as usize / 2))
+            .dim()
+            .render_ref(row(0), buf);
+        "/ D I F F".dim().render_ref(row(0), buf);
+        self.render_tabs(row(1), buf);
+
+        let content = Rect::new(area.x, area.y + 2, area.width, area.height - 6);
+        self.render_content(content, buf);
+
+        let bottom = content.bottom() - area.y;
+        Span::from("─".repeat(area.width as usize))
+            .dim()
+            .render_ref(row(bottom), buf);
+        if let Some(status) = &self.status {
+            let mut status = status.clone();
+            status.spans.insert(0, " ".into());
+            status.render_ref(row(bottom + 1), buf);
+        }
+        render_key_hints(row(bottom + 2), buf, NAVIGATION_HINTS);
+        let action_hints = match self.view {
+            DiffView::Unstaged => UNSTAGED_HINTS,
+            DiffView::Staged => STAGED_HINTS,
+        };
+        render_key_hints(row(bottom + 3), buf, action_hints);
+    }
+
+    fn render_tabs(&self, area: Rect, buf: &mut Buffer) {
+        let tab = |label: &str, count: usize, active: bool| {
+            let text = format!("{label} ({count})");
+            if active {
+                text.cyan().bold()
+            } else {
+                text.dim()
+            }
+        };
+        Line::from(vec![
+            " ".into(),
+            tab(
+                "Unstaged",
+                self.unstaged.len(),
+                self.view == DiffView::Unstaged,
+            ),
+            "   ".into(),
+            tab("Staged", self.staged.len(), self.view == DiffView::Staged),
+        ])
+        .render_ref(area, buf);
+    }
+
+    fn render_content(&mut self, area: Rect, buf: &mut Buffer) {
+        let (lines, item_rows) = self.layout(area.width as usize);
+        let height = area.height as usize;
+
+        // Keep the selected item on screen, showing as much of it as fits.
+        if let Some(selected) = self.selected
+            && let Some(position) = item_rows.iter().position(|(item, _)| *item == selected)
+        {
+            let start = item_rows[position].1;
+            let end = item_rows
+                .get(position + 1)
+                .map(|(_, row)| *row)
+                .unwrap_or(lines.len());
+            if start < self.scroll_offset {
+                self.scroll_offset = start;
+            } else if end > self.scroll_offset + height {
+                self.scroll_offset = start.min(end.saturating_sub(height));
+            }
+        }
+        self.scroll_offset = self.scroll_offset.min(lines.len().saturating_sub(height));
+        self.item_rows = item_rows;
+        self.page_height = height;
+
+        let visible: Vec<Line<'static>> = lines
+            .into_iter()
+            .skip(self.scroll_offset)
+            .take(height)
+            .collect();
+        Paragraph::new(visible).render(area, buf);
+    }
+
+    /// Renders the file tree for `width` columns, returning the lines and the
+    /// row at which each selectable item starts.
+    fn layout(&self, width: usize) -> (Vec<Line<'static>>, Vec<(Item, usize)>) {
+        let mut lines: Vec<Line<'static>> = Vec::new();
+        let mut item_rows = Vec::new();
+        if self.files().is_empty() {
+            let message = match self.view {
+                DiffView::Unstaged => "  No unstaged changes.",
+                DiffView::Staged => "  No staged changes.",
+            };
+            lines.push(message.dim().into());
+            return (lines, item_rows);
+        }
+
+        let cursor = |selected: bool| -> Span<'static> {
+            if selected { "› ".cyan() } else { "  ".into() }
+        };
+        let mut previous_dirs: Vec<String> = Vec::new();
+        for (index, file) in self.files().iter().enumerate() {
+            let dirs: Vec<String> = file
+                .path
+                .parent()
+                .map(|parent| {
+                    parent
+                        .components()
+                        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+                        .collect()
+                })
+                .unwrap_or_default();
+            let shared = previous_dirs
+                .iter()
+                .zip(&dirs)
+                .take_while(|(a, b)| a == b)
+                .count();
+            for (depth, dir) in dirs.iter().enumerate().skip(shared) {
+                lines.push(Line::from(format!("  {}{dir}/", "  ".repeat(depth))).dim());
+            }
+            let depth = dirs.len();
+            previous_dirs = dirs;
+
+            let selected = self.selected == Some(Item::File(index));
+            let expanded = self.expanded.contains(&file.path);
+            let name = file
+                .path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_else(|| file.path.display().to_string());
+            let mut spans = vec![
+                cursor(selected),
+                "  ".repeat(depth).into(),
+                if expanded { "▾ " } else { "▸ " }.dim(),
+                if selected {
+                    name.cyan().bold()
+                } else {
+                    name.into()
+                },
+                " ".into(),
+            ];
+            match &file.note {
+                Some(note) => spans.push(format!("({note})").dim()),
+                None => spans.extend(diff_render::render_line_count_summary(
+                    file.added,
+                    file.removed,
+                )),
+            }
+            item_rows.push((Item::File(index), lines.len()));
+            lines.push(Line::from(spans));
+            if !expanded {
+                continue;
+            }
+
+            // Hunks sit one level below their file, past the cursor column.
+            let indent = " ".repeat(2 + (depth + 1) * 2);
+            for (hunk_index, hunk) in file.hunks.iter().enumerate() {
+                let selected = self.selected == Some(Item::Hunk(index, hunk_index));
+                let header = hunk.lines().next().unwrap_or_default().to_string();
+                item_rows.push((Item::Hunk(index, hunk_index), lines.len()));
+                lines.push(Line::from(vec![
+                    cursor(selected),
+                    indent[2..].to_string().into(),
+                    if selected {
+                        header.cyan()
+                    } else {
+                        header.dim()
+                    },
+                ]));
+
+                let change = FileChange::Update {
+                    unified_diff: format!("{}{hunk}", file.file_header),
+                    move_path: None,
+                };
+                let mut rendered = Vec::new();
+                diff_render::render_change(
+                    &change,
+                    &mut rendered,
+                    width.saturating_sub(indent.len()),
+                );
+                lines.extend(prefix_lines(
+                    rendered,
+                    indent.clone().into(),
+                    indent.clone().into(),
+                ));
+            }
+        }
+        (lines, item_rows)
+    }
+}
+
+/// Describes a file change that has no hunks from its `git diff` header.
+fn header_only_note(header: &str) -> String {
+    let mut lines = header.lines();
+    if lines
+        .clone()
+        .any(|line| line == "GIT binary patch" || line.starts_with("Binary files "))
+    {
+        return "binary".to_string();
+    }
+    if let Some(source) = lines
+        .clone()
+        .find_map(|line| line.strip_prefix("rename from "))
+    {
+        return format!("renamed from {source}");
+    }
+    if lines.any(|line| line.starts_with("new mode ")) {
+        return "mode changed".to_string();
+    }
+    "no textual changes".to_string()
+}
+
+/// The first line of a hunk in the new file, from its `@@ -a,b +c,d @@` header.
+fn hunk_new_start(hunk: &str) -> Option<usize> {
+    let header = hunk.lines().next()?;
+    let new_range = header
+        .split_whitespace()
+        .find(|part| part.starts_with('+'))?;
+    new_range[1..].split(',').next()?.parse().ok()
+}
+
+/// A URI such as `vscode://file/path/to/file.rs:12` for a URI-based opener.
+fn file_uri(scheme: &str, path: &Path, line: usize) -> String {
+    let path = path.to_string_lossy().replace('\\', "/");
+    let separator = if path.starts_with('/') { "" } else { "/" };
+    format!("{scheme}://file{separator}{path}:{line}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+    use pretty_assertions::assert_eq;
+    use tokio::sync::mpsc::UnboundedReceiver;
+    use tokio::sync::mpsc::unbounded_channel;
+
+    const APP_DIFF: &str = "\
+diff --git a/src/app.rs b/src/app.rs
+index 1111111..2222222 100644
+--- a/src/app.rs
++++ b/src/app.rs
+@@ -1,3 +1,3 @@
+ fn main() {
+-    old();
++    new();
+ }
+@@ -20,3 +20,3 @@ fn helper() {
+ fn helper() {
+-    1
++    2
+ }
+";
+    const README_DIFF: &str = "\
+diff --git a/README.md b/README.md
+new file mode 100644
+index 0000000..3333333
+--- /dev/null
++++ b/README.md
+@@ -0,0 +1 @@
++# Demo
+";
+
+    fn browser(unstaged: &str, staged: &str) -> (DiffBrowserOverlay, UnboundedReceiver<AppEvent>) {
+        let (tx, rx) = unbounded_channel();
+        let diffs = GitDiffs {
+            repo_root: PathBuf::from("/repo"),
+            unstaged: unstaged.to_string(),
+            staged: staged.to_string(),
+        };
+        let browser =
+            DiffBrowserOverlay::new(diffs, UriBasedFileOpener::None, AppEventSender::new(tx));
+        (browser, rx)
+    }
+
+    fn press(browser: &mut DiffBrowserOverlay, code: KeyCode) {
+        browser.handle_key_event(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    fn layout_text(browser: &DiffBrowserOverlay) -> Vec<String> {
+        let (lines, _) = browser.layout(80);
+        lines
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect::<String>()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn collapsed_files_are_listed_as_a_tree() {
+        let (mut browser, _rx) = browser(&format!("{APP_DIFF}{README_DIFF}"), "");
+        browser.expanded.clear();
+
+        assert_eq!(
+            layout_text(&browser),
+            vec!["› ▸ README.md (+1 -0)", "  src/", "    ▸ app.rs (+2 -2)",]
+        );
+    }
+
+    #[test]
+    fn staging_a_hunk_sends_a_single_hunk_patch() {
+        let (mut browser, mut rx) = browser(APP_DIFF, "");
+        press(&mut browser, KeyCode::Char('n'));
+        press(&mut browser, KeyCode::Char('n'));
+        assert_eq!(browser.selected, Some(Item::Hunk(0, 1)));
+
+        press(&mut browser, KeyCode::Char('s'));
+        match rx.try_recv() {
+            Ok(AppEvent::ApplyDiffHunk { patch, action }) => {
+                assert_eq!(action, HunkAction::Stage);
+                assert_eq!(
+                    patch,
+                    "diff --git a/src/app.rs b/src/app.rs\n\
+                     index 1111111..2222222 100644\n\
+                     --- a/src/app.rs\n\
+                     +++ b/src/app.rs\n\
+                     @@ -20,3 +20,3 @@ fn helper() {\n fn helper() {\n-    1\n+    2\n }\n"
+                );
+            }
+            other => panic!("expected ApplyDiffHunk, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn discarding_requires_confirmation() {
+        let (mut browser, mut rx) = browser(README_DIFF, "");
+        press(&mut browser, KeyCode::Char('d'));
+        assert!(rx.try_recv().is_err());
+        assert!(browser.status.is_some());
+
+        press(&mut browser, KeyCode::Char('d'));
+        match rx.try_recv() {
+            Ok(AppEvent::ApplyDiffHunk { action, .. }) => assert_eq!(action, HunkAction::Revert),
+            other => panic!("expected ApplyDiffHunk, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn update_keeps_selection_on_the_same_file() {
+        let (mut browser, _rx) = browser(&format!("{APP_DIFF}{README_DIFF}"), "");
+        press(&mut browser, KeyCode::Char(']'));
+        assert_eq!(browser.selected, Some(Item::File(1)));
+
+        // README.md was staged, so src/app.rs is now the first file.
+        browser.update(Ok(GitDiffs {
+            repo_root: PathBuf::from("/repo"),
+            unstaged: APP_DIFF.to_string(),
+            staged: README_DIFF.to_string(),
+        }));
+        assert_eq!(browser.selected, Some(Item::File(0)));
+        assert_eq!(browser.files()[0].path, PathBuf::from("src/app.rs"));
+    }
+
+    #[test]
+    fn files_without_hunks_are_listed_and_staged_whole() {
+        let diff = "\
+diff --git a/logo.png b/logo.png
+index 4444444..5555555 100644
+GIT binary patch
+literal 3
+KcmZ?wbN>JX0RRAY
+
+diff --git a/run.sh b/run.sh
+old mode 100644
+new mode 100755
+";
+        let (mut browser, mut rx) = browser(diff, "");
+        assert_eq!(
+            layout_text(&browser),
+            vec!["› ▾ logo.png (binary)", "  ▾ run.sh (mode changed)"]
+        );
+
+        press(&mut browser, KeyCode::Char('s'));
+        match rx.try_recv() {
+            Ok(AppEvent::ApplyDiffHunk { patch, action }) => {
+                assert_eq!(action, HunkAction::Stage);
+                assert_eq!(
+                    patch,
+                    "diff --git a/logo.png b/logo.png\n\
+                     index 4444444..5555555 100644\n\
+                     GIT binary patch\n\
+                     literal 3\n\
+                     KcmZ?wbN>JX0RRAY\n\n"
+                );
+            }
+            other => panic!("expected ApplyDiffHunk, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn hunk_new_start_reads_the_new_range() {
+        assert_eq!(
+            hunk_new_start("@@ -20,3 +21,4 @@ fn helper() {\n"),
+            Some(21)
+        );
+        assert_eq!(hunk_new_start("@@ -0,0 +1 @@\n"), Some(1));
+        assert_eq!(
+            file_uri("vscode", Path::new("/repo/src/app.rs"), 21),
+            "vscode://file/repo/src/app.rs:21"
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use crossterm::event::KeyCode;
 use diffy::Hunk;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
@@ -11,8 +12,13 @@ use ratatui::widgets::Paragraph;
 use std::collections::HashMap;
 use std::path::Path;
 use std::path::PathBuf;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use unicode_width::UnicodeWidthStr;
 
 use crate::exec_command::relativize_to_home;
+use crate::key_hint;
+use crate::key_hint::KeyBinding;
 use crate::render::Insets;
 use crate::render::line_utils::prefix_lines;
 use crate::render::renderable::ColumnRenderable;
@@ -21,13 +27,91 @@ use crate::render::renderable::Renderable;
 use codex_core::git_info::get_git_repo_root;
 use codex_core::protocol::FileChange;
 
+/// Updates are shown side by side when at least this many columns are
+/// available (and the layout has not been toggled off).
+const SIDE_BY_SIDE_MIN_WIDTH: usize = 120;
+/// Lines with more tokens than this are not compared word by word.
+const MAX_WORD_DIFF_TOKENS: usize = 256;
+
+/// Key that flips the side-by-side layout in every view that renders diffs.
+pub(crate) const SIDE_BY_SIDE_KEY: KeyBinding = key_hint::plain(KeyCode::Char('v'));
+
+static SIDE_BY_SIDE_ENABLED: AtomicBool = AtomicBool::new(true);
+static CHANGE_MARKERS_ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Flips whether wide terminals use the side-by-side layout and returns the
+/// new setting. Shared by `/diff`, the patch approval overlay and the patch
+/// preview.
+pub(crate) fn toggle_side_by_side() -> bool {
+    !SIDE_BY_SIDE_ENABLED.fetch_xor(true, Ordering::Relaxed)
+}
+
+/// Surrounds changed words with `[-…-]` and `{+…+}` markers. Enabled when the
+/// terminal has no color support, where the highlight alone is invisible.
+pub(crate) fn set_change_markers(enabled: bool) {
+    CHANGE_MARKERS_ENABLED.store(enabled, Ordering::Relaxed);
+}
+
 // Internal representation for diff line rendering
+#[derive(Clone, Copy)]
 enum DiffLineType {
     Insert,
     Delete,
     Context,
 }
 
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct DiffRenderOptions {
+    side_by_side: bool,
+    change_markers: bool,
+}
+
+impl DiffRenderOptions {
+    fn current() -> Self {
+        Self {
+            side_by_side: SIDE_BY_SIDE_ENABLED.load(Ordering::Relaxed),
+            change_markers: CHANGE_MARKERS_ENABLED.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// A run of text within a diff line; `emphasized` runs are the words that
+/// differ from the paired line on the other side.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct DiffSegment {
+    text: String,
+    emphasized: bool,
+}
+
+impl DiffSegment {
+    fn plain(text: &str) -> Self {
+        Self {
+            text: text.to_string(),
+            emphasized: false,
+        }
+    }
+}
+
+/// One line of a hunk, numbered in the file it belongs to.
+struct NumberedLine {
+    line_number: usize,
+    segments: Vec<DiffSegment>,
+}
+
+enum HunkRow {
+    Context {
+        old_line_number: usize,
+        new_line_number: usize,
+        text: String,
+    },
+    /// A run of deleted lines followed by the inserted lines replacing them;
+    /// `deleted[i]` is paired with `inserted[i]`.
+    Changes {
+        deleted: Vec<NumberedLine>,
+        inserted: Vec<NumberedLine>,
+    },
+}
+
 pub struct DiffSummary {
     changes: HashMap<PathBuf, FileChange>,
     cwd: PathBuf,
@@ -123,7 +207,7 @@ fn collect_rows(changes: &HashMap<PathBuf, FileChange>) -> Vec<Row> {
     rows
 }
 
-fn render_line_count_summary(added: usize, removed: usize) -> Vec<RtSpan<'static>> {
+pub(crate) fn render_line_count_summary(added: usize, removed: usize) -> Vec<RtSpan<'static>> {
     let mut spans = Vec::new();
     spans.push("(".into());
     spans.push(format!("+{added}").green());
@@ -193,7 +277,16 @@ fn render_changes_block(rows: Vec<Row>, wrap_cols: usize, cwd: &Path) -> Vec<RtL
     out
 }
 
-fn render_change(change: &FileChange, out: &mut Vec<RtLine<'static>>, width: usize) {
+pub(crate) fn render_change(change: &FileChange, out: &mut Vec<RtLine<'static>>, width: usize) {
+    render_change_with_options(change, out, width, DiffRenderOptions::current());
+}
+
+fn render_change_with_options(
+    change: &FileChange,
+    out: &mut Vec<RtLine<'static>>,
+    width: usize,
+    options: DiffRenderOptions,
+) {
     match change {
         FileChange::Add { content } => {
             let line_number_width = line_number_width(content.lines().count());
@@ -244,6 +337,7 @@ fn render_change(change: &FileChange, out: &mut Vec<RtLine<'static>>, width: usi
                     }
                 }
                 let line_number_width = line_number_width(max_line_number);
+                let side_by_side = options.side_by_side && width >= SIDE_BY_SIDE_MIN_WIDTH;
                 let mut is_first_hunk = true;
                 for h in patch.hunks() {
                     if !is_first_hunk {
@@ -253,45 +347,11 @@ fn render_change(change: &FileChange, out: &mut Vec<RtLine<'static>>, width: usi
                     }
                     is_first_hunk = false;
 
-                    let mut old_ln = h.old_range().start();
-                    let mut new_ln = h.new_range().start();
-                    for l in h.lines() {
-                        match l {
-                            diffy::Line::Insert(text) => {
-                                let s = text.trim_end_matches('\n');
-                                out.extend(push_wrapped_diff_line(
-                                    new_ln,
-                                    DiffLineType::Insert,
-                                    s,
-                                    width,
-                                    line_number_width,
-                                ));
-                                new_ln += 1;
-                            }
-                            diffy::Line::Delete(text) => {
-                                let s = text.trim_end_matches('\n');
-                                out.extend(push_wrapped_diff_line(
-                                    old_ln,
-                                    DiffLineType::Delete,
-                                    s,
-                                    width,
-                                    line_number_width,
-                                ));
-                                old_ln += 1;
-                            }
-                            diffy::Line::Context(text) => {
-                                let s = text.trim_end_matches('\n');
-                                out.extend(push_wrapped_diff_line(
-                                    new_ln,
-                                    DiffLineType::Context,
-                                    s,
-                                    width,
-                                    line_number_width,
-                                ));
-                                old_ln += 1;
-                                new_ln += 1;
-                            }
-                        }
+                    let rows = hunk_rows(h, options.change_markers);
+                    if side_by_side {
+                        render_side_by_side_rows(&rows, out, width, line_number_width);
+                    } else {
+                        render_unified_rows(&rows, out, width, line_number_width);
                     }
                 }
             }
@@ -299,6 +359,335 @@ fn render_change(change: &FileChange, out: &mut Vec<RtLine<'static>>, width: usi
     }
 }
 
+/// Groups a hunk into context lines and runs of changes, pairing each deleted
+/// line with the inserted line at the same position so their differing words
+/// can be emphasized.
+fn hunk_rows(hunk: &Hunk<'_, str>, change_markers: bool) -> Vec<HunkRow> {
+    let mut rows = Vec::new();
+    let mut deleted: Vec<(usize, &str)> = Vec::new();
+    let mut inserted: Vec<(usize, &str)> = Vec::new();
+    let mut old_ln = hunk.old_range().start();
+    let mut new_ln = hunk.new_range().start();
+
+    let flush = |deleted: &mut Vec<(usize, &str)>,
+                 inserted: &mut Vec<(usize, &str)>,
+                 rows: &mut Vec<HunkRow>| {
+        if deleted.is_empty() && inserted.is_empty() {
+            return;
+        }
+        let mut deleted_lines: Vec<NumberedLine> = Vec::new();
+        let mut inserted_lines: Vec<NumberedLine> = Vec::new();
+        for i in 0..deleted.len().max(inserted.len()) {
+            let old = deleted.get(i).copied();
+            let new = inserted.get(i).copied();
+            let (old_segments, new_segments) = match (old, new) {
+                (Some((_, old_text)), Some((_, new_text))) => match word_diff(old_text, new_text) {
+                    Some((old_segments, new_segments)) => (old_segments, new_segments),
+                    None => (
+                        vec![DiffSegment::plain(old_text)],
+                        vec![DiffSegment::plain(new_text)],
+                    ),
+                },
+                (Some((_, old_text)), None) => (vec![DiffSegment::plain(old_text)], Vec::new()),
+                (None, Some((_, new_text))) => (Vec::new(), vec![DiffSegment::plain(new_text)]),
+                (None, None) => (Vec::new(), Vec::new()),
+            };
+            if let Some((line_number, _)) = old {
+                deleted_lines.push(NumberedLine {
+                    line_number,
+                    segments: with_change_markers(old_segments, "[-", "-]", change_markers),
+                });
+            }
+            if let Some((line_number, _)) = new {
+                inserted_lines.push(NumberedLine {
+                    line_number,
+                    segments: with_change_markers(new_segments, "{+", "+}", change_markers),
+                });
+            }
+        }
+        deleted.clear();
+        inserted.clear();
+        rows.push(HunkRow::Changes {
+            deleted: deleted_lines,
+            inserted: inserted_lines,
+        });
+    };
+
+    for line in hunk.lines() {
+        match line {
+            diffy::Line::Delete(text) => {
+                // A deletion after insertions starts a new run of changes.
+                if !inserted.is_empty() {
+                    flush(&mut deleted, &mut inserted, &mut rows);
+                }
+                deleted.push((old_ln, text.trim_end_matches('\n')));
+                old_ln += 1;
+            }
+            diffy::Line::Insert(text) => {
+                inserted.push((new_ln, text.trim_end_matches('\n')));
+                new_ln += 1;
+            }
+            diffy::Line::Context(text) => {
+                flush(&mut deleted, &mut inserted, &mut rows);
+                rows.push(HunkRow::Context {
+                    old_line_number: old_ln,
+                    new_line_number: new_ln,
+                    text: text.trim_end_matches('\n').to_string(),
+                });
+                old_ln += 1;
+                new_ln += 1;
+            }
+        }
+    }
+    flush(&mut deleted, &mut inserted, &mut rows);
+    rows
+}
+
+fn render_unified_rows(
+    rows: &[HunkRow],
+    out: &mut Vec<RtLine<'static>>,
+    width: usize,
+    line_number_width: usize,
+) {
+    for row in rows {
+        match row {
+            HunkRow::Context {
+                new_line_number,
+                text,
+                ..
+            } => out.extend(push_wrapped_diff_line(
+                *new_line_number,
+                DiffLineType::Context,
+                text,
+                width,
+                line_number_width,
+            )),
+            HunkRow::Changes { deleted, inserted } => {
+                for line in deleted {
+                    out.extend(push_wrapped_diff_segments(
+                        line.line_number,
+                        DiffLineType::Delete,
+                        &line.segments,
+                        width,
+                        line_number_width,
+                    ));
+                }
+                for line in inserted {
+                    out.extend(push_wrapped_diff_segments(
+                        line.line_number,
+                        DiffLineType::Insert,
+                        &line.segments,
+                        width,
+                        line_number_width,
+                    ));
+                }
+            }
+        }
+    }
+}
+
+/// Renders old lines on the left and new lines on the right, each side
+/// wrapped within its own column.
+fn render_side_by_side_rows(
+    rows: &[HunkRow],
+    out: &mut Vec<RtLine<'static>>,
+    width: usize,
+    line_number_width: usize,
+) {
+    let left_width = width.saturating_sub(SIDE_BY_SIDE_SEPARATOR.len()) / 2;
+    let right_width = width.saturating_sub(SIDE_BY_SIDE_SEPARATOR.len() + left_width);
+    let side = |line: Option<&NumberedLine>, kind: DiffLineType, side_width: usize| {
+        line.map(|line| {
+            push_wrapped_diff_segments(
+                line.line_number,
+                kind,
+                &line.segments,
+                side_width,
+                line_number_width,
+            )
+        })
+        .unwrap_or_default()
+    };
+
+    for row in rows {
+        match row {
+            HunkRow::Context {
+                old_line_number,
+                new_line_number,
+                text,
+            } => {
+                let left = push_wrapped_diff_line(
+                    *old_line_number,
+                    DiffLineType::Context,
+                    text,
+                    left_width,
+                    line_number_width,
+                );
+                let right = push_wrapped_diff_line(
+                    *new_line_number,
+                    DiffLineType::Context,
+                    text,
+                    right_width,
+                    line_number_width,
+                );
+                join_columns(out, left, right, left_width);
+            }
+            HunkRow::Changes { deleted, inserted } => {
+                for i in 0..deleted.len().max(inserted.len()) {
+                    let left = side(deleted.get(i), DiffLineType::Delete, left_width);
+                    let right = side(inserted.get(i), DiffLineType::Insert, right_width);
+                    join_columns(out, left, right, left_width);
+                }
+            }
+        }
+    }
+}
+
+const SIDE_BY_SIDE_SEPARATOR: &str = " │ ";
+
+fn join_columns(
+    out: &mut Vec<RtLine<'static>>,
+    left: Vec<RtLine<'static>>,
+    right: Vec<RtLine<'static>>,
+    left_width: usize,
+) {
+    let rows = left.len().max(right.len());
+    let mut left = left.into_iter();
+    let mut right = right.into_iter();
+    for _ in 0..rows {
+        let mut spans = left.next().map(|line| line.spans).unwrap_or_default();
+        let used: usize = spans.iter().map(|span| span.content.width()).sum();
+        spans.push(" ".repeat(left_width.saturating_sub(used)).into());
+        spans.push(SIDE_BY_SIDE_SEPARATOR.dim());
+        if let Some(line) = right.next() {
+            spans.extend(line.spans);
+        }
+        out.push(RtLine::from(spans));
+    }
+}
+
+/// Splits a line into words, runs of whitespace and single punctuation
+/// characters.
+fn tokenize(line: &str) -> Vec<&str> {
+    #[derive(PartialEq)]
+    enum Class {
+        Word,
+        Space,
+        Other,
+    }
+    let class_of = |c: char| {
+        if c.is_alphanumeric() || c == '_' {
+            Class::Word
+        } else if c.is_whitespace() {
+            Class::Space
+        } else {
+            Class::Other
+        }
+    };
+
+    let mut tokens = Vec::new();
+    let mut start = 0;
+    let mut previous: Option<Class> = None;
+    for (i, c) in line.char_indices() {
+        let class = class_of(c);
+        if let Some(previous) = &previous
+            && (*previous != class || class == Class::Other)
+        {
+            tokens.push(&line[start..i]);
+            start = i;
+        }
+        previous = Some(class);
+    }
+    if start < line.len() {
+        tokens.push(&line[start..]);
+    }
+    tokens
+}
+
+/// Compares two lines token by token and marks the tokens that differ.
+/// Returns `None` when the lines are too long to compare or share too little
+/// for word emphasis to help.
+fn word_diff(old: &str, new: &str) -> Option<(Vec<DiffSegment>, Vec<DiffSegment>)> {
+    let old_tokens = tokenize(old);
+    let new_tokens = tokenize(new);
+    if old_tokens.len() > MAX_WORD_DIFF_TOKENS || new_tokens.len() > MAX_WORD_DIFF_TOKENS {
+        return None;
+    }
+
+    // lcs[i][j] is the longest common subsequence of old[i..] and new[j..].
+    let (n, m) = (old_tokens.len(), new_tokens.len());
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if old_tokens[i] == new_tokens[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut old_segments = Vec::new();
+    let mut new_segments = Vec::new();
+    let mut unchanged_bytes = 0;
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && old_tokens[i] == new_tokens[j] {
+            push_token(&mut old_segments, old_tokens[i], false);
+            push_token(&mut new_segments, new_tokens[j], false);
+            unchanged_bytes += old_tokens[i].len();
+            i += 1;
+            j += 1;
+        } else if j < m && (i == n || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            push_token(&mut new_segments, new_tokens[j], true);
+            j += 1;
+        } else {
+            push_token(&mut old_segments, old_tokens[i], true);
+            i += 1;
+        }
+    }
+
+    // Emphasizing nearly every word of a rewritten line is just noise.
+    if unchanged_bytes * 3 < old.len().max(new.len()) {
+        return None;
+    }
+    Some((old_segments, new_segments))
+}
+
+fn push_token(segments: &mut Vec<DiffSegment>, token: &str, emphasized: bool) {
+    match segments.last_mut() {
+        Some(last) if last.emphasized == emphasized => last.text.push_str(token),
+        _ => segments.push(DiffSegment {
+            text: token.to_string(),
+            emphasized,
+        }),
+    }
+}
+
+fn with_change_markers(
+    segments: Vec<DiffSegment>,
+    open: &str,
+    close: &str,
+    enabled: bool,
+) -> Vec<DiffSegment> {
+    if !enabled {
+        return segments;
+    }
+    segments
+        .into_iter()
+        .map(|segment| {
+            if segment.emphasized {
+                DiffSegment {
+                    text: format!("{open}{}{close}", segment.text),
+                    emphasized: true,
+                }
+            } else {
+                segment
+            }
+        })
+        .collect()
+}
+
 pub(crate) fn display_path_for(path: &Path, cwd: &Path) -> String {
     let path_in_same_repo = match (get_git_repo_root(cwd), get_git_repo_root(path)) {
         (Some(cwd_repo), Some(path_repo)) => cwd_repo == path_repo,
@@ -314,7 +703,7 @@ pub(crate) fn display_path_for(path: &Path, cwd: &Path) -> String {
     chosen.display().to_string()
 }
 
-fn calculate_add_remove_from_diff(diff: &str) -> (usize, usize) {
+pub(crate) fn calculate_add_remove_from_diff(diff: &str) -> (usize, usize) {
     if let Ok(patch) = diffy::Patch::from_str(diff) {
         patch
             .hunks()
@@ -337,59 +726,90 @@ fn push_wrapped_diff_line(
     text: &str,
     width: usize,
     line_number_width: usize,
+) -> Vec<RtLine<'static>> {
+    push_wrapped_diff_segments(
+        line_number,
+        kind,
+        &[DiffSegment::plain(text)],
+        width,
+        line_number_width,
+    )
+}
+
+fn push_wrapped_diff_segments(
+    line_number: usize,
+    kind: DiffLineType,
+    segments: &[DiffSegment],
+    width: usize,
+    line_number_width: usize,
 ) -> Vec<RtLine<'static>> {
     let ln_str = line_number.to_string();
-    let mut remaining_text: &str = text;
 
     // Reserve a fixed number of spaces (equal to the widest line number plus a
     // trailing spacer) so the sign column stays aligned across the diff block.
     let gutter_width = line_number_width.max(1);
     let prefix_cols = gutter_width + 1;
 
-    let mut first = true;
     let (sign_char, line_style) = match kind {
         DiffLineType::Insert => ('+', style_add()),
         DiffLineType::Delete => ('-', style_del()),
         DiffLineType::Context => (' ', style_context()),
     };
-    let mut lines: Vec<RtLine<'static>> = Vec::new();
-
-    loop {
-        // Fit the content for the current terminal row:
-        // compute how many columns are available after the prefix, then split
-        // at a UTF-8 character boundary so this row's chunk fits exactly.
-        let available_content_cols = width.saturating_sub(prefix_cols + 1).max(1);
-        let split_at_byte_index = remaining_text
-            .char_indices()
-            .nth(available_content_cols)
-            .map(|(i, _)| i)
-            .unwrap_or_else(|| remaining_text.len());
-        let (chunk, rest) = remaining_text.split_at(split_at_byte_index);
-        remaining_text = rest;
-
-        if first {
-            // Build gutter (right-aligned line number plus spacer) as a dimmed span
-            let gutter = format!("{ln_str:>gutter_width$} ");
-            // Content with a sign ('+'/'-'/' ') styled per diff kind
-            let content = format!("{sign_char}{chunk}");
-            lines.push(RtLine::from(vec![
-                RtSpan::styled(gutter, style_gutter()),
-                RtSpan::styled(content, line_style),
-            ]));
-            first = false;
+    let emphasis_style = line_style.add_modifier(Modifier::REVERSED);
+
+    // Fit the content for each terminal row: compute how many columns are
+    // available after the prefix, then split segments at UTF-8 character
+    // boundaries so each row's chunks fit exactly.
+    let available_content_cols = width.saturating_sub(prefix_cols + 1).max(1);
+    let mut rows: Vec<Vec<RtSpan<'static>>> = vec![Vec::new()];
+    let mut used_cols = 0;
+    for segment in segments {
+        let style = if segment.emphasized {
+            emphasis_style
         } else {
-            // Continuation lines keep a space for the sign column so content aligns
-            let gutter = format!("{:gutter_width$}  ", "");
-            lines.push(RtLine::from(vec![
-                RtSpan::styled(gutter, style_gutter()),
-                RtSpan::styled(chunk.to_string(), line_style),
-            ]));
-        }
-        if remaining_text.is_empty() {
-            break;
+            line_style
+        };
+        let mut remaining_text = segment.text.as_str();
+        while !remaining_text.is_empty() {
+            if used_cols == available_content_cols {
+                rows.push(Vec::new());
+                used_cols = 0;
+            }
+            let split_at_byte_index = remaining_text
+                .char_indices()
+                .nth(available_content_cols - used_cols)
+                .map(|(i, _)| i)
+                .unwrap_or_else(|| remaining_text.len());
+            let (chunk, rest) = remaining_text.split_at(split_at_byte_index);
+            used_cols += chunk.chars().count();
+            remaining_text = rest;
+            if let Some(row) = rows.last_mut() {
+                row.push(RtSpan::styled(chunk.to_string(), style));
+            }
         }
     }
-    lines
+
+    rows.into_iter()
+        .enumerate()
+        .map(|(i, chunks)| {
+            let mut spans = if i == 0 {
+                // Gutter (right-aligned line number plus spacer) and the sign
+                // ('+'/'-'/' ') styled per diff kind.
+                vec![
+                    RtSpan::styled(format!("{ln_str:>gutter_width$} "), style_gutter()),
+                    RtSpan::styled(sign_char.to_string(), line_style),
+                ]
+            } else {
+                // Continuation lines keep a space for the sign column so content aligns
+                vec![RtSpan::styled(
+                    format!("{:gutter_width$}  ", ""),
+                    style_gutter(),
+                )]
+            };
+            spans.extend(chunks);
+            RtLine::from(spans)
+        })
+        .collect()
 }
 
 fn line_number_width(max_line_number: usize) -> usize {
@@ -670,4 +1090,72 @@ mod tests {
 
         snapshot_lines("apply_update_block_relativizes_path", lines, 80, 10);
     }
+
+    #[test]
+    fn word_diff_emphasizes_changed_words() {
+        let (old, new) = word_diff("    let x = 1;", "    let y = 1;").expect("word diff");
+        assert_eq!(
+            old,
+            vec![
+                DiffSegment::plain("    let "),
+                DiffSegment {
+                    text: "x".to_string(),
+                    emphasized: true,
+                },
+                DiffSegment::plain(" = 1;"),
+            ]
+        );
+        assert_eq!(new[1].text, "y");
+        assert!(new[1].emphasized);
+    }
+
+    #[test]
+    fn word_diff_skips_rewritten_lines() {
+        assert_eq!(word_diff("fn main() {}", "struct Config;"), None);
+    }
+
+    #[test]
+    fn unified_update_marks_changed_words_without_color() {
+        let patch = diffy::create_patch("let x = 1;\n", "let y = 1;\n").to_string();
+        let change = FileChange::Update {
+            unified_diff: patch,
+            move_path: None,
+        };
+        let mut lines = Vec::new();
+        render_change_with_options(
+            &change,
+            &mut lines,
+            80,
+            DiffRenderOptions {
+                side_by_side: true,
+                change_markers: true,
+            },
+        );
+        let text: Vec<String> = lines
+            .iter()
+            .map(|line| line.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect();
+        assert_eq!(text, vec!["1 -let [-x-] = 1;", "1 +let {+y+} = 1;"]);
+    }
+
+    #[test]
+    fn ui_snapshot_side_by_side_update() {
+        let original = "fn main() {\n    let x = 1;\n}\n";
+        let modified = "fn main() {\n    let y = 1;\n}\n";
+        let change = FileChange::Update {
+            unified_diff: diffy::create_patch(original, modified).to_string(),
+            move_path: None,
+        };
+        let mut lines = Vec::new();
+        render_change_with_options(
+            &change,
+            &mut lines,
+            SIDE_BY_SIDE_MIN_WIDTH,
+            DiffRenderOptions {
+                side_by_side: true,
+                change_markers: true,
+            },
+        );
+        snapshot_lines_text("side_by_side_update", &lines);
+    }
 }
@@ -0,0 +1,135 @@
+//! Compose the current draft in the user's own editor (`$VISUAL` / `$EDITOR`).
+//!
+//! The draft is written to a temporary Markdown file, the editor is run with
+//! the terminal handed over to it, and the saved contents are read back.
+
+use std::io;
+use std::io::Write;
+
+use tokio::process::Command;
+
+/// Environment variables consulted, in order, for the editor command.
+const EDITOR_ENV_VARS: [&str; 2] = ["VISUAL", "EDITOR"];
+
+/// Resolve the editor command line from `$VISUAL` or `$EDITOR`.
+///
+/// Values are split with shell quoting rules so commands such as
+/// `code --wait` work.
+pub(crate) fn editor_command() -> io::Result<Vec<String>> {
+    editor_command_from(|name| std::env::var(name).ok())
+}
+
+fn editor_command_from(lookup: impl Fn(&str) -> Option<String>) -> io::Result<Vec<String>> {
+    for name in EDITOR_ENV_VARS {
+        let Some(value) = lookup(name).filter(|value| !value.trim().is_empty()) else {
+            continue;
+        };
+        return match shlex::split(&value) {
+            Some(parts) if !parts.is_empty() => Ok(parts),
+            _ => Err(io::Error::other(format!(
+                "could not parse ${name}: {value}"
+            ))),
+        };
+    }
+    Err(io::Error::new(
+        io::ErrorKind::NotFound,
+        "set $VISUAL or $EDITOR to compose prompts in an external editor",
+    ))
+}
+
+/// Open `draft` in the external editor and return the edited text.
+///
+/// The caller must release the terminal before awaiting this; see
+/// [`crate::tui::Tui::with_released_terminal`].
+pub(crate) async fn edit_in_external_editor(command: &[String], draft: &str) -> io::Result<String> {
+    let Some((program, args)) = command.split_first() else {
+        return Err(io::Error::other("editor command is empty"));
+    };
+
+    let mut file = tempfile::Builder::new()
+        .prefix("codex-prompt-")
+        .suffix(".md")
+        .tempfile()?;
+    file.write_all(draft.as_bytes())?;
+    file.flush()?;
+    // Close our handle so editors that replace the file (or lock it on Windows) can save.
+    let path = file.into_temp_path();
+
+    let status = Command::new(program)
+        .args(args)
+        .arg(&path)
+        .status()
+        .await
+        .map_err(|err| io::Error::new(err.kind(), format!("failed to run {program}: {err}")))?;
+    if !status.success() {
+        return Err(io::Error::other(format!("{program} exited with {status}")));
+    }
+
+    let edited = std::fs::read_to_string(&path)?;
+    Ok(trim_trailing_newline(edited))
+}
+
+/// Editors conventionally terminate the file with a newline; drop exactly one so
+/// the draft does not grow a blank line on every round trip.
+fn trim_trailing_newline(mut text: String) -> String {
+    if text.ends_with('\n') {
+        text.pop();
+        if text.ends_with('\r') {
+            text.pop();
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn visual_takes_precedence_and_is_shell_split() {
+        let command = editor_command_from(|name| match name {
+            "VISUAL" => Some("code --wait".to_string()),
+            "EDITOR" => Some("vim".to_string()),
+            _ => None,
+        })
+        .expect("editor command");
+        assert_eq!(command, vec!["code".to_string(), "--wait".to_string()]);
+    }
+
+    #[test]
+    fn falls_back_to_editor_and_errors_when_unset() {
+        let command = editor_command_from(|name| match name {
+            "VISUAL" => Some("  ".to_string()),
+            "EDITOR" => Some("nano".to_string()),
+            _ => None,
+        })
+        .expect("editor command");
+        assert_eq!(command, vec!["nano".to_string()]);
+
+        let err = editor_command_from(|_| None).expect_err("no editor configured");
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn trims_single_trailing_newline() {
+        assert_eq!(trim_trailing_newline("hello\n\n".to_string()), "hello\n");
+        assert_eq!(trim_trailing_newline("hello\r\n".to_string()), "hello");
+        assert_eq!(trim_trailing_newline("hello".to_string()), "hello");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn reads_back_edited_file() {
+        let command = vec![
+            "sh".to_string(),
+            "-c".to_string(),
+            "printf 'edited [Pasted Content 1200 chars]\\n' > \"$1\"".to_string(),
+            "editor".to_string(),
+        ];
+        let edited = edit_in_external_editor(&command, "draft")
+            .await
+            .expect("edit succeeds");
+        assert_eq!(edited, "edited [Pasted Content 1200 chars]");
+    }
+}
@@ -1,31 +1,61 @@
-//! Utility to compute the current Git diff for the working directory.
+//! Utility to compute the current Git diff for a repository.
 //!
 //! The implementation mirrors the behaviour of the TypeScript version in
-//! `codex-cli`: it returns the diff for tracked changes as well as any
-//! untracked files. When the current directory is not inside a Git
-//! repository, the function returns `Ok((false, String::new()))`.
+//! `codex-cli`: the working tree diff covers tracked changes as well as any
+//! untracked files. Staged changes are collected separately so `/diff` can
+//! show and unstage them. When the directory is not inside a Git repository,
+//! [`get_git_diffs`] returns `Ok(None)`.
 
 use std::io;
 use std::path::Path;
+use std::path::PathBuf;
 use std::process::Stdio;
 use tokio::process::Command;
 
-/// Return value of [`get_git_diff`].
-///
-/// * `bool` – Whether the current working directory is inside a Git repo.
-/// * `String` – The concatenated diff (may be empty).
-pub(crate) async fn get_git_diff() -> io::Result<(bool, String)> {
-    // First check if we are inside a Git repository.
-    if !inside_git_repo().await? {
-        return Ok((false, String::new()));
+/// Return value of [`get_git_diffs`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct GitDiffs {
+    /// Root of the repository; every path in the diffs is relative to it.
+    pub(crate) repo_root: PathBuf,
+    /// Working tree changes not yet staged, including untracked files.
+    pub(crate) unstaged: String,
+    /// Changes staged in the index.
+    pub(crate) staged: String,
+}
+
+impl GitDiffs {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.unstaged.trim().is_empty() && self.staged.trim().is_empty()
     }
+}
+
+/// One file's section of a `git diff`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct FileDiff {
+    /// Path relative to the repository root.
+    pub(crate) path: PathBuf,
+    /// The `diff --git` line and extended headers (mode, index, rename).
+    pub(crate) header: String,
+    /// The `---`/`+++` lines followed by the hunks; empty for changes without
+    /// a textual diff (binary files, pure renames, mode-only changes).
+    pub(crate) unified_diff: String,
+}
 
-    // Run tracked diff and untracked file listing in parallel.
-    let (tracked_diff_res, untracked_output_res) = tokio::join!(
-        run_git_capture_diff(&["diff", "--color"]),
-        run_git_capture_stdout(&["ls-files", "--others", "--exclude-standard"]),
+/// Compute the staged and unstaged diffs of the repository containing `cwd`.
+pub(crate) async fn get_git_diffs(cwd: &Path) -> io::Result<Option<GitDiffs>> {
+    // First check if we are inside a Git repository.
+    let Some(repo_root) = repo_root(cwd).await? else {
+        return Ok(None);
+    };
+
+    // Run tracked diffs and untracked file listing in parallel.
+    let (tracked_diff_res, staged_diff_res, untracked_output_res) = tokio::join!(
+        run_git_capture_diff(&repo_root, &["diff", "--binary"]),
+        run_git_capture_diff(&repo_root, &["diff", "--binary", "--cached"]),
+        run_git_capture_stdout(&repo_root, &["ls-files", "--others", "--exclude-standard"]),
     );
     let tracked_diff = tracked_diff_res?;
+    let staged = staged_diff_res?;
     let untracked_output = untracked_output_res?;
 
     let mut untracked_diff = String::new();
@@ -44,9 +74,10 @@ pub(crate) async fn get_git_diff() -> io::Result<(bool, String)> {
     {
         let null_path = null_path.clone();
         let file = file.to_string();
+        let repo_root = repo_root.clone();
         join_set.spawn(async move {
-            let args = ["diff", "--color", "--no-index", "--", &null_path, &file];
-            run_git_capture_diff(&args).await
+            let args = ["diff", "--binary", "--no-index", "--", &null_path, &file];
+            run_git_capture_diff(&repo_root, &args).await
         });
     }
     while let Some(res) = join_set.join_next().await {
@@ -58,14 +89,82 @@ pub(crate) async fn get_git_diff() -> io::Result<(bool, String)> {
         }
     }
 
-    Ok((true, format!("{tracked_diff}{untracked_diff}")))
+    Ok(Some(GitDiffs {
+        repo_root,
+        unstaged: format!("{tracked_diff}{untracked_diff}"),
+        staged,
+    }))
+}
+
+/// Split a `git diff` into its per-file sections.
+///
+/// Files without a textual diff (binary files, pure renames, mode-only
+/// changes) keep their whole section as the header and have no hunks, so they
+/// can still be applied as a whole file.
+pub(crate) fn split_file_diffs(diff: &str) -> Vec<FileDiff> {
+    let mut files = Vec::new();
+    // Hunk lines always start with a marker column, so a file header is the
+    // only line that can begin with `diff --git`.
+    let diff = format!("\n{diff}");
+    let sections: Vec<&str> = diff.split("\ndiff --git ").skip(1).collect();
+    let last = sections.len().saturating_sub(1);
+    for (index, section) in sections.into_iter().enumerate() {
+        // Splitting dropped the newline ending every section but the last.
+        // Binary patches end with a blank line, so it must always be restored.
+        let mut section = section.to_string();
+        if index < last || !section.ends_with('\n') {
+            section.push('\n');
+        }
+        let Some(start) = section.find("\n--- ") else {
+            files.push(FileDiff {
+                path: header_only_path(&section),
+                header: format!("diff --git {section}"),
+                unified_diff: String::new(),
+            });
+            continue;
+        };
+        let body = &section[start + 1..];
+        let mut lines = body.lines();
+        let old_path = lines.next().and_then(|l| l.strip_prefix("--- "));
+        let new_path = lines.next().and_then(|l| l.strip_prefix("+++ "));
+        let path = match (old_path, new_path) {
+            (_, Some(new)) if new != "/dev/null" => new.strip_prefix("b/").unwrap_or(new),
+            (Some(old), _) => old.strip_prefix("a/").unwrap_or(old),
+            _ => continue,
+        };
+        files.push(FileDiff {
+            path: PathBuf::from(path.trim_end_matches('\t')),
+            header: format!("diff --git {}", &section[..start + 1]),
+            unified_diff: body.to_string(),
+        });
+    }
+    files
+}
+
+/// The path of a section without `---`/`+++` lines: the rename target when
+/// there is one, otherwise the `b/` side of the `diff --git a/… b/…` line.
+fn header_only_path(section: &str) -> PathBuf {
+    if let Some(target) = section
+        .lines()
+        .find_map(|line| line.strip_prefix("rename to "))
+    {
+        return PathBuf::from(target);
+    }
+    let first_line = section.lines().next().unwrap_or_default();
+    let path = match first_line.rfind(" b/") {
+        Some(idx) => &first_line[idx + 3..],
+        None => first_line.strip_prefix("a/").unwrap_or(first_line),
+    };
+    PathBuf::from(path)
 }
 
-/// Helper that executes `git` with the given `args` and returns `stdout` as a
-/// UTF-8 string. Any non-zero exit status is considered an *error*.
-async fn run_git_capture_stdout(args: &[&str]) -> io::Result<String> {
+/// Helper that executes `git` with the given `args` in `dir` and returns
+/// `stdout` as a UTF-8 string. Any non-zero exit status is considered an
+/// *error*.
+async fn run_git_capture_stdout(dir: &Path, args: &[&str]) -> io::Result<String> {
     let output = Command::new("git")
         .args(args)
+        .current_dir(dir)
         .stdout(Stdio::piped())
         .stderr(Stdio::null())
         .output()
@@ -83,9 +182,10 @@ async fn run_git_capture_stdout(args: &[&str]) -> io::Result<String> {
 
 /// Like [`run_git_capture_stdout`] but treats exit status 1 as success and
 /// returns stdout. Git returns 1 for diffs when differences are present.
-async fn run_git_capture_diff(args: &[&str]) -> io::Result<String> {
+async fn run_git_capture_diff(dir: &Path, args: &[&str]) -> io::Result<String> {
     let output = Command::new("git")
         .args(args)
+        .current_dir(dir)
         .stdout(Stdio::piped())
         .stderr(Stdio::null())
         .output()
@@ -101,19 +201,101 @@ async fn run_git_capture_diff(args: &[&str]) -> io::Result<String> {
     }
 }
 
-/// Determine if the current directory is inside a Git repository.
-async fn inside_git_repo() -> io::Result<bool> {
-    let status = Command::new("git")
-        .args(["rev-parse", "--is-inside-work-tree"])
-        .stdout(Stdio::null())
+/// Determine the root of the Git repository containing `cwd`, if any.
+async fn repo_root(cwd: &Path) -> io::Result<Option<PathBuf>> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--show-toplevel"])
+        .current_dir(cwd)
+        .stdout(Stdio::piped())
         .stderr(Stdio::null())
-        .status()
+        .output()
         .await;
 
-    match status {
-        Ok(s) if s.success() => Ok(true),
-        Ok(_) => Ok(false),
-        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(false), // git not installed
+    match output {
+        Ok(o) if o.status.success() => {
+            let root = String::from_utf8_lossy(&o.stdout).trim().to_string();
+            Ok(Some(PathBuf::from(root)))
+        }
+        Ok(_) => Ok(None),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None), // git not installed
         Err(e) => Err(e),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn split_file_diffs_keeps_each_file_patch() {
+        let diff = "\
+diff --git a/src/lib.rs b/src/lib.rs
+index 1111111..2222222 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1 +1 @@
+-old
++new
+diff --git a/gone.txt b/gone.txt
+deleted file mode 100644
+--- a/gone.txt
++++ /dev/null
+@@ -1 +0,0 @@
+-bye
+diff --git a/logo.png b/logo.png
+index 4444444..5555555 100644
+GIT binary patch
+literal 3
+KcmZ?wbN>JX0RRAY
+
+diff --git a/old name.txt b/new name.txt
+similarity index 100%
+rename from old name.txt
+rename to new name.txt
+diff --git a/run.sh b/run.sh
+old mode 100644
+new mode 100755
+";
+        let files = split_file_diffs(diff);
+        assert_eq!(
+            files,
+            vec![
+                FileDiff {
+                    path: PathBuf::from("src/lib.rs"),
+                    header: "diff --git a/src/lib.rs b/src/lib.rs\nindex 1111111..2222222 100644\n"
+                        .to_string(),
+                    unified_diff: "--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -1 +1 @@\n-old\n+new\n"
+                        .to_string(),
+                },
+                FileDiff {
+                    path: PathBuf::from("gone.txt"),
+                    header: "diff --git a/gone.txt b/gone.txt\ndeleted file mode 100644\n"
+                        .to_string(),
+                    unified_diff: "--- a/gone.txt\n+++ /dev/null\n@@ -1 +0,0 @@\n-bye\n"
+                        .to_string(),
+                },
+                FileDiff {
+                    path: PathBuf::from("logo.png"),
+                    header: "diff --git a/logo.png b/logo.png\nindex 4444444..5555555 100644\n\
+                             GIT binary patch\nliteral 3\nKcmZ?wbN>JX0RRAY\n\n"
+                        .to_string(),
+                    unified_diff: String::new(),
+                },
+                FileDiff {
+                    path: PathBuf::from("new name.txt"),
+                    header: "diff --git a/old name.txt b/new name.txt\nsimilarity index 100%\n\
+                             rename from old name.txt\nrename to new name.txt\n"
+                        .to_string(),
+                    unified_diff: String::new(),
+                },
+                FileDiff {
+                    path: PathBuf::from("run.sh"),
+                    header: "diff --git a/run.sh b/run.sh\nold mode 100644\nnew mode 100755\n"
+                        .to_string(),
+                    unified_diff: String::new(),
+                },
+            ]
+        );
+    }
+}
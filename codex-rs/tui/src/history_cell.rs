@@ -677,6 +677,18 @@ pub fn new_approval_decision_cell(
                 ],
             )
         }
+        ApprovedSubset { approved_paths } => (
+            "✔ ".green(),
+            vec![
+                "You ".into(),
+                "approved".bold(),
+                format!(
+                    " codex to change {} of the proposed files",
+                    approved_paths.len()
+                )
+                .into(),
+            ],
+        ),
         Denied => {
             let snippet = Span::from(exec_snippet(&command)).dim();
             (
@@ -1893,6 +1905,7 @@ mod tests {
             transport: McpServerTransportConfig::StreamableHttp {
                 url: "https://example.com/mcp".to_string(),
                 bearer_token_env_var: Some("MCP_TOKEN".to_string()),
+                auth_command: None,
                 http_headers: Some(headers),
                 env_http_headers: Some(env_headers),
             },
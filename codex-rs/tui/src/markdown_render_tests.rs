@@ -1,10 +1,14 @@
 use pretty_assertions::assert_eq;
+use ratatui::style::Color;
+use ratatui::style::Modifier;
+use ratatui::style::Style;
 use ratatui::style::Stylize;
 use ratatui::text::Line;
 use ratatui::text::Span;
 use ratatui::text::Text;
 
 use crate::markdown_render::render_markdown_text;
+use crate::markdown_render::render_markdown_text_with_width;
 use insta::assert_snapshot;
 
 #[test]
@@ -653,8 +657,8 @@ fn link() {
 
 #[test]
 fn code_block_unhighlighted() {
-    let text = render_markdown_text("```rust\nfn main() {}\n```\n");
-    let expected = Text::from_iter([Line::from_iter(["", "fn main() {}"])]);
+    let text = render_markdown_text("```brainfuck\n+[-->-[>>+>-----<<]<--<---]>-.\n```\n");
+    let expected = Text::from_iter([Line::from_iter(["", "+[-->-[>>+>-----<<]<--<---]>-."])]);
     assert_eq!(text, expected);
 }
 
@@ -993,3 +997,160 @@ fn nested_item_continuation_paragraph_is_indented() {
     ]);
     assert_eq!(text, expected);
 }
+
+fn plain_lines(text: &Text<'_>) -> Vec<String> {
+    text.lines
+        .iter()
+        .map(|line| {
+            line.spans
+                .iter()
+                .map(|span| span.content.clone())
+                .collect::<String>()
+        })
+        .collect()
+}
+
+/// Renders styled spans as `<style>text</>` so snapshots show highlighting.
+/// Adjacent spans with the same style are merged first.
+fn styled_markup(text: &Text<'_>) -> String {
+    text.lines
+        .iter()
+        .map(|line| {
+            let mut runs: Vec<(Style, String)> = Vec::new();
+            for span in &line.spans {
+                match runs.last_mut() {
+                    Some((style, content)) if *style == span.style => {
+                        content.push_str(&span.content);
+                    }
+                    _ => runs.push((span.style, span.content.to_string())),
+                }
+            }
+            runs.into_iter()
+                .map(|(style, content)| {
+                    let tag = style_tag(style);
+                    if tag.is_empty() {
+                        content
+                    } else {
+                        format!("<{tag}>{content}</>")
+                    }
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn style_tag(style: Style) -> String {
+    let mut parts = Vec::new();
+    if let Some(fg) = style.fg {
+        parts.push(format!("{fg:?}").to_lowercase());
+    }
+    for (modifier, name) in [
+        (Modifier::BOLD, "bold"),
+        (Modifier::DIM, "dim"),
+        (Modifier::ITALIC, "italic"),
+    ] {
+        if style.add_modifier.contains(modifier) {
+            parts.push(name.to_string());
+        }
+    }
+    parts.join(" ")
+}
+
+#[test]
+fn code_block_highlights_rust() {
+    let text =
+        render_markdown_text("```rust\n// entry point\nfn main() { let s = \"hi\"; }\n```\n");
+    let spans: Vec<_> = text
+        .lines
+        .iter()
+        .flat_map(|line| line.spans.iter())
+        .collect();
+    let style_of = |token: &str| {
+        spans
+            .iter()
+            .find(|span| span.content.trim() == token)
+            .map(|span| span.style)
+    };
+
+    assert_eq!(
+        style_of("// entry point"),
+        Some(Style::new().dim().italic())
+    );
+    assert_eq!(
+        style_of("fn").and_then(|style| style.fg),
+        Some(Color::Magenta)
+    );
+    assert_eq!(
+        style_of("let").and_then(|style| style.fg),
+        Some(Color::Magenta)
+    );
+    let string = spans
+        .iter()
+        .find(|span| span.content.contains("hi"))
+        .expect("string span");
+    assert_eq!(string.style.fg, Some(Color::Green));
+    assert_eq!(
+        plain_lines(&text),
+        vec!["// entry point", "fn main() { let s = \"hi\"; }"]
+    );
+}
+
+#[test]
+fn code_block_highlights_python_inside_list() {
+    let md = "- Example\n\n  ```py\n  def greet():\n      return 'hi'\n  ```\n";
+    let text = render_markdown_text(md);
+
+    assert_eq!(
+        plain_lines(&text),
+        vec!["- Example", "", "  def greet():", "      return 'hi'"]
+    );
+    let def = text.lines[2]
+        .spans
+        .iter()
+        .find(|span| span.content == "def")
+        .expect("def span");
+    assert_eq!(def.style.fg, Some(Color::Magenta));
+}
+
+#[test]
+fn code_block_json_snapshot() {
+    let md = "```json\n{ \"name\": \"codex\", \"tags\": [true, null], \"n\": 1 }\n```\n";
+    assert_snapshot!(styled_markup(&render_markdown_text(md)));
+}
+
+#[test]
+fn code_block_diff_snapshot() {
+    let md = r#"```diff
+diff --git a/src/lib.rs b/src/lib.rs
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,3 +1,3 @@
+ fn greet() {
+-    println!("hi");
++    println!("hello");
+ }
+```
+"#;
+    assert_snapshot!(styled_markup(&render_markdown_text(md)));
+}
+
+#[test]
+fn code_block_with_very_wide_lines_is_not_highlighted() {
+    let json = format!("{{\"values\": [{}]}}", vec!["1"; 40].join(", "));
+    let md = format!("```json\n{json}\n```\n");
+
+    let narrow = render_markdown_text_with_width(&md, Some(20));
+    assert_eq!(
+        narrow,
+        Text::from_iter([Line::from_iter(["", json.as_str()])])
+    );
+
+    let wide = render_markdown_text_with_width(&md, Some(80));
+    assert!(
+        wide.lines[0]
+            .spans
+            .iter()
+            .any(|span| span.style.fg == Some(Color::Cyan))
+    );
+}
@@ -1,10 +1,14 @@
 use std::io;
 use std::sync::LazyLock;
 
+use codex_core::DEFAULT_LLAMA_CPP_PORT;
 use codex_core::DEFAULT_LMSTUDIO_PORT;
 use codex_core::DEFAULT_OLLAMA_PORT;
+use codex_core::DEFAULT_VLLM_PORT;
+use codex_core::LLAMA_CPP_OSS_PROVIDER_ID;
 use codex_core::LMSTUDIO_OSS_PROVIDER_ID;
 use codex_core::OLLAMA_OSS_PROVIDER_ID;
+use codex_core::VLLM_OSS_PROVIDER_ID;
 use codex_core::config::set_default_oss_provider;
 use crossterm::event::Event;
 use crossterm::event::KeyCode;
@@ -37,12 +41,6 @@ use ratatui::widgets::WidgetRef;
 use ratatui::widgets::Wrap;
 use std::time::Duration;
 
-#[derive(Clone)]
-struct ProviderOption {
-    name: String,
-    status: ProviderStatus,
-}
-
 #[derive(Clone)]
 enum ProviderStatus {
     Running,
@@ -55,24 +53,52 @@ enum ProviderStatus {
 /// The `key` is matched case-insensitively.
 struct SelectOption {
     label: Line<'static>,
+    name: &'static str,
     description: &'static str,
     key: KeyCode,
     provider_id: &'static str,
+    port: u16,
+    /// Path probed to decide whether the server is running.
+    health_path: &'static str,
 }
 
 static OSS_SELECT_OPTIONS: LazyLock<Vec<SelectOption>> = LazyLock::new(|| {
     vec![
         SelectOption {
             label: Line::from(vec!["L".underlined(), "M Studio".into()]),
+            name: "LM Studio",
             description: "Local LM Studio server (default port 1234)",
             key: KeyCode::Char('l'),
             provider_id: LMSTUDIO_OSS_PROVIDER_ID,
+            port: DEFAULT_LMSTUDIO_PORT,
+            health_path: "",
         },
         SelectOption {
             label: Line::from(vec!["O".underlined(), "llama".into()]),
+            name: "Ollama",
             description: "Local Ollama server (default port 11434)",
             key: KeyCode::Char('o'),
             provider_id: OLLAMA_OSS_PROVIDER_ID,
+            port: DEFAULT_OLLAMA_PORT,
+            health_path: "",
+        },
+        SelectOption {
+            label: Line::from(vec!["llama.".into(), "c".underlined(), "pp".into()]),
+            name: "llama.cpp",
+            description: "Local llama.cpp llama-server (default port 8080)",
+            key: KeyCode::Char('c'),
+            provider_id: LLAMA_CPP_OSS_PROVIDER_ID,
+            port: DEFAULT_LLAMA_CPP_PORT,
+            health_path: "/health",
+        },
+        SelectOption {
+            label: Line::from(vec!["v".underlined(), "LLM".into()]),
+            name: "vLLM",
+            description: "Local vLLM OpenAI-compatible server (default port 8000)",
+            key: KeyCode::Char('v'),
+            provider_id: VLLM_OSS_PROVIDER_ID,
+            port: DEFAULT_VLLM_PORT,
+            health_path: "/health",
         },
     ]
 });
@@ -92,18 +118,8 @@ pub struct OssSelectionWidget<'a> {
 }
 
 impl OssSelectionWidget<'_> {
-    fn new(lmstudio_status: ProviderStatus, ollama_status: ProviderStatus) -> io::Result<Self> {
-        let providers = vec![
-            ProviderOption {
-                name: "LM Studio".to_string(),
-                status: lmstudio_status,
-            },
-            ProviderOption {
-                name: "Ollama".to_string(),
-                status: ollama_status,
-            },
-        ];
-
+    /// `statuses` holds one entry per option in [`OSS_SELECT_OPTIONS`].
+    fn new(statuses: &[ProviderStatus]) -> io::Result<Self> {
         let mut contents: Vec<Line> = vec![
             Line::from(vec![
                 "? ".fg(Color::Blue),
@@ -115,12 +131,12 @@ impl OssSelectionWidget<'_> {
         ];
 
         // Add status indicators for each provider
-        for provider in &providers {
-            let (status_symbol, status_color) = get_status_symbol_and_color(&provider.status);
+        for (option, status) in OSS_SELECT_OPTIONS.iter().zip(statuses) {
+            let (status_symbol, status_color) = get_status_symbol_and_color(status);
             contents.push(Line::from(vec![
                 Span::raw("  "),
                 Span::styled(status_symbol, Style::default().fg(status_color)),
-                Span::raw(format!(" {} ", provider.name)),
+                Span::raw(format!(" {} ", option.name)),
             ]));
         }
         contents.push(Line::from(""));
@@ -285,25 +301,21 @@ fn get_status_symbol_and_color(status: &ProviderStatus) -> (&'static str, Color)
 
 pub async fn select_oss_provider(codex_home: &std::path::Path) -> io::Result<String> {
     // Check provider statuses first
-    let lmstudio_status = check_lmstudio_status().await;
-    let ollama_status = check_ollama_status().await;
-
-    // Autoselect if only one is running
-    match (&lmstudio_status, &ollama_status) {
-        (ProviderStatus::Running, ProviderStatus::NotRunning) => {
-            let provider = LMSTUDIO_OSS_PROVIDER_ID.to_string();
-            return Ok(provider);
-        }
-        (ProviderStatus::NotRunning, ProviderStatus::Running) => {
-            let provider = OLLAMA_OSS_PROVIDER_ID.to_string();
-            return Ok(provider);
-        }
-        _ => {
-            // Both running or both not running - show UI
-        }
+    let mut statuses = Vec::with_capacity(OSS_SELECT_OPTIONS.len());
+    for option in OSS_SELECT_OPTIONS.iter() {
+        statuses.push(check_provider_status(option).await);
     }
 
-    let mut widget = OssSelectionWidget::new(lmstudio_status, ollama_status)?;
+    // Autoselect if exactly one is running and none are unknown
+    let mut running = OSS_SELECT_OPTIONS
+        .iter()
+        .zip(&statuses)
+        .filter(|(_, status)| !matches!(status, ProviderStatus::NotRunning));
+    if let (Some((option, ProviderStatus::Running)), None) = (running.next(), running.next()) {
+        return Ok(option.provider_id.to_string());
+    }
+
+    let mut widget = OssSelectionWidget::new(&statuses)?;
 
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -338,29 +350,21 @@ pub async fn select_oss_provider(codex_home: &std::path::Path) -> io::Result<Str
     result
 }
 
-async fn check_lmstudio_status() -> ProviderStatus {
-    match check_port_status(DEFAULT_LMSTUDIO_PORT).await {
-        Ok(true) => ProviderStatus::Running,
-        Ok(false) => ProviderStatus::NotRunning,
-        Err(_) => ProviderStatus::Unknown,
-    }
-}
-
-async fn check_ollama_status() -> ProviderStatus {
-    match check_port_status(DEFAULT_OLLAMA_PORT).await {
+async fn check_provider_status(option: &SelectOption) -> ProviderStatus {
+    match check_port_status(option.port, option.health_path).await {
         Ok(true) => ProviderStatus::Running,
         Ok(false) => ProviderStatus::NotRunning,
         Err(_) => ProviderStatus::Unknown,
     }
 }
 
-async fn check_port_status(port: u16) -> io::Result<bool> {
+async fn check_port_status(port: u16, health_path: &str) -> io::Result<bool> {
     let client = reqwest::Client::builder()
         .timeout(Duration::from_secs(2))
         .build()
         .map_err(io::Error::other)?;
 
-    let url = format!("http://localhost:{port}");
+    let url = format!("http://localhost:{port}{health_path}");
 
     match client.get(&url).send().await {
         Ok(response) => Ok(response.status().is_success()),
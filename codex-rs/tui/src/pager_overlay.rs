@@ -2,6 +2,8 @@ use std::io::Result;
 use std::sync::Arc;
 use std::time::Duration;
 
+use crate::diff_browser::DiffBrowserOverlay;
+use crate::diff_render::SIDE_BY_SIDE_KEY;
 use crate::history_cell::HistoryCell;
 use crate::history_cell::UserHistoryCell;
 use crate::key_hint;
@@ -31,6 +33,7 @@ use ratatui::widgets::Wrap;
 pub(crate) enum Overlay {
     Transcript(TranscriptOverlay),
     Static(StaticOverlay),
+    DiffBrowser(Box<DiffBrowserOverlay>),
 }
 
 impl Overlay {
@@ -49,10 +52,23 @@ impl Overlay {
         Self::Static(StaticOverlay::with_renderables(renderables, title))
     }
 
+    /// A static overlay showing rendered diffs, where [`SIDE_BY_SIDE_KEY`]
+    /// switches between the unified and side-by-side layouts.
+    pub(crate) fn new_static_diff(renderables: Vec<Box<dyn Renderable>>, title: String) -> Self {
+        let mut overlay = StaticOverlay::with_renderables(renderables, title);
+        overlay.toggles_diff_layout = true;
+        Self::Static(overlay)
+    }
+
+    pub(crate) fn new_diff_browser(browser: DiffBrowserOverlay) -> Self {
+        Self::DiffBrowser(Box::new(browser))
+    }
+
     pub(crate) fn handle_event(&mut self, tui: &mut tui::Tui, event: TuiEvent) -> Result<()> {
         match self {
             Overlay::Transcript(o) => o.handle_event(tui, event),
             Overlay::Static(o) => o.handle_event(tui, event),
+            Overlay::DiffBrowser(o) => o.handle_event(tui, event),
         }
     }
 
@@ -60,6 +76,7 @@ impl Overlay {
         match self {
             Overlay::Transcript(o) => o.is_done(),
             Overlay::Static(o) => o.is_done(),
+            Overlay::DiffBrowser(o) => o.is_done(),
         }
     }
 }
@@ -92,7 +109,7 @@ const PAGER_KEY_HINTS: &[(&[KeyBinding], &str)] = &[
 ];
 
 // Render a single line of key hints from (key(s), description) pairs.
-fn render_key_hints(area: Rect, buf: &mut Buffer, pairs: &[(&[KeyBinding], &str)]) {
+pub(crate) fn render_key_hints(area: Rect, buf: &mut Buffer, pairs: &[(&[KeyBinding], &str)]) {
     let mut spans: Vec<Span<'static>> = vec![" ".into()];
     let mut first = true;
     for (keys, desc) in pairs {
@@ -523,6 +540,7 @@ impl TranscriptOverlay {
 pub(crate) struct StaticOverlay {
     view: PagerView,
     is_done: bool,
+    toggles_diff_layout: bool,
 }
 
 impl StaticOverlay {
@@ -535,6 +553,7 @@ impl StaticOverlay {
         Self {
             view: PagerView::new(renderables, title, 0),
             is_done: false,
+            toggles_diff_layout: false,
         }
     }
 
@@ -542,7 +561,10 @@ impl StaticOverlay {
         let line1 = Rect::new(area.x, area.y, area.width, 1);
         let line2 = Rect::new(area.x, area.y.saturating_add(1), area.width, 1);
         render_key_hints(line1, buf, PAGER_KEY_HINTS);
-        let pairs: Vec<(&[KeyBinding], &str)> = vec![(&[KEY_Q], "to quit")];
+        let mut pairs: Vec<(&[KeyBinding], &str)> = vec![(&[KEY_Q], "to quit")];
+        if self.toggles_diff_layout {
+            pairs.push((&[SIDE_BY_SIDE_KEY], "to toggle side-by-side"));
+        }
         render_key_hints(line2, buf, &pairs);
     }
 
@@ -563,6 +585,11 @@ impl StaticOverlay {
                     self.is_done = true;
                     Ok(())
                 }
+                e if self.toggles_diff_layout && SIDE_BY_SIDE_KEY.is_press(e) => {
+                    crate::diff_render::toggle_side_by_side();
+                    tui.frame_requester().schedule_frame();
+                    Ok(())
+                }
                 other => self.view.handle_key_event(tui, other),
             },
             TuiEvent::Draw => {
@@ -7,66 +7,284 @@ use tree_sitter_highlight::Highlight;
 use tree_sitter_highlight::HighlightConfiguration;
 use tree_sitter_highlight::HighlightEvent;
 use tree_sitter_highlight::Highlighter;
+use unicode_width::UnicodeWidthStr;
 
-// Ref: https://github.com/tree-sitter/tree-sitter-bash/blob/master/queries/highlights.scm
-#[derive(Copy, Clone)]
-enum BashHighlight {
+/// Code blocks larger than this are shown without highlighting.
+const MAX_HIGHLIGHT_BYTES: usize = 64 * 1024;
+/// Code blocks with a line wider than this many rendered widths (minified
+/// JSON, generated code) are shown without highlighting.
+const MAX_LINE_WIDTH_FACTOR: usize = 4;
+
+/// Theme roles shared by every grammar. The names are the tree-sitter capture
+/// names they match; a capture such as `function.method` or
+/// `string.special.key` resolves to its longest configured prefix.
+// Ref: https://tree-sitter.github.io/tree-sitter/3-syntax-highlighting.html#highlights
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum HighlightRole {
+    Attribute,
+    Boolean,
     Comment,
     Constant,
+    Constructor,
     Embedded,
+    Escape,
     Function,
     Keyword,
     Number,
     Operator,
     Property,
+    Punctuation,
     String,
+    Type,
 }
 
-impl BashHighlight {
-    const ALL: [Self; 9] = [
+impl HighlightRole {
+    const ALL: [Self; 15] = [
+        Self::Attribute,
+        Self::Boolean,
         Self::Comment,
         Self::Constant,
+        Self::Constructor,
         Self::Embedded,
+        Self::Escape,
         Self::Function,
         Self::Keyword,
         Self::Number,
         Self::Operator,
         Self::Property,
+        Self::Punctuation,
         Self::String,
+        Self::Type,
     ];
 
     const fn as_str(self) -> &'static str {
         match self {
+            Self::Attribute => "attribute",
+            Self::Boolean => "boolean",
             Self::Comment => "comment",
             Self::Constant => "constant",
+            Self::Constructor => "constructor",
             Self::Embedded => "embedded",
+            Self::Escape => "escape",
             Self::Function => "function",
             Self::Keyword => "keyword",
             Self::Number => "number",
             Self::Operator => "operator",
             Self::Property => "property",
+            Self::Punctuation => "punctuation",
             Self::String => "string",
+            Self::Type => "type",
         }
     }
 
-    fn style(self) -> Style {
+    /// Shell commands stay monochrome so they read like the transcript around
+    /// them.
+    fn command_style(self) -> Style {
         match self {
             Self::Comment | Self::Operator | Self::String => Style::default().dim(),
             _ => Style::default(),
         }
     }
+
+    /// Colors for fenced code blocks, limited to the palette in `styles.md`.
+    fn code_style(self) -> Style {
+        match self {
+            Self::Comment => Style::default().dim().italic(),
+            Self::Keyword => Style::default().magenta(),
+            Self::String => Style::default().green(),
+            Self::Boolean
+            | Self::Constant
+            | Self::Constructor
+            | Self::Escape
+            | Self::Number
+            | Self::Type => Style::default().cyan(),
+            Self::Attribute | Self::Operator | Self::Punctuation => Style::default().dim(),
+            Self::Embedded | Self::Function | Self::Property => Style::default(),
+        }
+    }
+}
+
+/// Languages recognized in a fenced code block's info string.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum CodeLanguage {
+    Bash,
+    Diff,
+    JavaScript,
+    Json,
+    Python,
+    Rust,
+    Sql,
+    Toml,
+    Tsx,
+    TypeScript,
+    Yaml,
+}
+
+impl CodeLanguage {
+    /// Maps the first word of an info string (```` ```rust,ignore ````,
+    /// ```` ```{python} ````) to a language, ignoring case.
+    pub(crate) fn from_info_string(info: &str) -> Option<Self> {
+        let name = info
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .next()
+            .unwrap_or_default()
+            .trim_matches(|c| c == '{' || c == '}' || c == '.')
+            .to_ascii_lowercase();
+        let language = match name.as_str() {
+            "bash" | "sh" | "shell" | "zsh" => Self::Bash,
+            "diff" | "patch" | "udiff" => Self::Diff,
+            "javascript" | "js" | "jsx" | "mjs" | "cjs" => Self::JavaScript,
+            "json" | "jsonc" | "json5" => Self::Json,
+            "python" | "py" | "python3" => Self::Python,
+            "rust" | "rs" => Self::Rust,
+            "sql" | "postgresql" | "postgres" | "mysql" | "sqlite" => Self::Sql,
+            "toml" => Self::Toml,
+            "tsx" => Self::Tsx,
+            "typescript" | "ts" | "mts" | "cts" => Self::TypeScript,
+            "yaml" | "yml" => Self::Yaml,
+            _ => return None,
+        };
+        Some(language)
+    }
+
+    /// The tree-sitter configuration for this language, or `None` when the
+    /// language is highlighted without a grammar (diffs) or its query failed
+    /// to load.
+    fn highlight_config(self) -> Option<&'static HighlightConfiguration> {
+        static JAVASCRIPT: OnceLock<Option<HighlightConfiguration>> = OnceLock::new();
+        static JSON: OnceLock<Option<HighlightConfiguration>> = OnceLock::new();
+        static PYTHON: OnceLock<Option<HighlightConfiguration>> = OnceLock::new();
+        static RUST: OnceLock<Option<HighlightConfiguration>> = OnceLock::new();
+        static SQL: OnceLock<Option<HighlightConfiguration>> = OnceLock::new();
+        static TOML: OnceLock<Option<HighlightConfiguration>> = OnceLock::new();
+        static TSX: OnceLock<Option<HighlightConfiguration>> = OnceLock::new();
+        static TYPESCRIPT: OnceLock<Option<HighlightConfiguration>> = OnceLock::new();
+        static YAML: OnceLock<Option<HighlightConfiguration>> = OnceLock::new();
+
+        match self {
+            Self::Bash => Some(bash_highlight_config()),
+            Self::Diff => None,
+            Self::JavaScript => JAVASCRIPT
+                .get_or_init(|| {
+                    load_config(
+                        tree_sitter_javascript::LANGUAGE.into(),
+                        "javascript",
+                        tree_sitter_javascript::HIGHLIGHT_QUERY,
+                    )
+                })
+                .as_ref(),
+            Self::Json => JSON
+                .get_or_init(|| {
+                    load_config(
+                        tree_sitter_json::LANGUAGE.into(),
+                        "json",
+                        tree_sitter_json::HIGHLIGHTS_QUERY,
+                    )
+                })
+                .as_ref(),
+            Self::Python => PYTHON
+                .get_or_init(|| {
+                    load_config(
+                        tree_sitter_python::LANGUAGE.into(),
+                        "python",
+                        tree_sitter_python::HIGHLIGHTS_QUERY,
+                    )
+                })
+                .as_ref(),
+            Self::Rust => RUST
+                .get_or_init(|| {
+                    load_config(
+                        tree_sitter_rust::LANGUAGE.into(),
+                        "rust",
+                        tree_sitter_rust::HIGHLIGHTS_QUERY,
+                    )
+                })
+                .as_ref(),
+            Self::Sql => SQL
+                .get_or_init(|| {
+                    load_config(
+                        tree_sitter_sequel::LANGUAGE.into(),
+                        "sql",
+                        tree_sitter_sequel::HIGHLIGHTS_QUERY,
+                    )
+                })
+                .as_ref(),
+            Self::Toml => TOML
+                .get_or_init(|| {
+                    load_config(
+                        tree_sitter_toml_ng::LANGUAGE.into(),
+                        "toml",
+                        tree_sitter_toml_ng::HIGHLIGHTS_QUERY,
+                    )
+                })
+                .as_ref(),
+            Self::Tsx => TSX
+                .get_or_init(|| {
+                    load_config(
+                        tree_sitter_typescript::LANGUAGE_TSX.into(),
+                        "tsx",
+                        &typescript_highlights_query(),
+                    )
+                })
+                .as_ref(),
+            Self::TypeScript => TYPESCRIPT
+                .get_or_init(|| {
+                    load_config(
+                        tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
+                        "typescript",
+                        &typescript_highlights_query(),
+                    )
+                })
+                .as_ref(),
+            Self::Yaml => YAML
+                .get_or_init(|| {
+                    load_config(
+                        tree_sitter_yaml::LANGUAGE.into(),
+                        "yaml",
+                        tree_sitter_yaml::HIGHLIGHTS_QUERY,
+                    )
+                })
+                .as_ref(),
+        }
+    }
+}
+
+/// The TypeScript grammar extends JavaScript, and so do its queries: the
+/// TypeScript-specific patterns come first so they take precedence.
+fn typescript_highlights_query() -> String {
+    format!(
+        "{}\n{}",
+        tree_sitter_typescript::HIGHLIGHTS_QUERY,
+        tree_sitter_javascript::HIGHLIGHT_QUERY
+    )
 }
 
-static HIGHLIGHT_CONFIG: OnceLock<HighlightConfiguration> = OnceLock::new();
+fn load_config(
+    language: tree_sitter::Language,
+    name: &str,
+    highlights_query: &str,
+) -> Option<HighlightConfiguration> {
+    match HighlightConfiguration::new(language, name, highlights_query, "", "") {
+        Ok(mut config) => {
+            config.configure(highlight_names());
+            Some(config)
+        }
+        Err(err) => {
+            tracing::warn!("failed to load {name} highlight query: {err}");
+            None
+        }
+    }
+}
 
 fn highlight_names() -> &'static [&'static str] {
-    static NAMES: OnceLock<[&'static str; BashHighlight::ALL.len()]> = OnceLock::new();
+    static NAMES: OnceLock<[&'static str; HighlightRole::ALL.len()]> = OnceLock::new();
     NAMES
-        .get_or_init(|| BashHighlight::ALL.map(BashHighlight::as_str))
+        .get_or_init(|| HighlightRole::ALL.map(HighlightRole::as_str))
         .as_slice()
 }
 
-fn highlight_config() -> &'static HighlightConfiguration {
+fn bash_highlight_config() -> &'static HighlightConfiguration {
+    static HIGHLIGHT_CONFIG: OnceLock<HighlightConfiguration> = OnceLock::new();
     HIGHLIGHT_CONFIG.get_or_init(|| {
         let language = tree_sitter_bash::LANGUAGE.into();
         #[expect(clippy::expect_used)]
@@ -83,8 +301,8 @@ fn highlight_config() -> &'static HighlightConfiguration {
     })
 }
 
-fn highlight_for(highlight: Highlight) -> BashHighlight {
-    BashHighlight::ALL[highlight.0]
+fn role_for(highlight: Highlight) -> HighlightRole {
+    HighlightRole::ALL[highlight.0]
 }
 
 fn push_segment(lines: &mut Vec<Line<'static>>, segment: &str, style: Option<Style>) {
@@ -109,38 +327,103 @@ fn push_segment(lines: &mut Vec<Line<'static>>, segment: &str, style: Option<Sty
 /// bash highlight query. The highlighter is streamed so multi-line content is
 /// split into `Line`s while preserving style boundaries.
 pub(crate) fn highlight_bash_to_lines(script: &str) -> Vec<Line<'static>> {
+    highlight_with_config(
+        bash_highlight_config(),
+        script,
+        HighlightRole::command_style,
+    )
+    .unwrap_or_else(|| vec![script.to_string().into()])
+}
+
+/// Highlights the body of a fenced code block, one `Line` per source line.
+///
+/// Returns `None` when the block should be rendered as plain text instead:
+/// the highlighter failed, the block is very large, or `width` is known and
+/// the block has lines many times wider than it.
+pub(crate) fn highlight_code_to_lines(
+    language: CodeLanguage,
+    code: &str,
+    width: Option<usize>,
+) -> Option<Vec<Line<'static>>> {
+    if code.len() > MAX_HIGHLIGHT_BYTES {
+        return None;
+    }
+    if let Some(width) = width {
+        let max_line_width = width.max(1).saturating_mul(MAX_LINE_WIDTH_FACTOR);
+        if code.lines().any(|line| line.width() > max_line_width) {
+            return None;
+        }
+    }
+    let code = code.strip_suffix('\n').unwrap_or(code);
+    if language == CodeLanguage::Diff {
+        return Some(highlight_diff_to_lines(code));
+    }
+    highlight_with_config(
+        language.highlight_config()?,
+        code,
+        HighlightRole::code_style,
+    )
+}
+
+fn highlight_with_config(
+    config: &HighlightConfiguration,
+    source: &str,
+    style_for: fn(HighlightRole) -> Style,
+) -> Option<Vec<Line<'static>>> {
     let mut highlighter = Highlighter::new();
-    let iterator =
-        match highlighter.highlight(highlight_config(), script.as_bytes(), None, |_| None) {
-            Ok(iter) => iter,
-            Err(_) => return vec![script.to_string().into()],
-        };
+    let iterator = highlighter
+        .highlight(config, source.as_bytes(), None, |_| None)
+        .ok()?;
 
     let mut lines: Vec<Line<'static>> = vec![Line::from("")];
     let mut highlight_stack: Vec<Highlight> = Vec::new();
 
     for event in iterator {
-        match event {
-            Ok(HighlightEvent::HighlightStart(highlight)) => highlight_stack.push(highlight),
-            Ok(HighlightEvent::HighlightEnd) => {
+        match event.ok()? {
+            HighlightEvent::HighlightStart(highlight) => highlight_stack.push(highlight),
+            HighlightEvent::HighlightEnd => {
                 highlight_stack.pop();
             }
-            Ok(HighlightEvent::Source { start, end }) => {
+            HighlightEvent::Source { start, end } => {
                 if start == end {
                     continue;
                 }
-                let style = highlight_stack.last().map(|h| highlight_for(*h).style());
-                push_segment(&mut lines, &script[start..end], style);
+                let style = highlight_stack.last().map(|h| style_for(role_for(*h)));
+                push_segment(&mut lines, &source[start..end], style);
             }
-            Err(_) => return vec![script.to_string().into()],
         }
     }
 
-    if lines.is_empty() {
-        vec![Line::from("")]
-    } else {
-        lines
-    }
+    Some(lines)
+}
+
+/// Diffs are line oriented, so each line is styled by its prefix rather than
+/// through a grammar.
+fn highlight_diff_to_lines(diff: &str) -> Vec<Line<'static>> {
+    diff.split('\n')
+        .map(|line| {
+            let style = if line.starts_with("+++")
+                || line.starts_with("---")
+                || line.starts_with("diff ")
+                || line.starts_with("index ")
+            {
+                Style::default().bold()
+            } else if line.starts_with("@@") {
+                Style::default().cyan()
+            } else if line.starts_with('+') {
+                Style::default().green()
+            } else if line.starts_with('-') {
+                Style::default().red()
+            } else {
+                Style::default()
+            };
+            if line.is_empty() {
+                Line::from("")
+            } else {
+                Line::from(Span::styled(line.to_string(), style))
+            }
+        })
+        .collect()
 }
 
 #[cfg(test)]
@@ -233,4 +516,68 @@ mod tests {
         let body_style = body_style.expect("missing heredoc span");
         assert!(body_style.add_modifier.contains(Modifier::DIM));
     }
+
+    #[test]
+    fn detects_language_from_info_string() {
+        assert_eq!(
+            CodeLanguage::from_info_string("rust"),
+            Some(CodeLanguage::Rust)
+        );
+        assert_eq!(
+            CodeLanguage::from_info_string("rust,ignore"),
+            Some(CodeLanguage::Rust)
+        );
+        assert_eq!(
+            CodeLanguage::from_info_string("{.Python}"),
+            Some(CodeLanguage::Python)
+        );
+        assert_eq!(
+            CodeLanguage::from_info_string("ts title=app.ts"),
+            Some(CodeLanguage::TypeScript)
+        );
+        assert_eq!(
+            CodeLanguage::from_info_string("yml"),
+            Some(CodeLanguage::Yaml)
+        );
+        assert_eq!(CodeLanguage::from_info_string("text"), None);
+        assert_eq!(CodeLanguage::from_info_string(""), None);
+    }
+
+    #[test]
+    fn highlights_every_supported_language() {
+        let samples = [
+            (CodeLanguage::Bash, "echo \"hi\" # greet"),
+            (CodeLanguage::JavaScript, "const x = \"hi\";"),
+            (CodeLanguage::Json, "{\"a\": \"hi\"}"),
+            (CodeLanguage::Python, "x = \"hi\""),
+            (CodeLanguage::Rust, "let x = \"hi\";"),
+            (CodeLanguage::Sql, "SELECT 'hi' FROM t;"),
+            (CodeLanguage::Toml, "x = \"hi\""),
+            (CodeLanguage::Tsx, "const x: string = \"hi\";"),
+            (CodeLanguage::TypeScript, "const x: string = \"hi\";"),
+            (CodeLanguage::Yaml, "x: \"hi\""),
+        ];
+        for (language, code) in samples {
+            let lines = highlight_code_to_lines(language, code, None)
+                .unwrap_or_else(|| panic!("{language:?} should highlight"));
+            assert_eq!(reconstructed(&lines), code, "{language:?}");
+            assert!(
+                lines
+                    .iter()
+                    .flat_map(|line| line.spans.iter())
+                    .any(|span| span.style.fg == Some(ratatui::style::Color::Green)),
+                "{language:?} should color its string"
+            );
+        }
+    }
+
+    #[test]
+    fn falls_back_for_oversized_blocks() {
+        let code = "x = 1\n".repeat(MAX_HIGHLIGHT_BYTES);
+        assert!(highlight_code_to_lines(CodeLanguage::Python, &code, None).is_none());
+
+        let wide = format!("x = {}", "1".repeat(100));
+        assert!(highlight_code_to_lines(CodeLanguage::Python, &wide, Some(20)).is_none());
+        assert!(highlight_code_to_lines(CodeLanguage::Python, &wide, Some(40)).is_some());
+    }
 }
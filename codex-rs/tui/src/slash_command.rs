@@ -23,6 +23,9 @@ pub enum SlashCommand {
     Compact,
     Undo,
     Diff,
+    Copy,
+    Export,
+    Edit,
     Mention,
     Status,
     Mcp,
@@ -48,6 +51,9 @@ impl SlashCommand {
             SlashCommand::Undo => "ask Codex to undo a turn",
             SlashCommand::Quit | SlashCommand::Exit => "exit Codex",
             SlashCommand::Diff => "show git diff (including untracked files)",
+            SlashCommand::Copy => "copy the last message, command or a code block",
+            SlashCommand::Export => "save this session as Markdown, HTML or plain text",
+            SlashCommand::Edit => "compose the prompt in $VISUAL or $EDITOR",
             SlashCommand::Mention => "mention a file",
             SlashCommand::Skills => "use skills to improve how Codex performs specific tasks",
             SlashCommand::Status => "show current session configuration and token usage",
@@ -82,6 +88,9 @@ impl SlashCommand {
             | SlashCommand::Review
             | SlashCommand::Logout => false,
             SlashCommand::Diff
+            | SlashCommand::Copy
+            | SlashCommand::Export
+            | SlashCommand::Edit
             | SlashCommand::Mention
             | SlashCommand::Skills
             | SlashCommand::Status
@@ -2,6 +2,7 @@ use std::fmt;
 use std::io::IsTerminal;
 use std::io::Result;
 use std::io::Stdout;
+use std::io::Write;
 use std::io::stdin;
 use std::io::stdout;
 use std::panic;
@@ -9,6 +10,7 @@ use std::pin::Pin;
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering;
+use std::time::Instant;
 
 use crossterm::Command;
 use crossterm::SynchronizedUpdate;
@@ -35,6 +37,7 @@ use tokio::sync::broadcast;
 use tokio_stream::Stream;
 
 pub use self::frame_requester::FrameRequester;
+use crate::cues::BELL_INTERVAL;
 use crate::custom_terminal;
 use crate::custom_terminal::Terminal as CustomTerminal;
 use crate::notifications::DesktopNotificationBackend;
@@ -177,6 +180,9 @@ pub struct Tui {
     terminal_focused: Arc<AtomicBool>,
     enhanced_keys_supported: bool,
     notification_backend: Option<DesktopNotificationBackend>,
+    // Bells still to ring for cues, spaced by `BELL_INTERVAL`; see `ring_bell`.
+    pending_bells: u8,
+    next_bell_at: Instant,
 }
 
 impl Tui {
@@ -188,7 +194,8 @@ impl Tui {
         // crossterm poller can acquire its lock without contention.
         let enhanced_keys_supported = supports_keyboard_enhancement().unwrap_or(false);
         // Cache this to avoid contention with the event reader.
-        supports_color::on_cached(supports_color::Stream::Stdout);
+        let color_level = supports_color::on_cached(supports_color::Stream::Stdout);
+        crate::diff_render::set_change_markers(color_level.is_none());
         let _ = crate::terminal_palette::default_colors();
 
         Self {
@@ -204,6 +211,8 @@ impl Tui {
             terminal_focused: Arc::new(AtomicBool::new(true)),
             enhanced_keys_supported,
             notification_backend: Some(detect_backend()),
+            pending_bells: 0,
+            next_bell_at: Instant::now(),
         }
     }
 
@@ -215,18 +224,74 @@ impl Tui {
         self.enhanced_keys_supported
     }
 
-    // todo(sayan) unused for now; intend to use to enable opening external editors
-    #[allow(unused)]
     pub fn pause_events(&mut self) {
         self.event_broker.pause_events();
     }
 
-    // todo(sayan) unused for now; intend to use to enable opening external editors
-    #[allow(unused)]
     pub fn resume_events(&mut self) {
         self.event_broker.resume_events();
     }
 
+    /// Hand the terminal to another program (such as an external editor) while `fut` runs.
+    ///
+    /// Input polling is paused so the child owns stdin, terminal modes are restored as for
+    /// Ctrl+Z, and everything is reapplied once `fut` completes. The next draw realigns the
+    /// viewport (or re-enters the alt screen) the same way resuming from a suspend does.
+    pub async fn with_released_terminal<F: Future>(&mut self, fut: F) -> Result<F::Output> {
+        self.pause_events();
+        #[cfg(unix)]
+        let released = self
+            .suspend_context
+            .release_terminal(&self.alt_screen_active);
+        #[cfg(not(unix))]
+        let released = restore();
+        if let Err(err) = released {
+            self.resume_events();
+            return Err(err);
+        }
+
+        let output = fut.await;
+
+        let reacquired = set_modes();
+        #[cfg(not(unix))]
+        let _ = self.terminal.clear();
+        self.resume_events();
+        self.frame_requester().schedule_frame();
+        reacquired.map(|()| output)
+    }
+
+    pub fn is_terminal_focused(&self) -> bool {
+        self.terminal_focused.load(Ordering::Relaxed)
+    }
+
+    /// Ring the terminal bell `count` times through the terminal writer. Later
+    /// bells are spaced out by scheduling frames rather than sleeping.
+    pub fn ring_bell(&mut self, count: u8) {
+        self.pending_bells = self.pending_bells.saturating_add(count);
+        self.ring_due_bells();
+    }
+
+    fn ring_due_bells(&mut self) {
+        if self.pending_bells == 0 {
+            return;
+        }
+        let now = Instant::now();
+        if now < self.next_bell_at {
+            self.frame_requester
+                .schedule_frame_in(self.next_bell_at.saturating_duration_since(now));
+            return;
+        }
+        let writer = self.terminal.backend_mut();
+        if let Err(err) = writer.write_all(b"\x07").and_then(|()| writer.flush()) {
+            tracing::debug!("failed to ring terminal bell: {err}");
+        }
+        self.pending_bells -= 1;
+        self.next_bell_at = now + BELL_INTERVAL;
+        if self.pending_bells > 0 {
+            self.frame_requester.schedule_frame_in(BELL_INTERVAL);
+        }
+    }
+
     /// Emit a desktop notification now if the terminal is unfocused.
     /// Returns true if a notification was posted.
     pub fn notify(&mut self, message: impl AsRef<str>) -> bool {
@@ -334,6 +399,8 @@ impl Tui {
         height: u16,
         draw_fn: impl FnOnce(&mut custom_terminal::Frame),
     ) -> Result<()> {
+        self.ring_due_bells();
+
         // If we are resuming from ^Z, we need to prepare the resume action now so we can apply it
         // in the synchronized update.
         #[cfg(unix)]
@@ -62,6 +62,19 @@ impl SuspendContext {
     /// - Update the cached inline cursor row so suspend can place the cursor meaningfully.
     /// - Trigger SIGTSTP so the process can be resumed and continue drawing with the saved state.
     pub(crate) fn suspend(&self, alt_screen_active: &Arc<AtomicBool>) -> Result<()> {
+        self.release_terminal(alt_screen_active)?;
+        unsafe { libc::kill(0, libc::SIGTSTP) };
+        // After the process resumes, reapply terminal modes so drawing can continue.
+        super::set_modes()?;
+        Ok(())
+    }
+
+    /// Record the resume intent and restore the terminal so another program can use it.
+    ///
+    /// This is the first half of [`Self::suspend`]; it is also used to hand the terminal to an
+    /// external editor. Callers must reapply terminal modes with `set_modes` once they take the
+    /// terminal back; the next draw then applies the recorded resume action.
+    pub(crate) fn release_terminal(&self, alt_screen_active: &Arc<AtomicBool>) -> Result<()> {
         if alt_screen_active.load(Ordering::Relaxed) {
             // Leave alt-screen so the terminal returns to the normal buffer while suspended; also turn off alt-scroll.
             let _ = execute!(stdout(), DisableAlternateScroll);
@@ -72,7 +85,7 @@ impl SuspendContext {
         }
         let y = self.suspend_cursor_y.load(Ordering::Relaxed);
         let _ = execute!(stdout(), MoveTo(0, y), Show);
-        suspend_process()
+        super::restore()
     }
 
     /// Consume the pending resume intent and precompute any viewport changes needed post-resume.
@@ -171,12 +184,3 @@ impl PreparedResumeAction {
         Ok(())
     }
 }
-
-/// Deliver SIGTSTP after restoring terminal state, then re-applies terminal modes once resumed.
-fn suspend_process() -> Result<()> {
-    super::restore()?;
-    unsafe { libc::kill(0, libc::SIGTSTP) };
-    // After the process resumes, reapply terminal modes so drawing can continue.
-    super::set_modes()?;
-    Ok(())
-}
@@ -31,10 +31,14 @@ use crate::wrapping::RtOptions;
 use crate::wrapping::word_wrap_line;
 use crate::wrapping::word_wrap_lines_borrowed;
 use codex_ansi_escape::ansi_escape_line;
+use codex_common::oss::register_served_models;
 use codex_core::AuthManager;
 use codex_core::ConversationManager;
 use codex_core::config::Config;
 use codex_core::config::edit::ConfigEditsBuilder;
+use codex_core::export::ExportOptions;
+use codex_core::export::export_dir;
+use codex_core::export::export_rollout;
 #[cfg(target_os = "windows")]
 use codex_core::features::Feature;
 use codex_core::models_manager::manager::ModelsManager;
@@ -410,6 +414,7 @@ impl App {
             auth_manager.clone(),
             SessionSource::Cli,
         ));
+        register_served_models(&config, &conversation_manager.get_models_manager()).await;
         let mut model = conversation_manager
             .get_models_manager()
             .get_model(&config.model, &config)
@@ -1723,6 +1728,45 @@ impl App {
                 ));
                 tui.frame_requester().schedule_frame();
             }
+            AppEvent::ExportSession(format) => {
+                let Some(rollout_path) = self.chat_widget.rollout_path() else {
+                    self.chat_widget
+                        .add_info_message("Nothing to export yet.".to_string(), None);
+                    return Ok(true);
+                };
+                let file_name = match self.chat_widget.conversation_id() {
+                    Some(id) => format!("codex-session-{id}.{}", format.extension()),
+                    None => format!("codex-session.{}", format.extension()),
+                };
+                let dir = export_dir(&self.config.codex_home);
+                let output = dir.join(file_name);
+                let options = ExportOptions {
+                    format,
+                    redact_secrets: true,
+                };
+                match export_rollout(&rollout_path, options)
+                    .await
+                    .and_then(|transcript| {
+                        std::fs::create_dir_all(&dir)?;
+                        std::fs::write(&output, transcript)
+                    }) {
+                    Ok(()) => self.chat_widget.add_info_message(
+                        format!("Exported session to {}", output.display()),
+                        None,
+                    ),
+                    Err(err) => self
+                        .chat_widget
+                        .add_error_message(format!("Failed to export session: {err}")),
+                }
+            }
+            AppEvent::CopyToClipboard { text, label } => match clipboard_copy::copy_text(text) {
+                Ok(()) => self
+                    .chat_widget
+                    .add_info_message(format!("Copied {label} to the clipboard."), None),
+                Err(err) => self
+                    .chat_widget
+                    .add_error_message(format!("Failed to copy {label}: {err}")),
+            },
             AppEvent::StartFileSearch(query) => {
                 if !query.is_empty() {
                     self.file_search.on_user_query(query);
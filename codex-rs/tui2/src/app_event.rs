@@ -1,6 +1,7 @@
 use std::path::PathBuf;
 
 use codex_common::approval_presets::ApprovalPreset;
+use codex_core::export::ExportFormat;
 use codex_core::protocol::ConversationPathResponseEvent;
 use codex_core::protocol::Event;
 use codex_core::protocol::RateLimitSnapshot;
@@ -51,6 +52,16 @@ pub(crate) enum AppEvent {
     /// Result of computing a `/diff` command.
     DiffResult(String),
 
+    /// Write the current session transcript to a file in the working
+    /// directory.
+    ExportSession(ExportFormat),
+
+    /// Copy `text` to the clipboard; `label` names it in the confirmation.
+    CopyToClipboard {
+        text: String,
+        label: String,
+    },
+
     InsertHistoryCell(Box<dyn HistoryCell>),
 
     StartCommitAnimation,
@@ -69,6 +69,8 @@ pub(crate) struct ApprovalOverlay {
     current_complete: bool,
     done: bool,
     features: Features,
+    /// Per-file choices while the user picks which files of a patch to apply.
+    file_selection: Option<Vec<PatchFileChoice>>,
 }
 
 impl ApprovalOverlay {
@@ -83,6 +85,7 @@ impl ApprovalOverlay {
             current_complete: false,
             done: false,
             features,
+            file_selection: None,
         };
         view.set_current(request);
         view
@@ -97,6 +100,7 @@ impl ApprovalOverlay {
         let ApprovalRequestState { variant, header } = ApprovalRequestState::from(request);
         self.current_variant = Some(variant.clone());
         self.current_complete = false;
+        self.file_selection = None;
         let (options, params) = Self::build_options(variant, header, &self.features);
         self.options = options;
         self.list = ListSelectionView::new(params, self.app_event_tx.clone());
@@ -115,8 +119,8 @@ impl ApprovalOverlay {
                 exec_options(proposed_execpolicy_amendment.clone(), features),
                 "Would you like to run the following command?".to_string(),
             ),
-            ApprovalVariant::ApplyPatch { .. } => (
-                patch_options(),
+            ApprovalVariant::ApplyPatch { files, .. } => (
+                patch_options(files.len()),
                 "Would you like to make the following edits?".to_string(),
             ),
             ApprovalVariant::McpElicitation { server_name, .. } => (
@@ -174,6 +178,19 @@ impl ApprovalOverlay {
                 (ApprovalVariant::ApplyPatch { id, .. }, ApprovalDecision::Review(decision)) => {
                     self.handle_patch_decision(id, decision.clone());
                 }
+                (ApprovalVariant::ApplyPatch { files, .. }, ApprovalDecision::ChooseFiles) => {
+                    let choices = files
+                        .iter()
+                        .map(|(path, display)| PatchFileChoice {
+                            path: path.clone(),
+                            display: display.clone(),
+                            apply: true,
+                        })
+                        .collect();
+                    self.file_selection = Some(choices);
+                    self.show_file_selection(0);
+                    return;
+                }
                 (
                     ApprovalVariant::McpElicitation {
                         server_name,
@@ -207,6 +224,85 @@ impl ApprovalOverlay {
         }));
     }
 
+    /// Replaces the option list with one row per file of the current patch,
+    /// followed by rows to confirm or go back.
+    fn show_file_selection(&mut self, selected_idx: usize) {
+        let Some(choices) = self.file_selection.as_ref() else {
+            return;
+        };
+        let mut items: Vec<SelectionItem> = choices
+            .iter()
+            .map(|choice| {
+                let marker = if choice.apply { 'x' } else { ' ' };
+                SelectionItem {
+                    name: format!("[{marker}] {}", choice.display),
+                    dismiss_on_select: false,
+                    ..Default::default()
+                }
+            })
+            .collect();
+        let selected = choices.iter().filter(|choice| choice.apply).count();
+        items.push(SelectionItem {
+            name: format!("Apply the selected files ({selected}/{})", choices.len()),
+            dismiss_on_select: false,
+            ..Default::default()
+        });
+        items.push(SelectionItem {
+            name: "Back".to_string(),
+            dismiss_on_select: false,
+            ..Default::default()
+        });
+
+        let header = ColumnRenderable::with([
+            Line::from("Which files should be changed?".bold()),
+            Line::from("Codex is told which files you rejected so it can revise them.".dim()),
+        ]);
+        let params = SelectionViewParams {
+            footer_hint: Some(Line::from(vec![
+                "Press ".into(),
+                key_hint::plain(KeyCode::Enter).into(),
+                " to toggle or confirm, ".into(),
+                key_hint::plain(KeyCode::Esc).into(),
+                " to go back".into(),
+            ])),
+            items,
+            header: Box::new(header),
+            initial_selected_idx: Some(selected_idx),
+            ..Default::default()
+        };
+        self.list = ListSelectionView::new(params, self.app_event_tx.clone());
+    }
+
+    fn apply_file_selection(&mut self, idx: usize) {
+        let Some(choices) = self.file_selection.as_mut() else {
+            return;
+        };
+        let file_count = choices.len();
+        if let Some(choice) = choices.get_mut(idx) {
+            choice.apply = !choice.apply;
+            self.show_file_selection(idx);
+        } else if idx == file_count {
+            let approved_paths = choices
+                .iter()
+                .filter(|choice| choice.apply)
+                .map(|choice| choice.path.clone())
+                .collect();
+            if let Some(ApprovalVariant::ApplyPatch { id, .. }) = self.current_variant.as_ref() {
+                self.handle_patch_decision(id, ReviewDecision::ApprovedSubset { approved_paths });
+            }
+            self.current_complete = true;
+            self.advance_queue();
+        } else {
+            self.close_file_selection();
+        }
+    }
+
+    fn close_file_selection(&mut self) {
+        if let Some(request) = self.current_request.clone() {
+            self.set_current(request);
+        }
+    }
+
     fn handle_elicitation_decision(
         &self,
         server_name: &str,
@@ -263,6 +359,17 @@ impl ApprovalOverlay {
 
 impl BottomPaneView for ApprovalOverlay {
     fn handle_key_event(&mut self, key_event: KeyEvent) {
+        if self.file_selection.is_some() {
+            if key_event.code == KeyCode::Esc && key_event.kind == KeyEventKind::Press {
+                self.close_file_selection();
+                return;
+            }
+            self.list.handle_key_event(key_event);
+            if let Some(idx) = self.list.take_last_selected_index() {
+                self.apply_file_selection(idx);
+            }
+            return;
+        }
         if self.try_handle_shortcut(&key_event) {
             return;
         }
@@ -380,9 +487,17 @@ impl From<ApprovalRequest> for ApprovalRequestState {
                     ));
                     header.push(Box::new(Line::from("")));
                 }
+                let mut files: Vec<(PathBuf, String)> = changes
+                    .keys()
+                    .map(|path| {
+                        let display = path.strip_prefix(&cwd).unwrap_or(path).display();
+                        (path.clone(), display.to_string())
+                    })
+                    .collect();
+                files.sort();
                 header.push(DiffSummary::new(changes, cwd).into());
                 Self {
-                    variant: ApprovalVariant::ApplyPatch { id },
+                    variant: ApprovalVariant::ApplyPatch { id, files },
                     header: Box::new(ColumnRenderable::with(header)),
                 }
             }
@@ -418,6 +533,8 @@ enum ApprovalVariant {
     },
     ApplyPatch {
         id: String,
+        /// Absolute path and display path of every file the patch touches.
+        files: Vec<(PathBuf, String)>,
     },
     McpElicitation {
         server_name: String,
@@ -429,6 +546,14 @@ enum ApprovalVariant {
 enum ApprovalDecision {
     Review(ReviewDecision),
     McpElicitation(ElicitationAction),
+    /// Switch to picking which files of the patch to apply.
+    ChooseFiles,
+}
+
+struct PatchFileChoice {
+    path: PathBuf,
+    display: String,
+    apply: bool,
 }
 
 #[derive(Clone)]
@@ -486,21 +611,28 @@ fn exec_options(
     .collect()
 }
 
-fn patch_options() -> Vec<ApprovalOption> {
-    vec![
-        ApprovalOption {
-            label: "Yes, proceed".to_string(),
-            decision: ApprovalDecision::Review(ReviewDecision::Approved),
+fn patch_options(file_count: usize) -> Vec<ApprovalOption> {
+    let mut options = vec![ApprovalOption {
+        label: "Yes, proceed".to_string(),
+        decision: ApprovalDecision::Review(ReviewDecision::Approved),
+        display_shortcut: None,
+        additional_shortcuts: vec![key_hint::plain(KeyCode::Char('y'))],
+    }];
+    if file_count > 1 {
+        options.push(ApprovalOption {
+            label: "Choose which files to change".to_string(),
+            decision: ApprovalDecision::ChooseFiles,
             display_shortcut: None,
-            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('y'))],
-        },
-        ApprovalOption {
-            label: "No, and tell Codex what to do differently".to_string(),
-            decision: ApprovalDecision::Review(ReviewDecision::Abort),
-            display_shortcut: Some(key_hint::plain(KeyCode::Esc)),
-            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('n'))],
-        },
-    ]
+            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('f'))],
+        });
+    }
+    options.push(ApprovalOption {
+        label: "No, and tell Codex what to do differently".to_string(),
+        decision: ApprovalDecision::Review(ReviewDecision::Abort),
+        display_shortcut: Some(key_hint::plain(KeyCode::Esc)),
+        additional_shortcuts: vec![key_hint::plain(KeyCode::Char('n'))],
+    });
+    options
 }
 
 fn elicitation_options() -> Vec<ApprovalOption> {
@@ -693,6 +825,60 @@ mod tests {
         assert_eq!(rendered, expected);
     }
 
+    #[test]
+    fn patch_file_selection_emits_approved_subset() {
+        let (tx, mut rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx);
+        let cwd = PathBuf::from("/repo");
+        let changes = HashMap::from([
+            (
+                cwd.join("a.txt"),
+                FileChange::Add {
+                    content: "a".to_string(),
+                },
+            ),
+            (
+                cwd.join("b.txt"),
+                FileChange::Add {
+                    content: "b".to_string(),
+                },
+            ),
+        ]);
+        let mut view = ApprovalOverlay::new(
+            ApprovalRequest::ApplyPatch {
+                id: "patch".to_string(),
+                reason: None,
+                cwd: cwd.clone(),
+                changes,
+            },
+            tx,
+            Features::with_defaults(),
+        );
+
+        view.handle_key_event(KeyEvent::new(KeyCode::Char('f'), KeyModifiers::NONE));
+        assert!(!view.is_complete());
+        // Uncheck `a.txt`, then confirm.
+        view.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        view.handle_key_event(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+        view.handle_key_event(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+        view.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert!(view.is_complete());
+
+        let mut decision = None;
+        while let Ok(ev) = rx.try_recv() {
+            if let AppEvent::CodexOp(Op::PatchApproval { decision: d, .. }) = ev {
+                decision = Some(d);
+                break;
+            }
+        }
+        assert_eq!(
+            decision,
+            Some(ReviewDecision::ApprovedSubset {
+                approved_paths: vec![cwd.join("b.txt")],
+            })
+        );
+    }
+
     #[test]
     fn enter_sets_last_selected_index_without_dismissing() {
         let (tx_raw, mut rx) = unbounded_channel::<AppEvent>();
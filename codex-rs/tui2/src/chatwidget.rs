@@ -10,6 +10,7 @@ use codex_backend_client::Client as BackendClient;
 use codex_core::config::Config;
 use codex_core::config::ConstraintResult;
 use codex_core::config::types::Notifications;
+use codex_core::export::ExportFormat;
 use codex_core::git_info::current_branch_name;
 use codex_core::git_info::local_git_branches;
 use codex_core::models_manager::manager::ModelsManager;
@@ -66,7 +67,9 @@ use codex_protocol::ConversationId;
 use codex_protocol::account::PlanType;
 use codex_protocol::approvals::ElicitationRequestEvent;
 use codex_protocol::parse_command::ParsedCommand;
+use codex_protocol::user_input::LineRange;
 use codex_protocol::user_input::UserInput;
+use codex_protocol::user_input::parse_file_attachment;
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
 use crossterm::event::KeyEventKind;
@@ -95,11 +98,13 @@ use crate::bottom_pane::SelectionItem;
 use crate::bottom_pane::SelectionViewParams;
 use crate::bottom_pane::custom_prompt_view::CustomPromptView;
 use crate::bottom_pane::popup_consts::standard_popup_hint_line;
+use crate::clipboard_copy::fenced_code_blocks;
 use crate::clipboard_paste::paste_image_to_temp_png;
 use crate::diff_render::display_path_for;
 use crate::exec_cell::CommandOutput;
 use crate::exec_cell::ExecCell;
 use crate::exec_cell::new_active_exec_command;
+use crate::exec_command::strip_bash_lc_and_escape;
 use crate::get_git_diff::get_git_diff;
 use crate::history_cell;
 use crate::history_cell::AgentMessageCell;
@@ -117,6 +122,8 @@ use crate::slash_command::SlashCommand;
 use crate::status::RateLimitSnapshotDisplay;
 use crate::text_formatting::truncate_text;
 use crate::tui::FrameRequester;
+use codex_tui::cues;
+use codex_tui::cues::CueEvent;
 mod interrupts;
 use self::interrupts::InterruptManager;
 mod agent;
@@ -323,6 +330,10 @@ pub(crate) struct ChatWidget {
     queued_user_messages: VecDeque<UserMessage>,
     // Pending notification to show when unfocused on next Draw
     pending_notification: Option<Notification>,
+    // Cues to fire on next Draw, once terminal focus is known
+    pending_cues: Vec<CueEvent>,
+    // Whether the context-nearly-full cue fired since context was last above the threshold
+    context_nearly_full_cued: bool,
     // Simple review mode flag; used to adjust layout and banners.
     is_review_mode: bool,
     // Snapshot of token usage to restore after review mode exits.
@@ -335,6 +346,9 @@ pub(crate) struct ChatWidget {
     feedback: codex_feedback::CodexFeedback,
     // Current session rollout path (if known)
     current_rollout_path: Option<PathBuf>,
+    // Most recent final agent message and command, for /copy
+    last_agent_message: Option<String>,
+    last_command: Option<String>,
 }
 
 struct UserMessage {
@@ -466,6 +480,9 @@ impl ChatWidget {
     }
 
     fn on_agent_message(&mut self, message: String) {
+        if !message.trim().is_empty() {
+            self.last_agent_message = Some(message.clone());
+        }
         // If we have a stream_controller, then the final agent message is redundant and will be a
         // duplicate of what has already been streamed.
         if self.stream_controller.is_none() {
@@ -566,6 +583,13 @@ impl ChatWidget {
         let used_tokens = self.context_used_tokens(&info, percent.is_some());
         self.bottom_pane.set_context_window(percent, used_tokens);
         self.token_info = Some(info);
+        if let Some(percent) = percent {
+            let nearly_full = percent <= cues::context_nearly_full_threshold(&self.config.tui_cues);
+            if nearly_full && !self.context_nearly_full_cued {
+                self.cue(CueEvent::ContextNearlyFull);
+            }
+            self.context_nearly_full_cued = nearly_full;
+        }
     }
 
     fn context_remaining_percent(&self, info: &TokenUsageInfo) -> Option<i64> {
@@ -832,6 +856,9 @@ impl ChatWidget {
 
     fn on_exec_command_begin(&mut self, ev: ExecCommandBeginEvent) {
         self.flush_answer_stream_with_separator();
+        if ev.source != ExecCommandSource::UnifiedExecInteraction {
+            self.last_command = Some(strip_bash_lc_and_escape(&ev.command));
+        }
         let ev2 = ev.clone();
         self.defer_or_handle(|q| q.push_exec_begin(ev), |s| s.handle_exec_begin_now(ev2));
     }
@@ -1045,6 +1072,9 @@ impl ChatWidget {
         };
         let is_unified_exec_interaction =
             matches!(source, ExecCommandSource::UnifiedExecInteraction);
+        if ev.exit_code != 0 && !is_unified_exec_interaction {
+            self.cue(CueEvent::CommandFailed);
+        }
 
         let needs_new = self
             .active_cell
@@ -1331,12 +1361,16 @@ impl ChatWidget {
             show_welcome_banner: is_first_run,
             suppress_session_configured_redraw: false,
             pending_notification: None,
+            pending_cues: Vec::new(),
+            context_nearly_full_cued: false,
             is_review_mode: false,
             pre_review_token_info: None,
             needs_final_message_separator: false,
             last_rendered_width: std::cell::Cell::new(None),
             feedback,
             current_rollout_path: None,
+            last_agent_message: None,
+            last_command: None,
         };
 
         widget.prefetch_rate_limits();
@@ -1416,12 +1450,16 @@ impl ChatWidget {
             show_welcome_banner: false,
             suppress_session_configured_redraw: true,
             pending_notification: None,
+            pending_cues: Vec::new(),
+            context_nearly_full_cued: false,
             is_review_mode: false,
             pre_review_token_info: None,
             needs_final_message_separator: false,
             last_rendered_width: std::cell::Cell::new(None),
             feedback,
             current_rollout_path: None,
+            last_agent_message: None,
+            last_command: None,
         };
 
         widget.prefetch_rate_limits();
@@ -1601,6 +1639,12 @@ impl ChatWidget {
                     tx.send(AppEvent::DiffResult(text));
                 });
             }
+            SlashCommand::Copy => {
+                self.open_copy_popup();
+            }
+            SlashCommand::Export => {
+                self.open_export_popup();
+            }
             SlashCommand::Mention => {
                 self.insert_str("@");
             }
@@ -1759,6 +1803,10 @@ impl ChatWidget {
             }
         }
 
+        for (path, range) in find_file_mentions(&text, &self.config.cwd) {
+            items.push(UserInput::File { path, range });
+        }
+
         self.codex_op_tx
             .send(Op::UserInput { items })
             .unwrap_or_else(|e| {
@@ -1915,6 +1963,8 @@ impl ChatWidget {
             EventMsg::ExitedReviewMode(review) => self.on_exited_review_mode(review),
             EventMsg::ContextCompacted(_) => self.on_agent_message("Context compacted".to_owned()),
             EventMsg::RawResponseItem(_)
+            | EventMsg::ListHistoryEntriesResponse(_)
+            | EventMsg::ThreadRolledBack(_)
             | EventMsg::ItemStarted(_)
             | EventMsg::ItemCompleted(_)
             | EventMsg::AgentMessageContentDelta(_)
@@ -1987,7 +2037,13 @@ impl ChatWidget {
         self.frame_requester.schedule_frame();
     }
 
+    fn cue(&mut self, event: CueEvent) {
+        self.pending_cues.push(event);
+        self.request_redraw();
+    }
+
     fn notify(&mut self, notification: Notification) {
+        self.cue(notification.cue_event());
         if !notification.allowed_for(&self.config.tui_notifications) {
             return;
         }
@@ -1999,6 +2055,13 @@ impl ChatWidget {
         if let Some(notif) = self.pending_notification.take() {
             tui.notify(notif.display());
         }
+        let focused = tui.is_terminal_focused();
+        for event in self.pending_cues.drain(..) {
+            let bells = cues::fire(&self.config.tui_cues, event, focused);
+            if bells > 0 {
+                tui.ring_bell(bells);
+            }
+        }
     }
 
     /// Mark the active cell as failed (✗) and flush it into history.
@@ -2036,6 +2099,120 @@ impl ChatWidget {
         self.bottom_pane.set_queued_user_messages(messages);
     }
 
+    /// Offer the formats the current session can be exported in.
+    pub(crate) fn open_export_popup(&mut self) {
+        if self.current_rollout_path.is_none() {
+            self.add_info_message("Nothing to export yet.".to_string(), None);
+            return;
+        }
+        let items = [
+            ("Markdown", ExportFormat::Markdown),
+            ("HTML", ExportFormat::Html),
+            ("Plain text", ExportFormat::Text),
+        ]
+        .into_iter()
+        .map(|(name, format)| {
+            let actions: Vec<SelectionAction> = vec![Box::new(move |tx| {
+                tx.send(AppEvent::ExportSession(format));
+            })];
+            SelectionItem {
+                name: name.to_string(),
+                description: Some(format!("codex-session-<id>.{}", format.extension())),
+                actions,
+                dismiss_on_select: true,
+                ..Default::default()
+            }
+        })
+        .collect();
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some("Export session".to_string()),
+            subtitle: Some(
+                "Saved under CODEX_HOME/exports with secrets redacted. \
+                 Use `codex sessions export` for a raw transcript."
+                    .to_string(),
+            ),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+        self.request_redraw();
+    }
+
+    /// Offer the last agent message, the last command, and each code block
+    /// in the last agent message for copying to the clipboard.
+    pub(crate) fn open_copy_popup(&mut self) {
+        let mut entries: Vec<(String, Option<String>, String)> = Vec::new();
+        if let Some(message) = self.last_agent_message.as_ref() {
+            entries.push((
+                "Last agent message".to_string(),
+                Some(first_line_preview(message)),
+                message.clone(),
+            ));
+        }
+        if let Some(command) = self.last_command.as_ref() {
+            entries.push((
+                "Last command".to_string(),
+                Some(first_line_preview(command)),
+                command.clone(),
+            ));
+        }
+        let blocks = self
+            .last_agent_message
+            .as_deref()
+            .map(fenced_code_blocks)
+            .unwrap_or_default();
+        for (idx, block) in blocks.into_iter().enumerate() {
+            let lines = block.code.lines().count();
+            let name = match block.language.as_deref() {
+                Some(language) => format!("Code block {} ({language})", idx + 1),
+                None => format!("Code block {}", idx + 1),
+            };
+            let description = format!(
+                "{lines} line{} · {}",
+                if lines == 1 { "" } else { "s" },
+                first_line_preview(&block.code)
+            );
+            entries.push((name, Some(description), block.code));
+        }
+
+        if entries.is_empty() {
+            self.add_info_message(
+                "Nothing to copy yet.".to_string(),
+                Some("/copy becomes available after Codex replies or runs a command.".to_string()),
+            );
+            return;
+        }
+
+        let items = entries
+            .into_iter()
+            .map(|(name, description, text)| {
+                let label = name.to_lowercase();
+                let actions: Vec<SelectionAction> = vec![Box::new(move |tx| {
+                    tx.send(AppEvent::CopyToClipboard {
+                        text: text.clone(),
+                        label: label.clone(),
+                    });
+                })];
+                SelectionItem {
+                    name,
+                    description,
+                    actions,
+                    dismiss_on_select: true,
+                    ..Default::default()
+                }
+            })
+            .collect();
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some("Copy to clipboard".to_string()),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+        self.request_redraw();
+    }
+
     pub(crate) fn add_diff_in_progress(&mut self) {
         self.request_redraw();
     }
@@ -3394,6 +3571,15 @@ impl Notification {
         }
     }
 
+    fn cue_event(&self) -> CueEvent {
+        match self {
+            Notification::AgentTurnComplete { .. } => CueEvent::TurnComplete,
+            Notification::ExecApprovalRequested { .. }
+            | Notification::EditApprovalRequested { .. }
+            | Notification::ElicitationRequested { .. } => CueEvent::ApprovalRequested,
+        }
+    }
+
     fn allowed_for(&self, settings: &Notifications) -> bool {
         match settings {
             Notifications::Enabled(enabled) => *enabled,
@@ -3429,6 +3615,22 @@ const EXAMPLE_PROMPTS: [&str; 6] = [
     "Improve documentation in @filename",
 ];
 
+/// First non-empty line of `text`, shortened for a one-line popup description.
+fn first_line_preview(text: &str) -> String {
+    const MAX_CHARS: usize = 60;
+    let line = text
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or_default();
+    if line.chars().count() > MAX_CHARS {
+        let truncated: String = line.chars().take(MAX_CHARS - 1).collect();
+        format!("{truncated}…")
+    } else {
+        line.to_string()
+    }
+}
+
 // Extract the first bold (Markdown) element in the form **...** from `s`.
 // Returns the inner text if found; otherwise `None`.
 fn extract_first_bold(s: &str) -> Option<String> {
@@ -3531,6 +3733,26 @@ fn find_skill_mentions(text: &str, skills: &[SkillMetadata]) -> Vec<SkillMetadat
     matches
 }
 
+/// Returns the `@path` and `@path:START-END` mentions in `text` that name an
+/// existing file relative to `cwd`, so they can be attached to the prompt.
+fn find_file_mentions(text: &str, cwd: &Path) -> Vec<(PathBuf, Option<LineRange>)> {
+    let mut mentions: Vec<(PathBuf, Option<LineRange>)> = Vec::new();
+    for token in text.split_whitespace() {
+        let Some(spec) = token.strip_prefix('@') else {
+            continue;
+        };
+        let spec = spec.trim_end_matches([',', '.', ';', ')']);
+        if spec.is_empty() {
+            continue;
+        }
+        let mention = parse_file_attachment(spec);
+        if cwd.join(&mention.0).is_file() && !mentions.contains(&mention) {
+            mentions.push(mention);
+        }
+    }
+    mentions
+}
+
 fn skills_for_cwd(cwd: &Path, skills_entries: &[SkillsListEntry]) -> Vec<SkillMetadata> {
     skills_entries
         .iter()
@@ -3545,6 +3767,11 @@ fn skills_for_cwd(cwd: &Path, skills_entries: &[SkillsListEntry]) -> Vec<SkillMe
                     short_description: skill.short_description.clone(),
                     path: skill.path.clone(),
                     scope: skill.scope,
+                    allowed_tools: None,
+                    model: None,
+                    reasoning_effort: None,
+                    required_mcp_servers: Vec::new(),
+                    scripts: Vec::new(),
                 })
                 .collect()
         })
@@ -398,12 +398,16 @@ async fn make_chatwidget_manual(
         queued_user_messages: VecDeque::new(),
         suppress_session_configured_redraw: false,
         pending_notification: None,
+        pending_cues: Vec::new(),
+        context_nearly_full_cued: false,
         is_review_mode: false,
         pre_review_token_info: None,
         needs_final_message_separator: false,
         last_rendered_width: std::cell::Cell::new(None),
         feedback: codex_feedback::CodexFeedback::new(),
         current_rollout_path: None,
+        last_agent_message: None,
+        last_command: None,
     };
     (widget, rx, op_rx)
 }
@@ -3311,3 +3315,112 @@ async fn chatwidget_tall() {
     .unwrap();
     assert_snapshot!(term.backend().vt100().screen().contents());
 }
+
+#[test]
+fn file_mentions_attach_existing_files_with_ranges() {
+    let dir = tempdir().expect("tempdir");
+    std::fs::write(dir.path().join("notes.md"), "hello\n").expect("write");
+
+    assert_eq!(
+        find_file_mentions(
+            "see @notes.md:10-40, @missing.rs and @notes.md again",
+            dir.path()
+        ),
+        vec![
+            (
+                PathBuf::from("notes.md"),
+                Some(LineRange { start: 10, end: 40 })
+            ),
+            (PathBuf::from("notes.md"), None),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn copy_popup_offers_agent_message_and_code_blocks() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+    chat.handle_codex_event(Event {
+        id: "s1".into(),
+        msg: EventMsg::AgentMessage(AgentMessageEvent {
+            message: "Run this:\n\n```rust\nfn main() {}\n```".into(),
+        }),
+    });
+    while rx.try_recv().is_ok() {}
+
+    chat.dispatch_command(SlashCommand::Copy);
+    let popup = render_bottom_popup(&chat, 80);
+    assert!(popup.contains("Last agent message"), "popup: {popup}");
+    assert!(popup.contains("Code block 1 (rust)"), "popup: {popup}");
+
+    chat.handle_key_event(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+    let mut copied = None;
+    while let Ok(event) = rx.try_recv() {
+        if let AppEvent::CopyToClipboard { text, label } = event {
+            copied = Some((text, label));
+        }
+    }
+    assert_eq!(
+        copied,
+        Some((
+            "fn main() {}".to_string(),
+            "code block 1 (rust)".to_string()
+        ))
+    );
+}
+
+#[tokio::test]
+async fn export_popup_requires_a_recorded_session() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+
+    chat.dispatch_command(SlashCommand::Export);
+
+    let history = drain_insert_history(&mut rx)
+        .iter()
+        .map(|lines| lines_to_single_string(lines))
+        .collect::<String>();
+    assert!(history.contains("Nothing to export yet."), "{history}");
+}
+
+#[tokio::test]
+async fn export_popup_sends_selected_format() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+    chat.current_rollout_path = Some(PathBuf::from("/tmp/rollout.jsonl"));
+
+    chat.dispatch_command(SlashCommand::Export);
+    let popup = render_bottom_popup(&chat, 80);
+    assert!(popup.contains("Export session"), "popup: {popup}");
+    assert!(popup.contains("Markdown"), "popup: {popup}");
+    assert!(popup.contains("codex-session-<id>.html"), "popup: {popup}");
+
+    chat.handle_key_event(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+    let mut exported = None;
+    while let Ok(event) = rx.try_recv() {
+        if let AppEvent::ExportSession(format) = event {
+            exported = Some(format);
+        }
+    }
+    assert_eq!(exported, Some(ExportFormat::Html));
+}
+
+#[tokio::test]
+async fn failed_command_and_approval_queue_cues() {
+    let (mut chat, _rx, _op_rx) = make_chatwidget_manual(None).await;
+
+    let ok = begin_exec(&mut chat, "call-ok", "true");
+    end_exec(&mut chat, ok, "", "", 0);
+    assert_eq!(chat.pending_cues, Vec::<CueEvent>::new());
+
+    let failed = begin_exec(&mut chat, "call-fail", "false");
+    end_exec(&mut chat, failed, "", "boom", 1);
+    chat.notify(Notification::ExecApprovalRequested {
+        command: "rm -rf build".to_string(),
+    });
+    assert_eq!(
+        chat.pending_cues,
+        vec![CueEvent::CommandFailed, CueEvent::ApprovalRequested]
+    );
+}
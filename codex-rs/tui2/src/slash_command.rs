@@ -22,6 +22,8 @@ pub enum SlashCommand {
     Compact,
     Undo,
     Diff,
+    Copy,
+    Export,
     Mention,
     Status,
     Mcp,
@@ -46,6 +48,8 @@ impl SlashCommand {
             SlashCommand::Undo => "ask Codex to undo a turn",
             SlashCommand::Quit | SlashCommand::Exit => "exit Codex",
             SlashCommand::Diff => "show git diff (including untracked files)",
+            SlashCommand::Copy => "copy the last message, command or a code block",
+            SlashCommand::Export => "save this session as Markdown, HTML or plain text",
             SlashCommand::Mention => "mention a file",
             SlashCommand::Skills => "use skills to improve how Codex performs specific tasks",
             SlashCommand::Status => "show current session configuration and token usage",
@@ -77,6 +81,8 @@ impl SlashCommand {
             | SlashCommand::Review
             | SlashCommand::Logout => false,
             SlashCommand::Diff
+            | SlashCommand::Copy
+            | SlashCommand::Export
             | SlashCommand::Mention
             | SlashCommand::Skills
             | SlashCommand::Status
@@ -1,6 +1,7 @@
 use std::io::IsTerminal;
 use std::io::Result;
 use std::io::Stdout;
+use std::io::Write;
 use std::io::stdin;
 use std::io::stdout;
 use std::panic;
@@ -8,7 +9,9 @@ use std::pin::Pin;
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering;
+use std::time::Instant;
 
+use codex_tui::cues::BELL_INTERVAL;
 use crossterm::SynchronizedUpdate;
 use crossterm::event::DisableBracketedPaste;
 use crossterm::event::DisableFocusChange;
@@ -140,6 +143,9 @@ pub struct Tui {
     terminal_focused: Arc<AtomicBool>,
     enhanced_keys_supported: bool,
     notification_backend: Option<DesktopNotificationBackend>,
+    // Bells still to ring for cues, spaced by `BELL_INTERVAL`; see `ring_bell`.
+    pending_bells: u8,
+    next_bell_at: Instant,
 }
 
 impl Tui {
@@ -166,6 +172,8 @@ impl Tui {
             terminal_focused: Arc::new(AtomicBool::new(true)),
             enhanced_keys_supported,
             notification_backend: Some(detect_backend()),
+            pending_bells: 0,
+            next_bell_at: Instant::now(),
         }
     }
 
@@ -177,6 +185,38 @@ impl Tui {
         self.enhanced_keys_supported
     }
 
+    pub fn is_terminal_focused(&self) -> bool {
+        self.terminal_focused.load(Ordering::Relaxed)
+    }
+
+    /// Ring the terminal bell `count` times through the terminal writer. Later
+    /// bells are spaced out by scheduling frames rather than sleeping.
+    pub fn ring_bell(&mut self, count: u8) {
+        self.pending_bells = self.pending_bells.saturating_add(count);
+        self.ring_due_bells();
+    }
+
+    fn ring_due_bells(&mut self) {
+        if self.pending_bells == 0 {
+            return;
+        }
+        let now = Instant::now();
+        if now < self.next_bell_at {
+            self.frame_requester
+                .schedule_frame_in(self.next_bell_at.saturating_duration_since(now));
+            return;
+        }
+        let writer = self.terminal.backend_mut();
+        if let Err(err) = writer.write_all(b"\x07").and_then(|()| writer.flush()) {
+            tracing::debug!("failed to ring terminal bell: {err}");
+        }
+        self.pending_bells -= 1;
+        self.next_bell_at = now + BELL_INTERVAL;
+        if self.pending_bells > 0 {
+            self.frame_requester.schedule_frame_in(BELL_INTERVAL);
+        }
+    }
+
     /// Emit a desktop notification now if the terminal is unfocused.
     /// Returns true if a notification was posted.
     pub fn notify(&mut self, message: impl AsRef<str>) -> bool {
@@ -340,6 +380,8 @@ impl Tui {
         height: u16,
         draw_fn: impl FnOnce(&mut custom_terminal::Frame),
     ) -> Result<()> {
+        self.ring_due_bells();
+
         // If we are resuming from ^Z, we need to prepare the resume action now so we can apply it
         // in the synchronized update.
         #[cfg(unix)]
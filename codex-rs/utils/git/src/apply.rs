@@ -123,6 +123,49 @@ pub fn apply_git_patch(req: &ApplyGitRequest) -> io::Result<ApplyGitResult> {
     })
 }
 
+/// What [`apply_hunk`] does with a patch taken from `git diff`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HunkAction {
+    /// Add a working tree hunk (from `git diff`) to the index.
+    Stage,
+    /// Remove a staged hunk (from `git diff --cached`) from the index.
+    Unstage,
+    /// Discard a working tree hunk (from `git diff`), restoring the index version.
+    Revert,
+}
+
+/// Stage, unstage or discard the hunks in `patch` without touching the rest of
+/// the file.
+///
+/// `patch` may hold any subset of the hunks of a `git diff` file section; hunk
+/// line counts are recomputed, so hunks can be passed on their own.
+pub fn apply_hunk(cwd: &Path, patch: &str, action: HunkAction) -> io::Result<()> {
+    let git_root = resolve_git_root(cwd)?;
+    let (tmpdir, patch_path) = write_temp_patch(patch)?;
+    let _guard = tmpdir;
+
+    let mut args: Vec<String> = vec!["apply".into(), "--recount".into()];
+    match action {
+        HunkAction::Stage => args.push("--cached".into()),
+        HunkAction::Unstage => {
+            args.push("--cached".into());
+            args.push("-R".into());
+        }
+        HunkAction::Revert => args.push("-R".into()),
+    }
+    args.push(patch_path.to_string_lossy().to_string());
+
+    let (code, _stdout, stderr) = run_git(&git_root, &[], &args)?;
+    if code != 0 {
+        return Err(io::Error::other(format!(
+            "{} failed: {}",
+            render_command_for_log(&git_root, &[], &args),
+            stderr.trim()
+        )));
+    }
+    Ok(())
+}
+
 fn resolve_git_root(cwd: &Path) -> io::Result<PathBuf> {
     let out = std::process::Command::new("git")
         .arg("rev-parse")
@@ -712,4 +755,39 @@ diff --git a/ghost.txt b/ghost.txt\n--- a/ghost.txt\n+++ b/ghost.txt\n@@ -1,1 +1
             "non-preflight path should not use --check"
         );
     }
+
+    #[test]
+    fn apply_hunk_stages_unstages_and_reverts_single_hunks() {
+        let _g = env_lock().lock().unwrap();
+        let repo = init_repo();
+        let root = repo.path();
+        let original: String = (1..=20).map(|i| format!("line{i}\n")).collect();
+        std::fs::write(root.join("file.txt"), &original).unwrap();
+        let _ = run(root, &["git", "add", "file.txt"]);
+        let _ = run(root, &["git", "commit", "-m", "seed"]);
+
+        let modified = original
+            .replace("line2\n", "line2 changed\n")
+            .replace("line19\n", "line19 changed\n");
+        std::fs::write(root.join("file.txt"), &modified).unwrap();
+        let header = "diff --git a/file.txt b/file.txt\n--- a/file.txt\n+++ b/file.txt\n";
+        let first = "@@ -1,5 +1,5 @@\n line1\n-line2\n+line2 changed\n line3\n line4\n line5\n";
+        let second =
+            "@@ -16,5 +16,5 @@\n line16\n line17\n line18\n-line19\n+line19 changed\n line20\n";
+
+        apply_hunk(root, &format!("{header}{second}"), HunkAction::Stage).expect("stage");
+        let (_, staged, _) = run(root, &["git", "diff", "--cached"]);
+        assert!(staged.contains("+line19 changed"));
+        assert!(!staged.contains("+line2 changed"));
+
+        apply_hunk(root, &format!("{header}{second}"), HunkAction::Unstage).expect("unstage");
+        let (_, staged, _) = run(root, &["git", "diff", "--cached"]);
+        assert_eq!(staged, "");
+
+        apply_hunk(root, &format!("{header}{first}"), HunkAction::Revert).expect("revert");
+        assert_eq!(
+            read_file_normalized(&root.join("file.txt")),
+            original.replace("line19\n", "line19 changed\n")
+        );
+    }
 }
@@ -10,7 +10,9 @@ mod platform;
 
 pub use apply::ApplyGitRequest;
 pub use apply::ApplyGitResult;
+pub use apply::HunkAction;
 pub use apply::apply_git_patch;
+pub use apply::apply_hunk;
 pub use apply::extract_paths_from_patch;
 pub use apply::parse_git_apply_output;
 pub use apply::stage_paths;